@@ -0,0 +1,111 @@
+use crate::error::{CargoJamError, Result};
+use crate::process::output_with_timeout;
+use crate::toolchain::config::ToolchainConfig;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+// The two constants below are a size-based guess, not taken from the JAM gas
+// schedule: they put the number in a plausible range and make bigger blobs cost
+// more, nothing else. Only a testnet run measures real usage.
+
+/// Guessed base gas charged for invoking a service's accumulate entry point
+const GUESSED_BASE_GAS: u64 = 10_000;
+
+/// Guessed gas charged per byte of code that has to be loaded and initialized
+const GUESSED_GAS_PER_CODE_BYTE: u64 = 10;
+
+/// Headroom added to a guessed requirement when suggesting a gas limit, in percent
+const SUGGESTED_HEADROOM_PERCENT: u64 = 20;
+
+/// Size-based guess at the gas a `.jam` service blob needs; not a measurement
+#[derive(Debug, Clone, PartialEq)]
+pub struct GasEstimate {
+    /// Size of the blob in bytes
+    pub code_size: u64,
+    /// Guessed minimum gas to accumulate a single work item
+    pub min_item_gas: u64,
+}
+
+impl GasEstimate {
+    /// Guess gas from the blob's code size with the constants above
+    pub fn from_code_size(code_size: u64) -> Self {
+        Self {
+            code_size,
+            min_item_gas: GUESSED_BASE_GAS
+                .saturating_add(code_size.saturating_mul(GUESSED_GAS_PER_CODE_BYTE)),
+        }
+    }
+}
+
+/// A gas limit to suggest for a requirement: 20% above it, rounded up to a thousand
+pub fn suggested_gas(required: u64) -> u64 {
+    let padded = required.saturating_add(required * SUGGESTED_HEADROOM_PERCENT / 100);
+    padded.div_ceil(1_000).saturating_mul(1_000)
+}
+
+/// Guess the gas requirements of a `.jam` blob from its size
+pub fn estimate(blob: &Path) -> Result<GasEstimate> {
+    let metadata = std::fs::metadata(blob).map_err(|e| {
+        CargoJamError::Build(format!(
+            "Failed to read service blob {}: {}",
+            blob.display(),
+            e
+        ))
+    })?;
+
+    Ok(GasEstimate::from_code_size(metadata.len()))
+}
+
+/// Locate polkatool in the installed toolchain or on PATH
+pub fn polkatool_path(timeout: Option<Duration>) -> Option<PathBuf> {
+    if let Ok(Some(path)) = ToolchainConfig::binary_path("polkatool") {
+        return Some(path);
+    }
+
+    output_with_timeout(Command::new("polkatool").arg("--version"), timeout)
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|_| PathBuf::from("polkatool"))
+}
+
+/// Run `polkatool stats` on the blob and return its report
+pub fn polkatool_stats(polkatool: &Path, blob: &Path, timeout: Option<Duration>) -> Result<String> {
+    let output = output_with_timeout(Command::new(polkatool).arg("stats").arg(blob), timeout)
+        .map_err(|e| match e {
+            CargoJamError::Io(e) => {
+                CargoJamError::Build(format!("Failed to execute polkatool: {}", e))
+            }
+            e => e,
+        })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(CargoJamError::Build(format!(
+            "polkatool stats failed:\n{}",
+            stderr
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_scales_with_code_size() {
+        let small = GasEstimate::from_code_size(1_000);
+        let large = GasEstimate::from_code_size(100_000);
+        assert_eq!(small.min_item_gas, 20_000);
+        assert!(large.min_item_gas > small.min_item_gas);
+    }
+
+    #[test]
+    fn test_suggested_gas() {
+        assert_eq!(suggested_gas(20_000), 24_000);
+        assert_eq!(suggested_gas(1_234_567), 1_482_000);
+        assert_eq!(suggested_gas(0), 0);
+    }
+}
@@ -0,0 +1,187 @@
+use crate::error::Result;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::debug;
+use walkdir::WalkDir;
+
+/// Cargo bookkeeping directories that never hold the final ELF or PolkaVM blob
+const SKIP_DIRS: &[&str] = &[
+    "deps",
+    "build",
+    ".fingerprint",
+    "incremental",
+    "cargo-timings",
+];
+
+/// Intermediate build outputs left in the target directory by jam-pvm-build
+#[derive(Debug, Default)]
+pub struct Intermediates {
+    /// Cargo target directory the artifacts were found in
+    pub target_dir: PathBuf,
+    /// Linked RISC-V ELF files for the service crate
+    pub elf: Vec<PathBuf>,
+    /// `.polkavm` blobs produced before the final `.jam` was written
+    pub polkavm: Vec<PathBuf>,
+    /// The crate's own objects in `deps/` directories (`<crate>-<hash>`, `lib<crate>-<hash>.rlib`, ...)
+    pub deps: Vec<PathBuf>,
+}
+
+impl Intermediates {
+    /// Find the ELF and PolkaVM artifacts for `crate_name` under the project's target directory
+    pub fn find(project_path: &Path, crate_name: &str) -> Self {
+        let target_dir = target_dir(project_path);
+        let crate_name = crate_name.replace('-', "_");
+        let mut found = Self {
+            target_dir: target_dir.clone(),
+            ..Self::default()
+        };
+
+        let walker = WalkDir::new(&target_dir)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|e| {
+                let name = e.file_name().to_string_lossy();
+                !(e.file_type().is_dir() && name != "deps" && SKIP_DIRS.contains(&name.as_ref()))
+            });
+
+        for entry in walker.filter_map(|e| e.ok()) {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().replace('-', "_");
+            let in_deps = entry
+                .path()
+                .parent()
+                .is_some_and(|parent| parent.ends_with("deps"));
+            if in_deps {
+                // Only this crate's objects, never those of its dependencies
+                let own = [format!("{}_", crate_name), format!("lib{}_", crate_name)];
+                if own.iter().any(|prefix| name.starts_with(prefix.as_str())) {
+                    found.deps.push(entry.into_path());
+                }
+                continue;
+            }
+            if !name.contains(&crate_name) {
+                continue;
+            }
+
+            let path = entry.into_path();
+            if path.extension().is_some_and(|ext| ext == "polkavm") {
+                found.polkavm.push(path);
+            } else if is_elf(&path) {
+                found.elf.push(path);
+            }
+        }
+
+        found
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elf.is_empty() && self.polkavm.is_empty()
+    }
+
+    /// Remove the crate's ELF, PolkaVM and `deps/` artifacts, leaving `keep` (the final
+    /// blob) and everything else in the target directory untouched, since it may be
+    /// shared with other crates. Returns the number of files removed.
+    pub fn clean(&self, keep: &Path) -> Result<usize> {
+        let keep = keep.canonicalize().unwrap_or_else(|_| keep.to_path_buf());
+        let mut removed = 0;
+        for path in self.elf.iter().chain(&self.polkavm).chain(&self.deps) {
+            if path.canonicalize().is_ok_and(|path| path == keep) {
+                continue;
+            }
+            match std::fs::remove_file(path) {
+                Ok(()) => removed += 1,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(removed)
+    }
+}
+
+/// Cargo target directory for a project, as reported by `cargo metadata` (which accounts
+/// for workspaces, `CARGO_TARGET_DIR` and `build.target-dir`)
+pub fn target_dir(project_path: &Path) -> PathBuf {
+    cargo_target_dir(project_path).unwrap_or_else(|| {
+        std::env::var_os("CARGO_TARGET_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| project_path.join("target"))
+    })
+}
+
+fn cargo_target_dir(project_path: &Path) -> Option<PathBuf> {
+    let output = Command::new("cargo")
+        .args([
+            "metadata",
+            "--format-version",
+            "1",
+            "--no-deps",
+            "--manifest-path",
+        ])
+        .arg(project_path.join("Cargo.toml"))
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        debug!(
+            stderr = %String::from_utf8_lossy(&output.stderr),
+            "cargo metadata failed, guessing the target directory"
+        );
+        return None;
+    }
+    let metadata: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+    metadata["target_directory"].as_str().map(PathBuf::from)
+}
+
+fn is_elf(path: &Path) -> bool {
+    use std::io::Read;
+
+    let mut magic = [0u8; 4];
+    std::fs::File::open(path)
+        .and_then(|mut f| f.read_exact(&mut magic))
+        .is_ok()
+        && magic == *b"\x7fELF"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_and_clean_intermediates() {
+        let dir = tempfile::tempdir().unwrap();
+        let release = dir
+            .path()
+            .join("target/riscv64emac-unknown-none-polkavm/release");
+        std::fs::create_dir_all(release.join("deps")).unwrap();
+        std::fs::write(release.join("my_service"), b"\x7fELF....").unwrap();
+        std::fs::write(release.join("my_service.d"), "deps").unwrap();
+        std::fs::write(release.join("my-service.polkavm"), "PVM").unwrap();
+        std::fs::write(release.join("deps/my_service-1234"), b"\x7fELF....").unwrap();
+        std::fs::write(release.join("deps/libmy_service-1234.rlib"), "rlib").unwrap();
+        std::fs::write(release.join("deps/libserde-5678.rlib"), "rlib").unwrap();
+        std::fs::write(release.join("other_service"), b"\x7fELF....").unwrap();
+
+        let found = Intermediates::find(dir.path(), "my-service");
+        assert_eq!(found.elf, [release.join("my_service")]);
+        assert_eq!(found.polkavm, [release.join("my-service.polkavm")]);
+        assert_eq!(
+            found.deps,
+            [
+                release.join("deps/libmy_service-1234.rlib"),
+                release.join("deps/my_service-1234")
+            ]
+        );
+
+        // The blob is kept even when it is one of the artifacts
+        assert_eq!(found.clean(&release.join("my-service.polkavm")).unwrap(), 3);
+        assert!(release.join("my-service.polkavm").exists());
+        assert!(!release.join("my_service").exists());
+        assert!(!release.join("deps/my_service-1234").exists());
+
+        // Other crates' output in a shared target directory is left alone
+        assert!(release.join("other_service").exists());
+        assert!(release.join("deps/libserde-5678.rlib").exists());
+        assert!(release.join("my_service.d").exists());
+    }
+}
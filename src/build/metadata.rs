@@ -0,0 +1,162 @@
+use crate::error::{CargoJamError, Result};
+use crate::project::manifest::hash_bytes;
+use crate::toolchain::config::ToolchainConfig;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Version of the metadata document format, bumped on incompatible changes
+pub const METADATA_FORMAT_VERSION: u32 = 1;
+
+/// Service entry points that can be implemented via `jam_pvm_common::Service`
+const ENTRY_POINTS: &[&str] = &["refine", "accumulate", "on_transfer"];
+
+/// Interface description of a built service, written next to its `.jam` blob
+#[derive(Debug, Serialize)]
+pub struct ServiceMetadata {
+    pub format_version: u32,
+    /// Package name from Cargo.toml
+    pub name: String,
+    /// Package version from Cargo.toml
+    pub version: Option<String>,
+    /// Package description from Cargo.toml
+    pub description: Option<String>,
+    /// Type passed to `declare_service!`
+    pub service_type: Option<String>,
+    /// Entry points implemented by the service
+    pub entry_points: Vec<String>,
+    /// Free-form interface declared in `[package.metadata.polkajam.interface]`
+    pub interface: Option<serde_json::Value>,
+    pub blob: BlobInfo,
+    /// Toolchain version used for the build
+    pub toolchain: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BlobInfo {
+    /// File name of the blob
+    pub file: String,
+    /// Size of the blob in bytes
+    pub size: u64,
+    /// Hex-encoded SHA-256 of the blob
+    pub sha256: String,
+}
+
+impl ServiceMetadata {
+    /// Collect metadata for a project and the blob built from it
+    pub fn collect(project_path: &Path, blob: &Path) -> Result<Self> {
+        let manifest_path = project_path.join("Cargo.toml");
+        let manifest: toml::Value = toml::from_str(&std::fs::read_to_string(&manifest_path)?)
+            .map_err(|e| CargoJamError::Build(format!("Failed to parse Cargo.toml: {}", e)))?;
+
+        let package = manifest.get("package");
+        let package_str = |key: &str| {
+            package
+                .and_then(|p| p.get(key))
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        };
+
+        let name = package_str("name").ok_or_else(|| {
+            CargoJamError::Build("Missing package name in Cargo.toml".to_string())
+        })?;
+
+        let interface = package
+            .and_then(|p| p.get("metadata"))
+            .and_then(|m| m.get("polkajam"))
+            .and_then(|m| m.get("interface"))
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|e| CargoJamError::Build(format!("Invalid interface metadata: {}", e)))?;
+
+        let sources = read_sources(&project_path.join("src"));
+        let bytes = std::fs::read(blob)?;
+
+        Ok(Self {
+            format_version: METADATA_FORMAT_VERSION,
+            name,
+            version: package_str("version"),
+            description: package_str("description"),
+            service_type: service_type(&sources),
+            entry_points: entry_points(&sources),
+            interface,
+            blob: BlobInfo {
+                file: blob
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+                size: bytes.len() as u64,
+                sha256: hash_bytes(&bytes),
+            },
+            toolchain: ToolchainConfig::load()
+                .ok()
+                .and_then(|c| c.installed_version),
+        })
+    }
+
+    /// Write the metadata as JSON next to the blob, returning its path
+    pub fn write(&self, blob: &Path) -> Result<PathBuf> {
+        let path = metadata_path(blob);
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| CargoJamError::Build(format!("Failed to serialize metadata: {}", e)))?;
+        std::fs::write(&path, format!("{}\n", json))?;
+        Ok(path)
+    }
+}
+
+/// Metadata file for a blob: `my-service.jam` -> `my-service.metadata.json`
+pub fn metadata_path(blob: &Path) -> PathBuf {
+    blob.with_extension("metadata.json")
+}
+
+/// Concatenated contents of all Rust sources under `src/`
+fn read_sources(src_dir: &Path) -> String {
+    WalkDir::new(src_dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "rs"))
+        .filter_map(|e| std::fs::read_to_string(e.path()).ok())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Type name given to `declare_service!`
+fn service_type(sources: &str) -> Option<String> {
+    let re = regex::Regex::new(r"declare_service!\s*\(\s*([A-Za-z_][A-Za-z0-9_]*)").ok()?;
+    re.captures(sources).map(|c| c[1].to_string())
+}
+
+/// Service entry points defined in the sources
+fn entry_points(sources: &str) -> Vec<String> {
+    ENTRY_POINTS
+        .iter()
+        .filter(|name| {
+            regex::Regex::new(&format!(r"\bfn\s+{}\s*\(", name))
+                .map(|re| re.is_match(sources))
+                .unwrap_or(false)
+        })
+        .map(|name| name.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_sources() {
+        let sources = "declare_service!(CounterService);\n\
+                       impl Service for CounterService {\n\
+                           fn refine(core: CoreIndex) -> WorkOutput { todo!() }\n\
+                           fn accumulate (slot: Slot) -> Option<Hash> { None }\n\
+                       }";
+
+        assert_eq!(service_type(sources).as_deref(), Some("CounterService"));
+        assert_eq!(entry_points(sources), ["refine", "accumulate"]);
+        assert_eq!(
+            metadata_path(Path::new("out/counter.jam")),
+            Path::new("out/counter.metadata.json")
+        );
+    }
+}
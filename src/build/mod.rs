@@ -1,3 +1,10 @@
 pub mod cargo;
+pub mod estimate;
+pub mod intermediate;
+pub mod metadata;
 pub mod pipeline;
 pub mod polkatool;
+pub mod reproducible;
+pub mod signing;
+pub mod strip;
+pub mod watch;
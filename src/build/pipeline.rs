@@ -1,30 +1,166 @@
+use crate::build::intermediate::{target_dir, Intermediates};
+use crate::build::metadata::ServiceMetadata;
+use crate::build::reproducible::{find_lockfile, reproducible_env};
+use crate::build::strip::{strip_elf, StripReport};
 use crate::error::{CargoJamError, Result};
-use crate::toolchain::config::ToolchainConfig;
-use std::path::PathBuf;
+use crate::process::{output_with_line_times, output_with_timeout};
+use crate::project::workspace::find_workspace_root;
+use crate::toolchain::config::{ToolchainConfig, TOOLCHAIN_PIN_FILE};
+use clap::ValueEnum;
+use console::style;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Lines of stderr included in the error when the full output went to `log_file`
+const LOG_TAIL_LINES: usize = 20;
+
+/// Appended to a build failure caused by a missing toolchain when auto-install is off
+const MISSING_TOOLCHAIN_HINT: &str = "\n\nA rustc toolchain or component is missing and --no-auto-install is set. Install it with the rustup command above, or build without --no-auto-install to let jam-pvm-build install it";
+
+/// Target JAM services are compiled for, assumed when the compiler doesn't name it
+const PVM_TARGET: &str = "riscv32ema-unknown-none-elf";
+
+/// Smallest plausible `.jam` blob in bytes; even an empty service's code and metadata are larger
+const MIN_BLOB_SIZE: u64 = 64;
 
 pub struct BuildPipeline {
     project_path: PathBuf,
     output_path: Option<PathBuf>,
     profile: BuildProfile,
     auto_install: bool,
+    frozen_toolchain: bool,
+    emit_metadata: bool,
+    profile_timings: bool,
+    clean_intermediate: bool,
+    reproducible: bool,
+    strip: Option<StripMode>,
+    log_file: Option<PathBuf>,
+    message_format: Option<MessageFormat>,
+    timeout: Option<Duration>,
     verbose: bool,
 }
 
-#[derive(Clone, Copy, Default)]
+/// How much to strip from the service ELF, trading debuggability for blob size
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum StripMode {
+    /// Keep debug info and symbols
+    None,
+    /// Remove debug info, keeping symbol names
+    Debuginfo,
+    /// Remove debug info and symbols
+    Symbols,
+}
+
+impl StripMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StripMode::None => "none",
+            StripMode::Debuginfo => "debuginfo",
+            StripMode::Symbols => "symbols",
+        }
+    }
+}
+
+/// How cargo reports diagnostics, as with `cargo build --message-format`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum MessageFormat {
+    /// Cargo's default rendering
+    Human,
+    /// One line per diagnostic
+    Short,
+    /// JSON messages on stdout, for editors and build tools
+    Json,
+}
+
+impl MessageFormat {
+    fn as_str(self) -> &'static str {
+        match self {
+            MessageFormat::Human => "human",
+            MessageFormat::Short => "short",
+            MessageFormat::Json => "json",
+        }
+    }
+}
+
+/// Timing information collected by a `profile_timings` build
+/// What a build produced
+#[derive(Debug, Clone)]
+pub struct BuildOutput {
+    /// The `.jam` blob
+    pub jam_path: PathBuf,
+    /// Set when `profile_timings` is
+    pub timings: Option<BuildTimings>,
+    /// ELF sizes before and after stripping, when the build stripped it
+    pub strip: Option<StripReport>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BuildTimings {
+    /// Wall-clock duration of the jam-pvm-build invocation
+    pub elapsed: Duration,
+    /// Cargo's HTML timings report, when jam-pvm-build supports `--timings`
+    pub report: Option<PathBuf>,
+    /// Per-crate timings read from cargo's JSON messages when it doesn't
+    pub crates: Vec<CrateTiming>,
+}
+
+/// How long one crate took to compile
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrateTiming {
+    pub name: String,
+    /// Exact when cargo reports `timing-info` messages, otherwise the time since
+    /// the previous crate finished, which is an estimate when crates compile in parallel
+    pub duration: Duration,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub enum BuildProfile {
     Debug,
     #[default]
     Release,
     Production,
+    /// A `[profile.<name>]` defined in the project's (or its workspace's) Cargo.toml
+    Custom(String),
 }
 
 impl BuildProfile {
-    fn as_str(&self) -> &'static str {
+    /// The profile called `name`: one of the built-in profiles, or a custom one
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "debug" | "dev" => BuildProfile::Debug,
+            "release" => BuildProfile::Release,
+            "production" => BuildProfile::Production,
+            name => BuildProfile::Custom(name.to_string()),
+        }
+    }
+
+    /// Name of the cargo profile behind this build profile
+    fn cargo_name(&self) -> &str {
+        match self {
+            BuildProfile::Debug => "dev",
+            profile => profile.as_str(),
+        }
+    }
+
+    /// Strip setting used when neither `--strip` nor Cargo.toml sets one: release
+    /// builds are stripped, debug builds keep their symbols
+    fn default_strip(&self) -> Option<StripMode> {
+        match self {
+            BuildProfile::Debug => Some(StripMode::None),
+            BuildProfile::Release | BuildProfile::Production => Some(StripMode::Symbols),
+            BuildProfile::Custom(_) => None,
+        }
+    }
+
+    fn as_str(&self) -> &str {
         match self {
             BuildProfile::Debug => "debug",
             BuildProfile::Release => "release",
             BuildProfile::Production => "production",
+            BuildProfile::Custom(name) => name,
         }
     }
 }
@@ -36,6 +172,15 @@ impl BuildPipeline {
             output_path: None,
             profile: BuildProfile::Release,
             auto_install: true,
+            frozen_toolchain: false,
+            emit_metadata: false,
+            profile_timings: false,
+            clean_intermediate: false,
+            reproducible: false,
+            strip: None,
+            log_file: None,
+            message_format: None,
+            timeout: None,
             verbose: false,
         }
     }
@@ -64,6 +209,60 @@ impl BuildPipeline {
         self
     }
 
+    /// Error instead of warning when the installed toolchain doesn't match the project's pin
+    pub fn frozen_toolchain(mut self, frozen: bool) -> Self {
+        self.frozen_toolchain = frozen;
+        self
+    }
+
+    /// Write a `<name>.metadata.json` interface description next to the blob
+    pub fn emit_metadata(mut self, emit: bool) -> Self {
+        self.emit_metadata = emit;
+        self
+    }
+
+    /// Ask cargo for a `--timings` report (when jam-pvm-build supports it)
+    pub fn profile_timings(mut self, profile: bool) -> Self {
+        self.profile_timings = profile;
+        self
+    }
+
+    /// Remove the target directory once the blob has been written
+    pub fn clean_intermediate(mut self, clean: bool) -> Self {
+        self.clean_intermediate = clean;
+        self
+    }
+
+    /// Pin timestamps and remap absolute paths so the blob is byte-identical across machines
+    pub fn reproducible(mut self, reproducible: bool) -> Self {
+        self.reproducible = reproducible;
+        self
+    }
+
+    /// Strip the service ELF before the blob is generated (default: by profile)
+    pub fn strip(mut self, strip: Option<StripMode>) -> Self {
+        self.strip = strip;
+        self
+    }
+
+    /// Write jam-pvm-build's full stdout/stderr to this file, whatever the verbosity
+    pub fn log_file(mut self, path: Option<PathBuf>) -> Self {
+        self.log_file = path;
+        self
+    }
+
+    /// Forward `--message-format` to cargo; JSON messages are passed through to stdout
+    pub fn message_format(mut self, format: Option<MessageFormat>) -> Self {
+        self.message_format = format;
+        self
+    }
+
+    /// Kill jam-pvm-build if it runs longer than this
+    pub fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
     pub fn verbose(mut self, verbose: bool) -> Self {
         self.verbose = verbose;
         self
@@ -71,16 +270,107 @@ impl BuildPipeline {
 
     /// Execute the PVM build pipeline using jam-pvm-build
     pub fn run(&self) -> Result<PathBuf> {
+        self.run_timed().map(|output| output.jam_path)
+    }
+
+    /// Execute the build, also returning timings when `profile_timings` is set
+    pub fn run_timed(&self) -> Result<BuildOutput> {
         // Check for required tools
         self.check_toolchain()?;
+        self.check_profile()?;
 
         // Build using jam-pvm-build
-        let jam_path = self.jam_pvm_build()?;
+        let cargo_timings = self.profile_timings && supports_timings();
+        // Without --timings, per-crate timings can be read from cargo's JSON messages
+        let crate_timings = self.profile_timings
+            && !cargo_timings
+            && self
+                .message_format
+                .is_none_or(|format| format == MessageFormat::Json)
+            && supports_message_format();
+        debug!(project = %self.project_path.display(), cargo_timings, crate_timings, "starting build");
+        if crate_timings {
+            eprintln!(
+                "{} jam-pvm-build does not support --timings, estimating per-crate timings from cargo's JSON messages",
+                style("!").yellow().bold()
+            );
+        } else if self.profile_timings && !cargo_timings {
+            eprintln!(
+                "{} jam-pvm-build does not support --timings, reporting total build time only",
+                style("!").yellow().bold()
+            );
+        }
 
-        Ok(jam_path)
+        if self.reproducible && find_lockfile(&self.project_path).is_none() {
+            eprintln!(
+                "{} No Cargo.lock found: dependency versions may differ between machines (run 'cargo generate-lockfile' and commit it)",
+                style("!").yellow().bold()
+            );
+        }
+
+        let started = Instant::now();
+        let (mut jam_path, crates) = self.jam_pvm_build(cargo_timings, crate_timings, false)?;
+        let strip = self.strip_elf()?;
+        if strip.is_some_and(|report| report.after < report.before) {
+            // Cargo sees the stripped ELF as up to date, so this only relinks the blob
+            debug!("relinking the blob from the stripped ELF");
+            jam_path = self.jam_pvm_build(false, false, true)?.0;
+        }
+        let timings = self.profile_timings.then(|| BuildTimings {
+            elapsed: started.elapsed(),
+            report: cargo_timings.then(|| self.timings_report()).flatten(),
+            crates,
+        });
+
+        if self.emit_metadata {
+            ServiceMetadata::collect(&self.project_path, &jam_path)?.write(&jam_path)?;
+        }
+
+        if self.clean_intermediate {
+            let removed = self.intermediates()?.clean(&jam_path)?;
+            debug!(removed, "removed intermediate artifacts");
+        }
+
+        Ok(BuildOutput {
+            jam_path,
+            timings,
+            strip,
+        })
+    }
+
+    /// Strip the ELF cargo just linked, measuring it before and after. Nothing is
+    /// stripped when the strip setting is `none` or left to Cargo.toml.
+    fn strip_elf(&self) -> Result<Option<StripReport>> {
+        let Some(mode) = self.effective_strip()? else {
+            return Ok(None);
+        };
+        if mode == StripMode::None {
+            return Ok(None);
+        }
+        let newest = self
+            .intermediates()?
+            .elf
+            .into_iter()
+            .max_by_key(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok());
+        let Some(elf) = newest else {
+            debug!("no service ELF found to strip");
+            return Ok(None);
+        };
+        let report = strip_elf(&elf, mode)?;
+        debug!(elf = %elf.display(), before = report.before, after = report.after, "stripped ELF");
+        Ok(Some(report))
     }
 
-    fn check_toolchain(&self) -> Result<()> {
+    /// Intermediate ELF and PolkaVM artifacts left in the project's target directory
+    pub fn intermediates(&self) -> Result<Intermediates> {
+        Ok(Intermediates::find(
+            &self.project_path,
+            &self.get_project_name()?,
+        ))
+    }
+
+    /// Check that jam-pvm-build and the JAM toolchain are installed
+    pub fn check_toolchain(&self) -> Result<()> {
         // Check for jam-pvm-build
         let jam_build_check = Command::new("jam-pvm-build").arg("--version").output();
 
@@ -100,10 +390,80 @@ impl BuildPipeline {
             });
         }
 
+        self.check_toolchain_pin(&config)
+    }
+
+    /// Check that a custom profile is defined where cargo reads profiles from.
+    ///
+    /// Cargo only honors `[profile.*]` in the workspace root, so a workspace member's
+    /// profiles are looked up in the root's Cargo.toml.
+    pub fn check_profile(&self) -> Result<()> {
+        let BuildProfile::Custom(ref name) = self.profile else {
+            return Ok(());
+        };
+
+        let (cargo_toml, profile) = self.profile_table(name)?;
+        if !profile.is_some_and(|p| p.is_table()) {
+            return Err(CargoJamError::InvalidArgument {
+                arg: "--profile".to_string(),
+                reason: format!(
+                    "no [profile.{}] in {} (define it there, or use debug, release or production)",
+                    name,
+                    cargo_toml.display()
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    /// The Cargo.toml cargo reads profiles from, and its `[profile.<name>]` entry
+    fn profile_table(&self, name: &str) -> Result<(PathBuf, Option<toml::Value>)> {
+        let manifest_dir =
+            find_workspace_root(&self.project_path).unwrap_or_else(|| self.project_path.clone());
+        let cargo_toml = manifest_dir.join("Cargo.toml");
+        let content = std::fs::read_to_string(&cargo_toml)?;
+        let manifest: toml::Value = toml::from_str(&content)
+            .map_err(|e| CargoJamError::Build(format!("Failed to parse Cargo.toml: {}", e)))?;
+
+        let profile = manifest
+            .get("profile")
+            .and_then(|profiles| profiles.get(name))
+            .cloned();
+        Ok((cargo_toml, profile))
+    }
+
+    /// Strip setting for the service ELF: `--strip`, else the profile's default
+    /// unless Cargo.toml already sets `strip` for it (then cargo strips as configured)
+    fn effective_strip(&self) -> Result<Option<StripMode>> {
+        if self.strip.is_some() {
+            return Ok(self.strip);
+        }
+        let (_, profile) = self.profile_table(self.profile.cargo_name())?;
+        if profile.is_some_and(|p| p.get("strip").is_some()) {
+            return Ok(None);
+        }
+        Ok(self.profile.default_strip())
+    }
+
+    /// Refuse a toolchain other than the one the project's `jam-toolchain.toml` pins
+    fn check_toolchain_pin(&self, config: &ToolchainConfig) -> Result<()> {
+        let pinned = config.check_required_version(&self.project_path)?;
+        if pinned.is_none() && self.frozen_toolchain {
+            return Err(CargoJamError::Build(format!(
+                "--frozen-toolchain requires a {} in {}",
+                TOOLCHAIN_PIN_FILE,
+                self.project_path.display()
+            )));
+        }
         Ok(())
     }
 
-    fn jam_pvm_build(&self) -> Result<PathBuf> {
+    fn jam_pvm_build(
+        &self,
+        cargo_timings: bool,
+        crate_timings: bool,
+        relink: bool,
+    ) -> Result<(PathBuf, Vec<CrateTiming>)> {
         let mut cmd = Command::new("jam-pvm-build");
 
         // Set the project path
@@ -125,26 +485,132 @@ impl BuildPipeline {
             cmd.arg("--auto-install");
         }
 
-        if self.verbose {
-            println!(
+        if cargo_timings {
+            cmd.arg("--timings");
+        }
+
+        if let Some(format) = self.message_format.filter(|_| !relink) {
+            if !supports_message_format() {
+                return Err(CargoJamError::InvalidArgument {
+                    arg: "--message-format".to_string(),
+                    reason: "the installed jam-pvm-build does not accept --message-format (update it with 'cargo install jam-pvm-build')".to_string(),
+                });
+            }
+            cmd.arg("--message-format").arg(format.as_str());
+        } else if crate_timings {
+            // JSON messages on stdout, diagnostics still rendered on stderr
+            cmd.arg("--message-format").arg("json-render-diagnostics");
+        }
+
+        if self.effective_strip()?.is_some() {
+            // Cargo leaves the ELF whole so `strip_elf` can measure what stripping saves
+            let key = strip_env_var(self.profile.cargo_name());
+            debug!(%key, "leaving the ELF unstripped for strip_elf");
+            cmd.env(key, StripMode::None.as_str());
+        }
+
+        if self.reproducible {
+            for (key, value) in reproducible_env(&self.project_path) {
+                debug!(key, %value, "reproducible build environment");
+                cmd.env(key, value);
+            }
+        }
+
+        if self.verbose && !relink {
+            // stdout is reserved for JSON messages
+            eprintln!(
                 "Running: jam-pvm-build {:?}",
                 cmd.get_args().collect::<Vec<_>>()
             );
         }
 
-        let output = cmd
-            .output()
-            .map_err(|e| CargoJamError::Build(format!("Failed to execute jam-pvm-build: {}", e)))?;
+        let run = match crate_timings {
+            true => output_with_line_times(&mut cmd, self.timeout),
+            false => output_with_timeout(&mut cmd, self.timeout).map(|output| (output, Vec::new())),
+        };
+        let (output, line_times) = run.map_err(|e| {
+            let e = match e {
+                CargoJamError::Io(e) => {
+                    CargoJamError::Build(format!("Failed to execute jam-pvm-build: {}", e))
+                }
+                e => e,
+            };
+            match self.write_log(&cmd, &e.to_string(), &[], &[]) {
+                Ok(Some(log)) => CargoJamError::Build(format!("{} (log: {})", e, log.display())),
+                _ => e,
+            }
+        })?;
+
+        // The relink repeats the build's log and messages, so only the build writes them
+        let log = match relink {
+            true => None,
+            false => self.write_log(
+                &cmd,
+                &output.status.to_string(),
+                &output.stdout,
+                &output.stderr,
+            )?,
+        };
+
+        // Diagnostics matter most when the build fails, so pass them through first
+        let json = !relink && self.message_format == Some(MessageFormat::Json);
+        if json {
+            let mut stdout = std::io::stdout().lock();
+            stdout.write_all(&output.stdout)?;
+            stdout.flush()?;
+        }
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            let stdout = String::from_utf8_lossy(&output.stdout);
+            let missing = missing_toolchain(&stderr);
+            let hint = if !self.auto_install && missing.is_some() {
+                MISSING_TOOLCHAIN_HINT
+            } else {
+                ""
+            };
+
+            if let Some(MissingToolchain::Target { target, toolchain }) = missing {
+                let output = match log {
+                    Some(log) => format!(
+                        "Compiler output (full log in {}):\n{}",
+                        log.display(),
+                        tail(&stderr, LOG_TAIL_LINES)
+                    ),
+                    None => format!("Compiler output:\n{}", stderr.trim_end()),
+                };
+                return Err(CargoJamError::Build(format!(
+                    "{}\n\n{}{}",
+                    missing_target_message(&target, &toolchain),
+                    output,
+                    hint
+                )));
+            }
+
+            if let Some(log) = log {
+                return Err(CargoJamError::Build(format!(
+                    "jam-pvm-build failed ({}), full output in {}:\n{}{}",
+                    output.status,
+                    log.display(),
+                    tail(&stderr, LOG_TAIL_LINES),
+                    hint
+                )));
+            }
+
+            let stdout = match json || crate_timings {
+                true => Default::default(),
+                false => String::from_utf8_lossy(&output.stdout),
+            };
             return Err(CargoJamError::Build(format!(
-                "jam-pvm-build failed:\n{}\n{}",
-                stdout, stderr
+                "jam-pvm-build failed:\n{}\n{}{}",
+                stdout, stderr, hint
             )));
         }
 
+        let crates = match crate_timings {
+            true => parse_crate_timings(&output.stdout, &line_times),
+            false => Vec::new(),
+        };
+
         // Determine output path
         let output_path = if let Some(ref path) = self.output_path {
             path.clone()
@@ -159,7 +625,8 @@ impl BuildPipeline {
             let project_name = self.get_project_name()?;
             let alt_path = self.project_path.join(format!("{}.jam", project_name));
             if alt_path.exists() {
-                return Ok(alt_path);
+                check_blob_size(&alt_path)?;
+                return Ok((alt_path, crates));
             }
 
             return Err(CargoJamError::Build(format!(
@@ -168,7 +635,45 @@ impl BuildPipeline {
             )));
         }
 
-        Ok(output_path)
+        check_blob_size(&output_path)?;
+        Ok((output_path, crates))
+    }
+
+    /// Write the command, its outcome and full output to the log file, if one was requested
+    fn write_log(
+        &self,
+        cmd: &Command,
+        outcome: &str,
+        stdout: &[u8],
+        stderr: &[u8],
+    ) -> Result<Option<PathBuf>> {
+        let Some(ref path) = self.log_file else {
+            return Ok(None);
+        };
+
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let args: Vec<_> = cmd.get_args().map(|a| a.to_string_lossy()).collect();
+        let log = format!(
+            "$ jam-pvm-build {}\n# {}\n\n--- stdout ---\n{}\n--- stderr ---\n{}",
+            args.join(" "),
+            outcome,
+            String::from_utf8_lossy(stdout),
+            String::from_utf8_lossy(stderr)
+        );
+        std::fs::write(path, log)?;
+
+        Ok(Some(path.clone()))
+    }
+
+    /// Cargo's HTML timings report for the project, if one was written
+    fn timings_report(&self) -> Option<PathBuf> {
+        let report = target_dir(&self.project_path)
+            .join("cargo-timings")
+            .join("cargo-timing.html");
+        report.exists().then_some(report)
     }
 
     fn get_project_name(&self) -> Result<String> {
@@ -187,3 +692,294 @@ impl BuildPipeline {
             .ok_or_else(|| CargoJamError::Build("Missing package name in Cargo.toml".to_string()))
     }
 }
+
+/// Environment variable overriding `strip` for a cargo profile
+fn strip_env_var(cargo_profile: &str) -> String {
+    format!(
+        "CARGO_PROFILE_{}_STRIP",
+        cargo_profile.to_uppercase().replace('-', "_")
+    )
+}
+
+/// Fail on a blob too small to be a service, as left by a link that failed after creating the file
+fn check_blob_size(path: &Path) -> Result<()> {
+    let size = std::fs::metadata(path)?.len();
+    if size < MIN_BLOB_SIZE {
+        return Err(CargoJamError::Build(format!(
+            "jam-pvm-build reported success but {} is {} bytes, too small to be a service blob; rebuild, or check the build log with --verbose",
+            path.display(),
+            size
+        )));
+    }
+    Ok(())
+}
+
+/// A part of the rustc toolchain that a failed build reported missing
+#[derive(Debug, PartialEq)]
+enum MissingToolchain {
+    /// The compilation target, so rustc can't find `core` for it
+    Target { target: String, toolchain: String },
+    /// A toolchain or component rustup reports as not installed
+    Component,
+}
+
+/// What, if anything, a failed build's output says is missing from the toolchain.
+///
+/// rustc reports a missing target as ``can't find crate for `core` `` followed by a
+/// note that the target "may not be installed"; the target and toolchain are taken
+/// from the output when it names them.
+fn missing_toolchain(stderr: &str) -> Option<MissingToolchain> {
+    let lower = stderr.to_lowercase();
+    if lower.contains("target may not be installed")
+        || lower.contains("can't find crate for `core`")
+    {
+        let capture = |pattern: &str| {
+            regex::Regex::new(pattern)
+                .ok()?
+                .captures(stderr)
+                .map(|c| c[1].to_string())
+        };
+        return Some(MissingToolchain::Target {
+            target: capture(r"the `([^`]+)` target may not be installed")
+                .unwrap_or_else(|| PVM_TARGET.to_string()),
+            toolchain: capture(r"toolchain '([^']+)'").unwrap_or_else(|| "nightly".to_string()),
+        });
+    }
+
+    (lower.contains("rustup component add")
+        || lower.contains("rustup toolchain install")
+        || lower.contains("is not installed"))
+    .then_some(MissingToolchain::Component)
+}
+
+/// Explain a missing compilation target, with the rustup commands that install it
+fn missing_target_message(target: &str, toolchain: &str) -> String {
+    format!(
+        "The Rust target `{target}` is not installed for the `{toolchain}` toolchain jam-pvm-build compiles with, \
+         so the standard library for it can't be found. Install the target and the standard library sources with:\n\n  \
+         rustup toolchain install {toolchain}\n  \
+         rustup target add {target} --toolchain {toolchain}\n  \
+         rustup component add rust-src --toolchain {toolchain}\n\n\
+         then run the build again."
+    )
+}
+
+/// Last `lines` lines of `text`, for concise errors when the full output is in a log file
+fn tail(text: &str, lines: usize) -> String {
+    let all: Vec<&str> = text.trim_end().lines().collect();
+    all[all.len().saturating_sub(lines)..].join("\n")
+}
+
+/// Per-crate timings from cargo's JSON messages on stdout, given when each line arrived.
+///
+/// `timing-info` messages carry exact durations and are used when present. Otherwise
+/// each `compiler-artifact` is timed from the artifact before it.
+fn parse_crate_timings(stdout: &[u8], line_times: &[Duration]) -> Vec<CrateTiming> {
+    let mut exact = Vec::new();
+    let mut estimated = Vec::new();
+    let mut previous = Duration::ZERO;
+    for (line, &arrived) in stdout.split(|&b| b == b'\n').zip(line_times) {
+        let Ok(message) = serde_json::from_slice::<serde_json::Value>(line) else {
+            continue;
+        };
+        let Some(name) = message["target"]["name"].as_str() else {
+            continue;
+        };
+        match message["reason"].as_str() {
+            Some("timing-info") => {
+                if let Some(secs) = message["duration"].as_f64() {
+                    exact.push(CrateTiming {
+                        name: name.to_string(),
+                        duration: Duration::from_secs_f64(secs),
+                    });
+                }
+            }
+            Some("compiler-artifact") => {
+                estimated.push(CrateTiming {
+                    name: name.to_string(),
+                    duration: arrived.saturating_sub(previous),
+                });
+                previous = arrived;
+            }
+            _ => {}
+        }
+    }
+    if exact.is_empty() {
+        estimated
+    } else {
+        exact
+    }
+}
+
+/// Whether the installed jam-pvm-build accepts cargo's `--message-format` flag
+fn supports_message_format() -> bool {
+    Command::new("jam-pvm-build")
+        .arg("--help")
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).contains("--message-format"))
+        .unwrap_or(false)
+}
+
+/// Whether the installed jam-pvm-build accepts cargo's `--timings` flag
+fn supports_timings() -> bool {
+    Command::new("jam-pvm-build")
+        .arg("--help")
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).contains("--timings"))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_profile_must_be_defined() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"svc\"\n\n[profile.bench-size]\ninherits = \"release\"\nopt-level = \"z\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(BuildProfile::from_name("release"), BuildProfile::Release);
+        let pipeline = |name: &str| {
+            BuildPipeline::new(dir.path().to_path_buf()).profile(BuildProfile::from_name(name))
+        };
+        assert!(pipeline("bench-size").check_profile().is_ok());
+        assert!(pipeline("production").check_profile().is_ok());
+
+        let err = pipeline("bench-sizes").check_profile().unwrap_err();
+        assert!(err.to_string().contains("no [profile.bench-sizes]"));
+    }
+
+    #[test]
+    fn test_strip_setting() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"svc\"\n\n[profile.bench-size]\ninherits = \"release\"\nstrip = false\n",
+        )
+        .unwrap();
+        let pipeline = |profile: &str, strip: Option<StripMode>| {
+            BuildPipeline::new(dir.path().to_path_buf())
+                .profile(BuildProfile::from_name(profile))
+                .strip(strip)
+                .effective_strip()
+                .unwrap()
+        };
+
+        assert_eq!(pipeline("release", None), Some(StripMode::Symbols));
+        assert_eq!(pipeline("debug", None), Some(StripMode::None));
+        // Cargo.toml's own setting is respected unless --strip is given
+        assert_eq!(pipeline("bench-size", None), None);
+        assert_eq!(
+            pipeline("bench-size", Some(StripMode::Debuginfo)),
+            Some(StripMode::Debuginfo)
+        );
+
+        assert_eq!(strip_env_var("dev"), "CARGO_PROFILE_DEV_STRIP");
+        assert_eq!(
+            strip_env_var("bench-size"),
+            "CARGO_PROFILE_BENCH_SIZE_STRIP"
+        );
+    }
+
+    #[test]
+    fn test_missing_toolchain() {
+        assert_eq!(
+            missing_toolchain(
+                "error: component 'rust-src' for target 'x86_64-unknown-linux-gnu' is not installed"
+            ),
+            Some(MissingToolchain::Component)
+        );
+        assert_eq!(
+            missing_toolchain(
+                "error[E0463]: can't find crate for `core`\n  = note: the `riscv64emac-unknown-none-polkavm` target may not be installed\n",
+            ),
+            Some(MissingToolchain::Target {
+                target: "riscv64emac-unknown-none-polkavm".to_string(),
+                toolchain: "nightly".to_string(),
+            })
+        );
+        assert_eq!(
+            missing_toolchain(
+                "error: can't find crate for `core` (toolchain 'nightly-2025-05-10-x86_64-unknown-linux-gnu')",
+            ),
+            Some(MissingToolchain::Target {
+                target: PVM_TARGET.to_string(),
+                toolchain: "nightly-2025-05-10-x86_64-unknown-linux-gnu".to_string(),
+            })
+        );
+        assert_eq!(
+            missing_toolchain("error[E0425]: cannot find value `x` in this scope"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_missing_target_message() {
+        let message = missing_target_message("riscv64emac-unknown-none-polkavm", "nightly");
+        assert!(message.contains("`riscv64emac-unknown-none-polkavm` is not installed"));
+        assert!(message
+            .contains("rustup target add riscv64emac-unknown-none-polkavm --toolchain nightly"));
+    }
+
+    #[test]
+    fn test_check_blob_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let blob = dir.path().join("svc.jam");
+
+        std::fs::write(&blob, b"").unwrap();
+        let err = check_blob_size(&blob).unwrap_err();
+        assert!(err.to_string().contains("is 0 bytes"));
+
+        std::fs::write(&blob, vec![0u8; 4096]).unwrap();
+        assert!(check_blob_size(&blob).is_ok());
+    }
+
+    #[test]
+    fn test_parse_crate_timings() {
+        let artifact = |name: &str| {
+            format!(
+                r#"{{"reason":"compiler-artifact","target":{{"name":"{}"}},"fresh":false}}"#,
+                name
+            )
+        };
+        let stdout = format!(
+            "{}\n{}\n{}\n",
+            artifact("serde"),
+            r#"{"reason":"build-finished","success":true}"#,
+            artifact("svc")
+        );
+        let times = [
+            Duration::from_secs(2),
+            Duration::from_secs(3),
+            Duration::from_secs(7),
+        ];
+        let crates = parse_crate_timings(stdout.as_bytes(), &times);
+        assert_eq!(
+            crates,
+            [
+                CrateTiming {
+                    name: "serde".to_string(),
+                    duration: Duration::from_secs(2)
+                },
+                CrateTiming {
+                    name: "svc".to_string(),
+                    duration: Duration::from_secs(5)
+                },
+            ]
+        );
+
+        // Exact durations win over arrival times
+        let stdout = format!(
+            "{}\n{}\n",
+            artifact("serde"),
+            r#"{"reason":"timing-info","target":{"name":"serde"},"duration":1.5}"#
+        );
+        let crates = parse_crate_timings(stdout.as_bytes(), &times);
+        assert_eq!(crates.len(), 1);
+        assert_eq!(crates[0].duration, Duration::from_millis(1500));
+    }
+}
@@ -0,0 +1,222 @@
+use std::path::{Path, PathBuf};
+
+/// Prefix the project directory is remapped to in debug info and panic locations
+const PROJECT_PREFIX: &str = "/build";
+
+/// Prefix the cargo home (registry and git sources) is remapped to
+const CARGO_HOME_PREFIX: &str = "/cargo";
+
+/// Prefix the rustup home (toolchain sysroots, including the `rust-src` that
+/// `build-std` compiles) is remapped to
+const RUSTUP_HOME_PREFIX: &str = "/rustup";
+
+/// Separator of `CARGO_ENCODED_RUSTFLAGS`
+const FLAG_SEPARATOR: char = '\x1f';
+
+/// Environment variables that make a jam-pvm-build invocation deterministic.
+///
+/// Timestamps come from `SOURCE_DATE_EPOCH` (kept if already set, otherwise
+/// the project's last commit time), and absolute paths of the project and the
+/// cargo and rustup homes are remapped so the blob doesn't depend on where it
+/// was built. Existing rustflags are kept: `CARGO_ENCODED_RUSTFLAGS` overrides
+/// every other source, so the flags cargo would otherwise have used (from the
+/// environment or `build.rustflags` in `.cargo/config.toml`) are carried over.
+pub fn reproducible_env(project: &Path) -> Vec<(&'static str, String)> {
+    let project = std::path::absolute(project).unwrap_or_else(|_| project.to_path_buf());
+
+    let cargo_home = env_dir("CARGO_HOME", ".cargo");
+    let rustup_home = env_dir("RUSTUP_HOME", ".rustup");
+
+    let epoch = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+        .or_else(|| commit_time(&project).map(|t| t.to_string()))
+        .unwrap_or_else(|| "0".to_string());
+
+    let rustflags = match std::env::var("CARGO_ENCODED_RUSTFLAGS") {
+        Ok(flags) => flags
+            .split(FLAG_SEPARATOR)
+            .filter(|f| !f.is_empty())
+            .map(String::from)
+            .collect(),
+        Err(_) => match std::env::var("RUSTFLAGS") {
+            Ok(flags) => flags.split_whitespace().map(String::from).collect(),
+            Err(_) => config_rustflags(&project, cargo_home.as_deref()),
+        },
+    };
+
+    build_env(
+        &project,
+        cargo_home.as_deref(),
+        rustup_home.as_deref(),
+        rustflags,
+        epoch,
+    )
+}
+
+/// Directory named by `var`, falling back to `default` in the home directory
+fn env_dir(var: &str, default: &str) -> Option<PathBuf> {
+    std::env::var_os(var)
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(default)))
+}
+
+/// `build.rustflags` from the cargo config files that apply to `project`.
+///
+/// Files are read from lowest to highest precedence (the cargo home, then the
+/// project's ancestors from the root down): arrays are appended, as cargo
+/// joins them, while a string replaces what came before.
+fn config_rustflags(project: &Path, cargo_home: Option<&Path>) -> Vec<String> {
+    let mut dirs: Vec<PathBuf> = project
+        .ancestors()
+        .map(|dir| dir.join(".cargo"))
+        .filter(|dir| Some(dir.as_path()) != cargo_home)
+        .collect();
+    dirs.extend(cargo_home.map(Path::to_path_buf));
+
+    let mut rustflags = Vec::new();
+    for dir in dirs.iter().rev() {
+        let Some(config) = ["config.toml", "config"]
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|contents| contents.parse::<toml::Table>().ok())
+        else {
+            continue;
+        };
+        match config.get("build").and_then(|build| build.get("rustflags")) {
+            Some(toml::Value::String(flags)) => {
+                rustflags = flags.split_whitespace().map(String::from).collect();
+            }
+            Some(toml::Value::Array(flags)) => rustflags.extend(
+                flags
+                    .iter()
+                    .filter_map(|flag| flag.as_str())
+                    .map(String::from),
+            ),
+            _ => {}
+        }
+    }
+    rustflags
+}
+
+fn build_env(
+    project: &Path,
+    cargo_home: Option<&Path>,
+    rustup_home: Option<&Path>,
+    mut rustflags: Vec<String>,
+    epoch: String,
+) -> Vec<(&'static str, String)> {
+    for (dir, prefix) in [
+        (cargo_home, CARGO_HOME_PREFIX),
+        (rustup_home, RUSTUP_HOME_PREFIX),
+    ] {
+        if let Some(dir) = dir {
+            rustflags.push(format!("--remap-path-prefix={}={}", dir.display(), prefix));
+        }
+    }
+    // Later remappings win, so the project takes precedence over a home directory around it
+    rustflags.push(format!(
+        "--remap-path-prefix={}={}",
+        project.display(),
+        PROJECT_PREFIX
+    ));
+
+    vec![
+        ("SOURCE_DATE_EPOCH", epoch),
+        (
+            "CARGO_ENCODED_RUSTFLAGS",
+            rustflags.join(&FLAG_SEPARATOR.to_string()),
+        ),
+        ("CARGO_INCREMENTAL", "0".to_string()),
+        ("TZ", "UTC".to_string()),
+        ("LC_ALL", "C".to_string()),
+    ]
+}
+
+/// Commit time (seconds since the epoch) of HEAD in the repository containing `project`
+fn commit_time(project: &Path) -> Option<i64> {
+    let repo = git2::Repository::discover(project).ok()?;
+    let commit = repo.head().ok()?.peel_to_commit().ok()?;
+    Some(commit.time().seconds())
+}
+
+/// The `Cargo.lock` governing a project, in its directory or an enclosing workspace
+pub fn find_lockfile(project: &Path) -> Option<PathBuf> {
+    project
+        .ancestors()
+        .map(|dir| dir.join("Cargo.lock"))
+        .find(|lock| lock.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_env() {
+        let env = build_env(
+            Path::new("/home/me/svc"),
+            Some(Path::new("/home/me/.cargo")),
+            Some(Path::new("/home/me/.rustup")),
+            vec!["-Copt-level=3".to_string()],
+            "1700000000".to_string(),
+        );
+        let get = |key: &str| env.iter().find(|(k, _)| *k == key).map(|(_, v)| v.as_str());
+
+        assert_eq!(get("SOURCE_DATE_EPOCH"), Some("1700000000"));
+        assert_eq!(
+            get("CARGO_ENCODED_RUSTFLAGS"),
+            Some(
+                "-Copt-level=3\x1f--remap-path-prefix=/home/me/.cargo=/cargo\x1f--remap-path-prefix=/home/me/.rustup=/rustup\x1f--remap-path-prefix=/home/me/svc=/build"
+            )
+        );
+        assert_eq!(get("CARGO_INCREMENTAL"), Some("0"));
+    }
+
+    #[test]
+    fn test_config_rustflags() {
+        let temp = tempfile::tempdir().unwrap();
+        let cargo_home = temp.path().join("cargo-home");
+        let project = temp.path().join("workspace").join("svc");
+        std::fs::create_dir_all(&cargo_home).unwrap();
+        std::fs::create_dir_all(project.join(".cargo")).unwrap();
+        std::fs::create_dir_all(temp.path().join("workspace").join(".cargo")).unwrap();
+
+        std::fs::write(
+            cargo_home.join("config.toml"),
+            "[build]\nrustflags = [\"-Chome\"]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            temp.path()
+                .join("workspace")
+                .join(".cargo")
+                .join("config.toml"),
+            "[build]\nrustflags = [\"-Cworkspace\"]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            project.join(".cargo").join("config.toml"),
+            "[build]\nrustflags = [\"-Cproject\"]\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config_rustflags(&project, Some(&cargo_home)),
+            ["-Chome", "-Cworkspace", "-Cproject"]
+        );
+
+        // A string replaces the flags of lower precedence files
+        std::fs::write(
+            project.join(".cargo").join("config.toml"),
+            "[build]\nrustflags = \"-Ca -Cb\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config_rustflags(&project, Some(&cargo_home)),
+            ["-Ca", "-Cb"]
+        );
+    }
+}
@@ -0,0 +1,178 @@
+use crate::error::{CargoJamError, Result};
+use crate::hex;
+use ring::rand::{SecureRandom, SystemRandom};
+use ring::signature::{Ed25519KeyPair, KeyPair, UnparsedPublicKey, ED25519};
+use std::path::{Path, PathBuf};
+
+/// Length in bytes of an ed25519 private key seed and of a public key
+const KEY_LEN: usize = 32;
+
+/// An ed25519 signing key, stored on disk as the hex-encoded 32-byte seed
+pub struct SigningKey {
+    seed: [u8; KEY_LEN],
+    pair: Ed25519KeyPair,
+}
+
+impl SigningKey {
+    /// Generate a new random key
+    pub fn generate() -> Result<Self> {
+        let mut seed = [0u8; KEY_LEN];
+        SystemRandom::new()
+            .fill(&mut seed)
+            .map_err(|_| CargoJamError::Signature("Failed to generate a random key".to_string()))?;
+        Self::from_seed(seed)
+    }
+
+    /// Load a key written by `save`
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            CargoJamError::Signature(format!(
+                "Failed to read signing key {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let seed = hex::decode(content.trim())
+            .and_then(|bytes| <[u8; KEY_LEN]>::try_from(bytes).ok())
+            .ok_or_else(|| {
+                CargoJamError::Signature(format!(
+                    "{} is not a signing key (expected {} hex characters)",
+                    path.display(),
+                    KEY_LEN * 2
+                ))
+            })?;
+        Self::from_seed(seed)
+    }
+
+    fn from_seed(seed: [u8; KEY_LEN]) -> Result<Self> {
+        let pair = Ed25519KeyPair::from_seed_unchecked(&seed)
+            .map_err(|e| CargoJamError::Signature(format!("Invalid signing key: {}", e)))?;
+        Ok(Self { seed, pair })
+    }
+
+    /// Write the key (readable only by the owner on Unix) and its public key to `<path>.pub`
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+
+        use std::io::Write;
+        let mut file = options.open(path)?;
+        writeln!(file, "{}", hex::encode(&self.seed))?;
+        std::fs::write(public_key_path(path), format!("{}\n", self.public_key()))?;
+        Ok(())
+    }
+
+    /// Hex-encoded private key seed, as stored on disk
+    pub fn seed_hex(&self) -> String {
+        hex::encode(&self.seed)
+    }
+
+    /// Hex-encoded public key
+    pub fn public_key(&self) -> String {
+        hex::encode(self.pair.public_key().as_ref())
+    }
+
+    /// Sign a blob, returning the hex-encoded signature
+    pub fn sign(&self, data: &[u8]) -> String {
+        hex::encode(self.pair.sign(data).as_ref())
+    }
+}
+
+/// Detached signature path for a blob (`my-service.jam` → `my-service.jam.sig`)
+pub fn signature_path(blob: &Path) -> PathBuf {
+    let mut path = blob.as_os_str().to_owned();
+    path.push(".sig");
+    PathBuf::from(path)
+}
+
+/// Public key path for a signing key (`signing.key` → `signing.key.pub`)
+pub fn public_key_path(key: &Path) -> PathBuf {
+    let mut path = key.as_os_str().to_owned();
+    path.push(".pub");
+    PathBuf::from(path)
+}
+
+/// Verify a blob's detached signature against any of `public_keys`, returning the key that matched
+pub fn verify_blob(blob: &Path, signature: &Path, public_keys: &[String]) -> Result<String> {
+    if public_keys.is_empty() {
+        return Err(CargoJamError::Signature(
+            "No trusted public keys. Generate one with 'cargo polkajam sign --generate-key' or pass --public-key".to_string(),
+        ));
+    }
+
+    let data = std::fs::read(blob)?;
+    let content = std::fs::read_to_string(signature).map_err(|e| {
+        CargoJamError::Signature(format!(
+            "Failed to read signature {}: {}",
+            signature.display(),
+            e
+        ))
+    })?;
+    let signature_bytes = hex::decode(content.trim()).ok_or_else(|| {
+        CargoJamError::Signature(format!("{} is not a hex signature", signature.display()))
+    })?;
+
+    public_keys
+        .iter()
+        .find(|key| {
+            hex::decode(key).is_some_and(|key| {
+                UnparsedPublicKey::new(&ED25519, key)
+                    .verify(&data, &signature_bytes)
+                    .is_ok()
+            })
+        })
+        .cloned()
+        .ok_or_else(|| {
+            CargoJamError::Signature(format!(
+                "{} does not carry a valid signature from a trusted key",
+                blob.display()
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let dir = tempfile::tempdir().unwrap();
+        let key_path = dir.path().join("signing.key");
+        let blob = dir.path().join("service.jam");
+        std::fs::write(&blob, b"PVM blob").unwrap();
+
+        let key = SigningKey::generate().unwrap();
+        key.save(&key_path).unwrap();
+        assert!(
+            key.save(&key_path).is_err(),
+            "existing keys are not overwritten"
+        );
+
+        let key = SigningKey::load(&key_path).unwrap();
+        let public_key = std::fs::read_to_string(public_key_path(&key_path)).unwrap();
+        assert_eq!(public_key.trim(), key.public_key());
+
+        std::fs::write(signature_path(&blob), key.sign(b"PVM blob")).unwrap();
+        let trusted = vec![key.public_key()];
+        assert_eq!(
+            verify_blob(&blob, &signature_path(&blob), &trusted).unwrap(),
+            key.public_key()
+        );
+
+        std::fs::write(&blob, b"tampered").unwrap();
+        assert!(verify_blob(&blob, &signature_path(&blob), &trusted).is_err());
+
+        let other = vec![SigningKey::generate().unwrap().public_key()];
+        std::fs::write(&blob, b"PVM blob").unwrap();
+        assert!(verify_blob(&blob, &signature_path(&blob), &other).is_err());
+    }
+}
@@ -0,0 +1,493 @@
+use crate::build::pipeline::StripMode;
+use crate::error::{CargoJamError, Result};
+use std::path::Path;
+
+/// Section header type of a symbol table
+const SHT_SYMTAB: u32 = 2;
+/// Section header type of relocations with addends
+const SHT_RELA: u32 = 4;
+/// Section header type of a section occupying no file space
+const SHT_NOBITS: u32 = 8;
+/// Section header type of relocations without addends
+const SHT_REL: u32 = 9;
+/// Section flag: occupies memory at run time
+const SHF_ALLOC: u64 = 0x2;
+/// Section flag: `sh_info` holds a section index
+const SHF_INFO_LINK: u64 = 0x40;
+
+/// Sizes of an ELF before and after stripping it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StripReport {
+    pub mode: StripMode,
+    pub before: u64,
+    pub after: u64,
+}
+
+/// Where the fields of a 32- or 64-bit little-endian ELF live
+#[derive(Clone, Copy)]
+struct Layout {
+    is64: bool,
+}
+
+impl Layout {
+    fn word(self) -> usize {
+        if self.is64 {
+            8
+        } else {
+            4
+        }
+    }
+
+    fn read(self, bytes: &[u8], offset: usize, size: usize) -> Option<u64> {
+        let field = bytes.get(offset..offset + size)?;
+        Some(
+            field
+                .iter()
+                .rev()
+                .fold(0u64, |value, byte| (value << 8) | u64::from(*byte)),
+        )
+    }
+
+    fn write(self, bytes: &mut [u8], offset: usize, size: usize, value: u64) {
+        for (i, byte) in bytes[offset..offset + size].iter_mut().enumerate() {
+            *byte = (value >> (8 * i)) as u8;
+        }
+    }
+
+    /// Offset and size of a section header field: name, type, flags, offset, size,
+    /// link, info and alignment
+    fn shdr_field(self, field: ShdrField) -> (usize, usize) {
+        match (field, self.is64) {
+            (ShdrField::Name, _) => (0, 4),
+            (ShdrField::Type, _) => (4, 4),
+            (ShdrField::Flags, true) => (8, 8),
+            (ShdrField::Flags, false) => (8, 4),
+            (ShdrField::Offset, true) => (24, 8),
+            (ShdrField::Offset, false) => (16, 4),
+            (ShdrField::Size, true) => (32, 8),
+            (ShdrField::Size, false) => (20, 4),
+            (ShdrField::Link, true) => (40, 4),
+            (ShdrField::Link, false) => (24, 4),
+            (ShdrField::Info, true) => (44, 4),
+            (ShdrField::Info, false) => (28, 4),
+            (ShdrField::Align, true) => (48, 8),
+            (ShdrField::Align, false) => (32, 4),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ShdrField {
+    Name,
+    Type,
+    Flags,
+    Offset,
+    Size,
+    Link,
+    Info,
+    Align,
+}
+
+/// A section header, kept raw so unchanged fields are written back as they were
+struct Section {
+    header: Vec<u8>,
+    name: String,
+    kind: u32,
+    flags: u64,
+    offset: u64,
+    size: u64,
+    link: u32,
+    info: u32,
+    align: u64,
+}
+
+impl Section {
+    fn has_data(&self) -> bool {
+        self.kind != SHT_NOBITS
+    }
+
+    fn is_alloc(&self) -> bool {
+        self.flags & SHF_ALLOC != 0
+    }
+
+    fn is_relocation(&self) -> bool {
+        self.kind == SHT_REL || self.kind == SHT_RELA
+    }
+}
+
+/// A parsed ELF: its header fields and section table
+struct Elf {
+    layout: Layout,
+    phoff: u64,
+    phentsize: u64,
+    phnum: u64,
+    shentsize: usize,
+    shstrndx: usize,
+    sections: Vec<Section>,
+}
+
+impl Elf {
+    fn parse(bytes: &[u8]) -> Option<Self> {
+        if bytes.get(..4)? != b"\x7fELF" || *bytes.get(5)? != 1 {
+            return None;
+        }
+        let layout = Layout {
+            is64: *bytes.get(4)? == 2,
+        };
+        let word = layout.word();
+        // e_phoff, e_shoff and the 16-bit fields after e_flags
+        let (phoff_at, half_at) = if layout.is64 {
+            (0x20, 0x36)
+        } else {
+            (0x1c, 0x2a)
+        };
+        let phoff = layout.read(bytes, phoff_at, word)?;
+        let shoff = layout.read(bytes, phoff_at + word, word)? as usize;
+        let phentsize = layout.read(bytes, half_at, 2)?;
+        let phnum = layout.read(bytes, half_at + 2, 2)?;
+        let shentsize = layout.read(bytes, half_at + 4, 2)? as usize;
+        let shnum = layout.read(bytes, half_at + 6, 2)? as usize;
+        let shstrndx = layout.read(bytes, half_at + 8, 2)? as usize;
+
+        let field = |header: &[u8], field| {
+            let (offset, size) = layout.shdr_field(field);
+            layout.read(header, offset, size)
+        };
+        let mut sections = Vec::with_capacity(shnum);
+        for index in 0..shnum {
+            let start = shoff + index * shentsize;
+            let header = bytes.get(start..start + shentsize)?.to_vec();
+            sections.push(Section {
+                name: String::new(),
+                kind: field(&header, ShdrField::Type)? as u32,
+                flags: field(&header, ShdrField::Flags)?,
+                offset: field(&header, ShdrField::Offset)?,
+                size: field(&header, ShdrField::Size)?,
+                link: field(&header, ShdrField::Link)? as u32,
+                info: field(&header, ShdrField::Info)? as u32,
+                align: field(&header, ShdrField::Align)?,
+                header,
+            });
+        }
+
+        let names = sections
+            .get(shstrndx)
+            .map(|s| (s.offset as usize, s.size as usize));
+        for section in &mut sections {
+            let (Some((table, len)), Some(name)) = (names, field(&section.header, ShdrField::Name))
+            else {
+                continue;
+            };
+            let start = table + name as usize;
+            let end = (table + len).min(bytes.len());
+            if let Some(raw) = bytes.get(start..end) {
+                let raw = raw.split(|b| *b == 0).next().unwrap_or_default();
+                section.name = String::from_utf8_lossy(raw).into_owned();
+            }
+        }
+
+        Some(Self {
+            layout,
+            phoff,
+            phentsize,
+            phnum,
+            shentsize,
+            shstrndx,
+            sections,
+        })
+    }
+
+    /// Which sections `mode` removes: debug info, their relocations, and for `Symbols`
+    /// the symbol table when no remaining relocations refer to it
+    fn removable(&self, mode: StripMode) -> Vec<bool> {
+        let mut removed: Vec<bool> = self
+            .sections
+            .iter()
+            .enumerate()
+            .map(|(index, section)| {
+                index != 0
+                    && index != self.shstrndx
+                    && !section.is_alloc()
+                    && mode != StripMode::None
+                    && section.name.starts_with(".debug")
+            })
+            .collect();
+        let removed_target =
+            |removed: &[bool], index: u32| removed.get(index as usize).copied().unwrap_or(false);
+        for (index, section) in self.sections.iter().enumerate() {
+            if section.is_relocation()
+                && !section.is_alloc()
+                && removed_target(&removed, section.info)
+            {
+                removed[index] = true;
+            }
+        }
+
+        if mode == StripMode::Symbols {
+            for (index, section) in self.sections.iter().enumerate() {
+                let referenced = self.sections.iter().enumerate().any(|(other, s)| {
+                    !removed[other] && s.is_relocation() && s.link as usize == index
+                });
+                // jam-pvm-build links the blob from the relocations, which need their symbols
+                if section.kind == SHT_SYMTAB && !section.is_alloc() && !referenced {
+                    removed[index] = true;
+                    let strtab = section.link as usize;
+                    if strtab != 0 && strtab != self.shstrndx {
+                        removed[strtab] = true;
+                    }
+                }
+            }
+        }
+        removed
+    }
+
+    /// Rewrite `bytes` without the removed sections. Everything the program loads stays
+    /// where it is; the remaining non-loaded sections and the section table follow it.
+    fn rewrite(&self, bytes: &[u8], removed: &[bool]) -> Vec<u8> {
+        let layout = self.layout;
+        let word = layout.word() as u64;
+        let header_size = if layout.is64 { 64 } else { 52 };
+        let (offset_at, size_at) = if layout.is64 { (8, 32) } else { (4, 16) };
+
+        let mut base = header_size.max(self.phoff + self.phentsize * self.phnum);
+        for index in 0..self.phnum {
+            let phdr = (self.phoff + index * self.phentsize) as usize;
+            let offset = layout
+                .read(bytes, phdr + offset_at, word as usize)
+                .unwrap_or(0);
+            let size = layout
+                .read(bytes, phdr + size_at, word as usize)
+                .unwrap_or(0);
+            base = base.max(offset + size);
+        }
+        for section in &self.sections {
+            if section.is_alloc() && section.has_data() {
+                base = base.max(section.offset + section.size);
+            }
+        }
+        let base = (base as usize).min(bytes.len());
+
+        let mut indices = vec![0u32; self.sections.len()];
+        let mut next = 0;
+        for (index, gone) in removed.iter().enumerate() {
+            if !gone {
+                indices[index] = next;
+                next += 1;
+            }
+        }
+
+        let mut out = bytes[..base].to_vec();
+        let mut offsets: Vec<u64> = self.sections.iter().map(|s| s.offset).collect();
+        let mut order: Vec<usize> = (0..self.sections.len()).collect();
+        order.sort_by_key(|index| self.sections[*index].offset);
+        for index in order {
+            let section = &self.sections[index];
+            if removed[index] || section.is_alloc() || (section.offset as usize) < base {
+                continue;
+            }
+            if !section.has_data() {
+                offsets[index] = out.len() as u64;
+                continue;
+            }
+            out.resize(out.len().next_multiple_of(section.align.max(1) as usize), 0);
+            offsets[index] = out.len() as u64;
+            let start = section.offset as usize;
+            out.extend_from_slice(&bytes[start..start + section.size as usize]);
+        }
+
+        out.resize(out.len().next_multiple_of(word as usize), 0);
+        let shoff = out.len() as u64;
+        for (index, section) in self.sections.iter().enumerate() {
+            if removed[index] {
+                continue;
+            }
+            let mut header = section.header.clone();
+            let mut set = |field, value| {
+                let (offset, size) = layout.shdr_field(field);
+                layout.write(&mut header, offset, size, value);
+            };
+            set(ShdrField::Offset, offsets[index]);
+            let link = indices.get(section.link as usize).copied().unwrap_or(0);
+            set(ShdrField::Link, u64::from(link));
+            if section.is_relocation() || section.flags & SHF_INFO_LINK != 0 {
+                let info = indices.get(section.info as usize).copied().unwrap_or(0);
+                set(ShdrField::Info, u64::from(info));
+            }
+            out.extend_from_slice(&header[..self.shentsize]);
+        }
+
+        let (shoff_at, half_at) = if layout.is64 {
+            (0x28, 0x36)
+        } else {
+            (0x20, 0x2a)
+        };
+        layout.write(&mut out, shoff_at, word as usize, shoff);
+        layout.write(&mut out, half_at + 6, 2, u64::from(next));
+        layout.write(&mut out, half_at + 8, 2, u64::from(indices[self.shstrndx]));
+        out
+    }
+}
+
+/// Strip debug info (and with `Symbols`, unneeded symbol tables) from a linked ELF in
+/// place, returning its size before and after
+pub fn strip_elf(path: &Path, mode: StripMode) -> Result<StripReport> {
+    let bytes = std::fs::read(path)?;
+    let before = bytes.len() as u64;
+    let elf = Elf::parse(&bytes).ok_or_else(|| {
+        CargoJamError::Build(format!(
+            "Cannot strip {}: not a little-endian ELF file",
+            path.display()
+        ))
+    })?;
+
+    let removed = elf.removable(mode);
+    if !removed.contains(&true) {
+        return Ok(StripReport {
+            mode,
+            before,
+            after: before,
+        });
+    }
+    let stripped = elf.rewrite(&bytes, &removed);
+    std::fs::write(path, &stripped)?;
+    Ok(StripReport {
+        mode,
+        before,
+        after: stripped.len() as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A relocatable-style ELF64 with code, debug info, its relocations and a symbol table
+    fn sample_elf(symbol_relocations: bool) -> Vec<u8> {
+        let names =
+            b"\0.text\0.debug_info\0.rela.debug_info\0.symtab\0.strtab\0.shstrtab\0.rela.text\0";
+        let name = |section: &str| {
+            let needle = format!("\0{}\0", section);
+            names
+                .windows(needle.len())
+                .position(|w| w == needle.as_bytes())
+                .unwrap() as u32
+                + 1
+        };
+        // (name, type, flags, size, link, info, align)
+        let mut sections = vec![
+            (".text", 1, SHF_ALLOC | 0x4, 16, 0, 0, 4),
+            (".debug_info", 1, 0, 96, 0, 0, 1),
+            (".rela.debug_info", SHT_RELA, SHF_INFO_LINK, 24, 4, 2, 8),
+            (".symtab", SHT_SYMTAB, 0, 48, 5, 1, 8),
+            (".strtab", 3, 0, 8, 0, 0, 1),
+            (".shstrtab", 3, 0, names.len() as u64, 0, 0, 1),
+        ];
+        if symbol_relocations {
+            sections.push((".rela.text", SHT_RELA, SHF_INFO_LINK, 24, 4, 1, 8));
+        }
+
+        let mut bytes = vec![0u8; 64];
+        bytes[..4].copy_from_slice(b"\x7fELF");
+        bytes[4] = 2;
+        bytes[5] = 1;
+        let layout = Layout { is64: true };
+        let mut headers = vec![0u8; 64];
+        for (section, kind, flags, size, link, info, align) in sections.iter().copied() {
+            bytes.resize(bytes.len().next_multiple_of(align as usize), 0);
+            let offset = bytes.len() as u64;
+            if section == ".shstrtab" {
+                bytes.extend_from_slice(names);
+            } else {
+                bytes.resize(bytes.len() + size as usize, 0xab);
+            }
+            let mut header = vec![0u8; 64];
+            for (field, value) in [
+                (ShdrField::Name, u64::from(name(section))),
+                (ShdrField::Type, u64::from(kind)),
+                (ShdrField::Flags, flags),
+                (ShdrField::Offset, offset),
+                (ShdrField::Size, size),
+                (ShdrField::Link, link),
+                (ShdrField::Info, info),
+                (ShdrField::Align, align),
+            ] {
+                let (at, len) = layout.shdr_field(field);
+                layout.write(&mut header, at, len, value);
+            }
+            headers.extend(header);
+        }
+        bytes.resize(bytes.len().next_multiple_of(8), 0);
+        let shoff = bytes.len() as u64;
+        bytes.extend(headers);
+        layout.write(&mut bytes, 0x28, 8, shoff);
+        layout.write(&mut bytes, 0x3a, 2, 64);
+        layout.write(&mut bytes, 0x3c, 2, sections.len() as u64 + 1);
+        layout.write(&mut bytes, 0x3e, 2, 6);
+        bytes
+    }
+
+    fn section_names(bytes: &[u8]) -> Vec<String> {
+        Elf::parse(bytes)
+            .unwrap()
+            .sections
+            .into_iter()
+            .skip(1)
+            .map(|s| s.name)
+            .collect()
+    }
+
+    #[test]
+    fn test_strip_debuginfo() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("svc.elf");
+        std::fs::write(&path, sample_elf(false)).unwrap();
+
+        let report = strip_elf(&path, StripMode::Debuginfo).unwrap();
+        assert!(report.after < report.before);
+        let stripped = std::fs::read(&path).unwrap();
+        assert_eq!(stripped.len() as u64, report.after);
+        assert_eq!(
+            section_names(&stripped),
+            [".text", ".symtab", ".strtab", ".shstrtab"]
+        );
+        // The symbol table still points at its string table
+        let elf = Elf::parse(&stripped).unwrap();
+        assert_eq!(elf.sections[elf.sections[2].link as usize].name, ".strtab");
+
+        // Nothing left to remove
+        let again = strip_elf(&path, StripMode::Debuginfo).unwrap();
+        assert_eq!(again.before, again.after);
+    }
+
+    #[test]
+    fn test_strip_symbols_keeps_relocated_symbols() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("svc.elf");
+
+        std::fs::write(&path, sample_elf(false)).unwrap();
+        strip_elf(&path, StripMode::Symbols).unwrap();
+        assert_eq!(
+            section_names(&std::fs::read(&path).unwrap()),
+            [".text", ".shstrtab"]
+        );
+
+        std::fs::write(&path, sample_elf(true)).unwrap();
+        strip_elf(&path, StripMode::Symbols).unwrap();
+        let stripped = std::fs::read(&path).unwrap();
+        assert_eq!(
+            section_names(&stripped),
+            [".text", ".symtab", ".strtab", ".shstrtab", ".rela.text"]
+        );
+        let elf = Elf::parse(&stripped).unwrap();
+        assert_eq!(elf.sections[5].link, 2);
+        assert_eq!(elf.sections[5].info, 1);
+    }
+
+    #[test]
+    fn test_strip_rejects_non_elf() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("svc.elf");
+        std::fs::write(&path, b"not an elf").unwrap();
+        assert!(strip_elf(&path, StripMode::Debuginfo).is_err());
+    }
+}
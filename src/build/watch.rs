@@ -0,0 +1,63 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use walkdir::WalkDir;
+
+/// How often the project is polled for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Polling watcher for a JAM service project's sources
+pub struct SourceWatcher {
+    project_path: PathBuf,
+    snapshot: BTreeMap<PathBuf, SystemTime>,
+}
+
+impl SourceWatcher {
+    pub fn new(project_path: PathBuf) -> Self {
+        let snapshot = snapshot(&project_path);
+        Self {
+            project_path,
+            snapshot,
+        }
+    }
+
+    /// Block until sources change and then stay unchanged for `debounce`
+    pub fn wait_for_change(&mut self, debounce: Duration) {
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+            if snapshot(&self.project_path) != self.snapshot {
+                break;
+            }
+        }
+
+        // Let editors finish writing before rebuilding
+        let mut current = snapshot(&self.project_path);
+        loop {
+            std::thread::sleep(debounce);
+            let next = snapshot(&self.project_path);
+            if next == current {
+                break;
+            }
+            current = next;
+        }
+
+        self.snapshot = current;
+    }
+}
+
+/// Modification times of Cargo.toml and everything under src/
+fn snapshot(project_path: &Path) -> BTreeMap<PathBuf, SystemTime> {
+    let manifest = project_path.join("Cargo.toml");
+
+    WalkDir::new(project_path.join("src"))
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| e.into_path())
+        .chain(std::iter::once(manifest))
+        .filter_map(|path| {
+            let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((path, modified))
+        })
+        .collect()
+}
@@ -1,25 +1,82 @@
-use clap::{Parser, Subcommand};
+use crate::build::pipeline::{MessageFormat, StripMode};
+use crate::cli::validate::parse_path;
+use crate::project::deps::DepsSource;
+use crate::project::lockfile::LockfileStrategy;
+use crate::template::values::ValuesFormat;
+use crate::toolchain::deployments::parse_service_id;
+use crate::toolchain::download::{
+    DEFAULT_CHANNEL, DEFAULT_CONNECT_TIMEOUT_SECS, DEFAULT_READ_TIMEOUT_SECS,
+};
+use crate::toolchain::platform::Platform;
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use std::ffi::OsString;
 use std::path::PathBuf;
 
+/// Default minimum accumulation gas per work-item used by `deploy`
+pub const DEFAULT_MIN_ITEM_GAS: &str = "1000000";
+
+/// Default minimum on-transfer gas per memo used by `deploy`
+pub const DEFAULT_MIN_MEMO_GAS: &str = "1000000";
+
+/// Cargo subcommand name, as in `cargo polkajam <command>`
+pub const SUBCOMMAND: &str = "polkajam";
+
+/// Earlier name of the subcommand, still accepted as `cargo jam <command>`
+pub const LEGACY_SUBCOMMAND: &str = "jam";
+
 /// cargo-polkajam: Generate JAM service projects for Polkadot
 #[derive(Parser, Debug)]
 #[command(name = "cargo", bin_name = "cargo")]
 pub enum Cargo {
     /// JAM service generation and build tools
+    #[command(alias = LEGACY_SUBCOMMAND)]
     Polkajam(PolkajamArgs),
 }
 
+/// Insert the `polkajam` subcommand when the binary is run directly as `cargo-polkajam <command>`
+///
+/// Cargo runs `cargo-polkajam polkajam <command>` for `cargo polkajam <command>`, so the
+/// subcommand is only missing when the binary is invoked by name.
+pub fn normalize_args(args: impl IntoIterator<Item = OsString>) -> Vec<OsString> {
+    let mut args: Vec<OsString> = args.into_iter().collect();
+    let has_subcommand = args
+        .get(1)
+        .is_some_and(|arg| arg == SUBCOMMAND || arg == LEGACY_SUBCOMMAND);
+
+    if !has_subcommand {
+        args.insert(1.min(args.len()), SUBCOMMAND.into());
+    }
+    args
+}
+
 #[derive(Parser, Debug)]
 #[command(version, about = "Generate JAM service projects for Polkadot")]
 pub struct PolkajamArgs {
     #[command(subcommand)]
     pub command: PolkajamCommand,
+
+    /// Validate arguments and prerequisites without running the command
+    #[arg(long, visible_alias = "validate", global = true)]
+    pub check_only: bool,
+
+    /// Use an alternate config file instead of ~/.cargo-polkajam/config.toml
+    #[arg(long, global = true, env = "CARGO_JAM_CONFIG", value_name = "PATH", value_parser = parse_path)]
+    pub config: Option<PathBuf>,
+
+    /// ASCII-only output: [ok], -> and * instead of symbols (default: on when the terminal or locale lacks UTF-8)
+    #[arg(long, global = true, env = "CARGO_JAM_PLAIN")]
+    pub plain: bool,
+
+    /// Print a static status line instead of spinners and progress bars
+    #[arg(long, global = true, env = "CARGO_JAM_NO_PROGRESS")]
+    pub no_progress: bool,
 }
 
 #[derive(Subcommand, Debug)]
 pub enum PolkajamCommand {
     /// Create a new JAM service project
-    New(NewArgs),
+    New(Box<NewArgs>),
 
     /// Build a JAM service for PVM deployment
     Build(BuildArgs),
@@ -27,6 +84,12 @@ pub enum PolkajamCommand {
     /// Setup the JAM/PVM toolchain
     Setup(SetupArgs),
 
+    /// Remove cached toolchain versions other than the active one
+    CleanToolchain(CleanToolchainArgs),
+
+    /// Remove the installed toolchain
+    Uninstall(UninstallArgs),
+
     /// Start the local JAM testnet
     Up(UpArgs),
 
@@ -39,8 +102,44 @@ pub enum PolkajamCommand {
     /// Monitor the testnet with jamtop
     Monitor(MonitorArgs),
 
+    /// Show the testnet's work queue
+    Queue(QueueArgs),
+
     /// Run end-to-end tests
     Test(TestArgs),
+
+    /// Estimate gas requirements and code size of a built .jam blob
+    Bench(BenchArgs),
+
+    /// Show generated files that were modified since generation
+    Diff(DiffArgs),
+
+    /// Check a project's SDK dependencies and toolchain against the installed toolchain
+    CheckCompat(CheckCompatArgs),
+
+    /// Pull template changes into a generated project, writing conflicts to <file>.new
+    Update(UpdateArgs),
+
+    /// Summarize the project, blob, toolchain and testnet state
+    Info(InfoArgs),
+
+    /// Tools for template authors
+    Template(TemplateArgs),
+
+    /// Show or edit the cargo-polkajam config file
+    Config(ConfigArgs),
+
+    /// Create, list and select dev accounts for deploying services
+    Accounts(AccountsArgs),
+
+    /// Render a single template file to stdout
+    Render(RenderArgs),
+
+    /// Sign a .jam blob with an ed25519 key
+    Sign(SignArgs),
+
+    /// Check a .jam blob's detached signature
+    Verify(VerifyArgs),
 }
 
 #[derive(Parser, Debug)]
@@ -48,9 +147,10 @@ pub struct NewArgs {
     /// Name of the new JAM service project
     pub name: Option<String>,
 
-    /// Template to use (default: basic-service)
-    #[arg(short, long, default_value = "basic-service")]
-    pub template: String,
+    /// Bundled template to use (default: basic-service, or a choice when prompting
+    /// and several are bundled; see `template list`)
+    #[arg(short, long)]
+    pub template: Option<String>,
 
     /// Use a git repository as template source
     #[arg(long, conflicts_with = "template")]
@@ -60,49 +160,230 @@ pub struct NewArgs {
     #[arg(long, requires = "git")]
     pub branch: Option<String>,
 
+    /// Commit, tag or branch to check out after cloning (requires --git)
+    #[arg(long, requires = "git", value_name = "REV")]
+    pub rev: Option<String>,
+
     /// Subdirectory within git repo containing template
     #[arg(long, requires = "git")]
     pub path: Option<PathBuf>,
 
+    /// Access token for private HTTPS repositories (default: GITHUB_TOKEN for GitHub, or GIT_TOKEN)
+    #[arg(long, requires = "git", value_name = "TOKEN")]
+    pub git_token: Option<String>,
+
+    /// Pin the template to a content hash (and git commit) recorded in FILE on first use
+    #[arg(long, value_name = "FILE", value_parser = parse_path)]
+    pub template_lock: Option<PathBuf>,
+
+    /// Accept a template that no longer matches --template-lock and rewrite the lock
+    #[arg(long, requires = "template_lock")]
+    pub update_lock: bool,
+
     /// Output directory (default: ./<name>)
-    #[arg(short, long)]
+    #[arg(short, long, value_parser = parse_path)]
     pub output: Option<PathBuf>,
 
+    /// Add the new crate to the members of the enclosing workspace (or the one at ROOT)
+    #[arg(long, value_name = "ROOT", num_args = 0..=1, require_equals = true, value_parser = parse_path)]
+    pub into_workspace: Option<Option<PathBuf>>,
+
+    /// Generate into DIR/<name> as a member of the workspace at DIR, creating it if needed
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["output", "into_workspace"], value_parser = parse_path)]
+    pub workspace_root: Option<PathBuf>,
+
     /// Skip interactive prompts, use defaults
     #[arg(long)]
     pub defaults: bool,
 
+    /// Never prompt, and fail if a value is missing instead of using the template default
+    #[arg(long)]
+    pub no_interactive: bool,
+
+    /// Require every placeholder value to be given explicitly rather than taken from a default
+    #[arg(long)]
+    pub no_prompt_defaults: bool,
+
     /// Define template variables (key=value)
     #[arg(short, long, value_name = "KEY=VALUE")]
     pub define: Vec<String>,
 
+    /// Move a generated file or directory elsewhere in the project (repeatable)
+    #[arg(long, value_name = "FROM=TO")]
+    pub rename: Vec<String>,
+
     /// Template values file (TOML format)
-    #[arg(long)]
+    #[arg(long, value_parser = parse_path)]
     pub values_file: Option<PathBuf>,
 
+    /// Read template values (TOML or JSON) from stdin; implies --defaults
+    #[arg(long)]
+    pub values_stdin: bool,
+
+    /// Format of the --values-stdin document (default: detected)
+    #[arg(long, value_enum, requires = "values_stdin")]
+    pub values_format: Option<ValuesFormat>,
+
+    /// Project license as an SPDX expression, with its LICENSE text for MIT, Apache-2.0, GPL-3.0 and MIT OR Apache-2.0
+    #[arg(long, value_name = "SPDX")]
+    pub license: Option<String>,
+
+    /// Package description for Cargo.toml
+    #[arg(long, value_name = "TEXT")]
+    pub description: Option<String>,
+
+    /// Package homepage URL for Cargo.toml
+    #[arg(long, value_name = "URL")]
+    pub homepage: Option<String>,
+
+    /// Package repository URL for Cargo.toml
+    #[arg(long, value_name = "URL")]
+    pub repository: Option<String>,
+
     /// Don't initialize git repository
     #[arg(long)]
     pub no_git: bool,
 
+    /// Relax project name rules (allow uppercase letters and reserved keywords)
+    #[arg(long)]
+    pub force_name: bool,
+
+    /// Generate only the essentials, applying the template's `minimal` conditional
+    #[arg(long)]
+    pub minimal: bool,
+
+    /// Error on undefined template variables, even if the template doesn't opt in
+    #[arg(long)]
+    pub strict_undefined: bool,
+
+    /// Source of the JAM SDK dependencies in the generated Cargo.toml
+    #[arg(long, value_enum, default_value_t = DepsSource::Registry)]
+    pub deps: DepsSource,
+
+    /// jam-sdk git revision for --deps git (default: the repository's default branch)
+    #[arg(long, value_name = "REV")]
+    pub deps_rev: Option<String>,
+
+    /// Directory containing the SDK crates for --deps path
+    #[arg(long, value_name = "DIR", value_parser = parse_path)]
+    pub deps_path: Option<PathBuf>,
+
+    /// Seed for the `random_hex` and `uuid` template filters, for reproducible output
+    #[arg(long, value_name = "N")]
+    pub seed: Option<u64>,
+
+    /// How to handle Cargo.lock in the generated project
+    #[arg(long, value_enum, default_value_t = LockfileArg::Remove)]
+    pub lockfile: LockfileArg,
+
+    /// Print a tree of the generated files, marking rendered and copied ones (implied by --verbose)
+    #[arg(long)]
+    pub print_tree: bool,
+
+    /// Show the files that would be generated without writing anything
+    #[arg(long, conflicts_with_all = ["template_lock", "open"])]
+    pub dry_run: bool,
+
+    /// With --dry-run, print the resolved variables, planned files and target directory as JSON
+    #[arg(long, requires = "dry_run")]
+    pub json: bool,
+
+    /// Open the new project in $VISUAL, $EDITOR or a detected IDE (code, zed, ...)
+    #[arg(long, visible_alias = "post-generate-open")]
+    pub open: bool,
+
+    /// Editor command used by --open instead of the detected one
+    #[arg(long, value_name = "COMMAND", requires = "open")]
+    pub editor: Option<String>,
+
     /// Verbose output
     #[arg(short, long)]
     pub verbose: bool,
 }
 
+/// `--lockfile` values for `new`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum LockfileArg {
+    /// Leave whatever lockfile the template provides
+    Keep,
+    /// Delete any lockfile shipped by the template
+    Remove,
+    /// Run `cargo generate-lockfile` in the generated project
+    Generate,
+}
+
+impl From<LockfileArg> for LockfileStrategy {
+    fn from(arg: LockfileArg) -> Self {
+        match arg {
+            LockfileArg::Keep => LockfileStrategy::Keep,
+            LockfileArg::Remove => LockfileStrategy::Remove,
+            LockfileArg::Generate => LockfileStrategy::Generate,
+        }
+    }
+}
+
 #[derive(Parser, Debug)]
 pub struct BuildArgs {
     /// Path to the JAM service project (default: current directory)
-    #[arg(short, long)]
+    #[arg(short, long, value_parser = parse_path)]
     pub path: Option<PathBuf>,
 
     /// Build in release mode (default: true)
     #[arg(long, default_value = "true")]
     pub release: bool,
 
+    /// Don't let jam-pvm-build install missing rustc toolchains and components
+    #[arg(long)]
+    pub no_auto_install: bool,
+
+    /// Cargo profile to build with: debug, release, production or any [profile.*] in Cargo.toml
+    #[arg(long, value_name = "NAME", conflicts_with = "release")]
+    pub profile: Option<String>,
+
     /// Output path for the .jam blob
-    #[arg(short, long)]
+    #[arg(short, long, value_parser = parse_path)]
     pub output: Option<PathBuf>,
 
+    /// Fail if the project has no jam-toolchain.toml (a mismatched pin always fails)
+    #[arg(long)]
+    pub frozen_toolchain: bool,
+
+    /// Write an interface description (<name>.metadata.json) next to the blob
+    #[arg(long)]
+    pub emit_metadata: bool,
+
+    /// Report build timings (cargo's --timings HTML report when supported)
+    #[arg(long)]
+    pub profile_timings: bool,
+
+    /// Keep the target directory and print the paths of the intermediate ELF and blob
+    #[arg(long, conflicts_with = "clean_intermediate")]
+    pub keep_intermediate: bool,
+
+    /// Remove the service's ELF, PolkaVM and deps/ artifacts after the .jam blob has been written
+    #[arg(long)]
+    pub clean_intermediate: bool,
+
+    /// Kill subprocesses that run longer than this many seconds (0 disables)
+    #[arg(long, value_name = "SECS")]
+    pub timeout: Option<u64>,
+
+    /// Write the full jam-pvm-build output to this file
+    #[arg(long, value_name = "PATH", value_parser = parse_path)]
+    pub log_file: Option<PathBuf>,
+
+    /// Build deterministically (fixed timestamps, remapped paths) and print the blob's SHA-256
+    #[arg(long)]
+    pub reproducible: bool,
+
+    /// Strip the service before it is linked into a blob (default: symbols for release, none for debug)
+    #[arg(long, value_enum, value_name = "MODE")]
+    pub strip: Option<StripMode>,
+
+    /// Diagnostic format passed to cargo; with json, messages go to stdout and status to stderr
+    #[arg(long, value_enum, value_name = "FMT")]
+    pub message_format: Option<MessageFormat>,
+
     /// Verbose output
     #[arg(short, long)]
     pub verbose: bool,
@@ -110,18 +391,34 @@ pub struct BuildArgs {
 
 #[derive(Parser, Debug)]
 pub struct SetupArgs {
-    /// Install a specific version (default: latest nightly)
+    /// Install a specific version (default: latest release of the channel)
     #[arg(long)]
     pub version: Option<String>,
 
+    /// Release channel: the prefix of the tags considered for the latest release
+    #[arg(long, value_name = "NAME", default_value = DEFAULT_CHANNEL, conflicts_with_all = ["version", "from_archive"])]
+    pub channel: String,
+
+    /// Install the newest release of the channel published on or before this date (YYYY-MM-DD)
+    #[arg(long, visible_alias = "before", value_name = "YYYY-MM-DD", conflicts_with_all = ["version", "from_archive"])]
+    pub date: Option<String>,
+
     /// List available toolchain versions
     #[arg(long)]
     pub list: bool,
 
-    /// Update to the latest nightly version
+    /// Print the --list releases as JSON
+    #[arg(long, requires = "list")]
+    pub json: bool,
+
+    /// Update to the latest release of the channel
     #[arg(long)]
     pub update: bool,
 
+    /// Show what --update would install without downloading (exits with 10 if an update is available)
+    #[arg(long, requires = "update")]
+    pub dry_run: bool,
+
     /// Show currently installed toolchain info
     #[arg(long)]
     pub info: bool,
@@ -130,11 +427,77 @@ pub struct SetupArgs {
     #[arg(long)]
     pub force: bool,
 
+    /// Install from a local toolchain archive, or the cached one for --label in a cache directory, instead of downloading
+    #[arg(long, value_name = "PATH", requires = "label", conflicts_with_all = ["version", "update", "list"], value_parser = parse_path)]
+    pub from_archive: Option<PathBuf>,
+
+    /// Version label to record for an archive installed with --from-archive
+    #[arg(long, requires = "from_archive")]
+    pub label: Option<String>,
+
+    /// Install the toolchain built for this platform instead of the detected one (e.g. macos-x86_64)
+    #[arg(long, value_name = "PLATFORM", conflicts_with_all = ["list", "info", "all_platforms"])]
+    pub platform: Option<Platform>,
+
+    /// Keep the archive's directory layout instead of flattening its top-level directory
+    #[arg(long, conflicts_with_all = ["list", "info", "repair", "all_platforms"])]
+    pub no_normalize: bool,
+
+    /// Skip checking downloaded archives against the release's SHA-256 checksums
+    #[arg(long, conflicts_with_all = ["list", "info", "from_archive"])]
+    pub no_verify: bool,
+
+    /// Restore missing or corrupt toolchain files from the cached (or re-downloaded) archive
+    #[arg(long, conflicts_with_all = ["version", "date", "update", "list", "from_archive", "all_platforms"])]
+    pub repair: bool,
+
+    /// Download every platform's toolchain archive into the cache without installing
+    #[arg(long, conflicts_with_all = ["from_archive", "list", "info"])]
+    pub all_platforms: bool,
+
+    /// Cache directory for --all-platforms (default: ~/.cargo-polkajam/cache)
+    #[arg(long, value_name = "DIR", requires = "all_platforms", value_parser = parse_path)]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Keep the downloaded archive in DIR/<version>/ (default: ~/.cargo-polkajam/cache) for offline reinstalls
+    #[arg(long, value_name = "DIR", num_args = 0..=1, require_equals = true, conflicts_with_all = ["from_archive", "list", "info", "repair", "all_platforms"], value_parser = parse_path)]
+    pub keep_archive: Option<Option<PathBuf>>,
+
+    /// Seconds to wait for a response, or for more data while downloading (0 disables)
+    #[arg(long, value_name = "SECS", default_value_t = DEFAULT_READ_TIMEOUT_SECS)]
+    pub timeout: u64,
+
+    /// Seconds allowed to connect to the release server (0 disables)
+    #[arg(long, value_name = "SECS", default_value_t = DEFAULT_CONNECT_TIMEOUT_SECS)]
+    pub connect_timeout: u64,
+
     /// Verbose output
     #[arg(short, long)]
     pub verbose: bool,
 }
 
+#[derive(Parser, Debug)]
+pub struct CleanToolchainArgs {
+    /// Keep the most recently downloaded versions instead of removing them all
+    #[arg(long)]
+    pub prune: bool,
+
+    /// Number of recent versions kept by --prune (the active version is always kept)
+    #[arg(long, value_name = "N", default_value_t = 1, requires = "prune")]
+    pub keep: usize,
+
+    /// Show what would be removed without deleting anything
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct UninstallArgs {
+    /// Also remove the whole ~/.cargo-polkajam directory, including config.toml, dev accounts and testnet.pid
+    #[arg(long)]
+    pub purge: bool,
+}
+
 #[derive(Parser, Debug)]
 pub struct UpArgs {
     /// RPC URL for the testnet (default: ws://localhost:19800)
@@ -145,6 +508,22 @@ pub struct UpArgs {
     #[arg(long)]
     pub foreground: bool,
 
+    /// Restart the testnet if it exits unexpectedly
+    #[arg(long)]
+    pub supervise: bool,
+
+    /// Maximum number of restarts with --supervise
+    #[arg(long, default_value = "5", requires = "supervise")]
+    pub max_restarts: u32,
+
+    /// Use the next free port if the --rpc port is taken
+    #[arg(long)]
+    pub auto_port: bool,
+
+    /// Testnet settings file (validators, block time, genesis, extra arguments)
+    #[arg(long, value_name = "TOML", value_parser = parse_path)]
+    pub testnet_config: Option<PathBuf>,
+
     /// Verbose output
     #[arg(short, long)]
     pub verbose: bool,
@@ -156,6 +535,10 @@ pub struct DownArgs {
     #[arg(long)]
     pub force: bool,
 
+    /// Stop the testnet but leave testnet.pid in place for inspection
+    #[arg(long)]
+    pub keep_pid_file: bool,
+
     /// Verbose output
     #[arg(short, long)]
     pub verbose: bool,
@@ -164,6 +547,7 @@ pub struct DownArgs {
 #[derive(Parser, Debug)]
 pub struct DeployArgs {
     /// Path to the .jam blob to deploy
+    #[arg(value_parser = parse_path)]
     pub code: PathBuf,
 
     /// Initial endowment for the service
@@ -175,37 +559,157 @@ pub struct DeployArgs {
     pub memo: String,
 
     /// Minimum accumulation gas per work-item
-    #[arg(long, short = 'G', default_value = "1000000")]
+    #[arg(long, short = 'G', default_value = DEFAULT_MIN_ITEM_GAS)]
     pub min_item_gas: String,
 
     /// Minimum on-transfer gas per memo
-    #[arg(long, short = 'g', default_value = "1000000")]
+    #[arg(long, short = 'g', default_value = DEFAULT_MIN_MEMO_GAS)]
     pub min_memo_gas: String,
 
     /// Register the service with the Bootstrap service
     #[arg(long, short)]
     pub register: Option<String>,
 
-    /// RPC URL for the testnet
-    #[arg(long, default_value = "ws://localhost:19800")]
-    pub rpc: String,
+    /// Dev account that owns and funds the service (default: the one set with `accounts use`)
+    #[arg(long, value_name = "NAME")]
+    pub account: Option<String>,
+
+    /// Replace the code of an existing service instead of creating a new one
+    #[arg(long, value_name = "SERVICE_ID", value_parser = parse_service_id, conflicts_with_all = ["amount", "memo", "register", "interactive"])]
+    pub upgrade: Option<String>,
+
+    /// Fail unless the deploy produced (or upgraded) the service with this ID
+    #[arg(long, value_name = "SERVICE_ID", value_parser = parse_service_id, conflicts_with = "watch")]
+    pub expect_id: Option<String>,
+
+    /// Skip the deploy when the chain already has the --expect-id service
+    #[arg(long, requires = "expect_id", conflicts_with = "upgrade")]
+    pub skip_if_exists: bool,
+
+    /// RPC URL for the testnet, defaulting to that of a testnet started by `up`
+    #[arg(long, value_name = "URL")]
+    pub rpc: Option<String>,
+
+    /// Warn before submitting when --min-item-gas is below a size-based guess of what the service needs
+    #[arg(long)]
+    pub probe: bool,
+
+    /// Rebuild and redeploy whenever the project's sources change
+    #[arg(long)]
+    pub watch: bool,
+
+    /// Project to watch and rebuild with --watch (default: current directory)
+    #[arg(short, long, requires = "watch", value_parser = parse_path)]
+    pub path: Option<PathBuf>,
+
+    /// Shell command to run after a successful deploy (service ID in CARGO_JAM_SERVICE_ID)
+    #[arg(long, value_name = "SCRIPT")]
+    pub post_deploy: Option<String>,
+
+    /// Kill subprocesses that run longer than this many seconds (0 disables)
+    #[arg(long, value_name = "SECS")]
+    pub timeout: Option<u64>,
+
+    /// Refuse to deploy unless <code>.sig is a valid signature from a trusted key
+    #[arg(long, conflicts_with = "watch")]
+    pub require_signed: bool,
+
+    /// Prompt for the endowment, gas limits and RPC, then confirm the jamt command
+    #[arg(short, long, conflicts_with = "watch")]
+    pub interactive: bool,
 
     /// Verbose output
     #[arg(short, long)]
     pub verbose: bool,
 }
 
+#[derive(Parser, Debug)]
+pub struct InfoArgs {
+    /// Path to the JAM service project (default: current directory)
+    #[arg(short, long, value_parser = parse_path)]
+    pub path: Option<PathBuf>,
+
+    /// Print the report as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct QueueArgs {
+    /// RPC URL for the testnet, defaulting to that of a testnet started by `up`
+    #[arg(long, value_name = "URL")]
+    pub rpc: Option<String>,
+
+    /// Print jamt's JSON output unchanged (requires jamt support)
+    #[arg(long)]
+    pub json: bool,
+
+    /// Kill jamt if it runs longer than this many seconds (0 disables)
+    #[arg(long, value_name = "SECS")]
+    pub timeout: Option<u64>,
+
+    /// Verbose output
+    #[arg(short, long)]
+    pub verbose: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct SignArgs {
+    /// Path to the .jam blob to sign
+    #[arg(required_unless_present = "generate_key", value_parser = parse_path)]
+    pub blob: Option<PathBuf>,
+
+    /// ed25519 signing key file (hex-encoded 32-byte seed)
+    #[arg(long, value_name = "PATH", value_parser = parse_path)]
+    pub key: PathBuf,
+
+    /// Create a new key at --key and trust its public key
+    #[arg(long)]
+    pub generate_key: bool,
+
+    /// Signature output path (default: <blob>.sig)
+    #[arg(short, long, value_parser = parse_path)]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Parser, Debug)]
+pub struct VerifyArgs {
+    /// Path to the .jam blob to verify
+    #[arg(value_parser = parse_path)]
+    pub blob: PathBuf,
+
+    /// Detached signature file (default: <blob>.sig)
+    #[arg(long, value_name = "PATH", value_parser = parse_path)]
+    pub signature: Option<PathBuf>,
+
+    /// Public key to check against, hex-encoded or a .pub file (default: trusted keys)
+    #[arg(long, value_name = "KEY")]
+    pub public_key: Option<String>,
+}
+
 #[derive(Parser, Debug)]
 pub struct MonitorArgs {
-    /// RPC URL for the testnet
-    #[arg(long, default_value = "ws://localhost:19800")]
-    pub rpc: String,
+    /// RPC URL for the testnet, defaulting to that of a testnet started by `up`
+    #[arg(long, value_name = "URL")]
+    pub rpc: Option<String>,
 
     /// Verbose output
     #[arg(short, long)]
     pub verbose: bool,
 }
 
+/// A step of the end-to-end test, selectable with `--filter`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TestPhase {
+    /// Generate the test service with `cargo polkajam new`
+    Create,
+    /// Build the service's `.jam` blob
+    Build,
+    /// Deploy the blob to a local testnet
+    Deploy,
+}
+
 #[derive(Parser, Debug)]
 pub struct TestArgs {
     /// Keep testnet running after tests
@@ -217,10 +721,307 @@ pub struct TestArgs {
     pub skip_testnet: bool,
 
     /// Test directory (default: temp directory)
-    #[arg(long)]
+    #[arg(long, value_parser = parse_path)]
     pub dir: Option<std::path::PathBuf>,
 
+    /// Only run these phases (repeatable); missing prerequisites still run
+    #[arg(long, value_enum, value_name = "PHASE")]
+    pub filter: Vec<TestPhase>,
+
+    /// Print a table of each phase's result, duration and artifacts, with the toolchain and platform
+    #[arg(long)]
+    pub summary: bool,
+
+    /// Print the summary as JSON to stdout, with progress on stderr
+    #[arg(long)]
+    pub json: bool,
+
+    /// Also write the summary as JSON to this file
+    #[arg(long, value_name = "PATH", value_parser = parse_path)]
+    pub summary_file: Option<PathBuf>,
+
+    /// Kill subprocesses that run longer than this many seconds (0 disables)
+    #[arg(long, value_name = "SECS")]
+    pub timeout: Option<u64>,
+
+    /// Verbose output
+    #[arg(short, long)]
+    pub verbose: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct BenchArgs {
+    /// Path to the .jam blob to analyze
+    #[arg(value_parser = parse_path)]
+    pub code: PathBuf,
+
+    /// Minimum accumulation gas per work-item to compare against
+    #[arg(long, short = 'G', default_value = DEFAULT_MIN_ITEM_GAS)]
+    pub min_item_gas: String,
+
+    /// Minimum on-transfer gas per memo to compare against
+    #[arg(long, short = 'g', default_value = DEFAULT_MIN_MEMO_GAS)]
+    pub min_memo_gas: String,
+
+    /// Kill polkatool if it runs longer than this many seconds (0 disables)
+    #[arg(long, value_name = "SECS")]
+    pub timeout: Option<u64>,
+
     /// Verbose output
     #[arg(short, long)]
     pub verbose: bool,
 }
+
+#[derive(Parser, Debug)]
+pub struct UpdateArgs {
+    /// Path to the generated project (default: current directory)
+    #[arg(short, long, value_parser = parse_path)]
+    pub path: Option<PathBuf>,
+
+    /// Set or override template variables (key=value)
+    #[arg(short, long, value_name = "KEY=VALUE")]
+    pub define: Vec<String>,
+
+    /// Access token for a private template repository (default: GITHUB_TOKEN for GitHub, or GIT_TOKEN)
+    #[arg(long, value_name = "TOKEN")]
+    pub git_token: Option<String>,
+
+    /// Show what would change without writing anything
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Also list files that are already up to date
+    #[arg(short, long)]
+    pub verbose: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct DiffArgs {
+    /// Path to the generated project (default: current directory)
+    #[arg(short, long, value_parser = parse_path)]
+    pub path: Option<PathBuf>,
+
+    /// Also list unchanged files
+    #[arg(short, long)]
+    pub verbose: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct CheckCompatArgs {
+    /// Path to the project (default: current directory)
+    #[arg(short, long, value_parser = parse_path)]
+    pub path: Option<PathBuf>,
+
+    /// Fail when an incompatibility is found instead of only warning
+    #[arg(long)]
+    pub strict: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    pub command: ConfigCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    /// Open the config file in an editor, creating it if needed, and validate it
+    Edit(ConfigEditArgs),
+
+    /// Print the location of the config file
+    Path,
+}
+
+#[derive(Parser, Debug)]
+pub struct ConfigEditArgs {
+    /// Editor command to use instead of $VISUAL or $EDITOR
+    #[arg(long, value_name = "COMMAND")]
+    pub editor: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct AccountsArgs {
+    #[command(subcommand)]
+    pub command: AccountsCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AccountsCommand {
+    /// Generate a dev keypair and store it under a name
+    New(AccountsNewArgs),
+
+    /// List stored accounts, marking the default
+    List,
+
+    /// Set the account `deploy` uses by default
+    Use(AccountsUseArgs),
+}
+
+#[derive(Parser, Debug)]
+pub struct AccountsNewArgs {
+    /// Name of the account (letters, digits, '-' and '_')
+    pub name: String,
+
+    /// Make it the default account (the first account always is)
+    #[arg(long = "use")]
+    pub make_default: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct AccountsUseArgs {
+    /// Name of a stored account
+    pub name: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct TemplateArgs {
+    #[command(subcommand)]
+    pub command: TemplateCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TemplateCommand {
+    /// Print the JSON Schema for cargo-polkajam.toml
+    Schema(TemplateSchemaArgs),
+
+    /// Generate and check every [[test_case]] declared by a template
+    Test(TemplateTestArgs),
+
+    /// Show how the projects generated by two templates differ
+    Diff(TemplateDiffArgs),
+
+    /// Describe a template and the placeholders it prompts for
+    Info(TemplateInfoArgs),
+
+    /// Render a whole template into a directory from a values file, e.g. for golden files
+    RenderAll(TemplateRenderAllArgs),
+
+    /// List the bundled templates, grouped by category
+    List(TemplateListArgs),
+
+    /// Render a template with its defaults and test cases, erroring on undefined variables
+    Validate(TemplateValidateArgs),
+}
+
+#[derive(Parser, Debug)]
+pub struct TemplateValidateArgs {
+    /// A bundled template name or a template directory
+    #[arg(default_value = ".")]
+    pub template: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct TemplateListArgs {
+    /// Only list templates in this category (e.g. starters, examples)
+    #[arg(long)]
+    pub category: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct TemplateRenderAllArgs {
+    /// A bundled template name or a template directory
+    pub template: String,
+
+    /// Template values file (TOML format); placeholders it leaves out use their defaults
+    #[arg(long, value_parser = parse_path)]
+    pub values: PathBuf,
+
+    /// Directory to render into (must be empty or missing unless --force)
+    #[arg(long, value_parser = parse_path)]
+    pub out: PathBuf,
+
+    /// Seed for the random_hex and uuid filters
+    #[arg(long, default_value_t = 0)]
+    pub seed: u64,
+
+    /// Replace the contents of a non-empty --out directory
+    #[arg(long)]
+    pub force: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct TemplateInfoArgs {
+    /// A bundled template name or a template directory
+    #[arg(default_value = "basic-service")]
+    pub template: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct TemplateSchemaArgs {
+    /// Write the schema to a file instead of stdout ('-' for stdout)
+    #[arg(short, long, value_parser = parse_path)]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Parser, Debug)]
+pub struct TemplateDiffArgs {
+    /// First template: a bundled template name or a template directory
+    pub a: String,
+
+    /// Second template: a bundled template name or a template directory
+    pub b: String,
+
+    /// Only list the files that differ, without content diffs
+    #[arg(long)]
+    pub name_only: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct TemplateTestArgs {
+    /// Path to the template directory (containing cargo-polkajam.toml)
+    #[arg(default_value = ".", value_parser = parse_path)]
+    pub path: PathBuf,
+
+    /// Only generate each case, without running cargo check
+    #[arg(long)]
+    pub no_check: bool,
+
+    /// Show cargo check output for failing cases
+    #[arg(short, long)]
+    pub verbose: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct RenderArgs {
+    /// Template file to render (e.g. src/lib.rs.liquid)
+    #[arg(value_parser = parse_path)]
+    pub file: PathBuf,
+
+    /// Define template variables (key=value)
+    #[arg(short, long, value_name = "KEY=VALUE")]
+    pub define: Vec<String>,
+
+    /// Template values file (TOML format)
+    #[arg(long, value_parser = parse_path)]
+    pub values_file: Option<PathBuf>,
+
+    /// Write to a file instead of stdout ('-' for stdout)
+    #[arg(short, long, value_parser = parse_path)]
+    pub output: Option<PathBuf>,
+
+    /// Render undefined variables as empty instead of failing
+    #[arg(long)]
+    pub lenient: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> PolkajamArgs {
+        let Cargo::Polkajam(args) =
+            Cargo::try_parse_from(normalize_args(args.iter().map(OsString::from))).unwrap();
+        args
+    }
+
+    #[test]
+    fn test_subcommand_names_parse() {
+        for args in [
+            &["cargo", "polkajam", "diff"][..],
+            &["cargo", "jam", "diff"],
+            &["cargo-polkajam", "diff"],
+        ] {
+            assert!(matches!(parse(args).command, PolkajamCommand::Diff(_)));
+        }
+    }
+}
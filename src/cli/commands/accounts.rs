@@ -0,0 +1,101 @@
+use crate::cli::args::{AccountsArgs, AccountsCommand, AccountsNewArgs, AccountsUseArgs};
+use crate::cli::output::Glyph;
+use crate::error::Result;
+use crate::toolchain::accounts::{unknown_account, AccountStore};
+use crate::toolchain::config::ToolchainConfig;
+use console::style;
+
+pub fn execute(args: AccountsArgs) -> Result<()> {
+    let store = AccountStore::open()?;
+    match args.command {
+        AccountsCommand::New(new_args) => new(&store, new_args),
+        AccountsCommand::List => list(&store),
+        AccountsCommand::Use(use_args) => use_account(&store, use_args),
+    }
+}
+
+/// Generate and store a keypair, making it the default when asked or when none is set
+fn new(store: &AccountStore, args: AccountsNewArgs) -> Result<()> {
+    let account = store.create(&args.name)?;
+    println!(
+        "{} Created account {}",
+        style(Glyph::Success).green().bold(),
+        style(&account.name).cyan()
+    );
+    println!("  Public key: {}", account.public_key);
+    println!(
+        "  Key file:   {}",
+        style(store.key_path(&account.name).display()).dim()
+    );
+
+    let became_default = ToolchainConfig::update(|config| {
+        if args.make_default || config.default_account.is_none() {
+            config.default_account = Some(account.name.clone());
+            true
+        } else {
+            false
+        }
+    })?;
+    if became_default {
+        println!(
+            "{} {} is now the default account for deploy",
+            style(Glyph::Arrow).cyan(),
+            account.name
+        );
+    }
+    Ok(())
+}
+
+fn list(store: &AccountStore) -> Result<()> {
+    let accounts = store.list()?;
+    if accounts.is_empty() {
+        println!("No accounts yet. Create one with 'cargo polkajam accounts new <name>'.");
+        return Ok(());
+    }
+
+    let default = ToolchainConfig::load()?.default_account;
+    let width = accounts.iter().map(|a| a.name.len()).max().unwrap_or(0);
+    for account in &accounts {
+        let is_default = default.as_deref() == Some(account.name.as_str());
+        let marker = if is_default {
+            style(Glyph::Arrow).cyan().to_string()
+        } else {
+            " ".to_string()
+        };
+        println!(
+            "{} {:<width$}  {}{}",
+            marker,
+            account.name,
+            style(&account.public_key).dim(),
+            if is_default { " (default)" } else { "" },
+            width = width
+        );
+    }
+
+    if let Some(default) = default.filter(|name| !store.contains(name)) {
+        eprintln!(
+            "{} The default account '{}' no longer exists; pick another with 'cargo polkajam accounts use'",
+            style("!").yellow().bold(),
+            default
+        );
+    }
+    Ok(())
+}
+
+fn use_account(store: &AccountStore, args: AccountsUseArgs) -> Result<()> {
+    if !store.contains(&args.name) {
+        return Err(unknown_account(&args.name));
+    }
+    ToolchainConfig::update(|config| config.default_account = Some(args.name.clone()))?;
+    println!(
+        "{} {} is now the default account for deploy",
+        style(Glyph::Success).green().bold(),
+        style(&args.name).cyan()
+    );
+    Ok(())
+}
+
+/// Check that the account directory can be located
+pub fn validate(_args: &AccountsArgs) -> Result<()> {
+    AccountStore::open().map(|_| ())
+}
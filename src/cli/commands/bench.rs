@@ -0,0 +1,112 @@
+use crate::build::estimate::{estimate, polkatool_path, polkatool_stats};
+use crate::cli::args::BenchArgs;
+use crate::cli::output::Glyph;
+use crate::cli::validate::parse_gas;
+use crate::error::{CargoJamError, Result};
+use crate::process::resolve_timeout;
+use console::style;
+
+pub fn execute(args: BenchArgs) -> Result<()> {
+    let (min_item_gas, min_memo_gas) = validate(&args)?;
+    let timeout = resolve_timeout(args.timeout)?;
+
+    println!(
+        "{} Analyzing service blob: {}",
+        style(Glyph::Arrow).cyan(),
+        style(args.code.display()).yellow()
+    );
+
+    let estimate = estimate(&args.code)?;
+
+    println!(
+        "\n{}",
+        style("Size-based guess (not a measurement):").bold()
+    );
+    println!(
+        "  {} {} bytes",
+        style("Code size:").dim(),
+        style(estimate.code_size).cyan()
+    );
+    println!(
+        "  {} ~{}",
+        style("Min item gas:").dim(),
+        style(estimate.min_item_gas).cyan()
+    );
+
+    println!("\n{}", style("Configured limits:").bold());
+    println!("  {} {}", style("Min item gas:").dim(), min_item_gas);
+    println!("  {} {}", style("Min memo gas:").dim(), min_memo_gas);
+
+    // polkatool's report describes the actual code, unlike the guess above
+    match polkatool_path(timeout) {
+        Some(polkatool) => match polkatool_stats(&polkatool, &args.code, timeout) {
+            Ok(stats) => {
+                println!("\n{}", style("polkatool stats:").bold());
+                println!("{}", stats.trim_end());
+            }
+            Err(e) => {
+                if args.verbose {
+                    println!("\n  {} {}", style("!").yellow(), e);
+                }
+            }
+        },
+        None => {
+            if args.verbose {
+                println!(
+                    "\n  {} polkatool not found, skipping static analysis",
+                    style("!").yellow()
+                );
+            }
+        }
+    }
+
+    if min_item_gas < estimate.min_item_gas {
+        println!(
+            "\n{} --min-item-gas {} is below the size-based guess ({})",
+            style(Glyph::Warning).yellow(),
+            min_item_gas,
+            estimate.min_item_gas
+        );
+        println!(
+            "  Deploy with: {} polkajam deploy {} --min-item-gas {}",
+            style("cargo").green(),
+            args.code.display(),
+            estimate.min_item_gas
+        );
+    } else {
+        println!(
+            "\n{} --min-item-gas covers the size-based guess",
+            style(Glyph::Success).green().bold()
+        );
+    }
+
+    println!(
+        "\n{} The guess scales a fixed base cost by code size; it is not a measurement, and actual gas usage depends on the workload.",
+        style(Glyph::Arrow).cyan()
+    );
+
+    Ok(())
+}
+
+/// Validate bench arguments, returning the parsed item and memo gas limits
+pub fn validate(args: &BenchArgs) -> Result<(u64, u64)> {
+    if !args.code.exists() {
+        return Err(CargoJamError::Build(format!(
+            "Service blob not found: {}",
+            args.code.display()
+        )));
+    }
+
+    if args.code.extension().map(|e| e != "jam").unwrap_or(true) {
+        return Err(CargoJamError::Build(format!(
+            "Expected a .jam file, got: {}",
+            args.code.display()
+        )));
+    }
+
+    let min_item_gas = parse_gas("--min-item-gas", &args.min_item_gas)?;
+    let min_memo_gas = parse_gas("--min-memo-gas", &args.min_memo_gas)?;
+    resolve_timeout(args.timeout)?;
+
+    Ok((min_item_gas, min_memo_gas))
+}
@@ -1,23 +1,54 @@
-use crate::build::pipeline::BuildPipeline;
+use crate::build::metadata::metadata_path;
+use crate::build::pipeline::{
+    BuildOutput, BuildPipeline, BuildProfile, CrateTiming, MessageFormat,
+};
 use crate::cli::args::BuildArgs;
+use crate::cli::output::{create_spinner, Glyph};
 use crate::error::{CargoJamError, Result};
+use crate::process::resolve_timeout;
+use crate::project::manifest::hash_bytes;
+use crate::toolchain::prune::format_size;
 use console::style;
-use indicatif::{ProgressBar, ProgressStyle};
-use std::path::Path;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Crates listed by `--profile-timings` when timings come from cargo's JSON messages
+const SLOWEST_CRATES: usize = 10;
 
 pub fn execute(args: BuildArgs) -> Result<()> {
-    let project_path = args
-        .path
-        .unwrap_or_else(|| std::env::current_dir().expect("Failed to get current directory"));
+    let project_path = project_path(&args);
 
     // Validate this is a JAM service project
     validate_jam_project(&project_path)?;
 
+    let timeout = resolve_timeout(args.timeout)?;
+
+    // stdout is reserved for cargo's messages with --message-format json
+    let mut out: Box<dyn Write> = match args.message_format {
+        Some(MessageFormat::Json) => Box::new(std::io::stderr()),
+        _ => Box::new(std::io::stdout()),
+    };
+
     let spinner = create_spinner("Building JAM service with jam-pvm-build...");
 
     let mut pipeline = BuildPipeline::new(project_path.clone());
 
-    pipeline = pipeline.release(args.release);
+    pipeline = pipeline
+        .release(args.release)
+        .auto_install(!args.no_auto_install)
+        .frozen_toolchain(args.frozen_toolchain)
+        .emit_metadata(args.emit_metadata)
+        .profile_timings(args.profile_timings)
+        .clean_intermediate(args.clean_intermediate)
+        .reproducible(args.reproducible)
+        .strip(args.strip)
+        .log_file(args.log_file.clone())
+        .message_format(args.message_format)
+        .timeout(timeout);
+
+    if let Some(ref profile) = args.profile {
+        pipeline = pipeline.profile(BuildProfile::from_name(profile));
+    }
 
     if let Some(output) = args.output {
         pipeline = pipeline.output(output);
@@ -27,21 +58,85 @@ pub fn execute(args: BuildArgs) -> Result<()> {
         pipeline = pipeline.verbose(true);
     }
 
-    match pipeline.run() {
-        Ok(output_path) => {
+    match pipeline.run_timed() {
+        Ok(BuildOutput {
+            jam_path: output_path,
+            timings,
+            strip,
+        }) => {
             spinner.finish_and_clear();
-            println!(
+            writeln!(
+                out,
                 "\n{} Built JAM service: {}",
-                style("✓").green().bold(),
+                style(Glyph::Success).green().bold(),
                 style(output_path.display()).cyan()
-            );
+            )?;
+
+            let size = std::fs::metadata(&output_path)?.len();
+            writeln!(
+                out,
+                "{} Blob size: {}",
+                style(Glyph::Arrow).cyan(),
+                format_size(size)
+            )?;
+            if let Some(strip) = strip {
+                writeln!(
+                    out,
+                    "{} ELF size: {} -> {} (stripped {})",
+                    style(Glyph::Arrow).cyan(),
+                    format_size(strip.before),
+                    format_size(strip.after),
+                    strip.mode.as_str()
+                )?;
+            }
 
-            println!(
+            if args.reproducible {
+                writeln!(
+                    out,
+                    "{} SHA-256: {}",
+                    style(Glyph::Arrow).cyan(),
+                    style(hash_bytes(&std::fs::read(&output_path)?)).green()
+                )?;
+            }
+
+            if let Some(timings) = timings {
+                writeln!(
+                    out,
+                    "{} Build took {:.1}s",
+                    style(Glyph::Arrow).cyan(),
+                    timings.elapsed.as_secs_f64()
+                )?;
+                if let Some(report) = timings.report {
+                    writeln!(
+                        out,
+                        "{} Timings report: {}",
+                        style(Glyph::Arrow).cyan(),
+                        style(report.display()).cyan()
+                    )?;
+                }
+                print_crate_timings(&timings.crates, &mut out)?;
+            }
+
+            if args.keep_intermediate {
+                print_intermediates(&pipeline, &mut out)?;
+            }
+
+            if args.emit_metadata {
+                writeln!(
+                    out,
+                    "{} Wrote service metadata: {}",
+                    style(Glyph::Success).green().bold(),
+                    style(metadata_path(&output_path).display()).cyan()
+                )?;
+            }
+
+            writeln!(
+                out,
                 "\n{} Deploy with: {} polkajam deploy {}",
-                style("→").cyan(),
+                style(Glyph::Arrow).cyan(),
                 style("cargo").green(),
                 style(output_path.display()).yellow()
-            );
+            )?;
 
             Ok(())
         }
@@ -52,19 +147,91 @@ pub fn execute(args: BuildArgs) -> Result<()> {
     }
 }
 
-fn create_spinner(message: &str) -> ProgressBar {
-    let spinner = ProgressBar::new_spinner();
-    spinner.set_style(
-        ProgressStyle::default_spinner()
-            .template("{spinner:.green} {msg}")
-            .unwrap(),
-    );
-    spinner.set_message(message.to_string());
-    spinner.enable_steady_tick(std::time::Duration::from_millis(100));
-    spinner
+/// Validate build arguments and prerequisites without building
+pub fn validate(args: &BuildArgs) -> Result<()> {
+    let project_path = project_path(args);
+    validate_jam_project(&project_path)?;
+    let mut pipeline = BuildPipeline::new(project_path).frozen_toolchain(args.frozen_toolchain);
+    if let Some(ref profile) = args.profile {
+        pipeline = pipeline.profile(BuildProfile::from_name(profile));
+    }
+    pipeline.check_toolchain()?;
+    pipeline.check_profile()
+}
+
+/// Print the slowest crates of a build timed from cargo's JSON messages
+fn print_crate_timings(crates: &[CrateTiming], out: &mut dyn Write) -> Result<()> {
+    if crates.is_empty() {
+        return Ok(());
+    }
+    let mut slowest: Vec<&CrateTiming> = crates.iter().collect();
+    slowest.sort_by_key(|timing| std::cmp::Reverse(timing.duration));
+    writeln!(
+        out,
+        "{} Slowest crates ({} compiled):",
+        style(Glyph::Arrow).cyan(),
+        crates.len()
+    )?;
+    for timing in slowest.iter().take(SLOWEST_CRATES) {
+        writeln!(
+            out,
+            "  {:>7.1}s  {}",
+            timing.duration.as_secs_f64(),
+            timing.name
+        )?;
+    }
+    Ok(())
 }
 
-fn validate_jam_project(path: &Path) -> Result<()> {
+/// Print the retained intermediate artifacts, e.g. for CI cache configuration
+fn print_intermediates(pipeline: &BuildPipeline, out: &mut dyn Write) -> Result<()> {
+    let intermediates = pipeline.intermediates()?;
+    writeln!(
+        out,
+        "{} Target directory: {}",
+        style(Glyph::Arrow).cyan(),
+        style(intermediates.target_dir.display()).cyan()
+    )?;
+
+    if intermediates.is_empty() {
+        writeln!(
+            out,
+            "{} No intermediate ELF or PolkaVM artifacts found",
+            style("!").yellow().bold()
+        )?;
+    }
+    for elf in &intermediates.elf {
+        writeln!(
+            out,
+            "{} ELF: {}",
+            style(Glyph::Arrow).cyan(),
+            style(elf.display()).cyan()
+        )?;
+    }
+    for blob in &intermediates.polkavm {
+        writeln!(
+            out,
+            "{} PolkaVM blob: {}",
+            style(Glyph::Arrow).cyan(),
+            style(blob.display()).cyan()
+        )?;
+    }
+
+    Ok(())
+}
+
+fn project_path(args: &BuildArgs) -> PathBuf {
+    args.path
+        .clone()
+        .unwrap_or_else(|| std::env::current_dir().expect("Failed to get current directory"))
+}
+
+/// The SDK crate every JAM service depends on
+const JAM_SDK_CRATE: &str = "jam-pvm-common";
+
+/// Check that a directory is a JAM service project: a Cargo.toml with jam-pvm-common
+/// as a regular (not dev- or build-only) dependency
+pub fn validate_jam_project(path: &Path) -> Result<()> {
     let cargo_toml = path.join("Cargo.toml");
 
     if !cargo_toml.exists() {
@@ -74,13 +241,86 @@ fn validate_jam_project(path: &Path) -> Result<()> {
     }
 
     let content = std::fs::read_to_string(&cargo_toml)?;
+    let manifest: toml::Value = toml::from_str(&content)
+        .map_err(|e| CargoJamError::NotJamProject(format!("Failed to parse Cargo.toml: {}", e)))?;
 
-    // Check for JAM dependencies
-    if !content.contains("jam-pvm-common") && !content.contains("jam_pvm_common") {
-        return Err(CargoJamError::NotJamProject(
-            "jam-pvm-common dependency not found in Cargo.toml".to_string(),
-        ));
+    if declares_dependency(&manifest, "dependencies") {
+        return Ok(());
     }
 
-    Ok(())
+    let only_in = [
+        ("dev-dependencies", "dev-dependency"),
+        ("build-dependencies", "build-dependency"),
+    ]
+    .into_iter()
+    .find(|(table, _)| declares_dependency(&manifest, table));
+    Err(CargoJamError::NotJamProject(match only_in {
+        Some((_, kind)) => format!(
+            "{} is only a {} in Cargo.toml; a JAM service needs it under [dependencies]",
+            JAM_SDK_CRATE, kind
+        ),
+        None => format!("{} dependency not found in Cargo.toml", JAM_SDK_CRATE),
+    }))
+}
+
+/// Whether the SDK crate is declared in a dependency table, either at the top level
+/// or for a target (`[target.'cfg(..)'.dependencies]`), possibly renamed with `package`
+fn declares_dependency(manifest: &toml::Value, table: &str) -> bool {
+    let targets = manifest
+        .get("target")
+        .and_then(toml::Value::as_table)
+        .into_iter()
+        .flat_map(|targets| targets.values());
+    std::iter::once(manifest)
+        .chain(targets)
+        .filter_map(|section| section.get(table).and_then(toml::Value::as_table))
+        .flat_map(|deps| deps.iter())
+        .any(|(name, dep)| {
+            let package = dep
+                .get("package")
+                .and_then(toml::Value::as_str)
+                .unwrap_or(name);
+            package.replace('_', "-") == JAM_SDK_CRATE
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate_manifest(manifest: &str) -> Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), manifest).unwrap();
+        validate_jam_project(dir.path())
+    }
+
+    #[test]
+    fn test_validate_jam_project() {
+        let package = "[package]\nname = \"service\"\n";
+        assert!(validate_manifest(&format!(
+            "{}\n[dependencies]\njam-pvm-common = \"0.1\"\n",
+            package
+        ))
+        .is_ok());
+        assert!(validate_manifest(&format!(
+            "{}\n[target.'cfg(any())'.dependencies]\nsdk = {{ package = \"jam-pvm-common\", version = \"0.1\" }}\n",
+            package
+        ))
+        .is_ok());
+
+        let err = validate_manifest(&format!(
+            "{}\n[dev-dependencies]\njam-pvm-common = \"0.1\"\n",
+            package
+        ))
+        .unwrap_err();
+        assert!(err.to_string().contains("only a dev-dependency"), "{}", err);
+
+        // A mention outside the dependency tables is not a dependency
+        let err = validate_manifest(&format!(
+            "{}description = \"Helpers for jam-pvm-common services\"\n",
+            package
+        ))
+        .unwrap_err();
+        assert!(err.to_string().contains("not found"), "{}", err);
+    }
 }
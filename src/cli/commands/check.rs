@@ -0,0 +1,98 @@
+use crate::cli::args::PolkajamCommand;
+use crate::cli::commands;
+use crate::cli::output::Glyph;
+use crate::error::Result;
+use console::style;
+
+/// Validate a command's arguments and prerequisites without executing it
+pub fn execute(command: &PolkajamCommand) -> Result<()> {
+    let name = match command {
+        PolkajamCommand::New(args) => {
+            commands::new::validate(args)?;
+            "new"
+        }
+        PolkajamCommand::Build(args) => {
+            commands::build::validate(args)?;
+            "build"
+        }
+        PolkajamCommand::Setup(args) => {
+            commands::setup::validate(args)?;
+            "setup"
+        }
+        PolkajamCommand::CleanToolchain(_) => "clean-toolchain",
+        PolkajamCommand::Uninstall(_) => "uninstall",
+        PolkajamCommand::Up(args) => {
+            commands::up::validate(args)?;
+            "up"
+        }
+        PolkajamCommand::Down(_) => "down",
+        PolkajamCommand::Deploy(args) => {
+            commands::deploy::validate(args)?;
+            "deploy"
+        }
+        PolkajamCommand::Monitor(args) => {
+            commands::monitor::validate(args)?;
+            "monitor"
+        }
+        PolkajamCommand::Queue(args) => {
+            commands::queue::validate(args)?;
+            "queue"
+        }
+        PolkajamCommand::Test(args) => {
+            commands::test::validate(args)?;
+            "test"
+        }
+        PolkajamCommand::Bench(args) => {
+            commands::bench::validate(args)?;
+            "bench"
+        }
+        PolkajamCommand::Diff(args) => {
+            commands::diff::validate(args)?;
+            "diff"
+        }
+        PolkajamCommand::CheckCompat(args) => {
+            commands::check_compat::validate(args)?;
+            "check-compat"
+        }
+        PolkajamCommand::Update(args) => {
+            commands::update::validate(args)?;
+            "update"
+        }
+        PolkajamCommand::Info(args) => {
+            commands::info::validate(args)?;
+            "info"
+        }
+        PolkajamCommand::Template(args) => {
+            commands::template::validate(args)?;
+            "template"
+        }
+        PolkajamCommand::Config(args) => {
+            commands::config::validate(args)?;
+            "config"
+        }
+        PolkajamCommand::Accounts(args) => {
+            commands::accounts::validate(args)?;
+            "accounts"
+        }
+        PolkajamCommand::Render(args) => {
+            commands::render::validate(args)?;
+            "render"
+        }
+        PolkajamCommand::Sign(args) => {
+            commands::sign::validate(args)?;
+            "sign"
+        }
+        PolkajamCommand::Verify(args) => {
+            commands::verify::validate(args)?;
+            "verify"
+        }
+    };
+
+    println!(
+        "{} All checks passed for '{}' (nothing was executed)",
+        style(Glyph::Success).green().bold(),
+        style(name).cyan()
+    );
+
+    Ok(())
+}
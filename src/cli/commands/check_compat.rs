@@ -0,0 +1,140 @@
+use crate::cli::args::CheckCompatArgs;
+use crate::cli::output::Glyph;
+use crate::error::{CargoJamError, Result};
+use crate::project::manifest::GenerationManifest;
+use crate::toolchain::compat::{check_sdk_dependencies, SdkStatus};
+use crate::toolchain::config::ToolchainConfig;
+use console::style;
+use std::path::PathBuf;
+
+pub fn execute(args: CheckCompatArgs) -> Result<()> {
+    validate(&args)?;
+    let project_path = project_path(&args)?;
+    let config = ToolchainConfig::load()?;
+    let installed = config
+        .installed_version
+        .as_deref()
+        .filter(|_| config.is_installed());
+
+    println!(
+        "{} Checking {} against toolchain {}",
+        style(Glyph::Arrow).cyan(),
+        style(project_path.display()).yellow(),
+        style(installed.unwrap_or("(none installed)")).cyan()
+    );
+
+    let mut problems = 0;
+
+    println!("\n{}", style("SDK dependencies:").bold());
+    let dependencies = check_sdk_dependencies(&project_path)?;
+    if dependencies.is_empty() {
+        println!(
+            "  {} No JAM SDK dependencies found",
+            style(Glyph::Bullet).dim()
+        );
+    }
+    for dep in &dependencies {
+        match dep.status {
+            SdkStatus::Compatible => println!(
+                "  {} {} {}",
+                style(Glyph::Success).green(),
+                dep.name,
+                style(&dep.requirement).dim()
+            ),
+            SdkStatus::Incompatible => {
+                problems += 1;
+                println!(
+                    "  {} {} {} does not accept {}: set {} = \"{}\" in Cargo.toml",
+                    style(Glyph::Failure).red(),
+                    dep.name,
+                    style(&dep.requirement).yellow(),
+                    dep.expected,
+                    dep.name,
+                    dep.expected
+                );
+            }
+            SdkStatus::Unchecked => println!(
+                "  {} {} {} (not checked, the toolchain expects {})",
+                style(Glyph::Bullet).dim(),
+                dep.name,
+                style(&dep.requirement).dim(),
+                dep.expected
+            ),
+        }
+    }
+
+    println!("\n{}", style("Toolchain:").bold());
+    let generated_with = GenerationManifest::load(&project_path)
+        .ok()
+        .and_then(|manifest| manifest.toolchain);
+    match (generated_with.as_deref(), installed) {
+        (Some(generated), Some(installed)) if generated != installed => println!(
+            "  {} Generated with {}, {} is installed now",
+            style(Glyph::Arrow).cyan(),
+            generated,
+            style(installed).cyan()
+        ),
+        (Some(generated), _) => println!(
+            "  {} Generated with {}",
+            style(Glyph::Bullet).dim(),
+            generated
+        ),
+        (None, _) => println!(
+            "  {} Generating toolchain not recorded",
+            style(Glyph::Bullet).dim()
+        ),
+    }
+
+    match ToolchainConfig::required_version(&project_path)? {
+        Some(required) if Some(required.as_str()) != installed => {
+            problems += 1;
+            println!(
+                "  {} Pinned to {} but {} is installed: run {}",
+                style(Glyph::Failure).red(),
+                required,
+                installed.unwrap_or("none"),
+                style(format!("cargo polkajam setup --version {}", required)).cyan()
+            );
+        }
+        Some(required) => println!("  {} Pinned to {}", style(Glyph::Success).green(), required),
+        None => {}
+    }
+
+    if problems == 0 {
+        println!(
+            "\n{} No known incompatibilities with the installed toolchain",
+            style(Glyph::Success).green().bold()
+        );
+        return Ok(());
+    }
+
+    let summary = format!(
+        "{} known incompatibilit{} with the installed toolchain",
+        problems,
+        if problems == 1 { "y" } else { "ies" }
+    );
+    if args.strict {
+        return Err(CargoJamError::Build(summary));
+    }
+    println!("\n{} {}", style("!").yellow().bold(), summary);
+    Ok(())
+}
+
+/// Validate that the path is a Cargo project
+pub fn validate(args: &CheckCompatArgs) -> Result<()> {
+    let cargo_toml = project_path(args)?.join("Cargo.toml");
+    if !cargo_toml.is_file() {
+        return Err(CargoJamError::NotJamProject(format!(
+            "{} not found",
+            cargo_toml.display()
+        )));
+    }
+    Ok(())
+}
+
+fn project_path(args: &CheckCompatArgs) -> Result<PathBuf> {
+    match args.path {
+        Some(ref path) => Ok(path.clone()),
+        None => Ok(std::env::current_dir()?),
+    }
+}
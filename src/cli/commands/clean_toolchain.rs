@@ -0,0 +1,82 @@
+use crate::cli::args::CleanToolchainArgs;
+use crate::cli::output::Glyph;
+use crate::error::Result;
+use crate::toolchain::config::ToolchainConfig;
+use crate::toolchain::prune::{cached_versions, format_size, prunable};
+use console::style;
+
+pub fn execute(args: CleanToolchainArgs) -> Result<()> {
+    let cache_dir = ToolchainConfig::cache_dir()?;
+    let versions = cached_versions(&cache_dir)?;
+    if versions.is_empty() {
+        println!(
+            "{} No cached toolchain versions in {}",
+            style(Glyph::Arrow).cyan(),
+            style(cache_dir.display()).yellow()
+        );
+        return Ok(());
+    }
+
+    let config = ToolchainConfig::load()?;
+    let active = config.installed_version.as_deref();
+    let keep = if args.prune { args.keep } else { 0 };
+    let removed = prunable(&versions, keep, active);
+
+    println!(
+        "{} Cached toolchain versions in {}:",
+        style(Glyph::Arrow).cyan(),
+        style(cache_dir.display()).yellow()
+    );
+    for version in &versions {
+        let size = format_size(version.size);
+        if removed.iter().any(|r| r.version == version.version) {
+            println!(
+                "  {} {} ({})",
+                style(Glyph::Failure).red(),
+                version.version,
+                size
+            );
+        } else if Some(version.version.as_str()) == active {
+            println!(
+                "  {} {} ({}, active)",
+                style(Glyph::Success).green(),
+                style(&version.version).cyan(),
+                size
+            );
+        } else {
+            println!(
+                "  {} {} ({})",
+                style(Glyph::Success).green(),
+                version.version,
+                size
+            );
+        }
+    }
+
+    let freed: u64 = removed.iter().map(|v| v.size).sum();
+    if removed.is_empty() {
+        println!(
+            "\n{} Nothing to remove",
+            style(Glyph::Success).green().bold()
+        );
+    } else if args.dry_run {
+        println!(
+            "\n{} Would remove {} version(s), freeing {}",
+            style(Glyph::Arrow).cyan(),
+            removed.len(),
+            style(format_size(freed)).yellow()
+        );
+    } else {
+        for version in &removed {
+            std::fs::remove_dir_all(&version.path)?;
+        }
+        println!(
+            "\n{} Removed {} version(s), freeing {}",
+            style(Glyph::Success).green().bold(),
+            removed.len(),
+            style(format_size(freed)).yellow()
+        );
+    }
+
+    Ok(())
+}
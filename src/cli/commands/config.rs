@@ -0,0 +1,70 @@
+use crate::cli::args::{ConfigArgs, ConfigCommand, ConfigEditArgs};
+use crate::cli::output::Glyph;
+use crate::error::Result;
+use crate::project::editor::{edit_file, find_file_editor};
+use crate::prompt::interactive::PromptRunner;
+use crate::toolchain::config::{ToolchainConfig, CONFIG_TEMPLATE};
+use console::style;
+use std::io::IsTerminal;
+
+pub fn execute(args: ConfigArgs) -> Result<()> {
+    match args.command {
+        ConfigCommand::Path => {
+            println!("{}", ToolchainConfig::config_path()?.display());
+            Ok(())
+        }
+        ConfigCommand::Edit(edit_args) => edit(edit_args),
+    }
+}
+
+/// Open the config file in an editor, creating it from a commented template,
+/// and check that it still parses once the editor exits
+fn edit(args: ConfigEditArgs) -> Result<()> {
+    let config_path = ToolchainConfig::config_path()?;
+    if !config_path.exists() {
+        if let Some(parent) = config_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&config_path, CONFIG_TEMPLATE)?;
+        println!(
+            "{} Created {}",
+            style(Glyph::Success).green().bold(),
+            style(config_path.display()).cyan()
+        );
+    }
+
+    let editor = find_file_editor(args.editor.as_deref());
+    let interactive = std::io::stdin().is_terminal();
+    loop {
+        edit_file(&editor, &config_path)?;
+
+        let content = std::fs::read_to_string(&config_path)?;
+        match ToolchainConfig::parse(&content) {
+            Ok(_) => {
+                println!(
+                    "{} {} is valid",
+                    style(Glyph::Success).green().bold(),
+                    style(config_path.display()).cyan()
+                );
+                return Ok(());
+            }
+            Err(e) if interactive => {
+                eprintln!("{} {}", style(Glyph::Failure).red(), e);
+                if !PromptRunner::new().prompt_bool("Reopen the editor?", true)? {
+                    eprintln!(
+                        "{} Leaving {} as it is: commands reading the config will fail until it is fixed",
+                        style("!").yellow().bold(),
+                        config_path.display()
+                    );
+                    return Err(e);
+                }
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Check that the config location can be determined
+pub fn validate(_args: &ConfigArgs) -> Result<()> {
+    ToolchainConfig::config_path().map(|_| ())
+}
@@ -1,10 +1,535 @@
+use crate::build::estimate::{estimate, suggested_gas};
+use crate::build::pipeline::BuildPipeline;
+use crate::build::signing::{signature_path, verify_blob};
+use crate::build::watch::SourceWatcher;
 use crate::cli::args::DeployArgs;
+use crate::cli::commands::up::{resolve_rpc_url, DEFAULT_RPC_URL};
+use crate::cli::output::Glyph;
+use crate::cli::validate::{check_rpc_reachable, normalize_rpc_url, parse_gas};
 use crate::error::{CargoJamError, Result};
+use crate::process::{output_with_timeout, resolve_timeout};
+use crate::prompt::interactive::PromptRunner;
+use crate::toolchain::accounts::AccountStore;
 use crate::toolchain::config::ToolchainConfig;
+use crate::toolchain::deployments::{code_hash, parse_service_id, Deployments};
 use console::style;
+use std::cell::OnceCell;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::Duration;
 
-pub fn execute(args: DeployArgs) -> Result<()> {
+/// Quiet period required after a change before rebuilding in watch mode
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// jamt subcommands that look up a service by ID, in order of preference
+const JAMT_SERVICE_COMMANDS: &[&str] = &["service", "inspect"];
+
+pub fn execute(mut args: DeployArgs) -> Result<()> {
+    args.rpc = Some(resolve_rpc_url(args.rpc.as_deref())?);
+    let jamt = validate(&args)?;
+    let jamt_bin = &jamt.bin;
+    let timeout = resolve_timeout(args.timeout)?;
+    let seed = account_seed(&args, &jamt, &ToolchainConfig::load()?)?;
+
+    if args.watch {
+        return watch(&args, jamt_bin, seed.as_deref(), timeout);
+    }
+
+    if args.interactive && !wizard(&mut args, jamt_bin)? {
+        println!("{} Deployment cancelled", style("!").yellow().bold());
+        return Ok(());
+    }
+
+    check_rpc_reachable(rpc(&args))?;
+
+    if let (true, Some(id)) = (args.skip_if_exists, &args.expect_id) {
+        if service_exists(&jamt, rpc(&args), id, timeout)? {
+            println!(
+                "{} Service {} already exists, skipping deploy",
+                style(Glyph::Success).green().bold(),
+                style(id).cyan()
+            );
+            return Ok(());
+        }
+    }
+
+    let stdout = deploy_blob(&args, jamt_bin, seed.as_deref(), timeout)?;
+    if let Some(ref expected) = args.expect_id {
+        check_expected_id(expected, service_id(&args, &stdout).as_deref())?;
+    }
+
+    let done = if args.upgrade.is_some() {
+        "upgraded"
+    } else {
+        "deployed"
+    };
+    println!(
+        "\n{} Service {} successfully!",
+        style(Glyph::Success).green().bold(),
+        done
+    );
+
+    if let Some(ref script) = args.post_deploy {
+        run_post_deploy(script, service_id(&args, &stdout).as_deref(), rpc(&args))?;
+    }
+
+    Ok(())
+}
+
+/// Submit the blob with `jamt create-service`, or `jamt upgrade` with `--upgrade`,
+/// returning jamt's stdout. `seed` is the hex seed of the account to deploy from.
+fn deploy_blob(
+    args: &DeployArgs,
+    jamt_bin: &Path,
+    seed: Option<&str>,
+    timeout: Option<Duration>,
+) -> Result<String> {
+    let new_hash = code_hash(&std::fs::read(&args.code)?);
+    let mut deployments = Deployments::load()?;
+
+    match args.upgrade {
+        Some(ref id) => {
+            println!(
+                "{} Upgrading service {}: {}",
+                style(Glyph::Arrow).cyan(),
+                style(id).cyan(),
+                style(args.code.display()).yellow()
+            );
+            let old_hash = deployments.code_hash(rpc(args), id);
+            println!(
+                "  Old code hash: {}",
+                old_hash.unwrap_or("unknown (not deployed from this machine)")
+            );
+            println!("  New code hash: {}", new_hash);
+            if old_hash == Some(new_hash.as_str()) {
+                println!(
+                    "{} The service already runs this code",
+                    style("!").yellow().bold()
+                );
+            }
+        }
+        None => println!(
+            "{} Deploying service: {}",
+            style(Glyph::Arrow).cyan(),
+            style(args.code.display()).yellow()
+        ),
+    }
+
+    if args.verbose {
+        println!("  RPC: {}", style(rpc(args)).dim());
+        println!("  Amount: {}", args.amount);
+        println!("  Min item gas: {}", args.min_item_gas);
+        println!("  Min memo gas: {}", args.min_memo_gas);
+    }
+
+    if args.probe {
+        probe_gas(args)?;
+    }
+
+    let mut cmd = Command::new(jamt_bin);
+    // Through the environment rather than argv, where any local user could read it
+    if let Some(seed) = seed {
+        cmd.env(SEED_ENV, seed);
+    }
+    cmd.args(jamt_args(args));
+
+    let output = output_with_timeout(&mut cmd, timeout).map_err(|e| match e {
+        CargoJamError::Io(e) => CargoJamError::Build(format!("Failed to execute jamt: {}", e)),
+        e => e,
+    })?;
+
+    // Print output
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    if !stdout.is_empty() {
+        println!("{}", stdout);
+    }
+
+    if !output.status.success() {
+        if !stderr.is_empty() {
+            eprintln!("{}", stderr);
+        }
+        return Err(CargoJamError::Build(format!(
+            "Deployment failed with status: {}",
+            output.status
+        )));
+    }
+
+    if let Some(id) = service_id(args, &stdout) {
+        deployments.record(rpc(args), &id, &new_hash)?;
+    }
+
+    Ok(stdout)
+}
+
+/// The endpoint to deploy to; `execute` fills in the default when `--rpc` is omitted
+fn rpc(args: &DeployArgs) -> &str {
+    args.rpc.as_deref().unwrap_or(DEFAULT_RPC_URL)
+}
+
+/// Compare `--min-item-gas` with the size-based guess `bench` reports, warning when it
+/// is below. The guess is no measurement, so it never blocks the deploy.
+fn probe_gas(args: &DeployArgs) -> Result<()> {
+    let guess = estimate(&args.code)?.min_item_gas;
+    let item_gas = parse_gas("--min-item-gas", &args.min_item_gas)?;
+    println!(
+        "{} Probing gas limits against a size-based guess (not a measurement)",
+        style(Glyph::Arrow).cyan()
+    );
+
+    if item_gas >= guess {
+        println!(
+            "  {} --min-item-gas {} covers the guessed {}",
+            style(Glyph::Success).green(),
+            item_gas,
+            guess
+        );
+    } else {
+        println!(
+            "  {} --min-item-gas {} is below the guessed {}",
+            style(Glyph::Failure).red(),
+            item_gas,
+            guess
+        );
+    }
+    println!(
+        "  {} --min-memo-gas {} (not estimated)",
+        style(Glyph::Bullet).dim(),
+        args.min_memo_gas
+    );
+
+    if item_gas < guess {
+        eprintln!(
+            "{} --min-item-gas may be too low and the deploy may fail on-chain; try {}",
+            style("!").yellow().bold(),
+            style(format!("--min-item-gas {}", suggested_gas(guess))).cyan()
+        );
+    }
+    Ok(())
+}
+
+/// The ID of the service deployed or upgraded
+fn service_id(args: &DeployArgs, stdout: &str) -> Option<String> {
+    args.upgrade
+        .clone()
+        .or_else(|| service_id_from_output(stdout))
+}
+
+/// Arguments passed to `jamt` to create or upgrade the service
+fn jamt_args(args: &DeployArgs) -> Vec<std::ffi::OsString> {
+    // Note: --rpc is a global option and must come BEFORE the subcommand
+    let mut jamt: Vec<std::ffi::OsString> = vec!["--rpc".into(), rpc(args).into()];
+
+    match args.upgrade {
+        Some(ref id) => {
+            jamt.push("upgrade".into());
+            jamt.push(id.clone().into());
+            jamt.push(args.code.clone().into());
+        }
+        None => {
+            jamt.push("create-service".into());
+            jamt.push(args.code.clone().into());
+            jamt.push(args.amount.clone().into());
+            if !args.memo.is_empty() {
+                jamt.push(args.memo.clone().into());
+            }
+        }
+    }
+
+    jamt.push("--min-item-gas".into());
+    jamt.push(args.min_item_gas.clone().into());
+    jamt.push("--min-memo-gas".into());
+    jamt.push(args.min_memo_gas.clone().into());
+
+    if let Some(ref register) = args.register {
+        jamt.push("--register".into());
+        jamt.push(register.clone().into());
+    }
+
+    jamt
+}
+
+/// Render a command line for display, quoting arguments that contain whitespace
+fn display_command(program: &Path, args: &[std::ffi::OsString]) -> String {
+    std::iter::once(program.as_os_str())
+        .chain(args.iter().map(|a| a.as_os_str()))
+        .map(|a| {
+            let a = a.to_string_lossy();
+            if a.is_empty() || a.contains(char::is_whitespace) {
+                format!("'{}'", a.replace('\'', "'\\''"))
+            } else {
+                a.into_owned()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Walk through the deploy parameters with `--interactive`, returning whether
+/// the user confirmed the resulting jamt command
+fn wizard(args: &mut DeployArgs, jamt_bin: &Path) -> Result<bool> {
+    let runner = PromptRunner::new();
+    let number = Some(r"^[0-9]+$");
+
+    println!(
+        "\n{} The initial balance transferred to the new service (0 is fine on a local testnet)",
+        style(Glyph::Arrow).cyan()
+    );
+    args.amount = runner.prompt_string("Endowment amount", Some(&args.amount), number)?;
+
+    println!(
+        "\n{} Optional data attached to the endowment transfer (leave empty for none)",
+        style(Glyph::Arrow).cyan()
+    );
+    args.memo = runner.prompt_string("Memo", Some(&args.memo), None)?;
+
+    println!(
+        "\n{} Gas reserved for accumulating each work-item. Too little and accumulate runs out of gas",
+        style(Glyph::Arrow).cyan()
+    );
+    args.min_item_gas =
+        runner.prompt_string("Minimum item gas", Some(&args.min_item_gas), number)?;
+
+    println!(
+        "\n{} Gas reserved for handling each incoming transfer memo (on-transfer)",
+        style(Glyph::Arrow).cyan()
+    );
+    args.min_memo_gas =
+        runner.prompt_string("Minimum memo gas", Some(&args.min_memo_gas), number)?;
+
+    println!(
+        "\n{} Name to register with the Bootstrap service (leave empty to skip registration)",
+        style(Glyph::Arrow).cyan()
+    );
+    let register = runner.prompt_string(
+        "Register as",
+        Some(args.register.as_deref().unwrap_or("")),
+        None,
+    )?;
+    args.register = Some(register.trim().to_string()).filter(|r| !r.is_empty());
+
+    println!(
+        "\n{} WebSocket endpoint of the node to deploy to",
+        style(Glyph::Arrow).cyan()
+    );
+    args.rpc = Some(loop {
+        let url = runner.prompt_string("RPC URL", Some(rpc(args)), None)?;
+        match normalize_rpc_url(&url) {
+            Ok(url) => break url,
+            Err(e) => println!("{} {}", style(Glyph::Failure).red(), e),
+        }
+    });
+
+    parse_gas("--min-item-gas", &args.min_item_gas)?;
+    parse_gas("--min-memo-gas", &args.min_memo_gas)?;
+
+    println!(
+        "\n{} {}\n",
+        style("Command:").bold(),
+        style(display_command(jamt_bin, &jamt_args(args))).green()
+    );
+    runner.prompt_bool("Run this command?", true)
+}
+
+/// Rebuild and redeploy the project every time its sources change
+fn watch(
+    args: &DeployArgs,
+    jamt_bin: &Path,
+    seed: Option<&str>,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    let project_path = args
+        .path
+        .clone()
+        .unwrap_or_else(|| std::env::current_dir().expect("Failed to get current directory"));
+
+    let mut watcher = SourceWatcher::new(project_path.clone());
+
+    println!(
+        "{} Watching {} for changes (press Ctrl+C to stop)",
+        style(Glyph::Arrow).cyan(),
+        style(project_path.display()).yellow()
+    );
+
+    loop {
+        let build = BuildPipeline::new(project_path.clone())
+            .output(args.code.clone())
+            .timeout(timeout)
+            .verbose(args.verbose)
+            .run();
+
+        match build {
+            Ok(_) => match deploy_blob(args, jamt_bin, seed, timeout) {
+                Ok(stdout) => {
+                    let service_id = service_id(args, &stdout);
+                    let done = if args.upgrade.is_some() {
+                        "Upgraded"
+                    } else {
+                        "Deployed"
+                    };
+                    match service_id {
+                        Some(ref id) => println!(
+                            "{} {} service {}",
+                            style(Glyph::Success).green().bold(),
+                            done,
+                            style(id).cyan()
+                        ),
+                        None => {
+                            println!("{} Service deployed", style(Glyph::Success).green().bold())
+                        }
+                    }
+
+                    if let Some(ref script) = args.post_deploy {
+                        if let Err(e) = run_post_deploy(script, service_id.as_deref(), rpc(args)) {
+                            println!("{} {}", style(Glyph::Failure).red().bold(), e);
+                        }
+                    }
+                }
+                Err(e) => println!("{} {}", style(Glyph::Failure).red().bold(), e),
+            },
+            Err(e) => println!(
+                "{} Build failed, skipping deploy: {}",
+                style(Glyph::Failure).red().bold(),
+                e
+            ),
+        }
+
+        println!("\n{} Waiting for changes...", style(Glyph::Arrow).cyan());
+        watcher.wait_for_change(WATCH_DEBOUNCE);
+        println!(
+            "{} Change detected, rebuilding...",
+            style(Glyph::Arrow).cyan()
+        );
+    }
+}
+
+/// Run the `--post-deploy` command through the platform shell.
+///
+/// The command sees the deployed service ID as `CARGO_JAM_SERVICE_ID` (when
+/// jamt reported one) and the endpoint as `CARGO_JAM_RPC`.
+fn run_post_deploy(script: &str, service_id: Option<&str>, rpc: &str) -> Result<()> {
+    println!(
+        "{} Running post-deploy script: {}",
+        style(Glyph::Arrow).cyan(),
+        style(script).yellow()
+    );
+
+    let mut cmd = if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C").arg(script);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(script);
+        cmd
+    };
+
+    cmd.env("CARGO_JAM_RPC", rpc);
+    match service_id {
+        Some(id) => cmd.env("CARGO_JAM_SERVICE_ID", id),
+        None => {
+            println!(
+                "  {} Could not determine the service ID; CARGO_JAM_SERVICE_ID is not set",
+                style("!").yellow()
+            );
+            cmd.env_remove("CARGO_JAM_SERVICE_ID")
+        }
+    };
+
+    let status = cmd
+        .status()
+        .map_err(|e| CargoJamError::Build(format!("Failed to run post-deploy script: {}", e)))?;
+
+    if !status.success() {
+        return Err(CargoJamError::Build(format!(
+            "Post-deploy script failed with status: {}",
+            status
+        )));
+    }
+
+    println!(
+        "{} Post-deploy script passed",
+        style(Glyph::Success).green().bold()
+    );
+    Ok(())
+}
+
+/// Fail unless the deployed service has the `--expect-id` ID
+fn check_expected_id(expected: &str, actual: Option<&str>) -> Result<()> {
+    let Some(actual) = actual else {
+        return Err(CargoJamError::Build(format!(
+            "jamt did not report the new service ID, so it can't be checked against --expect-id {}",
+            expected
+        )));
+    };
+    match parse_service_id(actual) {
+        Ok(ref id) if id == expected => Ok(()),
+        _ => Err(CargoJamError::Build(format!(
+            "deployed service {} does not match --expect-id {}",
+            actual, expected
+        ))),
+    }
+}
+
+/// Whether the chain has a service with this ID, looked up with jamt
+fn service_exists(jamt: &Jamt, rpc: &str, id: &str, timeout: Option<Duration>) -> Result<bool> {
+    let command = jamt.service_command().ok_or_else(no_service_lookup)?;
+
+    let mut cmd = Command::new(&jamt.bin);
+    cmd.arg("--rpc").arg(rpc).arg(command).arg(id);
+    let output = output_with_timeout(&mut cmd, timeout).map_err(|e| match e {
+        CargoJamError::Io(e) => CargoJamError::Build(format!("Failed to execute jamt: {}", e)),
+        e => e,
+    })?;
+    if output.status.success() {
+        return Ok(true);
+    }
+    // Only a lookup jamt reports as missing means "absent"; an unreachable RPC or
+    // any other failure must not be taken as license to create a duplicate
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if is_service_not_found(&stderr) || is_service_not_found(&stdout) {
+        return Ok(false);
+    }
+    Err(CargoJamError::Build(format!(
+        "Failed to look up service {} with jamt: {}",
+        id,
+        stderr.trim()
+    )))
+}
+
+/// Whether jamt's output reports that the looked-up service doesn't exist
+fn is_service_not_found(output: &str) -> bool {
+    let output = output.to_lowercase();
+    [
+        "not found",
+        "no such service",
+        "does not exist",
+        "doesn't exist",
+    ]
+    .iter()
+    .any(|phrase| output.contains(phrase))
+}
+
+fn no_service_lookup() -> CargoJamError {
+    CargoJamError::InvalidArgument {
+        arg: "--skip-if-exists".to_string(),
+        reason: "the installed jamt has no command to look up a service (run 'cargo polkajam setup' to update the toolchain)".to_string(),
+    }
+}
+
+/// Extract the new service ID from jamt's `create-service` output
+pub fn service_id_from_output(output: &str) -> Option<String> {
+    let re = regex::Regex::new(r"Service\s+#?([0-9A-Za-z]+)").ok()?;
+    output
+        .lines()
+        .filter(|l| l.contains("created"))
+        .find_map(|l| re.captures(l).map(|c| c[1].to_string()))
+}
+
+/// Validate deploy arguments and prerequisites, returning the installed jamt
+pub fn validate(args: &DeployArgs) -> Result<Jamt> {
     // Check toolchain is installed
     let config = ToolchainConfig::load()?;
     if !config.is_installed() {
@@ -21,8 +546,8 @@ pub fn execute(args: DeployArgs) -> Result<()> {
                 .to_string(),
         })?;
 
-    // Verify the .jam file exists
-    if !args.code.exists() {
+    // Verify the .jam file exists (in watch mode it is produced by the build)
+    if !args.watch && !args.code.exists() {
         return Err(CargoJamError::Build(format!(
             "Service blob not found: {}",
             args.code.display()
@@ -37,64 +562,263 @@ pub fn execute(args: DeployArgs) -> Result<()> {
         )));
     }
 
+    parse_gas("--min-item-gas", &args.min_item_gas)?;
+    parse_gas("--min-memo-gas", &args.min_memo_gas)?;
+    if let Some(ref rpc) = args.rpc {
+        normalize_rpc_url(rpc)?;
+    }
+
+    let jamt = Jamt::new(jamt_bin, resolve_timeout(args.timeout)?);
+    if args.skip_if_exists && jamt.service_command().is_none() {
+        return Err(no_service_lookup());
+    }
+
+    if args.upgrade.is_some() && !jamt.supports_upgrade() {
+        return Err(CargoJamError::InvalidArgument {
+            arg: "--upgrade".to_string(),
+            reason: "the installed jamt has no 'upgrade' command (run 'cargo polkajam setup' to update the toolchain)".to_string(),
+        });
+    }
+
+    if args.require_signed {
+        verify_blob(
+            &args.code,
+            &signature_path(&args.code),
+            &config.trusted_keys,
+        )?;
+    }
+
+    Ok(jamt)
+}
+
+/// Environment variable jamt reads the signing account's hex seed from
+const SEED_ENV: &str = "JAMT_SEED";
+
+/// Seed of the account to deploy from: `--account`, else the default account.
+///
+/// jamt that doesn't read [`SEED_ENV`] always uses its built-in dev account, which
+/// is an error for an explicit `--account` and a warning for the default one.
+fn account_seed(
+    args: &DeployArgs,
+    jamt: &Jamt,
+    config: &ToolchainConfig,
+) -> Result<Option<String>> {
+    let (name, explicit) = match (&args.account, &config.default_account) {
+        (Some(name), _) => (name, true),
+        (None, Some(name)) => (name, false),
+        (None, None) => return Ok(None),
+    };
+    let key = AccountStore::open()?.key(name)?;
+
+    if !jamt.reads_seed() {
+        if explicit {
+            return Err(CargoJamError::InvalidArgument {
+                arg: "--account".to_string(),
+                reason: format!("the installed jamt doesn't read the account seed from {} (run 'cargo polkajam setup' to update the toolchain)", SEED_ENV),
+            });
+        }
+        eprintln!(
+            "{} The installed jamt doesn't read the account seed from {}; deploying from its built-in account instead of '{}'",
+            style("!").yellow().bold(),
+            SEED_ENV,
+            name
+        );
+        return Ok(None);
+    }
+
     println!(
-        "{} Deploying service: {}",
-        style("→").cyan(),
-        style(args.code.display()).yellow()
+        "{} Using account {} ({})",
+        style(Glyph::Arrow).cyan(),
+        style(name).cyan(),
+        style(key.public_key()).dim()
     );
+    Ok(Some(key.seed_hex()))
+}
 
-    if args.verbose {
-        println!("  RPC: {}", style(&args.rpc).dim());
-        println!("  Amount: {}", args.amount);
-        println!("  Min item gas: {}", args.min_item_gas);
-        println!("  Min memo gas: {}", args.min_memo_gas);
+/// The installed jamt, whose `--help` is read at most once per deploy to see what it supports
+pub struct Jamt {
+    bin: PathBuf,
+    timeout: Option<Duration>,
+    help: OnceCell<String>,
+}
+
+impl Jamt {
+    fn new(bin: PathBuf, timeout: Option<Duration>) -> Self {
+        Self {
+            bin,
+            timeout,
+            help: OnceCell::new(),
+        }
     }
 
-    // Build jamt command
-    // Note: --rpc is a global option and must come BEFORE the subcommand
-    let mut cmd = Command::new(&jamt_bin);
-    cmd.arg("--rpc").arg(&args.rpc);
-    cmd.arg("create-service");
-    cmd.arg(&args.code);
-    cmd.arg(&args.amount);
+    /// `jamt --help`, run within the timeout on first use; empty if it fails
+    fn help(&self) -> &str {
+        self.help.get_or_init(|| {
+            output_with_timeout(Command::new(&self.bin).arg("--help"), self.timeout)
+                .map(|o| String::from_utf8_lossy(&o.stdout).into_owned())
+                .unwrap_or_default()
+        })
+    }
 
-    if !args.memo.is_empty() {
-        cmd.arg(&args.memo);
+    /// Whether jamt's help lists `command`, as the first word of a line
+    fn has_command(&self, command: &str) -> bool {
+        self.help()
+            .lines()
+            .any(|line| line.split_whitespace().next() == Some(command))
     }
 
-    cmd.arg("--min-item-gas").arg(&args.min_item_gas);
-    cmd.arg("--min-memo-gas").arg(&args.min_memo_gas);
+    /// The subcommand jamt offers to look up a service, if any
+    fn service_command(&self) -> Option<&'static str> {
+        JAMT_SERVICE_COMMANDS
+            .iter()
+            .copied()
+            .find(|command| self.has_command(command))
+    }
 
-    if let Some(ref register) = args.register {
-        cmd.arg("--register").arg(register);
+    /// Whether jamt reads the account seed from [`SEED_ENV`], which its help lists
+    /// as `[env: JAMT_SEED=]`
+    fn reads_seed(&self) -> bool {
+        advertises_env(self.help(), SEED_ENV)
     }
 
-    let output = cmd
-        .output()
-        .map_err(|e| CargoJamError::Build(format!("Failed to execute jamt: {}", e)))?;
+    /// Whether jamt can replace a service's code
+    fn supports_upgrade(&self) -> bool {
+        self.has_command("upgrade")
+    }
+}
 
-    // Print output
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let stderr = String::from_utf8_lossy(&output.stderr);
+fn advertises_env(help: &str, var: &str) -> bool {
+    help.contains(&format!("[env: {}=", var))
+}
 
-    if !stdout.is_empty() {
-        println!("{}", stdout);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_service_not_found() {
+        assert!(is_service_not_found("Error: Service 0x1a not found"));
+        assert!(is_service_not_found("error: no such service: 42"));
+        assert!(!is_service_not_found(
+            "Error: connection refused (ws://localhost:19800)"
+        ));
+        assert!(!is_service_not_found(""));
     }
 
-    if !output.status.success() {
-        if !stderr.is_empty() {
-            eprintln!("{}", stderr);
-        }
-        return Err(CargoJamError::Build(format!(
-            "Deployment failed with status: {}",
-            output.status
-        )));
+    #[test]
+    fn test_advertises_env() {
+        let help = "      --rpc <RPC>  RPC endpoint [env: JAMT_RPC=] [default: ws://localhost:19800]\n      --seed <HEX>  Account seed [env: JAMT_SEED=]\n";
+        assert!(advertises_env(help, "JAMT_SEED"));
+        assert!(!advertises_env(help, "JAMT_SEED_FILE"));
+        assert!(!advertises_env(
+            "      --seed <HEX>  Account seed\n",
+            "JAMT_SEED"
+        ));
     }
 
-    println!(
-        "\n{} Service deployed successfully!",
-        style("✓").green().bold()
-    );
+    #[test]
+    #[cfg(unix)]
+    fn test_jamt_help_probed_once() {
+        use std::os::unix::fs::PermissionsExt;
 
-    Ok(())
+        let dir = tempfile::tempdir().unwrap();
+        let bin = dir.path().join("jamt");
+        let calls = dir.path().join("calls");
+        std::fs::write(
+            &bin,
+            format!(
+                "#!/bin/sh\necho >> {}\necho 'Commands:'\necho '  upgrade  Replace code'\necho '  service  Show a service'\necho '      --seed <HEX>  [env: JAMT_SEED=]'\n",
+                calls.display()
+            ),
+        )
+        .unwrap();
+        std::fs::set_permissions(&bin, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let jamt = Jamt::new(bin, Some(Duration::from_secs(10)));
+        assert_eq!(jamt.service_command(), Some("service"));
+        assert!(jamt.reads_seed());
+        assert!(jamt.supports_upgrade());
+        assert_eq!(std::fs::read_to_string(&calls).unwrap().lines().count(), 1);
+    }
+
+    #[test]
+    fn test_jamt_upgrade_command_exact() {
+        let jamt = Jamt::new(PathBuf::from("jamt"), None);
+        jamt.help
+            .set("  upgrade-all  Upgrade everything\n  upgraded  List upgrades\n".to_string())
+            .unwrap();
+        assert!(!jamt.supports_upgrade());
+
+        let jamt = Jamt::new(PathBuf::from("jamt"), None);
+        jamt.help
+            .set("  upgrade  Replace a service's code\n".to_string())
+            .unwrap();
+        assert!(jamt.supports_upgrade());
+    }
+
+    #[test]
+    fn test_display_command() {
+        let args = [
+            "--rpc".into(),
+            "ws://localhost:19800".into(),
+            "my memo".into(),
+            "".into(),
+        ];
+        assert_eq!(
+            display_command(Path::new("jamt"), &args),
+            "jamt --rpc ws://localhost:19800 'my memo' ''"
+        );
+    }
+
+    #[test]
+    fn test_jamt_args_upgrade() {
+        use clap::Parser;
+        let args =
+            DeployArgs::try_parse_from(["deploy", "svc.jam", "--upgrade", "0x1A", "-G", "5"])
+                .unwrap();
+        let jamt: Vec<_> = jamt_args(&args)
+            .iter()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            jamt[2..],
+            [
+                "upgrade",
+                "1a",
+                "svc.jam",
+                "--min-item-gas",
+                "5",
+                "--min-memo-gas",
+                "1000000"
+            ]
+        );
+        assert_eq!(service_id(&args, "").as_deref(), Some("1a"));
+
+        // An upgrade keeps the service's balance and registration
+        assert!(DeployArgs::try_parse_from([
+            "deploy",
+            "svc.jam",
+            "--upgrade",
+            "1a",
+            "--amount",
+            "5"
+        ])
+        .is_err());
+        assert!(DeployArgs::try_parse_from(["deploy", "svc.jam", "--upgrade", "svc"]).is_err());
+    }
+
+    #[test]
+    fn test_check_expected_id() {
+        check_expected_id("1a2b", Some("0x1A2B")).unwrap();
+        let err = check_expected_id("1a2b", Some("ff")).unwrap_err();
+        assert!(err.to_string().contains("does not match --expect-id 1a2b"));
+        assert!(check_expected_id("1a2b", None).is_err());
+    }
+
+    #[test]
+    fn test_service_id_from_output() {
+        let output = "Submitting...\nService 1a2b3c created at slot 42\n";
+        assert_eq!(service_id_from_output(output).as_deref(), Some("1a2b3c"));
+        assert_eq!(service_id_from_output("nothing here"), None);
+    }
 }
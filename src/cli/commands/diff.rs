@@ -0,0 +1,63 @@
+use crate::cli::args::DiffArgs;
+use crate::cli::output::Glyph;
+use crate::error::Result;
+use crate::project::manifest::{FileStatus, GenerationManifest};
+use console::style;
+use std::path::PathBuf;
+
+pub fn execute(args: DiffArgs) -> Result<()> {
+    let manifest = validate(&args)?;
+    let project_path = project_path(&args);
+
+    let statuses = manifest.diff(&project_path)?;
+
+    let mut modified = 0;
+    let mut missing = 0;
+
+    for (path, status) in &statuses {
+        match status {
+            FileStatus::Modified => {
+                modified += 1;
+                println!("  {} {}", style("M").yellow().bold(), path);
+            }
+            FileStatus::Missing => {
+                missing += 1;
+                println!("  {} {}", style("D").red().bold(), path);
+            }
+            FileStatus::Unchanged => {
+                if args.verbose {
+                    println!("  {} {}", style("=").dim(), style(path).dim());
+                }
+            }
+        }
+    }
+
+    if modified == 0 && missing == 0 {
+        println!(
+            "{} All {} generated files match the template output",
+            style(Glyph::Success).green().bold(),
+            statuses.len()
+        );
+    } else {
+        println!(
+            "\n{} {} modified, {} missing out of {} generated files",
+            style(Glyph::Arrow).cyan(),
+            style(modified).yellow(),
+            style(missing).red(),
+            statuses.len()
+        );
+    }
+
+    Ok(())
+}
+
+/// Validate that the project has a generation manifest, returning it
+pub fn validate(args: &DiffArgs) -> Result<GenerationManifest> {
+    GenerationManifest::load(&project_path(args))
+}
+
+fn project_path(args: &DiffArgs) -> PathBuf {
+    args.path
+        .clone()
+        .unwrap_or_else(|| std::env::current_dir().expect("Failed to get current directory"))
+}
@@ -1,17 +1,23 @@
 use crate::cli::args::DownArgs;
+use crate::cli::commands::info::format_age;
+use crate::cli::commands::up::RPC_FILE;
+use crate::cli::output::Glyph;
 use crate::error::{CargoJamError, Result};
 use crate::toolchain::config::ToolchainConfig;
+use crate::toolchain::testnet::{is_process_running, PID_FILE, STOP_FILE};
 use console::style;
 use std::fs;
-
-const PID_FILE: &str = "testnet.pid";
+use std::path::Path;
 
 pub fn execute(args: DownArgs) -> Result<()> {
     let home_dir = ToolchainConfig::home_dir()?;
     let pid_file = home_dir.join(PID_FILE);
 
     if !pid_file.exists() {
-        println!("{} No testnet is currently running", style("→").cyan());
+        println!(
+            "{} No testnet is currently running",
+            style(Glyph::Arrow).cyan()
+        );
         return Ok(());
     }
 
@@ -21,34 +27,55 @@ pub fn execute(args: DownArgs) -> Result<()> {
         .parse()
         .map_err(|_| CargoJamError::Build("Invalid PID in testnet.pid file".to_string()))?;
 
+    // Whatever happens below, the recorded endpoint no longer points at a testnet
+    let _ = fs::remove_file(home_dir.join(RPC_FILE));
+
     if !is_process_running(pid) {
-        // Process not running, clean up stale PID file
-        fs::remove_file(&pid_file)?;
         println!(
-            "{} Testnet was not running (cleaned up stale PID file)",
-            style("→").cyan()
+            "{} Testnet was not running: PID {} recorded {} has exited",
+            style(Glyph::Arrow).cyan(),
+            style(pid).yellow(),
+            pid_file_age(&pid_file)
         );
-        return Ok(());
+        return clean_stale_pid_file(&pid_file, args.keep_pid_file);
+    }
+
+    // The testnet may have died and its PID been reused by an unrelated process
+    if let Some(name) = process_name(pid).filter(|name| !is_testnet_process(name)) {
+        println!(
+            "{} Testnet was not running: PID {} recorded {} now belongs to another process ({}), leaving it alone",
+            style("!").yellow().bold(),
+            style(pid).yellow(),
+            pid_file_age(&pid_file),
+            style(name).cyan()
+        );
+        return clean_stale_pid_file(&pid_file, args.keep_pid_file);
     }
 
     println!(
         "{} Stopping JAM testnet (PID: {})...",
-        style("→").cyan(),
+        style(Glyph::Arrow).cyan(),
         style(pid).yellow()
     );
 
     // Kill the process
     let signal = if args.force { "KILL" } else { "TERM" };
+    let stop_file = home_dir.join(STOP_FILE);
+    fs::write(&stop_file, pid.to_string())?;
 
     if kill_process(pid, signal) {
         // Wait a moment for process to terminate
         std::thread::sleep(std::time::Duration::from_millis(500));
 
-        // Clean up PID file
-        fs::remove_file(&pid_file)?;
+        println!("{} Testnet stopped", style(Glyph::Success).green().bold());
 
-        println!("{} Testnet stopped", style("✓").green().bold());
+        if args.keep_pid_file {
+            println!("  PID file kept at {}", style(pid_file.display()).yellow());
+        } else {
+            fs::remove_file(&pid_file)?;
+        }
     } else {
+        let _ = fs::remove_file(&stop_file);
         return Err(CargoJamError::Build(format!(
             "Failed to stop testnet (PID: {}). Try 'cargo polkajam down --force'",
             pid
@@ -58,14 +85,62 @@ pub fn execute(args: DownArgs) -> Result<()> {
     Ok(())
 }
 
+/// Remove a PID file whose process is gone, unless asked to keep it
+fn clean_stale_pid_file(pid_file: &Path, keep: bool) -> Result<()> {
+    if keep {
+        println!(
+            "  Stale PID file kept at {}",
+            style(pid_file.display()).yellow()
+        );
+    } else {
+        fs::remove_file(pid_file)?;
+        println!("  Cleaned up stale PID file");
+    }
+    Ok(())
+}
+
+/// When the PID file was written, e.g. `3h ago`
+fn pid_file_age(pid_file: &Path) -> String {
+    fs::metadata(pid_file)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .map(format_age)
+        .unwrap_or_else(|| "at an unknown time".to_string())
+}
+
+/// Whether a process name is the testnet or a `cargo-polkajam` supervisor running it
+fn is_testnet_process(name: &str) -> bool {
+    name.to_ascii_lowercase().contains("polkajam")
+}
+
 #[cfg(unix)]
-fn is_process_running(pid: i32) -> bool {
+fn process_name(pid: i32) -> Option<String> {
     use std::process::Command;
-    Command::new("kill")
-        .args(["-0", &pid.to_string()])
+    let output = Command::new("ps")
+        .args(["-p", &pid.to_string(), "-o", "comm="])
         .output()
-        .map(|o| o.status.success())
-        .unwrap_or(false)
+        .ok()?;
+    let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !name.is_empty()).then_some(name)
+}
+
+#[cfg(windows)]
+fn process_name(pid: i32) -> Option<String> {
+    use std::process::Command;
+    let output = Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {}", pid), "/FO", "CSV", "/NH"])
+        .output()
+        .ok()?;
+    // "polkajam-testnet.exe","1234",...
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let name = stdout
+        .split(',')
+        .next()?
+        .trim()
+        .trim_matches('"')
+        .to_string();
+    (stdout.contains(&pid.to_string()) && !name.is_empty()).then_some(name)
 }
 
 #[cfg(unix)]
@@ -79,16 +154,6 @@ fn kill_process(pid: i32, signal: &str) -> bool {
         .unwrap_or(false)
 }
 
-#[cfg(windows)]
-fn is_process_running(pid: i32) -> bool {
-    use std::process::Command;
-    Command::new("tasklist")
-        .args(["/FI", &format!("PID eq {}", pid)])
-        .output()
-        .map(|o| String::from_utf8_lossy(&o.stdout).contains(&pid.to_string()))
-        .unwrap_or(false)
-}
-
 #[cfg(windows)]
 fn kill_process(pid: i32, signal: &str) -> bool {
     use std::process::Command;
@@ -0,0 +1,278 @@
+use crate::cli::args::InfoArgs;
+use crate::cli::commands::build::validate_jam_project;
+use crate::cli::commands::up::{default_rpc_url, DEFAULT_RPC_URL};
+use crate::cli::output::Glyph;
+use crate::error::Result;
+use crate::toolchain::config::ToolchainConfig;
+use crate::toolchain::testnet::{is_process_running, recorded_pid};
+use console::style;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+
+/// Snapshot of the project, blob, toolchain and testnet state
+#[derive(Debug, Serialize)]
+struct InfoReport {
+    /// The JAM service project at the given path, if it is one
+    project: Option<ProjectInfo>,
+    /// The project's built `.jam` blob, if one exists
+    blob: Option<BlobInfo>,
+    toolchain: ToolchainInfo,
+    testnet: TestnetInfo,
+    jam_pvm_build: JamPvmBuildInfo,
+}
+
+#[derive(Debug, Serialize)]
+struct ProjectInfo {
+    name: String,
+    version: Option<String>,
+    path: PathBuf,
+}
+
+#[derive(Debug, Serialize)]
+struct BlobInfo {
+    path: PathBuf,
+    size: u64,
+    /// Seconds since the blob was last written
+    age_secs: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+struct ToolchainInfo {
+    installed_version: Option<String>,
+    path: Option<PathBuf>,
+}
+
+#[derive(Debug, Serialize)]
+struct TestnetInfo {
+    running: bool,
+    pid: Option<i32>,
+    rpc: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct JamPvmBuildInfo {
+    available: bool,
+    version: Option<String>,
+}
+
+pub fn execute(args: InfoArgs) -> Result<()> {
+    let project_path = project_path(&args);
+
+    let project = project_info(&project_path);
+    let blob = project
+        .as_ref()
+        .and_then(|p| blob_info(&project_path, &p.name));
+    let config = ToolchainConfig::load()?;
+
+    let report = InfoReport {
+        project,
+        blob,
+        toolchain: ToolchainInfo {
+            installed_version: config
+                .is_installed()
+                .then(|| config.installed_version.clone())
+                .flatten(),
+            path: config
+                .is_installed()
+                .then(|| config.toolchain_path.clone())
+                .flatten(),
+        },
+        testnet: testnet_info(),
+        jam_pvm_build: jam_pvm_build_info(),
+    };
+
+    if args.json {
+        let json = serde_json::to_string_pretty(&report)
+            .map_err(|e| std::io::Error::other(format!("Failed to serialize report: {}", e)))?;
+        println!("{}", json);
+        return Ok(());
+    }
+
+    print_report(&report);
+    Ok(())
+}
+
+/// Validate info arguments (nothing to check: every part of the report is optional)
+pub fn validate(_args: &InfoArgs) -> Result<()> {
+    Ok(())
+}
+
+fn project_path(args: &InfoArgs) -> PathBuf {
+    args.path
+        .clone()
+        .unwrap_or_else(|| std::env::current_dir().expect("Failed to get current directory"))
+}
+
+fn project_info(path: &Path) -> Option<ProjectInfo> {
+    validate_jam_project(path).ok()?;
+
+    let content = std::fs::read_to_string(path.join("Cargo.toml")).ok()?;
+    let manifest: toml::Value = toml::from_str(&content).ok()?;
+    let package = manifest.get("package")?;
+    let field = |key: &str| {
+        package
+            .get(key)
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+    };
+
+    Some(ProjectInfo {
+        name: field("name")?,
+        version: field("version"),
+        path: path.to_path_buf(),
+    })
+}
+
+/// Look for `<name>.jam` where `build` writes it: the current directory, then the project
+fn blob_info(project_path: &Path, name: &str) -> Option<BlobInfo> {
+    let file_name = format!("{}.jam", name);
+    let candidates = std::env::current_dir()
+        .ok()
+        .map(|cwd| cwd.join(&file_name))
+        .into_iter()
+        .chain([project_path.join(&file_name)]);
+
+    candidates.into_iter().find_map(|path| {
+        let metadata = std::fs::metadata(&path).ok().filter(|m| m.is_file())?;
+        let age_secs = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .map(|age| age.as_secs());
+        Some(BlobInfo {
+            path,
+            size: metadata.len(),
+            age_secs,
+        })
+    })
+}
+
+fn testnet_info() -> TestnetInfo {
+    let pid = ToolchainConfig::home_dir()
+        .ok()
+        .and_then(|home| recorded_pid(&home));
+    let running = pid.is_some_and(is_process_running);
+
+    TestnetInfo {
+        running,
+        pid,
+        rpc: running.then(default_rpc_url),
+    }
+}
+
+fn jam_pvm_build_info() -> JamPvmBuildInfo {
+    let version = Command::new("jam-pvm-build")
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string());
+
+    JamPvmBuildInfo {
+        available: version.is_some(),
+        version: version.filter(|v| !v.is_empty()),
+    }
+}
+
+fn print_report(report: &InfoReport) {
+    let yes = |ok: bool| {
+        if ok {
+            style(Glyph::Success).green().bold()
+        } else {
+            style(Glyph::Failure).red().bold()
+        }
+    };
+
+    println!("{}", style("Project").bold());
+    match report.project {
+        Some(ref project) => {
+            println!(
+                "  {} {} {}",
+                style("Crate:").dim(),
+                style(&project.name).cyan(),
+                project.version.as_deref().unwrap_or("")
+            );
+            match report.blob {
+                Some(ref blob) => println!(
+                    "  {} {} ({} bytes{})",
+                    style("Blob:").dim(),
+                    style(blob.path.display()).yellow(),
+                    blob.size,
+                    blob.age_secs
+                        .map(|secs| format!(", built {}", format_age(Duration::from_secs(secs))))
+                        .unwrap_or_default()
+                ),
+                None => println!(
+                    "  {} {} (run {})",
+                    style("Blob:").dim(),
+                    style("not built").yellow(),
+                    style("cargo polkajam build").green()
+                ),
+            }
+        }
+        None => println!("  {}", style("Not inside a JAM service project").dim()),
+    }
+
+    println!("\n{}", style("Toolchain").bold());
+    match report.toolchain.installed_version {
+        Some(ref version) => println!("  {} JAM toolchain {}", yes(true), style(version).green()),
+        None => println!(
+            "  {} JAM toolchain not installed (run {})",
+            yes(false),
+            style("cargo polkajam setup").green()
+        ),
+    }
+    match report.jam_pvm_build.version {
+        Some(ref version) => println!("  {} {}", yes(true), version),
+        None => println!(
+            "  {} jam-pvm-build {}",
+            yes(report.jam_pvm_build.available),
+            if report.jam_pvm_build.available {
+                "available"
+            } else {
+                "not found (install with: cargo install jam-pvm-build)"
+            }
+        ),
+    }
+
+    println!("\n{}", style("Testnet").bold());
+    match (report.testnet.running, report.testnet.pid) {
+        (true, Some(pid)) => println!(
+            "  {} Running (PID {}) at {}",
+            yes(true),
+            pid,
+            report.testnet.rpc.as_deref().unwrap_or(DEFAULT_RPC_URL)
+        ),
+        _ => println!(
+            "  {} Not running (start with {})",
+            yes(false),
+            style("cargo polkajam up").green()
+        ),
+    }
+}
+
+/// Human-readable age such as `45s ago` or `3h ago`
+pub(crate) fn format_age(age: Duration) -> String {
+    let secs = age.as_secs();
+    match secs {
+        0..=59 => format!("{}s ago", secs),
+        60..=3599 => format!("{}m ago", secs / 60),
+        3600..=86399 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86400),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(Duration::from_secs(5)), "5s ago");
+        assert_eq!(format_age(Duration::from_secs(150)), "2m ago");
+        assert_eq!(format_age(Duration::from_secs(7200)), "2h ago");
+        assert_eq!(format_age(Duration::from_secs(3 * 86400)), "3d ago");
+    }
+}
@@ -1,8 +1,23 @@
+pub mod accounts;
+pub mod bench;
 pub mod build;
+pub mod check;
+pub mod check_compat;
+pub mod clean_toolchain;
+pub mod config;
 pub mod deploy;
+pub mod diff;
 pub mod down;
+pub mod info;
 pub mod monitor;
 pub mod new;
+pub mod queue;
+pub mod render;
 pub mod setup;
+pub mod sign;
+pub mod template;
 pub mod test;
+pub mod uninstall;
 pub mod up;
+pub mod update;
+pub mod verify;
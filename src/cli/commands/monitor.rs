@@ -1,37 +1,31 @@
 use crate::cli::args::MonitorArgs;
+use crate::cli::commands::up::resolve_rpc_url;
+use crate::cli::output::Glyph;
+use crate::cli::validate::normalize_rpc_url;
 use crate::error::{CargoJamError, Result};
 use crate::toolchain::config::ToolchainConfig;
 use console::style;
+use std::path::PathBuf;
 use std::process::{Command, Stdio};
 
 pub fn execute(args: MonitorArgs) -> Result<()> {
-    // Check toolchain is installed
-    let config = ToolchainConfig::load()?;
-    if !config.is_installed() {
-        return Err(CargoJamError::ToolchainMissing {
-            tool: "JAM toolchain".to_string(),
-            install_hint: "Run 'cargo polkajam setup' to install the JAM toolchain".to_string(),
-        });
-    }
-
-    let jamtop_bin =
-        ToolchainConfig::binary_path("jamtop")?.ok_or_else(|| CargoJamError::ToolchainMissing {
-            tool: "jamtop".to_string(),
-            install_hint: "Run 'cargo polkajam setup --force' to reinstall the toolchain"
-                .to_string(),
-        })?;
+    let rpc = resolve_rpc_url(args.rpc.as_deref())?;
+    let jamtop_bin = validate(&args)?;
 
-    println!("{} Starting JAM testnet monitor...", style("→").cyan());
+    println!(
+        "{} Starting JAM testnet monitor...",
+        style(Glyph::Arrow).cyan()
+    );
 
     if args.verbose {
-        println!("  RPC: {}", style(&args.rpc).dim());
+        println!("  RPC: {}", style(&rpc).dim());
     }
 
     println!("  Press 'q' to quit\n");
 
     // Run jamtop in foreground with inherited stdio for interactive TUI
     let mut cmd = Command::new(&jamtop_bin);
-    cmd.arg("--rpc").arg(&args.rpc);
+    cmd.arg("--rpc").arg(&rpc);
 
     let status = cmd
         .stdin(Stdio::inherit())
@@ -46,3 +40,28 @@ pub fn execute(args: MonitorArgs) -> Result<()> {
 
     Ok(())
 }
+
+/// Validate monitor arguments and prerequisites, returning the jamtop binary path
+pub fn validate(args: &MonitorArgs) -> Result<PathBuf> {
+    // Check toolchain is installed
+    let config = ToolchainConfig::load()?;
+    if !config.is_installed() {
+        return Err(CargoJamError::ToolchainMissing {
+            tool: "JAM toolchain".to_string(),
+            install_hint: "Run 'cargo polkajam setup' to install the JAM toolchain".to_string(),
+        });
+    }
+
+    let jamtop_bin =
+        ToolchainConfig::binary_path("jamtop")?.ok_or_else(|| CargoJamError::ToolchainMissing {
+            tool: "jamtop".to_string(),
+            install_hint: "Run 'cargo polkajam setup --force' to reinstall the toolchain"
+                .to_string(),
+        })?;
+
+    if let Some(ref rpc) = args.rpc {
+        normalize_rpc_url(rpc)?;
+    }
+
+    Ok(jamtop_bin)
+}
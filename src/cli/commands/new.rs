@@ -1,82 +1,271 @@
 use crate::cli::args::NewArgs;
+use crate::cli::output::{create_spinner, set_message, Glyph};
 use crate::error::{CargoJamError, Result};
-use crate::project::generator::ProjectGenerator;
+use crate::project::deps::DepsOptions;
+use crate::project::editor::{find_editor, open_in_editor};
+use crate::project::generator::{
+    tree_lines, GeneratedKind, GeneratedPath, PlannedAction, PlannedFile, ProjectGenerator,
+};
+use crate::project::license::{current_year, write_license, License};
+use crate::project::lockfile::{apply_lockfile_strategy, LockfileStrategy};
+use crate::project::manifest::TemplateOrigin;
+use crate::project::validation::{validate_project_name, validate_project_name_relaxed};
+use crate::project::workspace::{
+    add_workspace_member, check_workspace_root, find_workspace_root, hoist_profiles,
+    init_workspace, is_workspace_root, MemberStatus,
+};
 use crate::prompt::interactive::PromptRunner;
-use crate::template::bundled::BundledTemplates;
-use crate::template::config::TemplateConfig;
-use crate::template::git::GitTemplateSource;
+use crate::template::builtins::{apply_renamed_variables, builtin_variables};
+use crate::template::bundled::{BundledTemplates, DEFAULT_TEMPLATE};
+use crate::template::config::{Choice, TemplateConfig};
+use crate::template::git::{discover_templates, GitTemplateSource};
+use crate::template::lock::{tree_hash, LockOutcome, TemplateLock};
+use crate::template::values::parse_values;
+use crate::toolchain::config::ToolchainConfig;
 use console::style;
-use indicatif::{ProgressBar, ProgressStyle};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use indicatif::ProgressBar;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
 // Enum to hold template source so it stays alive (the temp directory)
 #[allow(dead_code)]
-enum TemplateSource {
+pub(crate) enum TemplateSource {
     Bundled(BundledTemplates),
     Git(GitTemplateSource),
 }
 
+/// What `new --dry-run --json` reports
+#[derive(Debug, Serialize)]
+struct GenerationPlan<'a> {
+    target_dir: &'a Path,
+    variables: BTreeMap<&'a str, &'a str>,
+    files: Vec<PlannedFile>,
+    /// Manifests outside the project that would be created or edited
+    edits: Vec<PathBuf>,
+}
+
 pub fn execute(args: NewArgs) -> Result<()> {
+    let deps = deps_options(&args);
+    deps.validate()?;
+
+    // A git template stays at the locked commit unless another is asked for
+    let locked = match args.template_lock {
+        Some(ref path) => TemplateLock::load(path)?,
+        None => None,
+    };
+    let rev = args.rev.clone().or_else(|| {
+        locked
+            .as_ref()
+            .filter(|lock| !args.update_lock && lock.git.is_some() && lock.git == args.git)
+            .and_then(|lock| lock.rev.clone())
+    });
+
+    let bundled_name = match args.git {
+        Some(_) => None,
+        None => Some(bundled_template_name(&args)?),
+    };
+
     let spinner = create_spinner("Preparing template...");
 
     // Determine template source and keep it alive
-    let (_template_source, template_dir) = if let Some(git_url) = &args.git {
-        spinner.set_message("Cloning template repository...");
+    let (template_source, template_dir, mut origin) = if let Some(git_url) = &args.git {
+        set_message(&spinner, "Cloning template repository...");
         let mut source = GitTemplateSource::new(git_url.clone())
             .branch(args.branch.clone())
-            .subpath(args.path.clone());
-        let dir = source.fetch()?;
-        (TemplateSource::Git(source), dir)
+            .rev(rev.clone())
+            .subpath(args.path.clone())
+            .token(args.git_token.clone());
+        let root = source.fetch()?;
+        let dir = match args.path {
+            Some(_) => root.clone(),
+            None => select_git_template(&spinner, root.clone(), interactive(&args))?,
+        };
+        let path = match args.path {
+            Some(ref path) => Some(path.to_string_lossy().replace('\\', "/")),
+            None => dir
+                .strip_prefix(&root)
+                .ok()
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+                .filter(|p| !p.is_empty()),
+        };
+        let origin = TemplateOrigin {
+            git: Some(git_url.clone()),
+            branch: args.branch.clone(),
+            rev,
+            path,
+            ..Default::default()
+        };
+        (TemplateSource::Git(source), dir, origin)
     } else {
-        spinner.set_message("Loading bundled template...");
+        set_message(&spinner, "Loading bundled template...");
         let mut templates = BundledTemplates::new();
-        let dir = templates.extract(&args.template)?;
-        (TemplateSource::Bundled(templates), dir)
+        let dir = templates.extract(bundled_name.as_deref().unwrap_or(DEFAULT_TEMPLATE))?;
+        let origin = TemplateOrigin {
+            bundled: bundled_name.clone(),
+            ..Default::default()
+        };
+        (TemplateSource::Bundled(templates), dir, origin)
     };
 
-    let config = TemplateConfig::load_from_dir(&template_dir)?;
+    if let Some(ref lock_path) = args.template_lock {
+        set_message(&spinner, "Hashing template...");
+        let commit = match template_source {
+            TemplateSource::Git(ref source) => source.commit().map(String::from),
+            TemplateSource::Bundled(_) => None,
+        };
+        let lock = TemplateLock {
+            bundled: origin.bundled.clone(),
+            git: origin.git.clone(),
+            rev: commit,
+            path: origin.path.clone(),
+            hash: tree_hash(&template_dir)?,
+        };
+        let outcome = lock.enforce(lock_path, args.update_lock);
+        spinner.finish_and_clear();
+        let done = match outcome? {
+            LockOutcome::Recorded => "Recorded template in",
+            LockOutcome::Verified => "Template matches",
+            LockOutcome::Updated => "Updated template in",
+        };
+        println!(
+            "{} {} {}",
+            style(Glyph::Success).green().bold(),
+            done,
+            style(lock_path.display()).cyan()
+        );
+    }
+
+    let mut config = TemplateConfig::load_from_dir(&template_dir)?;
 
     spinner.finish_and_clear();
 
+    if args.minimal {
+        if config.apply_conditional("minimal") {
+            origin.conditionals.push("minimal".to_string());
+        } else {
+            eprintln!(
+                "{} Template does not declare a 'minimal' conditional, generating all files",
+                style(Glyph::Warning).yellow()
+            );
+        }
+    }
+
     // Collect template variables
     let mut variables = collect_predefined_variables(&args)?;
+    warn_renamed_variables(apply_renamed_variables(&mut variables, &config));
+
+    // Predefined values must be among a placeholder's choices and match its pattern
+    for (key, value) in &variables {
+        if let Some(placeholder) = config.placeholders.get(key) {
+            placeholder.validate_value(key, value)?;
+        }
+    }
 
     // Get project name
+    let name_pattern = if args.force_name {
+        r"^[A-Za-z][A-Za-z0-9_-]*$"
+    } else {
+        r"^[a-z][a-z0-9_-]*$"
+    };
     let project_name = if let Some(name) = args.name.clone() {
-        validate_project_name(&name)?;
+        check_project_name(&name, args.force_name)?;
         name
-    } else if args.defaults {
+    } else if !interactive(&args) {
         return Err(CargoJamError::InvalidProjectName {
             name: String::new(),
-            reason: "Project name is required when using --defaults".to_string(),
+            reason:
+                "Project name is required when using --defaults, --no-interactive or --values-stdin"
+                    .to_string(),
         });
     } else {
         let runner = PromptRunner::new();
-        runner.prompt_string("Project name", None, Some(r"^[a-z][a-z0-9_-]*$"))?
+        let name = runner.prompt_string("Project name", None, Some(name_pattern))?;
+        check_project_name(&name, args.force_name)?;
+        name
     };
 
     variables.insert("project_name".to_string(), project_name.clone());
-    variables.insert("crate_name".to_string(), project_name.replace('-', "_"));
+    variables.insert(
+        "crate_name".to_string(),
+        project_name.replace(['-', '.'], "_"),
+    );
+
+    // Built-in variables (author_name, author_email) derived from git config/env
+    let builtins = builtin_variables();
 
     // Run interactive prompts for remaining variables
-    if !args.defaults {
-        let runner = PromptRunner::new();
+    if interactive(&args) {
+        let runner = PromptRunner::new()
+            .defaults(builtins.clone())
+            .confirm_defaults(args.no_prompt_defaults);
         let prompted_vars = runner.collect_variables(&config, &variables)?;
         variables.extend(prompted_vars);
+    } else if strict(&args) {
+        // Only explicit values and built-ins; template defaults aren't applied
+        let mut missing = Vec::new();
+        for (key, placeholder) in config.ordered_placeholders() {
+            if variables.contains_key(key) {
+                continue;
+            }
+            if let Some(value) = builtins.get(key) {
+                variables.insert(key.to_string(), value.clone());
+                continue;
+            }
+            match placeholder.default_value() {
+                // An empty default marks an optional value, such as `homepage`
+                Some(default) if default.is_empty() => {
+                    variables.insert(key.to_string(), default);
+                }
+                _ => missing.push(key.as_str()),
+            }
+        }
+
+        if !missing.is_empty() {
+            return Err(CargoJamError::InvalidArgument {
+                arg: "--no-interactive".to_string(),
+                reason: format!(
+                    "no value given for: {} (pass them with --define or --values-file, or add --defaults to use the template defaults)",
+                    missing.join(", ")
+                ),
+            });
+        }
     } else {
         // Apply defaults from config
+        let mut defaulted = Vec::new();
         for (key, placeholder) in &config.placeholders {
             if !variables.contains_key(key) {
-                if let Some(default) = placeholder.default_value() {
+                if let Some(default) = builtins.get(key).cloned().or(placeholder.default_value()) {
+                    // An empty default marks an optional value, such as `homepage`
+                    if !default.is_empty() {
+                        defaulted.push(key.as_str());
+                    }
                     variables.insert(key.clone(), default);
                 }
             }
         }
+
+        if args.no_prompt_defaults && !defaulted.is_empty() {
+            return Err(CargoJamError::InvalidArgument {
+                arg: "--no-prompt-defaults".to_string(),
+                reason: format!(
+                    "no explicit value given for: {} (pass them with --define)",
+                    defaulted.join(", ")
+                ),
+            });
+        }
+    }
+
+    // Make built-ins available even when the template doesn't declare them
+    for (key, value) in builtins.into_iter().chain(deps.variables()) {
+        variables.entry(key).or_insert(value);
     }
 
     // Determine output directory
-    let output_dir = args.output.unwrap_or_else(|| PathBuf::from(&project_name));
+    let output_dir = output_dir(&args, &project_name);
+    if let Some(ref root) = args.workspace_root {
+        check_workspace_root(root)?;
+    }
 
     // Check if output directory exists
     if output_dir.exists() {
@@ -85,55 +274,538 @@ pub fn execute(args: NewArgs) -> Result<()> {
         ));
     }
 
+    let strict_undefined = config.strict_undefined(args.strict_undefined);
+    let generator = ProjectGenerator::new(template_dir, output_dir.clone(), config)
+        .strict_undefined(strict_undefined)
+        .seed(args.seed)
+        .toolchain(installed_toolchain())
+        .origin(origin)
+        .renames(parse_renames(&args.rename)?);
+
+    if args.dry_run {
+        return print_plan(&args, &generator, &variables, &output_dir, &project_name);
+    }
+
     // Generate project
     let spinner = create_spinner("Generating project...");
-    let generator = ProjectGenerator::new(template_dir, output_dir.clone(), config);
-    generator.generate(&variables)?;
+    let mut generated = generator.generate(&variables)?;
     spinner.finish_and_clear();
 
+    // Switch the SDK dependencies to git or local sources
+    let rewritten = deps.apply(&output_dir)?;
+    if !rewritten.is_empty() {
+        println!(
+            "{} Using {} sources for {}",
+            style(Glyph::Arrow).cyan(),
+            args.deps.as_str(),
+            rewritten.join(", ")
+        );
+    }
+
+    // Write the license text for --license
+    if let Some(ref spdx) = args.license {
+        for name in add_license(&output_dir, spdx, &variables)? {
+            generated.push(GeneratedPath {
+                path: name.to_string(),
+                kind: GeneratedKind::Rendered,
+            });
+        }
+    }
+
+    // Register the crate with its workspace, before Cargo sees it as a stray package
+    let mut git_dir = Some(output_dir.as_path());
+    if let Some(ref root) = args.workspace_root {
+        let created = add_to_workspace_root(root, &output_dir)?;
+        // The repository belongs at the root of a new workspace, not in each member
+        git_dir = created.then_some(root.as_path());
+    } else if let Some(ref root) = args.into_workspace {
+        register_workspace_member(&output_dir, root.as_deref())?;
+    }
+
+    // Keep, remove or regenerate Cargo.lock
+    let lockfile = LockfileStrategy::from(args.lockfile);
+    let spinner = (lockfile == LockfileStrategy::Generate)
+        .then(|| create_spinner("Generating Cargo.lock..."));
+    apply_lockfile_strategy(&output_dir, lockfile)?;
+    if let Some(spinner) = spinner {
+        spinner.finish_and_clear();
+    }
+
     // Initialize git repository
-    if !args.no_git {
+    if let Some(git_dir) = git_dir.filter(|_| !args.no_git) {
         let spinner = create_spinner("Initializing git repository...");
-        crate::project::git_init::init_git_repo(&output_dir)?;
+        crate::project::git_init::init_git_repo(git_dir)?;
         spinner.finish_and_clear();
     }
 
     // Print success message
     println!(
         "\n{} Created JAM service '{}' at {}",
-        style("✓").green().bold(),
+        style(Glyph::Success).green().bold(),
         style(&project_name).cyan(),
         style(output_dir.display()).yellow()
     );
+    if args.print_tree || args.verbose {
+        // A template's Cargo.lock may have been removed by the lockfile strategy
+        generated.retain(|g| output_dir.join(&g.path).exists());
+        println!("\n{}/", style(output_dir.display()).bold());
+        for line in tree_lines(&generated) {
+            println!("{}", line);
+        }
+    }
+
     println!("\nNext steps:");
     println!("  {} {}", style("cd").cyan(), project_name);
     println!("  {} polkajam build", style("cargo").cyan());
 
+    if args.open {
+        open_project(&output_dir, args.editor.as_deref(), interactive(&args));
+    }
+
     Ok(())
 }
 
-fn create_spinner(message: &str) -> ProgressBar {
-    let spinner = ProgressBar::new_spinner();
-    spinner.set_style(
-        ProgressStyle::default_spinner()
-            .template("{spinner:.green} {msg}")
-            .unwrap(),
+/// `--dry-run`: list the files generation would write, as JSON with `--json`
+fn print_plan(
+    args: &NewArgs,
+    generator: &ProjectGenerator,
+    variables: &HashMap<String, String>,
+    output_dir: &Path,
+    project_name: &str,
+) -> Result<()> {
+    let mut files = generator.plan(variables)?;
+    if let Some(license) = args.license.as_deref().and_then(License::from_spdx) {
+        for (name, _) in license.files(&license_holder(variables), current_year()) {
+            files.push(PlannedFile {
+                path: name.to_string(),
+                action: PlannedAction::Render,
+                source: None,
+            });
+        }
+    }
+    // What the lockfile strategy does after generation
+    match LockfileStrategy::from(args.lockfile) {
+        LockfileStrategy::Keep => {}
+        LockfileStrategy::Remove => files.retain(|file| file.path != "Cargo.lock"),
+        LockfileStrategy::Generate => {
+            files.retain(|file| file.path != "Cargo.lock");
+            files.push(PlannedFile {
+                path: "Cargo.lock".to_string(),
+                action: PlannedAction::Render,
+                source: None,
+            });
+        }
+    }
+    let edits: Vec<PathBuf> = workspace_manifest(args, output_dir).into_iter().collect();
+
+    if args.json {
+        let plan = GenerationPlan {
+            target_dir: output_dir,
+            variables: variables
+                .iter()
+                .map(|(k, v)| (k.as_str(), v.as_str()))
+                .collect(),
+            files,
+            edits,
+        };
+        let json = serde_json::to_string_pretty(&plan)
+            .map_err(|e| std::io::Error::other(format!("Failed to serialize plan: {}", e)))?;
+        println!("{}", json);
+        return Ok(());
+    }
+
+    println!(
+        "{} Would create JAM service '{}' at {}",
+        style(Glyph::Arrow).cyan(),
+        style(project_name).cyan(),
+        style(output_dir.display()).yellow()
     );
-    spinner.set_message(message.to_string());
-    spinner.enable_steady_tick(std::time::Duration::from_millis(100));
-    spinner
+    let written: Vec<GeneratedPath> = files
+        .iter()
+        .filter_map(|file| {
+            let kind = match file.action {
+                PlannedAction::Render => GeneratedKind::Rendered,
+                PlannedAction::Copy => GeneratedKind::Copied,
+                PlannedAction::Skip => return None,
+            };
+            Some(GeneratedPath {
+                path: file.path.clone(),
+                kind,
+            })
+        })
+        .collect();
+    println!("\n{}/", style(output_dir.display()).bold());
+    for line in tree_lines(&written) {
+        println!("{}", line);
+    }
+
+    let skipped: Vec<&str> = files
+        .iter()
+        .filter(|file| file.action == PlannedAction::Skip)
+        .map(|file| file.path.as_str())
+        .collect();
+    if !skipped.is_empty() {
+        println!("\nSkipped by the template: {}", skipped.join(", "));
+    }
+    for edit in &edits {
+        println!("Would also edit {}", style(edit.display()).yellow());
+    }
+    println!("\nNo files were written.");
+    Ok(())
 }
 
-fn collect_predefined_variables(args: &NewArgs) -> Result<HashMap<String, String>> {
-    let mut variables = HashMap::new();
+/// Best-effort `--open`: problems are reported as warnings, never failures
+fn open_project(output_dir: &Path, editor: Option<&str>, interactive: bool) {
+    if !interactive {
+        println!(
+            "\n{} Not opening an editor in non-interactive mode",
+            style("!").yellow().bold()
+        );
+        return;
+    }
+
+    let Some(editor) = find_editor(editor) else {
+        println!(
+            "\n{} No editor found to open the project (set $EDITOR or pass --editor)",
+            style("!").yellow().bold()
+        );
+        return;
+    };
+
+    println!(
+        "\n{} Opening project with {}...",
+        style(Glyph::Arrow).cyan(),
+        style(&editor).cyan()
+    );
+    if let Err(e) = open_in_editor(&editor, output_dir) {
+        println!(
+            "{} Could not open the project: {}",
+            style("!").yellow().bold(),
+            e
+        );
+    }
+}
+
+/// Validate new arguments without fetching templates or writing files
+pub fn validate(args: &NewArgs) -> Result<()> {
+    match &args.name {
+        Some(name) => check_project_name(name, args.force_name)?,
+        None if !interactive(args) => {
+            return Err(CargoJamError::InvalidProjectName {
+                name: String::new(),
+                reason: "Project name is required when using --defaults, --no-interactive or --values-stdin"
+                    .to_string(),
+            });
+        }
+        None => {}
+    }
+
+    if let (None, Some(template)) = (&args.git, &args.template) {
+        if !BundledTemplates::new().list().contains(template) {
+            return Err(CargoJamError::TemplateNotFound(template.clone()));
+        }
+    }
 
-    // Parse --define flags
     for define in &args.define {
-        if let Some((key, value)) = define.split_once('=') {
-            variables.insert(key.to_string(), value.to_string());
+        if !define.contains('=') {
+            return Err(CargoJamError::InvalidArgument {
+                arg: "--define".to_string(),
+                reason: format!("'{}' is not in KEY=VALUE form", define),
+            });
+        }
+    }
+
+    parse_renames(&args.rename)?;
+
+    deps_options(args).validate()?;
+
+    if let Some(values_file) = &args.values_file {
+        if !values_file.is_file() {
+            return Err(CargoJamError::InvalidArgument {
+                arg: "--values-file".to_string(),
+                reason: format!("'{}' does not exist", values_file.display()),
+            });
+        }
+    }
+
+    if let Some(ref root) = args.workspace_root {
+        check_workspace_root(root)?;
+    }
+
+    if let Some(ref name) = args.name {
+        let output_dir = output_dir(args, name);
+        if output_dir.exists() {
+            return Err(CargoJamError::ProjectExists(
+                output_dir.display().to_string(),
+            ));
         }
     }
 
+    Ok(())
+}
+
+/// Write the LICENSE file(s) for an SPDX expression, warning when no text is bundled for it
+fn add_license(
+    output_dir: &Path,
+    spdx: &str,
+    variables: &HashMap<String, String>,
+) -> Result<Vec<&'static str>> {
+    let Some(license) = License::from_spdx(spdx) else {
+        println!(
+            "{} No license text bundled for '{}', add a LICENSE file by hand",
+            style(Glyph::Warning).yellow(),
+            spdx
+        );
+        return Ok(Vec::new());
+    };
+
+    let files = write_license(
+        output_dir,
+        license,
+        &license_holder(variables),
+        current_year(),
+    )?;
+    println!(
+        "{} Added {} ({})",
+        style(Glyph::Arrow).cyan(),
+        files.join(", "),
+        spdx
+    );
+
+    Ok(files)
+}
+
+/// Copyright holder named in a generated license: the author, or the project's authors
+fn license_holder(variables: &HashMap<String, String>) -> String {
+    match variables.get("author_name").filter(|name| !name.is_empty()) {
+        Some(name) => name.clone(),
+        None => format!("The {} Authors", variables["project_name"]),
+    }
+}
+
+/// Version of the installed toolchain, if any, to record in the manifest
+fn installed_toolchain() -> Option<String> {
+    let config = ToolchainConfig::load().ok()?;
+    config
+        .is_installed()
+        .then_some(config.installed_version)
+        .flatten()
+}
+
+/// Where the project is generated: `--output`, `<workspace-root>/<name>` or `./<name>`
+fn output_dir(args: &NewArgs, project_name: &str) -> PathBuf {
+    match (&args.output, &args.workspace_root) {
+        (Some(output), _) => output.clone(),
+        (None, Some(root)) => root.join(project_name),
+        (None, None) => PathBuf::from(project_name),
+    }
+}
+
+/// Make the project a member of the workspace at `root`, creating the workspace
+/// on first use. Returns whether the workspace was created.
+fn add_to_workspace_root(root: &Path, output_dir: &Path) -> Result<bool> {
+    let created = init_workspace(root)?;
+    if created {
+        println!(
+            "{} Created workspace at {}",
+            style(Glyph::Success).green().bold(),
+            style(root.display()).yellow()
+        );
+    }
+
+    // Cargo ignores the profiles of workspace members
+    for profile in hoist_profiles(root, output_dir)? {
+        println!(
+            "{} Moved [profile.{}] to the workspace manifest",
+            style(Glyph::Arrow).cyan(),
+            profile
+        );
+    }
+
+    register_workspace_member(output_dir, Some(root))?;
+    Ok(created)
+}
+
+/// The workspace manifest `--workspace-root` or `--into-workspace` would create or edit
+fn workspace_manifest(args: &NewArgs, output_dir: &Path) -> Option<PathBuf> {
+    let root = match (&args.workspace_root, &args.into_workspace) {
+        (Some(root), _) | (None, Some(Some(root))) => Some(root.clone()),
+        // The project doesn't exist yet, so search from where it would be created
+        (None, Some(None)) => output_dir
+            .parent()
+            .map(|parent| {
+                if parent.as_os_str().is_empty() {
+                    Path::new(".")
+                } else {
+                    parent
+                }
+            })
+            .and_then(|parent| parent.canonicalize().ok())
+            .and_then(|parent| find_workspace_root(&parent)),
+        (None, None) => None,
+    };
+    root.map(|root| root.join("Cargo.toml"))
+}
+
+/// Add the generated crate to a workspace's `members`, warning if there is none
+fn register_workspace_member(output_dir: &Path, root: Option<&Path>) -> Result<()> {
+    let root = match root {
+        Some(root) if is_workspace_root(root) => Some(root.to_path_buf()),
+        Some(root) => {
+            return Err(CargoJamError::InvalidArgument {
+                arg: "--into-workspace".to_string(),
+                reason: format!(
+                    "no [workspace] found in {}",
+                    root.join("Cargo.toml").display()
+                ),
+            })
+        }
+        None => output_dir
+            .canonicalize()
+            .ok()
+            .and_then(|dir| dir.parent().and_then(find_workspace_root)),
+    };
+
+    let Some(root) = root else {
+        println!(
+            "{} No enclosing Cargo workspace found, the crate was not registered",
+            style(Glyph::Warning).yellow()
+        );
+        return Ok(());
+    };
+
+    match add_workspace_member(&root, output_dir)? {
+        MemberStatus::Added(member) => println!(
+            "{} Added '{}' to the workspace members in {}",
+            style(Glyph::Success).green().bold(),
+            style(member).cyan(),
+            style(root.join("Cargo.toml").display()).yellow()
+        ),
+        MemberStatus::AlreadyMember(member) => println!(
+            "{} '{}' is already covered by the workspace members",
+            style(Glyph::Arrow).cyan(),
+            style(member).cyan()
+        ),
+    }
+
+    Ok(())
+}
+
+/// Pick the template to use from a cloned repository that may contain several.
+///
+/// A single template is used as-is; with several, the user chooses one when
+/// `interactive`, otherwise the candidates are listed in the error.
+fn select_git_template(spinner: &ProgressBar, root: PathBuf, interactive: bool) -> Result<PathBuf> {
+    let mut templates = discover_templates(&root);
+
+    // The fetch already failed if the repository holds no template
+    if templates.len() <= 1 {
+        return Ok(match templates.pop() {
+            Some(template) => root.join(template),
+            None => root,
+        });
+    }
+
+    let names: Vec<String> = templates
+        .iter()
+        .map(|t| t.to_string_lossy().replace('\\', "/"))
+        .collect();
+
+    if !interactive {
+        return Err(CargoJamError::TemplateNotFound(format!(
+            "repository contains {} templates, choose one with --path: {}",
+            names.len(),
+            names.join(", ")
+        )));
+    }
+
+    spinner.finish_and_clear();
+    let choices: Vec<Choice> = names.iter().cloned().map(Choice::Plain).collect();
+    let selected = PromptRunner::new().prompt_select("Template", &choices, None)?;
+    Ok(root.join(Path::new(&selected)))
+}
+
+/// The bundled template to generate: `--template`, else one picked from the index
+/// when prompting and several are bundled, else the default
+fn bundled_template_name(args: &NewArgs) -> Result<String> {
+    if let Some(ref template) = args.template {
+        return Ok(template.clone());
+    }
+    let templates = BundledTemplates::new().templates()?;
+    if !interactive(args) || templates.len() <= 1 {
+        return Ok(DEFAULT_TEMPLATE.to_string());
+    }
+
+    let choices: Vec<Choice> = templates
+        .into_iter()
+        .map(|template| Choice::Labeled {
+            label: match template.description {
+                Some(description) => {
+                    format!("{} [{}] {}", template.name, template.category, description)
+                }
+                None => format!("{} [{}]", template.name, template.category),
+            },
+            value: template.name,
+        })
+        .collect();
+    PromptRunner::new().prompt_select("Template", &choices, Some(DEFAULT_TEMPLATE))
+}
+
+fn deps_options(args: &NewArgs) -> DepsOptions {
+    DepsOptions {
+        source: args.deps,
+        rev: args.deps_rev.clone(),
+        path: args.deps_path.clone(),
+    }
+}
+
+/// Parse `--rename FROM=TO` values, checking both sides are paths inside the project
+fn parse_renames(renames: &[String]) -> Result<Vec<(String, String)>> {
+    let invalid = |reason: String| CargoJamError::InvalidArgument {
+        arg: "--rename".to_string(),
+        reason,
+    };
+    let inside_project = |path: &str| {
+        let path = Path::new(path);
+        path.components().next().is_some()
+            && path.components().all(|c| {
+                matches!(
+                    c,
+                    std::path::Component::Normal(_) | std::path::Component::CurDir
+                )
+            })
+    };
+
+    let mut parsed: Vec<(String, String)> = Vec::new();
+    for rename in renames {
+        let Some((from, to)) = rename.split_once('=') else {
+            return Err(invalid(format!("'{}' is not in FROM=TO form", rename)));
+        };
+        for path in [from, to] {
+            if !inside_project(path) {
+                return Err(invalid(format!(
+                    "'{}' must be a relative path inside the project",
+                    path
+                )));
+            }
+        }
+        if parsed.iter().any(|(other, _)| other == from) {
+            return Err(invalid(format!("'{}' is renamed more than once", from)));
+        }
+        if parsed.iter().any(|(_, other)| other == to) {
+            return Err(invalid(format!(
+                "more than one path is renamed to '{}'",
+                to
+            )));
+        }
+        parsed.push((from.to_string(), to.to_string()));
+    }
+    Ok(parsed)
+}
+
+fn collect_predefined_variables(args: &NewArgs) -> Result<HashMap<String, String>> {
+    let mut variables = HashMap::new();
+
     // Load from values file if provided
     if let Some(values_file) = &args.values_file {
         let content = std::fs::read_to_string(values_file)?;
@@ -141,16 +813,67 @@ fn collect_predefined_variables(args: &NewArgs) -> Result<HashMap<String, String
         variables.extend(values);
     }
 
+    if args.values_stdin {
+        let content = std::io::read_to_string(std::io::stdin())?;
+        variables.extend(parse_values(
+            &content,
+            args.values_format,
+            "--values-stdin",
+        )?);
+    }
+
+    // --define flags win over values documents
+    for define in &args.define {
+        if let Some((key, value)) = define.split_once('=') {
+            variables.insert(key.to_string(), value.to_string());
+        }
+    }
+
+    if let Some(license) = &args.license {
+        variables.insert("license".to_string(), license.clone());
+    }
+
+    let metadata = [
+        ("description", &args.description),
+        ("homepage", &args.homepage),
+        ("repository", &args.repository),
+    ];
+    for (key, value) in metadata {
+        if let Some(value) = value {
+            variables.insert(key.to_string(), value.clone());
+        }
+    }
+
     Ok(variables)
 }
 
-fn validate_project_name(name: &str) -> Result<()> {
-    let re = regex::Regex::new(r"^[a-z][a-z0-9_-]*$").unwrap();
-    if !re.is_match(name) {
-        return Err(CargoJamError::InvalidProjectName {
-            name: name.to_string(),
-            reason: "Must start with lowercase letter, contain only lowercase letters, numbers, underscores, and hyphens".to_string(),
-        });
+/// Warn that values were given under a variable's old name
+pub(crate) fn warn_renamed_variables(moved: Vec<(&str, &str)>) {
+    for (old, new) in moved {
+        eprintln!(
+            "{} The '{}' variable was renamed to '{}'; using the value for '{}'",
+            style(Glyph::Warning).yellow(),
+            old,
+            new,
+            new
+        );
+    }
+}
+
+/// Whether to prompt; stdin is taken by the values document with --values-stdin
+fn interactive(args: &NewArgs) -> bool {
+    !args.defaults && !args.no_interactive && !args.values_stdin
+}
+
+/// Whether missing values are an error rather than filled from template defaults
+fn strict(args: &NewArgs) -> bool {
+    args.no_interactive && !args.defaults
+}
+
+fn check_project_name(name: &str, force: bool) -> Result<()> {
+    if force {
+        validate_project_name_relaxed(name)
+    } else {
+        validate_project_name(name)
     }
-    Ok(())
 }
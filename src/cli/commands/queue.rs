@@ -0,0 +1,105 @@
+use crate::cli::args::QueueArgs;
+use crate::cli::commands::up::resolve_rpc_url;
+use crate::cli::output::Glyph;
+use crate::cli::validate::{check_rpc_reachable, normalize_rpc_url};
+use crate::error::{CargoJamError, Result};
+use crate::process::{output_with_timeout, resolve_timeout};
+use crate::toolchain::config::ToolchainConfig;
+use console::style;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+pub fn execute(args: QueueArgs) -> Result<()> {
+    let rpc = resolve_rpc_url(args.rpc.as_deref())?;
+    let jamt_bin = validate(&args)?;
+    let timeout = resolve_timeout(args.timeout)?;
+
+    check_rpc_reachable(&rpc)?;
+
+    if args.json && !supports_json(&jamt_bin) {
+        return Err(CargoJamError::InvalidArgument {
+            arg: "--json".to_string(),
+            reason: "the installed jamt does not support 'queue --json'".to_string(),
+        });
+    }
+
+    // Note: --rpc is a global option and must come BEFORE the subcommand
+    let mut cmd = Command::new(&jamt_bin);
+    cmd.arg("--rpc").arg(&rpc).arg("queue");
+    if args.json {
+        cmd.arg("--json");
+    }
+
+    if args.verbose {
+        println!("Running: jamt {:?}", cmd.get_args().collect::<Vec<_>>());
+    }
+
+    let output = output_with_timeout(&mut cmd, timeout).map_err(|e| match e {
+        CargoJamError::Io(e) => CargoJamError::Build(format!("Failed to execute jamt: {}", e)),
+        e => e,
+    })?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(CargoJamError::Build(format!(
+            "jamt queue failed with status {}:\n{}",
+            output.status,
+            stderr.trim_end()
+        )));
+    }
+
+    // JSON goes to stdout untouched so it can be piped into other tools
+    if args.json {
+        print!("{}", stdout);
+        return Ok(());
+    }
+
+    println!(
+        "{} Work queue at {}",
+        style(Glyph::Arrow).cyan(),
+        style(&rpc).yellow()
+    );
+    if stdout.trim().is_empty() {
+        println!("  {}", style("(empty)").dim());
+    }
+    for line in stdout.trim_end().lines() {
+        println!("  {}", line);
+    }
+
+    Ok(())
+}
+
+/// Validate queue arguments and prerequisites, returning the jamt binary path
+pub fn validate(args: &QueueArgs) -> Result<PathBuf> {
+    let config = ToolchainConfig::load()?;
+    if !config.is_installed() {
+        return Err(CargoJamError::ToolchainMissing {
+            tool: "JAM toolchain".to_string(),
+            install_hint: "Run 'cargo polkajam setup' to install the JAM toolchain".to_string(),
+        });
+    }
+
+    let jamt_bin =
+        ToolchainConfig::binary_path("jamt")?.ok_or_else(|| CargoJamError::ToolchainMissing {
+            tool: "jamt".to_string(),
+            install_hint: "Run 'cargo polkajam setup --force' to reinstall the toolchain"
+                .to_string(),
+        })?;
+
+    if let Some(ref rpc) = args.rpc {
+        normalize_rpc_url(rpc)?;
+    }
+    resolve_timeout(args.timeout)?;
+
+    Ok(jamt_bin)
+}
+
+/// Whether the installed jamt's `queue` subcommand accepts `--json`
+fn supports_json(jamt_bin: &Path) -> bool {
+    Command::new(jamt_bin)
+        .args(["queue", "--help"])
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).contains("--json"))
+        .unwrap_or(false)
+}
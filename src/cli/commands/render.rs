@@ -0,0 +1,72 @@
+use crate::cli::args::RenderArgs;
+use crate::cli::output::{write_output, Glyph};
+use crate::error::{CargoJamError, Result};
+use crate::template::builtins::builtin_variables;
+use crate::template::engine::TemplateEngine;
+use console::style;
+use std::collections::HashMap;
+
+pub fn execute(args: RenderArgs) -> Result<()> {
+    validate(&args)?;
+
+    let mut variables = builtin_variables();
+
+    if let Some(values_file) = &args.values_file {
+        let content = std::fs::read_to_string(values_file)?;
+        let values: HashMap<String, String> = toml::from_str(&content)?;
+        variables.extend(values);
+    }
+
+    for define in &args.define {
+        if let Some((key, value)) = define.split_once('=') {
+            variables.insert(key.to_string(), value.to_string());
+        }
+    }
+
+    let template = std::fs::read_to_string(&args.file)?;
+    let rendered = TemplateEngine::new()?
+        .strict_undefined(!args.lenient)
+        .render(&template, &variables)
+        .map_err(|e| CargoJamError::TemplateRender(format!("in {}: {}", args.file.display(), e)))?;
+
+    if write_output(args.output.as_deref(), &rendered)? {
+        eprintln!(
+            "{} Rendered {} to {}",
+            style(Glyph::Success).green().bold(),
+            style(args.file.display()).cyan(),
+            style(args.output.as_ref().unwrap().display()).yellow()
+        );
+    }
+
+    Ok(())
+}
+
+/// Validate render arguments
+pub fn validate(args: &RenderArgs) -> Result<()> {
+    if !args.file.is_file() {
+        return Err(CargoJamError::TemplateNotFound(format!(
+            "'{}' does not exist",
+            args.file.display()
+        )));
+    }
+
+    for define in &args.define {
+        if !define.contains('=') {
+            return Err(CargoJamError::InvalidArgument {
+                arg: "--define".to_string(),
+                reason: format!("'{}' is not in KEY=VALUE form", define),
+            });
+        }
+    }
+
+    if let Some(values_file) = &args.values_file {
+        if !values_file.is_file() {
+            return Err(CargoJamError::InvalidArgument {
+                arg: "--values-file".to_string(),
+                reason: format!("'{}' does not exist", values_file.display()),
+            });
+        }
+    }
+
+    Ok(())
+}
@@ -1,96 +1,233 @@
 use crate::cli::args::SetupArgs;
-use crate::error::Result;
+use crate::cli::output::{
+    download_bar, download_spinner_style, multi_progress, set_message, Glyph,
+};
+use crate::error::{CargoJamError, Result};
+use crate::project::manifest::FileStatus;
+use crate::toolchain::checksums::ToolchainChecksums;
 use crate::toolchain::config::ToolchainConfig;
 use crate::toolchain::download::{
-    download_and_install, fetch_releases, get_latest_release, get_release,
+    cached_archive, cached_release_archive, download_and_install, fetch_releases, find_asset,
+    get_latest_release, get_latest_release_before, get_release, install_from_archive,
+    prefetch_asset, repair_from_archive, validate_archive, validate_release_date, GitHubRelease,
+    ReleaseClient,
 };
 use crate::toolchain::platform::Platform;
+use crate::toolchain::prune::format_size;
 use console::style;
+use indexmap::IndexMap;
 use indicatif::{ProgressBar, ProgressStyle};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Mutex;
+
+/// Maximum number of archives downloaded at once by --all-platforms
+const PREFETCH_JOBS: usize = 3;
+
+/// Exit code of `setup --update --dry-run` when a newer release is available
+pub const UPDATE_AVAILABLE_EXIT_CODE: u8 = 10;
+
+/// How `setup` finished, which decides the process exit status
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetupOutcome {
+    Done,
+    /// `--dry-run` found a release that `--update` would install
+    UpdateAvailable,
+}
+
+impl SetupOutcome {
+    pub fn exit_code(self) -> ExitCode {
+        match self {
+            SetupOutcome::Done => ExitCode::SUCCESS,
+            SetupOutcome::UpdateAvailable => ExitCode::from(UPDATE_AVAILABLE_EXIT_CODE),
+        }
+    }
+}
+
+pub fn execute(args: SetupArgs) -> Result<SetupOutcome> {
+    ReleaseClient::from_secs(args.timeout, args.connect_timeout).install();
 
-pub fn execute(args: SetupArgs) -> Result<()> {
     // Handle --info flag
     if args.info {
-        return show_info();
+        show_info()?;
+        return Ok(SetupOutcome::Done);
     }
 
     // Handle --list flag
     if args.list {
-        return list_releases();
+        list_releases(args.json)?;
+        return Ok(SetupOutcome::Done);
     }
 
-    // Detect platform
-    let platform = Platform::detect()?;
-    println!(
-        "{} Detected platform: {}",
-        style("→").cyan(),
-        style(platform.to_string()).yellow()
-    );
+    // Handle --repair (restore individual files of the installed toolchain)
+    if args.repair {
+        repair(&args)?;
+        return Ok(SetupOutcome::Done);
+    }
+
+    // Handle --all-platforms (cache warming, nothing is installed)
+    if args.all_platforms {
+        prefetch_all_platforms(&args)?;
+        return Ok(SetupOutcome::Done);
+    }
+
+    let platform = match platform_override(&args)? {
+        Some(platform) => {
+            println!(
+                "{} Target platform: {}",
+                style(Glyph::Arrow).cyan(),
+                style(platform.to_string()).yellow()
+            );
+            platform
+        }
+        None => {
+            let platform = Platform::detect()?;
+            println!(
+                "{} Detected platform: {}",
+                style(Glyph::Arrow).cyan(),
+                style(platform.to_string()).yellow()
+            );
+            platform
+        }
+    };
+
+    // Handle --from-archive (offline install)
+    if let Some(ref archive) = args.from_archive {
+        let label = args.label.as_deref().unwrap_or_default();
+        let archive = &archive_path(archive, label, &platform)?;
+        println!(
+            "{} Installing from archive {}...",
+            style(Glyph::Arrow).cyan(),
+            style(archive.display()).yellow()
+        );
+        let install_path = install_from_archive(archive, &platform, label, !args.no_normalize)?;
+        println!(
+            "\n{} Installed JAM toolchain {} to {}",
+            style(Glyph::Success).green().bold(),
+            style(label).cyan(),
+            style(install_path.display()).yellow()
+        );
+        return Ok(SetupOutcome::Done);
+    }
 
     // Get the release to install
-    let release = if let Some(ref version) = args.version {
+    let config = ToolchainConfig::load()?;
+    let (release, installed) = if let Some(ref version) = args.version {
         println!(
             "{} Fetching release {}...",
-            style("→").cyan(),
+            style(Glyph::Arrow).cyan(),
             style(version).yellow()
         );
-        get_release(version)?
+        let release = get_release(version)?;
+        let installed = config.is_installed_version(&release.tag_name);
+        (release, installed)
+    } else if let Some(ref date) = args.date {
+        println!(
+            "{} Fetching latest {} release as of {}...",
+            style(Glyph::Arrow).cyan(),
+            args.channel,
+            style(date).yellow()
+        );
+        let release = get_latest_release_before(&args.channel, date)?;
+        let installed = config.is_installed_version(&release.tag_name);
+        (release, installed)
     } else {
-        println!("{} Fetching latest nightly release...", style("→").cyan());
-        get_latest_release()?
+        println!(
+            "{} Fetching latest {} release...",
+            style(Glyph::Arrow).cyan(),
+            args.channel
+        );
+        let latest = get_latest_release(&args.channel)?;
+        (latest.release, latest.installed)
     };
 
     println!(
         "{} Found release: {}",
-        style("→").cyan(),
+        style(Glyph::Arrow).cyan(),
         style(&release.tag_name).green()
     );
 
-    // Check if already installed (unless --force or --update)
-    let config = ToolchainConfig::load()?;
-    if config.is_installed() && !args.force && !args.update {
-        if let Some(ref installed) = config.installed_version {
-            if installed == &release.tag_name {
-                println!(
-                    "\n{} Toolchain {} is already installed at {}",
-                    style("✓").green().bold(),
-                    style(&release.tag_name).cyan(),
-                    style(config.toolchain_path.unwrap().display()).yellow()
-                );
-                println!(
-                    "\nUse {} to reinstall or {} to update to latest.",
-                    style("--force").cyan(),
-                    style("--update").cyan()
-                );
-                return Ok(());
-            }
-        }
+    if args.dry_run {
+        return preview_update(&config, &release, &platform, installed);
     }
 
-    // Create progress spinner
-    let spinner = ProgressBar::new_spinner();
-    spinner.set_style(
-        ProgressStyle::default_spinner()
-            .template("{spinner:.green} {msg}")
-            .unwrap(),
-    );
-    spinner.enable_steady_tick(std::time::Duration::from_millis(100));
+    // Check if already installed (unless --force or --update)
+    if installed && !args.force && !args.update {
+        println!(
+            "\n{} Toolchain {} is already installed at {}",
+            style(Glyph::Success).green().bold(),
+            style(&release.tag_name).cyan(),
+            style(config.toolchain_path.unwrap().display()).yellow()
+        );
+        println!(
+            "\nUse {} to reinstall or {} to update to latest.",
+            style("--force").cyan(),
+            style("--update").cyan()
+        );
+        return Ok(SetupOutcome::Done);
+    }
 
-    // Download and install
-    spinner.set_message(format!("Downloading {}...", release.tag_name));
-    let install_path = download_and_install(&release, &platform, args.force)?;
-    spinner.finish_and_clear();
+    // Install from the archive cache when it has this release, otherwise download
+    let cache_dir = match args.keep_archive {
+        Some(Some(ref dir)) => dir.clone(),
+        _ => ToolchainConfig::cache_dir()?,
+    };
+    let install_path = match cached_release_archive(&release, &platform, &cache_dir) {
+        Some(archive) => {
+            println!(
+                "{} Using cached archive {}",
+                style(Glyph::Arrow).cyan(),
+                style(archive.display()).yellow()
+            );
+            install_from_archive(&archive, &platform, &release.tag_name, !args.no_normalize)?
+        }
+        None if args.keep_archive.is_some() => {
+            let progress = download_bar(&format!("Downloading {}", release.tag_name));
+            let archive = prefetch_asset(
+                &release,
+                &platform,
+                &cache_dir,
+                &progress,
+                &download_spinner_style(),
+                !args.no_verify,
+            );
+            progress.finish_and_clear();
+            let archive = archive?;
+            let install_path =
+                install_from_archive(&archive, &platform, &release.tag_name, !args.no_normalize)?;
+            println!(
+                "{} Kept archive {}",
+                style(Glyph::Arrow).cyan(),
+                style(archive.display()).yellow()
+            );
+            install_path
+        }
+        None => {
+            let progress = download_bar(&format!("Downloading {}", release.tag_name));
+            let install_path = download_and_install(
+                &release,
+                &platform,
+                args.force,
+                !args.no_normalize,
+                !args.no_verify,
+                &progress,
+                &download_spinner_style(),
+            );
+            progress.finish_and_clear();
+            install_path?
+        }
+    };
 
     println!(
         "\n{} Installed JAM toolchain {} to {}",
-        style("✓").green().bold(),
+        style(Glyph::Success).green().bold(),
         style(&release.tag_name).cyan(),
         style(install_path.display()).yellow()
     );
 
-    // List installed binaries from the normalized polkajam-nightly directory
-    let nightly_dir = install_path.join("polkajam-nightly");
-    if nightly_dir.exists() {
+    // List installed binaries from wherever the archive put them
+    if let Some(nightly_dir) = ToolchainConfig::polkajam_dir()? {
         println!("\n{}", style("Installed binaries:").bold());
         if let Ok(bin_entries) = std::fs::read_dir(&nightly_dir) {
             for bin_entry in bin_entries.flatten() {
@@ -102,7 +239,7 @@ pub fn execute(args: SetupArgs) -> Result<()> {
                         .unwrap_or(false)
                 {
                     let bin_name = bin_path.file_name().unwrap().to_string_lossy();
-                    println!("  {} {}", style("✓").green(), bin_name);
+                    println!("  {} {}", style(Glyph::Success).green(), bin_name);
                 }
             }
         }
@@ -110,13 +247,319 @@ pub fn execute(args: SetupArgs) -> Result<()> {
 
     println!(
         "\n{} You can now use {}",
-        style("→").cyan(),
+        style(Glyph::Arrow).cyan(),
         style("cargo polkajam build").green()
     );
 
+    Ok(SetupOutcome::Done)
+}
+
+/// Restore missing or corrupt files of the installed toolchain
+fn repair(args: &SetupArgs) -> Result<()> {
+    let config = ToolchainConfig::load()?;
+    let (Some(version), Some(toolchain_dir)) = (
+        config
+            .installed_version
+            .clone()
+            .filter(|_| config.is_installed()),
+        config.toolchain_path.clone(),
+    ) else {
+        return Err(CargoJamError::ToolchainMissing {
+            tool: "JAM toolchain".to_string(),
+            install_hint: "Run 'cargo polkajam setup' to install the JAM toolchain".to_string(),
+        });
+    };
+    let platform = target_platform(args)?;
+
+    match ToolchainChecksums::load(&toolchain_dir)? {
+        Some(checksums) => {
+            let damaged = checksums.damaged(&toolchain_dir)?;
+            if damaged.is_empty() && !args.force {
+                println!(
+                    "{} All {} files of toolchain {} are intact",
+                    style(Glyph::Success).green().bold(),
+                    checksums.files.len(),
+                    style(&version).cyan()
+                );
+                return Ok(());
+            }
+            for (path, status) in &damaged {
+                let label = match status {
+                    FileStatus::Missing => "missing",
+                    _ => "corrupt",
+                };
+                println!("  {} {} ({})", style(Glyph::Failure).red(), path, label);
+            }
+        }
+        None => println!(
+            "{} No checksums recorded for this installation, comparing every file with the release archive",
+            style("!").yellow().bold()
+        ),
+    }
+
+    let cache_dir = ToolchainConfig::cache_dir()?;
+    let archive = match cached_archive(&cache_dir, &version, &platform) {
+        Some(archive) => {
+            println!(
+                "{} Using cached archive {}",
+                style(Glyph::Arrow).cyan(),
+                style(archive.display()).yellow()
+            );
+            archive
+        }
+        None => {
+            println!(
+                "{} Downloading {} (kept in {} for next time)...",
+                style(Glyph::Arrow).cyan(),
+                style(&version).green(),
+                style(cache_dir.display()).yellow()
+            );
+            let release = get_release(&version)?;
+            let progress = ProgressBar::hidden();
+            prefetch_asset(
+                &release,
+                &platform,
+                &cache_dir,
+                &progress,
+                &download_spinner_style(),
+                !args.no_verify,
+            )?
+        }
+    };
+
+    let repaired =
+        repair_from_archive(&archive, &toolchain_dir, &platform, !config.preserve_layout)?;
+    if repaired.is_empty() {
+        println!(
+            "\n{} Nothing needed repairing",
+            style(Glyph::Success).green().bold()
+        );
+        return Ok(());
+    }
+
+    println!();
+    for path in &repaired {
+        println!("  {} {}", style(Glyph::Success).green(), path);
+    }
+    println!(
+        "\n{} Repaired {} file(s) of toolchain {}",
+        style(Glyph::Success).green().bold(),
+        repaired.len(),
+        style(&version).cyan()
+    );
+
     Ok(())
 }
 
+/// Report what `--update` would do, and whether it would install something
+fn preview_update(
+    config: &ToolchainConfig,
+    release: &GitHubRelease,
+    platform: &Platform,
+    installed: bool,
+) -> Result<SetupOutcome> {
+    let current = match config.installed_version {
+        Some(ref version) if config.is_installed() => style(version.as_str()).cyan(),
+        _ => style("none").dim(),
+    };
+    let asset = find_asset(release, platform)?;
+
+    println!();
+    println!("  Installed:     {}", current);
+    println!("  Latest:        {}", style(&release.tag_name).cyan());
+    println!(
+        "  Download size: {} ({})",
+        format_size(asset.size),
+        style(&asset.name).dim()
+    );
+
+    if installed {
+        println!(
+            "\n{} Already up to date, nothing to download",
+            style(Glyph::Success).green().bold()
+        );
+        return Ok(SetupOutcome::Done);
+    }
+
+    println!(
+        "\n{} Update available: run {} to install it",
+        style(Glyph::Arrow).cyan(),
+        style("cargo polkajam setup --update").cyan()
+    );
+    Ok(SetupOutcome::UpdateAvailable)
+}
+
+/// Download the release archive of every platform into the cache directory
+fn prefetch_all_platforms(args: &SetupArgs) -> Result<()> {
+    let release = match (&args.version, &args.date) {
+        (Some(version), _) => get_release(version)?,
+        (None, Some(date)) => get_latest_release_before(&args.channel, date)?,
+        (None, None) => get_latest_release(&args.channel)?.release,
+    };
+    let cache_dir = match args.cache_dir {
+        Some(ref dir) => dir.clone(),
+        None => ToolchainConfig::cache_dir()?,
+    };
+
+    println!(
+        "{} Prefetching {} for {} platforms into {}",
+        style(Glyph::Arrow).cyan(),
+        style(&release.tag_name).green(),
+        Platform::ALL.len(),
+        style(cache_dir.display()).yellow()
+    );
+
+    let results = download_platforms(&release, &cache_dir, !args.no_verify);
+
+    println!();
+    let mut errors = Vec::new();
+    for (platform, result) in Platform::ALL.iter().zip(results) {
+        match result {
+            Ok(path) => println!(
+                "  {} {} {}",
+                style(Glyph::Success).green(),
+                platform,
+                style(path.display()).dim()
+            ),
+            Err(e) => {
+                println!("  {} {} {}", style(Glyph::Failure).red(), platform, e);
+                errors.push(e);
+            }
+        }
+    }
+
+    // Fail with the first platform's own error, as a single-platform download would
+    let failed = errors.len();
+    if let Some(first) = errors.into_iter().next() {
+        eprintln!(
+            "\n{} Downloads failed for {} of {} platforms",
+            style(Glyph::Failure).red().bold(),
+            failed,
+            Platform::ALL.len()
+        );
+        return Err(first);
+    }
+
+    println!(
+        "\n{} Cached {} for all platforms",
+        style(Glyph::Success).green().bold(),
+        style(&release.tag_name).cyan()
+    );
+
+    Ok(())
+}
+
+/// Download every platform's archive with at most `PREFETCH_JOBS` in flight,
+/// returning one result per entry of `Platform::ALL`
+fn download_platforms(
+    release: &GitHubRelease,
+    cache_dir: &Path,
+    verify: bool,
+) -> Vec<Result<PathBuf>> {
+    let multi = multi_progress();
+    let bar_style = ProgressStyle::default_bar()
+        .template("{prefix:>15} [{bar:30.cyan/blue}] {bytes}/{total_bytes} {msg}")
+        .unwrap()
+        .progress_chars("=> ");
+
+    let bars: Vec<ProgressBar> = Platform::ALL
+        .iter()
+        .map(|platform| {
+            let bar = multi.add(ProgressBar::new(0));
+            bar.set_style(bar_style.clone());
+            bar.set_prefix(platform.to_string());
+            bar.set_message("queued");
+            bar
+        })
+        .collect();
+
+    let queue = Mutex::new((0..Platform::ALL.len()).collect::<Vec<_>>());
+    let results: Mutex<Vec<Option<Result<PathBuf>>>> =
+        Mutex::new((0..Platform::ALL.len()).map(|_| None).collect());
+
+    std::thread::scope(|scope| {
+        for _ in 0..PREFETCH_JOBS.min(Platform::ALL.len()) {
+            scope.spawn(|| loop {
+                let Some(index) = queue.lock().unwrap().pop() else {
+                    break;
+                };
+
+                let bar = &bars[index];
+                set_message(bar, "downloading");
+                let result = prefetch_asset(
+                    release,
+                    &Platform::ALL[index],
+                    cache_dir,
+                    bar,
+                    &download_spinner_style(),
+                    verify,
+                );
+                set_message(bar, if result.is_ok() { "done" } else { "failed" });
+                bar.finish();
+
+                results.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|r| r.expect("every platform is downloaded"))
+        .collect()
+}
+
+/// The platform set by `--platform` or the config's `platform`, if any
+fn platform_override(args: &SetupArgs) -> Result<Option<Platform>> {
+    match args.platform {
+        Some(ref platform) => Ok(Some(platform.clone())),
+        None => Ok(ToolchainConfig::load()?.platform),
+    }
+}
+
+/// The platform whose archive is installed: the override, or the one this machine runs on
+fn target_platform(args: &SetupArgs) -> Result<Platform> {
+    match platform_override(args)? {
+        Some(platform) => Ok(platform),
+        None => Platform::detect(),
+    }
+}
+
+/// Validate setup prerequisites without downloading anything
+pub fn validate(args: &SetupArgs) -> Result<()> {
+    let platform = target_platform(args)?;
+
+    if let Some(ref archive) = args.from_archive {
+        let label = args.label.as_deref().unwrap_or_default();
+        validate_archive(&archive_path(archive, label, &platform)?, &platform)?;
+    }
+
+    if let Some(ref date) = args.date {
+        validate_release_date(date)?;
+    }
+
+    Ok(())
+}
+
+/// The archive to install with `--from-archive`: the path itself, or for a cache
+/// directory (as filled by `--keep-archive` or `--all-platforms`) its archive of
+/// the `--label` version for this platform
+fn archive_path(path: &Path, label: &str, platform: &Platform) -> Result<PathBuf> {
+    if !path.is_dir() {
+        return Ok(path.to_path_buf());
+    }
+    cached_archive(path, label, platform).ok_or_else(|| CargoJamError::InvalidArgument {
+        arg: "--from-archive".to_string(),
+        reason: format!(
+            "no {} archive of {} in {}",
+            platform,
+            label,
+            path.join(label).display()
+        ),
+    })
+}
+
 fn show_info() -> Result<()> {
     let config = ToolchainConfig::load()?;
 
@@ -158,7 +601,7 @@ fn show_info() -> Result<()> {
                             .unwrap_or(false)
                     {
                         let name = path.file_name().unwrap().to_string_lossy();
-                        println!("  • {}", name);
+                        println!("  {} {}", Glyph::Bullet, name);
                     }
                 }
             }
@@ -175,25 +618,34 @@ fn show_info() -> Result<()> {
                 let version = String::from_utf8_lossy(&output.stdout);
                 println!(
                     "  {} jam-pvm-build {}",
-                    style("✓").green(),
+                    style(Glyph::Success).green(),
                     style(version.trim()).dim()
                 );
             } else {
-                println!("  {} jam-pvm-build (not installed)", style("✗").red());
+                println!(
+                    "  {} jam-pvm-build (not installed)",
+                    style(Glyph::Failure).red()
+                );
                 println!(
                     "    Install with: {}",
                     style("cargo install jam-pvm-build").cyan()
                 );
             }
         } else {
-            println!("  {} jam-pvm-build (not installed)", style("✗").red());
+            println!(
+                "  {} jam-pvm-build (not installed)",
+                style(Glyph::Failure).red()
+            );
             println!(
                 "    Install with: {}",
                 style("cargo install jam-pvm-build").cyan()
             );
         }
     } else {
-        println!("  {} No toolchain installed", style("⚠").yellow());
+        println!(
+            "  {} No toolchain installed",
+            style(Glyph::Warning).yellow()
+        );
         println!(
             "\n  Run {} to install the latest nightly.",
             style("cargo polkajam setup").cyan()
@@ -203,13 +655,58 @@ fn show_info() -> Result<()> {
     Ok(())
 }
 
-fn list_releases() -> Result<()> {
-    println!("{} Fetching available releases...\n", style("→").cyan());
+/// A release as printed by `setup --list --json`
+#[derive(Debug, Serialize)]
+struct ReleaseEntry<'a> {
+    tag_name: &'a str,
+    published_at: Option<&'a str>,
+    installed: bool,
+    /// Whether the release has an archive for each platform, keyed by asset suffix
+    platforms: IndexMap<&'static str, bool>,
+}
+
+impl<'a> ReleaseEntry<'a> {
+    fn new(release: &'a GitHubRelease, installed: Option<&str>) -> Self {
+        let platforms = Platform::ALL
+            .iter()
+            .map(|platform| {
+                let suffix = platform.asset_suffix();
+                let available = release.assets.iter().any(|a| a.name.contains(suffix));
+                (suffix, available)
+            })
+            .collect();
+        Self {
+            tag_name: &release.tag_name,
+            published_at: release.published_at.as_deref(),
+            installed: installed == Some(release.tag_name.as_str()),
+            platforms,
+        }
+    }
+}
+
+fn list_releases(json: bool) -> Result<()> {
+    if !json {
+        println!(
+            "{} Fetching available releases...\n",
+            style(Glyph::Arrow).cyan()
+        );
+    }
 
     let releases = fetch_releases(10)?;
     let config = ToolchainConfig::load()?;
     let installed = config.installed_version.as_deref();
 
+    if json {
+        let entries: Vec<ReleaseEntry> = releases
+            .iter()
+            .map(|release| ReleaseEntry::new(release, installed))
+            .collect();
+        let json = serde_json::to_string_pretty(&entries)
+            .map_err(|e| std::io::Error::other(format!("Failed to serialize releases: {}", e)))?;
+        println!("{}", json);
+        return Ok(());
+    }
+
     println!("{}", style("Available releases:").bold());
     for release in releases {
         let is_installed = installed == Some(release.tag_name.as_str());
@@ -222,9 +719,9 @@ fn list_releases() -> Result<()> {
         println!(
             "  {} {} {}",
             if is_installed {
-                style("✓").green()
+                style(Glyph::Success).green()
             } else {
-                style("•").dim()
+                style(Glyph::Bullet).dim()
             },
             style(&release.tag_name).cyan(),
             marker
@@ -238,3 +735,38 @@ fn list_releases() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::toolchain::download::GitHubAsset;
+
+    #[test]
+    fn test_release_entry_json() {
+        let asset = |name: &str| GitHubAsset {
+            name: name.to_string(),
+            browser_download_url: format!("https://example.com/{}", name),
+            size: 1,
+            digest: None,
+        };
+        let release = GitHubRelease {
+            tag_name: "nightly-2025-12-29".to_string(),
+            name: None,
+            published_at: Some("2025-12-29T03:00:00Z".to_string()),
+            assets: vec![
+                asset("polkajam-nightly-2025-12-29-linux-x86_64.tar.gz"),
+                asset("polkajam-nightly-2025-12-29-windows-x86_64.zip"),
+            ],
+        };
+
+        let json =
+            serde_json::to_value(ReleaseEntry::new(&release, Some("nightly-2025-12-29"))).unwrap();
+        assert_eq!(json["tag_name"], "nightly-2025-12-29");
+        assert_eq!(json["published_at"], "2025-12-29T03:00:00Z");
+        assert_eq!(json["installed"], true);
+        assert_eq!(json["platforms"]["linux-x86_64"], true);
+        assert_eq!(json["platforms"]["windows-x86_64"], true);
+        assert_eq!(json["platforms"]["macos-aarch64"], false);
+        assert_eq!(json["platforms"].as_object().unwrap().len(), 5);
+    }
+}
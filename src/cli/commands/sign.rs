@@ -0,0 +1,98 @@
+use crate::build::signing::{public_key_path, signature_path, SigningKey};
+use crate::cli::args::SignArgs;
+use crate::cli::output::Glyph;
+use crate::error::{CargoJamError, Result};
+use crate::toolchain::config::ToolchainConfig;
+use console::style;
+
+pub fn execute(args: SignArgs) -> Result<()> {
+    validate(&args)?;
+
+    let key = if args.generate_key {
+        generate_key(&args)?
+    } else {
+        SigningKey::load(&args.key)?
+    };
+
+    let Some(ref blob) = args.blob else {
+        return Ok(());
+    };
+
+    let signature = key.sign(&std::fs::read(blob)?);
+    let output = args.output.clone().unwrap_or_else(|| signature_path(blob));
+    std::fs::write(&output, format!("{}\n", signature))?;
+
+    println!(
+        "{} Signed {}: {}",
+        style(Glyph::Success).green().bold(),
+        style(blob.display()).cyan(),
+        style(output.display()).cyan()
+    );
+    println!(
+        "{} Public key: {}",
+        style(Glyph::Arrow).cyan(),
+        style(key.public_key()).dim()
+    );
+
+    Ok(())
+}
+
+/// Validate sign arguments without signing
+pub fn validate(args: &SignArgs) -> Result<()> {
+    if let Some(ref blob) = args.blob {
+        if !blob.exists() {
+            return Err(CargoJamError::Build(format!(
+                "Service blob not found: {}",
+                blob.display()
+            )));
+        }
+    }
+
+    if args.generate_key && args.key.exists() {
+        return Err(CargoJamError::InvalidArgument {
+            arg: "--key".to_string(),
+            reason: format!(
+                "{} already exists; refusing to overwrite a signing key",
+                args.key.display()
+            ),
+        });
+    }
+
+    if !args.generate_key && !args.key.exists() {
+        return Err(CargoJamError::InvalidArgument {
+            arg: "--key".to_string(),
+            reason: format!(
+                "{} not found (create one with --generate-key)",
+                args.key.display()
+            ),
+        });
+    }
+
+    Ok(())
+}
+
+/// Create a key at `--key` and add its public key to the trusted keys in the config
+fn generate_key(args: &SignArgs) -> Result<SigningKey> {
+    let key = SigningKey::generate()?;
+    key.save(&args.key)?;
+
+    ToolchainConfig::update(|config| {
+        if !config.trusted_keys.contains(&key.public_key()) {
+            config.trusted_keys.push(key.public_key());
+        }
+    })?;
+
+    println!(
+        "{} Generated signing key {} (public key: {})",
+        style(Glyph::Success).green().bold(),
+        style(args.key.display()).cyan(),
+        style(public_key_path(&args.key).display()).cyan()
+    );
+    println!(
+        "{} Trusted for verify and deploy --require-signed: {}",
+        style(Glyph::Arrow).cyan(),
+        style(key.public_key()).dim()
+    );
+
+    Ok(key)
+}
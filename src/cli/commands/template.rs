@@ -0,0 +1,615 @@
+use crate::cli::args::{
+    TemplateArgs, TemplateCommand, TemplateDiffArgs, TemplateInfoArgs, TemplateListArgs,
+    TemplateRenderAllArgs, TemplateSchemaArgs, TemplateTestArgs, TemplateValidateArgs,
+};
+use crate::cli::output::{write_output, Glyph};
+use crate::error::{CargoJamError, Result};
+use crate::project::generator::{GeneratedKind, ProjectGenerator};
+use crate::project::manifest::MANIFEST_FILE;
+use crate::template::builtins::builtin_variables;
+use crate::template::bundled::{BundledTemplate, BundledTemplates};
+use crate::template::config::{Placeholder, TemplateConfig, TestCase};
+use crate::template::diff::{diff_trees, FileChange};
+use crate::template::schema::config_schema_json;
+use console::style;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Project name both templates are generated with by `template diff`
+const DIFF_PROJECT_NAME: &str = "jam-service";
+
+pub fn execute(args: TemplateArgs) -> Result<()> {
+    match args.command {
+        TemplateCommand::Schema(schema_args) => schema(schema_args),
+        TemplateCommand::Test(test_args) => test(test_args),
+        TemplateCommand::Diff(diff_args) => diff(diff_args),
+        TemplateCommand::Info(info_args) => info(info_args),
+        TemplateCommand::RenderAll(render_args) => render_all(render_args),
+        TemplateCommand::List(list_args) => list(list_args),
+        TemplateCommand::Validate(validate_args) => validate_template(validate_args),
+    }
+}
+
+/// Print the bundled templates grouped by category, in index order
+fn list(args: TemplateListArgs) -> Result<()> {
+    let templates: Vec<BundledTemplate> = BundledTemplates::new()
+        .templates()?
+        .into_iter()
+        .filter(|t| args.category.as_ref().is_none_or(|c| &t.category == c))
+        .collect();
+    if templates.is_empty() {
+        println!(
+            "{} No bundled templates in category '{}'",
+            style(Glyph::Warning).yellow(),
+            args.category.unwrap_or_default()
+        );
+        return Ok(());
+    }
+
+    let mut categories: Vec<&str> = Vec::new();
+    for template in &templates {
+        if !categories.contains(&template.category.as_str()) {
+            categories.push(&template.category);
+        }
+    }
+
+    let width = templates.iter().map(|t| t.name.len()).max().unwrap_or(0);
+    for (index, category) in categories.iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+        println!("{}", style(category).bold());
+        for template in templates.iter().filter(|t| t.category == *category) {
+            let mut line = format!(
+                "  {}  {}",
+                style(format!("{:<width$}", template.name, width = width)).cyan(),
+                template.description.as_deref().unwrap_or("")
+            );
+            if !template.tags.is_empty() {
+                line.push_str(&format!(
+                    " {}",
+                    style(format!("[{}]", template.tags.join(", "))).dim()
+                ));
+            }
+            println!("{}", line.trim_end());
+        }
+    }
+
+    Ok(())
+}
+
+/// Generate a whole template into `--out` from a values file, without prompts or git.
+///
+/// Only the values file and placeholder defaults are used (not built-ins read from
+/// git config or the environment) and the random filters get a fixed seed, so the
+/// same inputs always produce the same files.
+fn render_all(args: TemplateRenderAllArgs) -> Result<()> {
+    let mut bundled = BundledTemplates::new();
+    let template_dir = resolve_template(&args.template, &mut bundled)?;
+    let config = TemplateConfig::load_from_dir(&template_dir)?;
+
+    let content = std::fs::read_to_string(&args.values)?;
+    let values: HashMap<String, String> = toml::from_str(&content)?;
+    let variables = render_all_variables(&config, values)?;
+
+    prepare_output_dir(&args.out, args.force)?;
+
+    let strict_undefined = config.strict_undefined(false);
+    let written = ProjectGenerator::new(template_dir, args.out.clone(), config)
+        .strict_undefined(strict_undefined)
+        .seed(Some(args.seed))
+        .generate(&variables)?;
+
+    let files = written
+        .iter()
+        .filter(|path| path.kind != GeneratedKind::Directory)
+        .count();
+    println!(
+        "{} Rendered {} file(s) from '{}' to {}",
+        style(Glyph::Success).green().bold(),
+        files,
+        args.template,
+        style(args.out.display()).cyan()
+    );
+
+    Ok(())
+}
+
+/// Variables for `render-all`: the given values, `crate_name`, then placeholder defaults
+fn render_all_variables(
+    config: &TemplateConfig,
+    mut variables: HashMap<String, String>,
+) -> Result<HashMap<String, String>> {
+    for (key, value) in &variables {
+        if let Some(placeholder) = config.placeholders.get(key) {
+            placeholder.validate_value(key, value)?;
+        }
+    }
+
+    if let Some(project_name) = variables.get("project_name") {
+        let crate_name = project_name.replace(['-', '.'], "_");
+        variables
+            .entry("crate_name".to_string())
+            .or_insert(crate_name);
+    }
+
+    let mut missing = Vec::new();
+    for (key, placeholder) in config.ordered_placeholders() {
+        if variables.contains_key(key) {
+            continue;
+        }
+        match placeholder.default_value() {
+            Some(default) => {
+                variables.insert(key.to_string(), default);
+            }
+            None => missing.push(key.as_str()),
+        }
+    }
+
+    if !missing.is_empty() {
+        return Err(CargoJamError::InvalidArgument {
+            arg: "--values".to_string(),
+            reason: format!(
+                "no value given for: {} (these placeholders have no default)",
+                missing.join(", ")
+            ),
+        });
+    }
+
+    Ok(variables)
+}
+
+/// Make sure `dir` exists and is empty, clearing it with `force`
+fn prepare_output_dir(dir: &Path, force: bool) -> Result<()> {
+    if dir.exists() {
+        if !dir.is_dir() {
+            return Err(CargoJamError::InvalidArgument {
+                arg: "--out".to_string(),
+                reason: format!("'{}' is not a directory", dir.display()),
+            });
+        }
+        if std::fs::read_dir(dir)?.next().is_some() {
+            if !force {
+                return Err(CargoJamError::InvalidArgument {
+                    arg: "--out".to_string(),
+                    reason: format!(
+                        "'{}' is not empty (pass --force to replace its contents)",
+                        dir.display()
+                    ),
+                });
+            }
+            std::fs::remove_dir_all(dir)?;
+        }
+    }
+    std::fs::create_dir_all(dir)?;
+    Ok(())
+}
+
+/// Print a template's metadata and placeholders, in prompt order
+fn info(args: TemplateInfoArgs) -> Result<()> {
+    let mut bundled = BundledTemplates::new();
+    let template_dir = resolve_template(&args.template, &mut bundled)?;
+    let config = TemplateConfig::load_from_dir(&template_dir)?;
+
+    let metadata = &config.template;
+    match metadata.version {
+        Some(ref version) => println!("{} {}", style(&metadata.name).bold(), version),
+        None => println!("{}", style(&metadata.name).bold()),
+    }
+    if let Some(ref description) = metadata.description {
+        println!("{}", description);
+    }
+
+    println!("\n{}", style("Placeholders:").bold());
+    for (key, placeholder) in config.ordered_placeholders() {
+        let kind = match placeholder {
+            Placeholder::String { .. } => "string",
+            Placeholder::Bool { .. } => "bool",
+        };
+        println!(
+            "  {} ({}) {}",
+            style(key).cyan(),
+            kind,
+            placeholder.prompt()
+        );
+
+        match placeholder.default_value() {
+            Some(default) if default.is_empty() => println!("      optional"),
+            Some(default) => println!("      default: {}", default),
+            None => {}
+        }
+        if let Some(choices) = placeholder.choices() {
+            let values: Vec<&str> = choices.iter().map(|c| c.value()).collect();
+            println!("      choices: {}", values.join(", "));
+        }
+        if let Some(pattern) = placeholder.regex() {
+            println!("      pattern: {}", pattern);
+        }
+        if let Some(help) = placeholder.help() {
+            for line in help.lines() {
+                println!("      {}", style(line).dim());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Print or write the JSON Schema for `cargo-polkajam.toml`
+fn schema(args: TemplateSchemaArgs) -> Result<()> {
+    let json = config_schema_json()?;
+
+    if write_output(args.output.as_deref(), &format!("{}\n", json))? {
+        eprintln!(
+            "{} Wrote schema to {}",
+            style(Glyph::Success).green().bold(),
+            style(args.output.unwrap().display()).cyan()
+        );
+    }
+
+    Ok(())
+}
+
+/// Generate every test case of a template into a temp dir and `cargo check` it
+fn test(args: TemplateTestArgs) -> Result<()> {
+    let config = TemplateConfig::load_from_dir(&args.path)?;
+
+    if config.test_cases.is_empty() {
+        println!(
+            "{} {} declares no [[test_case]] entries",
+            style(Glyph::Warning).yellow(),
+            args.path.join("cargo-polkajam.toml").display()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{} Running {} template test cases for '{}'\n",
+        style(Glyph::Arrow).cyan(),
+        config.test_cases.len(),
+        style(&config.template.name).cyan()
+    );
+
+    let mut failed = 0;
+    for (index, case) in config.test_cases.iter().enumerate() {
+        let name = case
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("case-{}", index + 1));
+
+        match run_case(&args, case, &name) {
+            Ok(()) => println!("  {} {}", style(Glyph::Success).green(), name),
+            Err(e) => {
+                failed += 1;
+                println!("  {} {}: {}", style(Glyph::Failure).red(), name, e);
+            }
+        }
+    }
+
+    let total = config.test_cases.len();
+    if failed > 0 {
+        return Err(CargoJamError::Build(format!(
+            "{} of {} template test cases failed",
+            failed, total
+        )));
+    }
+
+    println!(
+        "\n{} All {} template test cases passed",
+        style(Glyph::Success).green().bold(),
+        total
+    );
+
+    Ok(())
+}
+
+/// Generate one test case and check that the result compiles
+fn run_case(args: &TemplateTestArgs, case: &TestCase, name: &str) -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let output_dir = generate_case(&args.path, case, name, temp_dir.path(), false)?;
+
+    if !args.no_check {
+        cargo_check(&output_dir, args.verbose)?;
+    }
+
+    Ok(())
+}
+
+/// Generate one test case into a project directory under `parent`, returning it.
+///
+/// `strict_undefined` errors on undefined variables even if the template doesn't opt in.
+fn generate_case(
+    template_dir: &Path,
+    case: &TestCase,
+    name: &str,
+    parent: &Path,
+    strict_undefined: bool,
+) -> Result<PathBuf> {
+    // Each case gets a fresh config, as conditionals modify it
+    let mut config = TemplateConfig::load_from_dir(template_dir)?;
+
+    for conditional in &case.conditionals {
+        if !config.apply_conditional(conditional) {
+            return Err(CargoJamError::TemplateConfig(format!(
+                "unknown conditional '{}'",
+                conditional
+            )));
+        }
+    }
+
+    let variables = case_variables(&config, case, name)?;
+    let strict_undefined = config.strict_undefined(strict_undefined);
+    let output_dir = parent.join(&variables["project_name"]);
+
+    ProjectGenerator::new(template_dir.to_path_buf(), output_dir.clone(), config)
+        .strict_undefined(strict_undefined)
+        .generate(&variables)?;
+
+    Ok(output_dir)
+}
+
+/// Render a template with undefined variables as errors: once with placeholder
+/// defaults, then once per `[[test_case]]` so conditional files are covered too
+fn validate_template(args: TemplateValidateArgs) -> Result<()> {
+    let mut bundled = BundledTemplates::new();
+    let template_dir = resolve_template(&args.template, &mut bundled)?;
+    let config = TemplateConfig::load_from_dir(&template_dir)?;
+
+    let mut cases = vec![TestCase {
+        name: Some("defaults".to_string()),
+        values: Default::default(),
+        conditionals: Vec::new(),
+    }];
+    cases.extend(config.test_cases.iter().cloned());
+
+    println!(
+        "{} Validating '{}' with {} case(s)\n",
+        style(Glyph::Arrow).cyan(),
+        style(&config.template.name).cyan(),
+        cases.len()
+    );
+
+    let mut failed = 0;
+    for (index, case) in cases.iter().enumerate() {
+        let name = case
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("case-{}", index));
+
+        let temp_dir = tempfile::tempdir()?;
+        match generate_case(&template_dir, case, &name, temp_dir.path(), true) {
+            Ok(_) => println!("  {} {}", style(Glyph::Success).green(), name),
+            Err(e) => {
+                failed += 1;
+                println!("  {} {}: {}", style(Glyph::Failure).red(), name, e);
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(CargoJamError::TemplateRender(format!(
+            "{} of {} cases failed to render",
+            failed,
+            cases.len()
+        )));
+    }
+
+    println!(
+        "\n{} '{}' renders without undefined variables",
+        style(Glyph::Success).green().bold(),
+        args.template
+    );
+
+    Ok(())
+}
+
+/// Variables for a test case: its values, then built-ins and placeholder defaults
+fn case_variables(
+    config: &TemplateConfig,
+    case: &TestCase,
+    name: &str,
+) -> Result<HashMap<String, String>> {
+    let mut variables: HashMap<String, String> = case
+        .values
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+
+    for (key, value) in &variables {
+        if let Some(placeholder) = config.placeholders.get(key) {
+            placeholder.validate_value(key, value)?;
+        }
+    }
+
+    let project_name = variables
+        .entry("project_name".to_string())
+        .or_insert_with(|| format!("template-test-{}", name.to_lowercase().replace(' ', "-")))
+        .clone();
+    variables.insert(
+        "crate_name".to_string(),
+        project_name.replace(['-', '.'], "_"),
+    );
+
+    let builtins = builtin_variables();
+    for (key, placeholder) in &config.placeholders {
+        if !variables.contains_key(key) {
+            if let Some(default) = builtins.get(key).cloned().or(placeholder.default_value()) {
+                variables.insert(key.clone(), default);
+            }
+        }
+    }
+    for (key, value) in builtins {
+        variables.entry(key).or_insert(value);
+    }
+
+    Ok(variables)
+}
+
+/// Generate two templates with the same default values and print how the results differ
+fn diff(args: TemplateDiffArgs) -> Result<()> {
+    let mut bundled_a = BundledTemplates::new();
+    let mut bundled_b = BundledTemplates::new();
+    let template_a = resolve_template(&args.a, &mut bundled_a)?;
+    let template_b = resolve_template(&args.b, &mut bundled_b)?;
+
+    let temp_dir = tempfile::tempdir()?;
+    let output_a = temp_dir.path().join("a");
+    let output_b = temp_dir.path().join("b");
+    generate_with_defaults(&template_a, &output_a)?;
+    generate_with_defaults(&template_b, &output_b)?;
+
+    let mut changes = diff_trees(&output_a, &output_b)?;
+    // The generation manifest only restates the other files' hashes
+    changes.retain(|change| change.path() != MANIFEST_FILE);
+    if changes.is_empty() {
+        println!(
+            "{} '{}' and '{}' generate identical projects",
+            style(Glyph::Success).green().bold(),
+            args.a,
+            args.b
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{} {} file(s) differ between '{}' and '{}':\n",
+        style(Glyph::Arrow).cyan(),
+        changes.len(),
+        style(&args.a).cyan(),
+        style(&args.b).cyan()
+    );
+    for change in &changes {
+        let marker = match change {
+            FileChange::Added(_) => style("A").green(),
+            FileChange::Removed(_) => style("D").red(),
+            FileChange::Modified { .. } => style("M").yellow(),
+        };
+        println!("  {} {}", marker, change.path());
+    }
+
+    if args.name_only {
+        return Ok(());
+    }
+
+    for change in &changes {
+        if let FileChange::Modified { path, diff } = change {
+            println!();
+            match diff {
+                Some(diff) => print!("{}", colorize_diff(diff)),
+                None => println!("Binary file {} differs", path),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// A template directory given as-is, or the bundled template of that name
+fn resolve_template(spec: &str, bundled: &mut BundledTemplates) -> Result<PathBuf> {
+    let path = Path::new(spec);
+    if path.is_dir() {
+        return Ok(path.to_path_buf());
+    }
+    bundled.extract(spec)
+}
+
+/// Generate a template with its placeholder defaults into `output_dir`
+fn generate_with_defaults(template_dir: &Path, output_dir: &Path) -> Result<()> {
+    let config = TemplateConfig::load_from_dir(template_dir)?;
+    let case = TestCase {
+        name: None,
+        values: [("project_name".to_string(), DIFF_PROJECT_NAME.to_string())]
+            .into_iter()
+            .collect(),
+        conditionals: Vec::new(),
+    };
+    let variables = case_variables(&config, &case, DIFF_PROJECT_NAME)?;
+    let strict_undefined = config.strict_undefined(false);
+    ProjectGenerator::new(template_dir.to_path_buf(), output_dir.to_path_buf(), config)
+        .strict_undefined(strict_undefined)
+        .generate(&variables)?;
+    Ok(())
+}
+
+/// Color a unified diff's headers, hunks, additions and removals
+fn colorize_diff(diff: &str) -> String {
+    diff.lines()
+        .map(|line| {
+            let styled = if line.starts_with("+++") || line.starts_with("---") {
+                style(line).bold()
+            } else if line.starts_with("@@") {
+                style(line).cyan()
+            } else if line.starts_with('+') {
+                style(line).green()
+            } else if line.starts_with('-') {
+                style(line).red()
+            } else {
+                style(line)
+            };
+            format!("{}\n", styled)
+        })
+        .collect()
+}
+
+/// Run `cargo check` in a generated project
+fn cargo_check(project_dir: &Path, verbose: bool) -> Result<()> {
+    let output = Command::new("cargo")
+        .arg("check")
+        .arg("--quiet")
+        .current_dir(project_dir)
+        .output()
+        .map_err(|e| CargoJamError::Build(format!("Failed to execute cargo check: {}", e)))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let detail = if verbose {
+            format!(":\n{}", stderr)
+        } else {
+            " (rerun with --verbose for details)".to_string()
+        };
+        return Err(CargoJamError::Build(format!(
+            "cargo check failed{}",
+            detail
+        )));
+    }
+
+    Ok(())
+}
+
+/// Validate template arguments
+pub fn validate(args: &TemplateArgs) -> Result<()> {
+    match &args.command {
+        TemplateCommand::Schema(_) | TemplateCommand::List(_) => Ok(()),
+        TemplateCommand::Test(test_args) => {
+            TemplateConfig::load_from_dir(&test_args.path)?;
+            Ok(())
+        }
+        TemplateCommand::Diff(diff_args) => {
+            for spec in [&diff_args.a, &diff_args.b] {
+                check_template_spec(spec)?;
+            }
+            Ok(())
+        }
+        TemplateCommand::Info(info_args) => check_template_spec(&info_args.template),
+        TemplateCommand::Validate(validate_args) => check_template_spec(&validate_args.template),
+        TemplateCommand::RenderAll(render_args) => {
+            check_template_spec(&render_args.template)?;
+            if !render_args.values.is_file() {
+                return Err(CargoJamError::InvalidArgument {
+                    arg: "--values".to_string(),
+                    reason: format!("'{}' does not exist", render_args.values.display()),
+                });
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Check that a template is a directory or the name of a bundled template
+fn check_template_spec(spec: &str) -> Result<()> {
+    if !Path::new(spec).is_dir() && !BundledTemplates::new().list().contains(&spec.to_string()) {
+        return Err(CargoJamError::TemplateNotFound(spec.to_string()));
+    }
+    Ok(())
+}
@@ -1,189 +1,428 @@
-use crate::cli::args::TestArgs;
+use crate::cli::args::{TestArgs, TestPhase};
+use crate::cli::commands::deploy::service_id_from_output;
+use crate::cli::commands::up::{default_rpc_url, ensure_not_running, DEFAULT_RPC_URL};
+use crate::cli::output::Glyph;
+use crate::cli::validate::check_rpc_reachable;
 use crate::error::{CargoJamError, Result};
+use crate::process::{output_with_timeout, resolve_timeout};
 use crate::toolchain::config::ToolchainConfig;
+use crate::toolchain::platform::Platform;
+use crate::toolchain::prune::format_size;
+use crate::toolchain::testnet::{is_process_running, recorded_pid};
 use console::style;
+use serde::Serialize;
 use std::fs;
+use std::io::Write;
 use std::path::PathBuf;
 use std::process::Command;
 use std::time::{Duration, Instant};
 
 const TEST_SERVICE_NAME: &str = "jam-test-service";
 
+/// Whether a phase runs, and why
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PhaseRun {
+    /// Requested (every phase is when there is no `--filter`)
+    Selected,
+    /// Not requested, but a requested phase needs its output
+    Dependency,
+    /// Not requested and not needed
+    Skipped,
+}
+
+impl PhaseRun {
+    fn runs(self) -> bool {
+        self != PhaseRun::Skipped
+    }
+}
+
+/// Outcome of one phase in the `--summary` report
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum StepStatus {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+/// A phase's result, duration and the artifacts it produced
+#[derive(Debug, Serialize)]
+struct StepReport {
+    phase: TestPhase,
+    status: StepStatus,
+    duration_secs: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    blob_size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    service_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl StepReport {
+    fn finish(
+        phase: TestPhase,
+        started: Instant,
+        outcome: std::result::Result<(), String>,
+    ) -> Self {
+        let (status, error) = match outcome {
+            Ok(()) => (StepStatus::Passed, None),
+            Err(e) => (StepStatus::Failed, Some(e)),
+        };
+        Self {
+            phase,
+            status,
+            duration_secs: started.elapsed().as_secs_f64(),
+            blob_size: None,
+            service_id: None,
+            error,
+        }
+    }
+
+    fn skipped(phase: TestPhase) -> Self {
+        Self {
+            phase,
+            status: StepStatus::Skipped,
+            duration_secs: 0.0,
+            blob_size: None,
+            service_id: None,
+            error: None,
+        }
+    }
+}
+
+/// The run's results and the environment they were produced in, for `--summary` and `--json`
+#[derive(Debug, Serialize)]
+struct TestSummary {
+    toolchain: Option<String>,
+    platform: String,
+    duration_secs: f64,
+    passed: usize,
+    failed: usize,
+    steps: Vec<StepReport>,
+}
+
+impl TestSummary {
+    fn new(steps: Vec<StepReport>, elapsed: Duration, toolchain: Option<String>) -> Self {
+        let count = |status| steps.iter().filter(|s| s.status == status).count();
+        Self {
+            toolchain,
+            platform: Platform::detect().map_or_else(
+                |_| format!("{}-{}", std::env::consts::OS, std::env::consts::ARCH),
+                |p| p.to_string(),
+            ),
+            duration_secs: elapsed.as_secs_f64(),
+            passed: count(StepStatus::Passed),
+            failed: count(StepStatus::Failed),
+            steps,
+        }
+    }
+
+    /// Print one row per phase, then the environment
+    fn print_table(&self, out: &mut dyn Write) -> Result<()> {
+        writeln!(
+            out,
+            "  {:<8} {:<8} {:>8}  {}",
+            style("Phase").bold(),
+            style("Result").bold(),
+            style("Time").bold(),
+            style("Details").bold()
+        )?;
+        for step in &self.steps {
+            let status = match step.status {
+                StepStatus::Passed => style("passed").green(),
+                StepStatus::Failed => style("failed").red(),
+                StepStatus::Skipped => style("skipped").dim(),
+            };
+            let details = [
+                step.blob_size
+                    .map(|size| format!("blob {}", format_size(size))),
+                step.service_id.as_ref().map(|id| format!("service {}", id)),
+            ]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(", ");
+            writeln!(
+                out,
+                "  {:<8} {:<8} {:>7.1}s  {}",
+                format!("{:?}", step.phase).to_lowercase(),
+                status,
+                step.duration_secs,
+                details
+            )?;
+        }
+        writeln!(
+            out,
+            "\n  Toolchain: {}, platform: {}",
+            self.toolchain.as_deref().unwrap_or("(none installed)"),
+            self.platform
+        )?;
+        Ok(())
+    }
+}
+
+/// Decide which of create, build and deploy run.
+///
+/// Phases outside a non-empty `filter` still run when a later selected phase
+/// needs their output and it isn't already in the test directory.
+fn plan_phases(filter: &[TestPhase], has_service: bool, has_blob: bool) -> [PhaseRun; 3] {
+    let pick = |phase: TestPhase, needed: bool| {
+        if filter.is_empty() || filter.contains(&phase) {
+            PhaseRun::Selected
+        } else if needed {
+            PhaseRun::Dependency
+        } else {
+            PhaseRun::Skipped
+        }
+    };
+
+    // Creating the service afresh discards any previously built blob
+    let recreated = filter.is_empty() || filter.contains(&TestPhase::Create);
+    let deploy = pick(TestPhase::Deploy, false);
+    let build = pick(TestPhase::Build, deploy.runs() && (recreated || !has_blob));
+    let create = pick(TestPhase::Create, build.runs() && !has_service);
+    [create, build, deploy]
+}
+
 pub fn execute(args: TestArgs) -> Result<()> {
-    println!(
+    // stdout is reserved for the summary with --json
+    let mut out: Box<dyn Write> = match args.json {
+        true => Box::new(std::io::stderr()),
+        false => Box::new(std::io::stdout()),
+    };
+    let out: &mut dyn Write = &mut out;
+
+    writeln!(
+        out,
         "\n{} Running cargo-polkajam end-to-end tests\n",
-        style("🧪").bold()
-    );
+        style(Glyph::Test).bold()
+    )?;
 
-    // Check toolchain is installed
-    let config = ToolchainConfig::load()?;
-    if !config.is_installed() {
-        return Err(CargoJamError::ToolchainMissing {
-            tool: "JAM toolchain".to_string(),
-            install_hint: "Run 'cargo polkajam setup' to install the JAM toolchain".to_string(),
-        });
-    }
+    validate(&args)?;
+    let timeout = resolve_timeout(args.timeout)?;
 
     // Create test directory
     let test_dir = args
         .dir
         .clone()
         .unwrap_or_else(|| std::env::temp_dir().join("cargo-polkajam-test"));
+    let service_dir = test_dir.join(TEST_SERVICE_NAME);
+    let jam_file = service_dir.join(format!("{}.jam", TEST_SERVICE_NAME));
+
+    let [create, build, deploy] = plan_phases(
+        &args.filter,
+        service_dir.join("Cargo.toml").exists(),
+        jam_file.exists(),
+    );
 
     // Clean up previous test if exists
-    if test_dir.exists() {
+    if create.runs() && test_dir.exists() {
         fs::remove_dir_all(&test_dir)?;
     }
     fs::create_dir_all(&test_dir)?;
 
-    let service_dir = test_dir.join(TEST_SERVICE_NAME);
-
     // Track test results
-    let mut passed = 0;
-    let mut failed = 0;
+    let mut steps = Vec::new();
     let start_time = Instant::now();
 
     // Test 1: Create new service
-    print_test_header("1", "Create new JAM service");
-    match run_cargo_jam(
-        &["new", TEST_SERVICE_NAME, "--defaults"],
-        Some(&test_dir),
-        args.verbose,
-    ) {
-        Ok(output) => {
-            if service_dir.exists() && service_dir.join("Cargo.toml").exists() {
-                print_test_pass("Service created successfully");
-                if args.verbose {
-                    println!("{}", output);
+    print_phase_header(out, "1", "Create new JAM service", create)?;
+    if create.runs() {
+        let started = Instant::now();
+        let outcome = match run_cargo_jam(
+            out,
+            &["new", TEST_SERVICE_NAME, "--defaults"],
+            Some(&test_dir),
+            args.verbose,
+            timeout,
+        ) {
+            Ok(output) => {
+                if service_dir.exists() && service_dir.join("Cargo.toml").exists() {
+                    print_test_pass(out, "Service created successfully")?;
+                    if args.verbose {
+                        writeln!(out, "{}", output)?;
+                    }
+                    Ok(())
+                } else {
+                    Err("Service directory not created".to_string())
                 }
-                passed += 1;
-            } else {
-                print_test_fail("Service directory not created");
-                failed += 1;
             }
+            Err(e) => Err(format!("Failed to create service: {}", e)),
+        };
+        if let Err(ref e) = outcome {
+            print_test_fail(out, e)?;
         }
-        Err(e) => {
-            print_test_fail(&format!("Failed to create service: {}", e));
-            failed += 1;
-        }
+        steps.push(StepReport::finish(TestPhase::Create, started, outcome));
+    } else {
+        steps.push(StepReport::skipped(TestPhase::Create));
     }
 
     // Test 2: Build service
-    print_test_header("2", "Build JAM service");
-    let jam_file = service_dir.join(format!("{}.jam", TEST_SERVICE_NAME));
-    match run_cargo_jam(&["build"], Some(&service_dir), args.verbose) {
-        Ok(output) => {
-            if jam_file.exists() {
-                let size = fs::metadata(&jam_file).map(|m| m.len()).unwrap_or(0);
-                print_test_pass(&format!("Built {} ({} bytes)", jam_file.display(), size));
-                if args.verbose {
-                    println!("{}", output);
+    print_phase_header(out, "2", "Build JAM service", build)?;
+    if build.runs() {
+        let started = Instant::now();
+        let mut blob_size = None;
+        let outcome =
+            match run_cargo_jam(out, &["build"], Some(&service_dir), args.verbose, timeout) {
+                Ok(output) => {
+                    if jam_file.exists() {
+                        let size = fs::metadata(&jam_file).map(|m| m.len()).unwrap_or(0);
+                        blob_size = Some(size);
+                        print_test_pass(
+                            out,
+                            &format!("Built {} ({} bytes)", jam_file.display(), size),
+                        )?;
+                        if args.verbose {
+                            writeln!(out, "{}", output)?;
+                        }
+                        Ok(())
+                    } else {
+                        print_test_fail(out, "JAM blob not created")?;
+                        writeln!(out, "Expected: {}", jam_file.display())?;
+                        Err("JAM blob not created".to_string())
+                    }
                 }
-                passed += 1;
-            } else {
-                print_test_fail("JAM blob not created");
-                println!("Expected: {}", jam_file.display());
-                failed += 1;
-            }
-        }
-        Err(e) => {
-            print_test_fail(&format!("Failed to build: {}", e));
-            failed += 1;
-        }
+                Err(e) => {
+                    let e = format!("Failed to build: {}", e);
+                    print_test_fail(out, &e)?;
+                    Err(e)
+                }
+            };
+        let mut step = StepReport::finish(TestPhase::Build, started, outcome);
+        step.blob_size = blob_size;
+        steps.push(step);
+    } else {
+        steps.push(StepReport::skipped(TestPhase::Build));
     }
 
     // Test 3: Deploy to local testnet (start, deploy, stop)
-    if !args.skip_testnet {
-        print_test_header("3", "Deploy to local testnet");
+    if !deploy.runs() {
+        print_phase_header(out, "3", "Deploy to local testnet", deploy)?;
+        steps.push(StepReport::skipped(TestPhase::Deploy));
+    } else if !args.skip_testnet {
+        print_test_header(out, "3", "Deploy to local testnet")?;
 
+        let deploy_started = Instant::now();
         let mut test3_passed = true;
+        let mut failure = None;
+        let mut service_id = None;
         let mut testnet_started = false;
 
         // Step 1: Start testnet
-        println!("  {} Starting testnet...", style("→").cyan());
-        match run_cargo_jam(&["up"], None, args.verbose) {
+        writeln!(out, "  {} Starting testnet...", style(Glyph::Arrow).cyan())?;
+        let started = ToolchainConfig::home_dir()
+            .and_then(|home| ensure_not_running(&home, DEFAULT_RPC_URL))
+            .and_then(|()| run_cargo_jam(out, &["up"], None, args.verbose, timeout));
+        match started {
             Ok(output) => {
-                println!("  {} Testnet started", style("✓").green());
+                writeln!(out, "  {} Testnet started", style(Glyph::Success).green())?;
                 if args.verbose {
-                    println!("{}", output);
+                    writeln!(out, "{}", output)?;
                 }
                 testnet_started = true;
 
                 // Verify process is actually running after a moment
                 std::thread::sleep(Duration::from_secs(2));
                 if !is_testnet_process_running() {
-                    print_test_fail("Testnet process died immediately after starting");
-                    println!(
+                    print_test_fail(out, "Testnet process died immediately after starting")?;
+                    failure = Some("Testnet process died immediately after starting".to_string());
+                    writeln!(
+                        out,
                         "    {} The testnet may have crashed. Try running manually:",
                         style("!").yellow()
-                    );
-                    println!("    {} cargo polkajam up --foreground", style("$").dim());
+                    )?;
+                    writeln!(
+                        out,
+                        "    {} cargo polkajam up --foreground",
+                        style("$").dim()
+                    )?;
                     test3_passed = false;
                     testnet_started = false;
                 }
             }
+            Err(CargoJamError::TestnetAlreadyRunning { pid, .. }) => {
+                writeln!(
+                    out,
+                    "  {} Testnet already running (PID: {})",
+                    style(Glyph::Success).green(),
+                    pid
+                )?;
+            }
             Err(e) => {
-                if e.to_string().contains("already running") {
-                    println!("  {} Testnet already running", style("✓").green());
-                } else {
-                    print_test_fail(&format!("Failed to start testnet: {}", e));
-                    test3_passed = false;
-                }
+                let e = format!("Failed to start testnet: {}", e);
+                print_test_fail(out, &e)?;
+                failure = Some(e);
+                test3_passed = false;
             }
         }
 
         // Step 2: Wait for testnet to initialize
         if test3_passed {
-            println!(
+            writeln!(
+                out,
                 "  {} Waiting for testnet to initialize...",
-                style("→").cyan()
-            );
+                style(Glyph::Arrow).cyan()
+            )?;
             // Give testnet time to start up (longer for CI environments)
             std::thread::sleep(Duration::from_secs(10));
         }
 
         // Step 3: Deploy service (with retries for connection issues)
         if test3_passed {
-            println!("  {} Deploying service...", style("→").cyan());
+            writeln!(out, "  {} Deploying service...", style(Glyph::Arrow).cyan())?;
 
             let max_retries = 3;
             let mut deploy_success = false;
 
             for attempt in 1..=max_retries {
-                match run_cargo_jam(&["deploy", jam_file.to_str().unwrap()], None, args.verbose) {
+                let deployed = check_rpc_reachable(&default_rpc_url()).and_then(|()| {
+                    run_cargo_jam(
+                        out,
+                        &["deploy", jam_file.to_str().unwrap()],
+                        None,
+                        args.verbose,
+                        timeout,
+                    )
+                });
+                match deployed {
                     Ok(output) => {
                         if output.contains("deployed successfully")
                             || output.contains("created at slot")
                         {
-                            println!("  {} Service deployed", style("✓").green());
+                            writeln!(out, "  {} Service deployed", style(Glyph::Success).green())?;
+                            service_id = service_id_from_output(&output);
                             if let Some(line) = output
                                 .lines()
                                 .find(|l| l.contains("Service") && l.contains("created"))
                             {
-                                println!("    {}", style(line.trim()).dim());
+                                writeln!(out, "    {}", style(line.trim()).dim())?;
                             }
                             deploy_success = true;
                             break;
                         } else {
-                            print_test_fail("Deploy succeeded but output unexpected");
-                            println!("{}", output);
+                            print_test_fail(out, "Deploy succeeded but output unexpected")?;
+                            writeln!(out, "{}", output)?;
+                            failure = Some("Deploy succeeded but output unexpected".to_string());
                             break;
                         }
                     }
+                    Err(CargoJamError::RpcUnreachable { reason, .. }) if attempt < max_retries => {
+                        writeln!(
+                            out,
+                            "    {} RPC not reachable yet ({}), retrying ({}/{})",
+                            style("!").yellow(),
+                            reason,
+                            attempt,
+                            max_retries
+                        )?;
+                        std::thread::sleep(Duration::from_secs(5));
+                    }
                     Err(e) => {
-                        let err_str = e.to_string();
-                        if err_str.contains("Connection refused") && attempt < max_retries {
-                            println!(
-                                "    {} Connection refused, retrying ({}/{})",
-                                style("!").yellow(),
-                                attempt,
-                                max_retries
-                            );
-                            std::thread::sleep(Duration::from_secs(5));
-                        } else {
-                            print_test_fail(&format!("Failed to deploy: {}", e));
-                            break;
-                        }
+                        let e = format!("Failed to deploy: {}", e);
+                        print_test_fail(out, &e)?;
+                        failure = Some(e);
+                        break;
                     }
                 }
             }
@@ -195,53 +434,79 @@ pub fn execute(args: TestArgs) -> Result<()> {
 
         // Step 4: Stop testnet (cleanup)
         if testnet_started && !args.keep_running {
-            println!("  {} Stopping testnet...", style("→").cyan());
-            match run_cargo_jam(&["down"], None, args.verbose) {
+            writeln!(out, "  {} Stopping testnet...", style(Glyph::Arrow).cyan())?;
+            match run_cargo_jam(out, &["down"], None, args.verbose, timeout) {
                 Ok(_) => {
-                    println!("  {} Testnet stopped", style("✓").green());
+                    writeln!(out, "  {} Testnet stopped", style(Glyph::Success).green())?;
                 }
                 Err(e) => {
-                    println!("  {} Failed to stop testnet: {}", style("!").yellow(), e);
+                    writeln!(
+                        out,
+                        "  {} Failed to stop testnet: {}",
+                        style("!").yellow(),
+                        e
+                    )?;
                 }
             }
         } else if args.keep_running {
-            println!(
+            writeln!(
+                out,
                 "  {} Testnet left running (--keep-running)",
-                style("→").cyan()
-            );
+                style(Glyph::Arrow).cyan()
+            )?;
         }
 
-        if test3_passed {
-            print_test_pass("Deployment complete");
-            passed += 1;
+        let outcome = if test3_passed {
+            print_test_pass(out, "Deployment complete")?;
+            Ok(())
         } else {
-            failed += 1;
-        }
+            Err(failure.unwrap_or_else(|| "Deployment failed".to_string()))
+        };
+        let mut step = StepReport::finish(TestPhase::Deploy, deploy_started, outcome);
+        step.service_id = service_id;
+        steps.push(step);
     } else {
-        print_test_header("3", "Deploy to local testnet (skipped)");
-        println!("  {} Skipped (--skip-testnet)", style("→").cyan());
+        print_test_header(out, "3", "Deploy to local testnet (skipped)")?;
+        writeln!(
+            out,
+            "  {} Skipped (--skip-testnet)",
+            style(Glyph::Arrow).cyan()
+        )?;
+        steps.push(StepReport::skipped(TestPhase::Deploy));
     }
 
-    // Clean up test directory
-    if !args.verbose {
+    // Clean up test directory, keeping it for later filtered runs to reuse
+    if !args.verbose && args.filter.is_empty() {
         let _ = fs::remove_dir_all(&test_dir);
     } else {
-        println!(
+        writeln!(
+            out,
             "\n  {} Test artifacts at: {}",
-            style("→").cyan(),
+            style(Glyph::Arrow).cyan(),
             test_dir.display()
-        );
+        )?;
     }
 
     // Print summary
-    let elapsed = start_time.elapsed();
-    println!("\n{}", style("─".repeat(50)).dim());
-    println!(
+    let config = ToolchainConfig::load()?;
+    let toolchain = config
+        .is_installed()
+        .then(|| config.installed_version.clone())
+        .flatten();
+    let summary = TestSummary::new(steps, start_time.elapsed(), toolchain);
+    let (passed, failed) = (summary.passed, summary.failed);
+    writeln!(out, "\n{}", style(Glyph::Rule.as_str().repeat(50)).dim())?;
+    if args.summary {
+        writeln!(out)?;
+        summary.print_table(out)?;
+    }
+    writeln!(
+        out,
         "\n{} Test Results: {} passed, {} failed (in {:.1}s)\n",
         if failed == 0 {
-            style("✓").green().bold()
+            style(Glyph::Success).green().bold()
         } else {
-            style("✗").red().bold()
+            style(Glyph::Failure).red().bold()
         },
         style(passed).green(),
         if failed > 0 {
@@ -249,8 +514,25 @@ pub fn execute(args: TestArgs) -> Result<()> {
         } else {
             style(failed).dim()
         },
-        elapsed.as_secs_f32()
-    );
+        summary.duration_secs
+    )?;
+
+    if args.json || args.summary_file.is_some() {
+        let json = serde_json::to_string_pretty(&summary)
+            .map_err(|e| std::io::Error::other(format!("Failed to serialize summary: {}", e)))?;
+        if args.json {
+            println!("{}", json);
+        }
+        if let Some(ref path) = args.summary_file {
+            fs::write(path, format!("{}\n", json))?;
+            writeln!(
+                out,
+                "{} Wrote summary: {}",
+                style(Glyph::Arrow).cyan(),
+                style(path.display()).cyan()
+            )?;
+        }
+    }
 
     if failed > 0 {
         return Err(CargoJamError::Build(format!("{} test(s) failed", failed)));
@@ -259,7 +541,27 @@ pub fn execute(args: TestArgs) -> Result<()> {
     Ok(())
 }
 
-fn run_cargo_jam(args: &[&str], cwd: Option<&PathBuf>, verbose: bool) -> Result<String> {
+/// Validate test prerequisites without running the tests
+pub fn validate(_args: &TestArgs) -> Result<()> {
+    // Check toolchain is installed
+    let config = ToolchainConfig::load()?;
+    if !config.is_installed() {
+        return Err(CargoJamError::ToolchainMissing {
+            tool: "JAM toolchain".to_string(),
+            install_hint: "Run 'cargo polkajam setup' to install the JAM toolchain".to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+fn run_cargo_jam(
+    out: &mut dyn Write,
+    args: &[&str],
+    cwd: Option<&PathBuf>,
+    verbose: bool,
+    timeout: Option<Duration>,
+) -> Result<String> {
     let cargo_polkajam = std::env::current_exe()
         .ok()
         .and_then(|p| p.parent().map(|p| p.join("cargo-polkajam")))
@@ -269,22 +571,31 @@ fn run_cargo_jam(args: &[&str], cwd: Option<&PathBuf>, verbose: bool) -> Result<
     cmd.arg("polkajam");
     cmd.args(args);
 
+    // Child commands must see the same config file
+    if let Some(config) = ToolchainConfig::config_path_override() {
+        cmd.env("CARGO_JAM_CONFIG", config);
+    }
+
     if let Some(dir) = cwd {
         cmd.current_dir(dir);
     }
 
     if verbose {
-        println!(
+        writeln!(
+            out,
             "  {} {:?} polkajam {}",
             style("$").dim(),
             cargo_polkajam,
             args.join(" ")
-        );
+        )?;
     }
 
-    let output = cmd
-        .output()
-        .map_err(|e| CargoJamError::Build(format!("Failed to execute cargo-polkajam: {}", e)))?;
+    let output = output_with_timeout(&mut cmd, timeout).map_err(|e| match e {
+        CargoJamError::Io(e) => {
+            CargoJamError::Build(format!("Failed to execute cargo-polkajam: {}", e))
+        }
+        e => e,
+    })?;
 
     let stdout = String::from_utf8_lossy(&output.stdout).to_string();
     let stderr = String::from_utf8_lossy(&output.stderr).to_string();
@@ -299,61 +610,117 @@ fn run_cargo_jam(args: &[&str], cwd: Option<&PathBuf>, verbose: bool) -> Result<
     Ok(format!("{}{}", stdout, stderr))
 }
 
-fn print_test_header(num: &str, name: &str) {
-    println!(
+fn print_test_header(out: &mut dyn Write, num: &str, name: &str) -> Result<()> {
+    writeln!(
+        out,
         "\n{} Test {}: {}",
-        style("▶").cyan(),
+        style(Glyph::Play).cyan(),
         style(num).bold(),
         name
-    );
+    )?;
+    Ok(())
+}
+
+/// Print a phase's header, noting when it runs only as a dependency or is skipped
+fn print_phase_header(out: &mut dyn Write, num: &str, name: &str, run: PhaseRun) -> Result<()> {
+    match run {
+        PhaseRun::Selected => print_test_header(out, num, name),
+        PhaseRun::Dependency => {
+            print_test_header(out, num, &format!("{} (needed by a selected phase)", name))
+        }
+        PhaseRun::Skipped => {
+            print_test_header(out, num, &format!("{} (skipped)", name))?;
+            writeln!(
+                out,
+                "  {} Skipped (not selected by --filter)",
+                style(Glyph::Arrow).cyan()
+            )?;
+            Ok(())
+        }
+    }
 }
 
-fn print_test_pass(msg: &str) {
-    println!("  {} {}", style("✓").green().bold(), msg);
+fn print_test_pass(out: &mut dyn Write, msg: &str) -> Result<()> {
+    writeln!(out, "  {} {}", style(Glyph::Success).green().bold(), msg)?;
+    Ok(())
 }
 
-fn print_test_fail(msg: &str) {
-    println!("  {} {}", style("✗").red().bold(), msg);
+fn print_test_fail(out: &mut dyn Write, msg: &str) -> Result<()> {
+    writeln!(out, "  {} {}", style(Glyph::Failure).red().bold(), msg)?;
+    Ok(())
 }
 
 /// Check if the testnet process is running by reading the PID file
 fn is_testnet_process_running() -> bool {
-    let home_dir = match dirs::home_dir() {
-        Some(h) => h,
-        None => return false,
-    };
-
-    let pid_file = home_dir.join(".cargo-polkajam").join("testnet.pid");
-    if !pid_file.exists() {
-        return false;
-    }
-
-    let pid_str = match fs::read_to_string(&pid_file) {
-        Ok(s) => s,
-        Err(_) => return false,
-    };
-
-    let pid: i32 = match pid_str.trim().parse() {
-        Ok(p) => p,
-        Err(_) => return false,
-    };
+    ToolchainConfig::home_dir()
+        .ok()
+        .and_then(|home| recorded_pid(&home))
+        .is_some_and(is_process_running)
+}
 
-    // Check if process is running
-    #[cfg(unix)]
-    {
-        Command::new("kill")
-            .args(["-0", &pid.to_string()])
-            .output()
-            .map(|o| o.status.success())
-            .unwrap_or(false)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_phases() {
+        use PhaseRun::*;
+        use TestPhase::*;
+
+        // (filter, has_service, has_blob)
+        let cases: [(&[TestPhase], bool, bool, [PhaseRun; 3]); 7] = [
+            (&[], false, false, [Selected, Selected, Selected]),
+            (&[], true, true, [Selected, Selected, Selected]),
+            (&[Build], true, true, [Skipped, Selected, Skipped]),
+            (&[Build], false, false, [Dependency, Selected, Skipped]),
+            (&[Deploy], true, true, [Skipped, Skipped, Selected]),
+            (&[Deploy], false, false, [Dependency, Dependency, Selected]),
+            (
+                &[Create, Deploy],
+                true,
+                true,
+                [Selected, Dependency, Selected],
+            ),
+        ];
+
+        for (filter, has_service, has_blob, expected) in cases {
+            assert_eq!(
+                plan_phases(filter, has_service, has_blob),
+                expected,
+                "filter={:?} has_service={} has_blob={}",
+                filter,
+                has_service,
+                has_blob
+            );
+        }
     }
 
-    #[cfg(windows)]
-    {
-        Command::new("tasklist")
-            .args(["/FI", &format!("PID eq {}", pid)])
-            .output()
-            .map(|o| String::from_utf8_lossy(&o.stdout).contains(&pid.to_string()))
-            .unwrap_or(false)
+    #[test]
+    fn test_summary_json() {
+        let mut build = StepReport::finish(TestPhase::Build, Instant::now(), Ok(()));
+        build.blob_size = Some(10_240);
+        let steps = vec![
+            StepReport::skipped(TestPhase::Create),
+            build,
+            StepReport::finish(
+                TestPhase::Deploy,
+                Instant::now(),
+                Err("Failed to deploy".to_string()),
+            ),
+        ];
+        let summary = TestSummary::new(
+            steps,
+            Duration::from_secs(3),
+            Some("nightly-2025-12-29".to_string()),
+        );
+        assert_eq!((summary.passed, summary.failed), (1, 1));
+
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["toolchain"], "nightly-2025-12-29");
+        assert_eq!(json["steps"][0]["status"], "skipped");
+        assert_eq!(json["steps"][1]["phase"], "build");
+        assert_eq!(json["steps"][1]["blob_size"], 10_240);
+        assert_eq!(json["steps"][2]["error"], "Failed to deploy");
+        assert!(json["steps"][2].get("service_id").is_none());
     }
 }
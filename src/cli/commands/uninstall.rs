@@ -0,0 +1,82 @@
+use crate::cli::args::UninstallArgs;
+use crate::cli::output::Glyph;
+use crate::error::Result;
+use crate::toolchain::checksums::{CHECKSUM_FILE, INSTALL_DIR};
+use crate::toolchain::config::ToolchainConfig;
+use crate::toolchain::lock::ToolchainLock;
+use crate::toolchain::prune::{dir_size, format_size};
+use console::style;
+use std::path::Path;
+
+pub fn execute(args: UninstallArgs) -> Result<()> {
+    let removed = remove_toolchain()?;
+
+    if args.purge {
+        let home_dir = ToolchainConfig::home_dir()?;
+        if home_dir.exists() {
+            let size = dir_size(&home_dir);
+            std::fs::remove_dir_all(&home_dir)?;
+            println!(
+                "{} Removed {} ({}), including config.toml, dev accounts, cached archives and testnet.pid",
+                style(Glyph::Success).green().bold(),
+                style(home_dir.display()).yellow(),
+                format_size(size)
+            );
+            return Ok(());
+        }
+    }
+
+    if !removed {
+        eprintln!(
+            "{} No toolchain is installed, nothing to remove",
+            style("!").yellow().bold()
+        );
+    }
+    Ok(())
+}
+
+/// Delete the installed toolchain and clear it from the config, holding the toolchain
+/// lock so a concurrent `setup` can't interleave. Returns whether anything was installed.
+fn remove_toolchain() -> Result<bool> {
+    let lock = ToolchainLock::acquire()?;
+    let config = ToolchainConfig::load()?;
+    let toolchain_dir = match config.toolchain_path {
+        Some(ref path) => path.clone(),
+        None => ToolchainConfig::toolchain_dir()?,
+    };
+    let install_dir = toolchain_dir.join(INSTALL_DIR);
+    if !install_dir.exists() && config.installed_version.is_none() {
+        return Ok(false);
+    }
+
+    if install_dir.exists() {
+        let size = dir_size(&install_dir);
+        std::fs::remove_dir_all(&install_dir)?;
+        println!(
+            "{} Removed {} ({})",
+            style(Glyph::Success).green().bold(),
+            style(install_dir.display()).yellow(),
+            format_size(size)
+        );
+    }
+    // The recorded checksums describe the files just removed
+    remove_if_exists(&toolchain_dir.join(CHECKSUM_FILE))?;
+
+    lock.update_config(|config| config.clear_installed())?;
+    if let Some(version) = config.installed_version {
+        println!(
+            "{} Cleared toolchain {} from {}",
+            style(Glyph::Success).green().bold(),
+            style(version).cyan(),
+            ToolchainConfig::config_path()?.display()
+        );
+    }
+    Ok(true)
+}
+
+fn remove_if_exists(path: &Path) -> Result<()> {
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
@@ -1,69 +1,94 @@
 use crate::cli::args::UpArgs;
+use crate::cli::output::Glyph;
+use crate::cli::validate::{normalize_rpc_url, rpc_port, with_rpc_port};
 use crate::error::{CargoJamError, Result};
 use crate::toolchain::config::ToolchainConfig;
+use crate::toolchain::testnet::{
+    is_process_running, recorded_pid, TestnetConfig, KNOWN_KEYS, PID_FILE, STOP_FILE,
+};
 use console::style;
+use std::ffi::OsStr;
 use std::fs;
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
+use std::time::Duration;
+use tracing::debug;
 
-const PID_FILE: &str = "testnet.pid";
+/// Endpoint of the running testnet, read by commands taking `--rpc`
+pub(crate) const RPC_FILE: &str = "testnet.rpc";
 
-pub fn execute(args: UpArgs) -> Result<()> {
-    // Check toolchain is installed
-    let config = ToolchainConfig::load()?;
-    if !config.is_installed() {
-        return Err(CargoJamError::ToolchainMissing {
-            tool: "JAM toolchain".to_string(),
-            install_hint: "Run 'cargo polkajam setup' to install the JAM toolchain".to_string(),
-        });
-    }
+/// Endpoint commands use when no testnet has recorded one
+pub const DEFAULT_RPC_URL: &str = "ws://localhost:19800";
 
-    let testnet_bin = ToolchainConfig::binary_path("polkajam-testnet")?.ok_or_else(|| {
-        CargoJamError::ToolchainMissing {
-            tool: "polkajam-testnet".to_string(),
-            install_hint: "Run 'cargo polkajam setup --force' to reinstall the toolchain"
-                .to_string(),
-        }
-    })?;
+/// How many ports past the requested one `--auto-port` tries
+const AUTO_PORT_RANGE: u16 = 100;
+
+/// Log file of a background supervisor
+const SUPERVISOR_LOG_FILE: &str = "testnet-supervisor.log";
+
+/// Delay before restarting a crashed testnet
+const RESTART_DELAY: Duration = Duration::from_secs(1);
+
+pub fn execute(mut args: UpArgs) -> Result<()> {
+    args.rpc = normalize_rpc_url(&args.rpc)?;
+    let testnet_bin = validate(&args)?;
+    let testnet = load_testnet_config(&args)?;
 
-    // Check if already running
     let home_dir = ToolchainConfig::home_dir()?;
     let pid_file = home_dir.join(PID_FILE);
 
-    if pid_file.exists() {
-        let pid_str = fs::read_to_string(&pid_file)?;
-        if let Ok(pid) = pid_str.trim().parse::<i32>() {
-            // Check if process is still running
-            if is_process_running(pid) {
-                println!(
-                    "{} Testnet is already running (PID: {})",
-                    style("→").cyan(),
-                    style(pid).yellow()
-                );
-                println!("  RPC endpoint: {}", style("ws://localhost:19800").green());
-                println!("\n  Stop with: {}", style("cargo polkajam down").cyan());
-                return Ok(());
-            }
+    match ensure_not_running(&home_dir, &args.rpc) {
+        Err(CargoJamError::TestnetAlreadyRunning { pid, rpc }) => {
+            println!(
+                "{} Testnet is already running (PID: {})",
+                style(Glyph::Arrow).cyan(),
+                style(pid).yellow()
+            );
+            println!("  RPC endpoint: {}", style(rpc).green());
+            println!("\n  Stop with: {}", style("cargo polkajam down").cyan());
+            return Ok(());
+        }
+        result => result?,
+    }
+
+    // A stop request left over from an earlier supervisor no longer applies
+    let _ = fs::remove_file(home_dir.join(STOP_FILE));
+
+    args.rpc = select_port(&args)?;
+
+    if args.supervise {
+        if args.foreground {
+            return supervise(&testnet_bin, &args, &testnet, &home_dir);
         }
-        // Stale PID file, remove it
-        fs::remove_file(&pid_file)?;
+        return spawn_supervisor(&args, &home_dir);
     }
 
+    debug!(binary = %testnet_bin.display(), pid_file = %pid_file.display(), "starting testnet");
     if args.foreground {
         // Run in foreground
         println!(
             "{} Starting JAM testnet in foreground...",
-            style("→").cyan()
+            style(Glyph::Arrow).cyan()
         );
         println!("  RPC endpoint: {}", style(&args.rpc).green());
         println!("  Press Ctrl+C to stop\n");
 
-        let status = Command::new(&testnet_bin)
+        let mut child = testnet_command(&testnet_bin, &args.rpc, &testnet)
             .stdout(Stdio::inherit())
             .stderr(Stdio::inherit())
-            .status()
+            .spawn()
             .map_err(|e| CargoJamError::Build(format!("Failed to start testnet: {}", e)))?;
 
-        if !status.success() {
+        // Recorded while it runs, so other commands find an --auto-port endpoint
+        let rpc_file = home_dir.join(RPC_FILE);
+        fs::write(&pid_file, child.id().to_string())?;
+        fs::write(&rpc_file, &args.rpc)?;
+        let status = child.wait();
+        let _ = fs::remove_file(&pid_file);
+        let _ = fs::remove_file(&rpc_file);
+
+        if !status?.success() {
             return Err(CargoJamError::Build(
                 "Testnet exited with error".to_string(),
             ));
@@ -72,10 +97,10 @@ pub fn execute(args: UpArgs) -> Result<()> {
         // Run in background
         println!(
             "{} Starting JAM testnet in background...",
-            style("→").cyan()
+            style(Glyph::Arrow).cyan()
         );
 
-        let child = Command::new(&testnet_bin)
+        let child = testnet_command(&testnet_bin, &args.rpc, &testnet)
             .stdout(Stdio::null())
             .stderr(Stdio::null())
             .spawn()
@@ -83,15 +108,16 @@ pub fn execute(args: UpArgs) -> Result<()> {
 
         let pid = child.id();
 
-        // Save PID to file
+        // Save PID and endpoint for `down` and the commands talking to the testnet
         fs::write(&pid_file, pid.to_string())?;
+        fs::write(home_dir.join(RPC_FILE), &args.rpc)?;
 
         println!(
             "{} Testnet started (PID: {})",
-            style("✓").green().bold(),
+            style(Glyph::Success).green().bold(),
             style(pid).yellow()
         );
-        println!("  RPC endpoint: {}", style("ws://localhost:19800").green());
+        println!("  RPC endpoint: {}", style(&args.rpc).green());
         println!("\n  Stop with: {}", style("cargo polkajam down").cyan());
         println!(
             "  View logs: {}",
@@ -102,22 +128,336 @@ pub fn execute(args: UpArgs) -> Result<()> {
     Ok(())
 }
 
-#[cfg(unix)]
-fn is_process_running(pid: i32) -> bool {
-    use std::process::Command;
-    Command::new("kill")
-        .args(["-0", &pid.to_string()])
-        .output()
-        .map(|o| o.status.success())
-        .unwrap_or(false)
+/// Run the testnet in the foreground, restarting it whenever it exits unexpectedly.
+///
+/// The testnet's PID is kept in the PID file so `down` can stop it; `down`
+/// leaves a stop marker first, which tells the supervisor not to restart.
+fn supervise(
+    testnet_bin: &Path,
+    args: &UpArgs,
+    testnet: &TestnetConfig,
+    home_dir: &Path,
+) -> Result<()> {
+    let pid_file = home_dir.join(PID_FILE);
+    let stop_file = home_dir.join(STOP_FILE);
+    let rpc_file = home_dir.join(RPC_FILE);
+    let mut restarts = 0;
+
+    println!(
+        "{} Starting supervised JAM testnet (max restarts: {})...",
+        style(Glyph::Arrow).cyan(),
+        args.max_restarts
+    );
+    println!("  RPC endpoint: {}", style(&args.rpc).green());
+    println!("  Stop with: {}\n", style("cargo polkajam down").cyan());
+
+    fs::write(&rpc_file, &args.rpc)?;
+
+    loop {
+        let mut child = testnet_command(testnet_bin, &args.rpc, testnet)
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| CargoJamError::Build(format!("Failed to start testnet: {}", e)))?;
+
+        fs::write(&pid_file, child.id().to_string())?;
+
+        let status = child.wait()?;
+
+        if stop_file.exists() {
+            let _ = fs::remove_file(&stop_file);
+            let _ = fs::remove_file(&pid_file);
+            let _ = fs::remove_file(&rpc_file);
+            println!("{} Testnet stopped", style(Glyph::Success).green().bold());
+            return Ok(());
+        }
+
+        if restarts >= args.max_restarts {
+            let _ = fs::remove_file(&pid_file);
+            let _ = fs::remove_file(&rpc_file);
+            return Err(CargoJamError::Build(format!(
+                "Testnet exited ({}) and was restarted {} times, giving up",
+                status, restarts
+            )));
+        }
+
+        restarts += 1;
+        println!(
+            "{} Testnet exited unexpectedly ({}), restarting ({}/{})...",
+            style("!").yellow().bold(),
+            status,
+            restarts,
+            args.max_restarts
+        );
+        std::thread::sleep(RESTART_DELAY);
+    }
+}
+
+/// Start `up --supervise --foreground` as a background process logging to a file
+fn spawn_supervisor(args: &UpArgs, home_dir: &Path) -> Result<()> {
+    println!(
+        "{} Starting supervised JAM testnet in background...",
+        style(Glyph::Arrow).cyan()
+    );
+
+    let log_path = home_dir.join(SUPERVISOR_LOG_FILE);
+    let log = fs::File::create(&log_path)?;
+
+    let mut cmd = Command::new(std::env::current_exe()?);
+    cmd.args(["polkajam", "up", "--foreground", "--supervise"])
+        .arg("--max-restarts")
+        .arg(args.max_restarts.to_string())
+        .arg("--rpc")
+        .arg(&args.rpc)
+        .args(
+            args.testnet_config
+                .iter()
+                .flat_map(|path| [OsStr::new("--testnet-config"), path.as_os_str()]),
+        )
+        .stdin(Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log);
+
+    // The supervisor must see the same config file
+    if let Some(config) = ToolchainConfig::config_path_override() {
+        cmd.env("CARGO_JAM_CONFIG", config);
+    }
+
+    debug!(command = ?cmd, log = %log_path.display(), "spawning supervisor");
+    let child = cmd
+        .spawn()
+        .map_err(|e| CargoJamError::Build(format!("Failed to start supervisor: {}", e)))?;
+
+    println!(
+        "{} Testnet supervisor started (PID: {})",
+        style(Glyph::Success).green().bold(),
+        style(child.id()).yellow()
+    );
+    println!("  RPC endpoint: {}", style(&args.rpc).green());
+    println!("\n  Stop with: {}", style("cargo polkajam down").cyan());
+    println!("  Restart log: {}", style(log_path.display()).dim());
+
+    Ok(())
+}
+
+/// The RPC URL to start the testnet on, moving to the next free port with `--auto-port`
+fn select_port(args: &UpArgs) -> Result<String> {
+    let port = rpc_port(&args.rpc);
+    if port_is_free(port) {
+        return Ok(args.rpc.clone());
+    }
+
+    if !args.auto_port {
+        return Err(CargoJamError::InvalidArgument {
+            arg: "--rpc".to_string(),
+            reason: format!(
+                "port {} is already in use by another process (pass --auto-port to pick a free one)",
+                port
+            ),
+        });
+    }
+
+    let free = (port.saturating_add(1)..=port.saturating_add(AUTO_PORT_RANGE))
+        .find(|&p| port_is_free(p))
+        .ok_or_else(|| CargoJamError::InvalidArgument {
+            arg: "--auto-port".to_string(),
+            reason: format!(
+                "no free port between {} and {}",
+                port,
+                port.saturating_add(AUTO_PORT_RANGE)
+            ),
+        })?;
+
+    let rpc = with_rpc_port(&args.rpc, free);
+    println!(
+        "{} Port {} is in use, using {}",
+        style("!").yellow().bold(),
+        port,
+        style(&rpc).green()
+    );
+    Ok(rpc)
+}
+
+fn port_is_free(port: u16) -> bool {
+    TcpListener::bind(("127.0.0.1", port)).is_ok()
+}
+
+/// Read `--testnet-config`, warning about keys that are ignored
+fn load_testnet_config(args: &UpArgs) -> Result<TestnetConfig> {
+    let Some(ref path) = args.testnet_config else {
+        return Ok(TestnetConfig::default());
+    };
+
+    let config = TestnetConfig::load(path)?;
+    for key in &config.unknown_keys {
+        println!(
+            "{} Ignoring unknown key '{}' in {} (known: {})",
+            style("!").yellow().bold(),
+            key,
+            path.display(),
+            KNOWN_KEYS.join(", ")
+        );
+    }
+    Ok(config)
+}
+
+/// Command running the testnet, told about its RPC port when it isn't the default
+/// and given the settings of `--testnet-config`
+fn testnet_command(testnet_bin: &Path, rpc: &str, testnet: &TestnetConfig) -> Command {
+    let mut cmd = Command::new(testnet_bin);
+    let port = rpc_port(rpc);
+    if port != rpc_port(DEFAULT_RPC_URL) {
+        cmd.arg("--rpc-port").arg(port.to_string());
+    }
+    cmd.args(&testnet.args);
+    debug!(command = ?cmd, "testnet command");
+    cmd
+}
+
+/// Fail with `TestnetAlreadyRunning` if the recorded testnet is alive, removing a stale PID file.
+///
+/// `rpc` is reported when the running testnet didn't record its endpoint.
+pub fn ensure_not_running(home_dir: &Path, rpc: &str) -> Result<()> {
+    let pid_file = home_dir.join(PID_FILE);
+    if !pid_file.exists() {
+        return Ok(());
+    }
+
+    let pid_str = fs::read_to_string(&pid_file)?;
+    if let Ok(pid) = pid_str.trim().parse::<i32>() {
+        if is_process_running(pid) {
+            return Err(CargoJamError::TestnetAlreadyRunning {
+                pid,
+                rpc: recorded_rpc_url(home_dir).unwrap_or_else(|| rpc.to_string()),
+            });
+        }
+    }
+
+    // Stale PID and endpoint files, remove them
+    fs::remove_file(&pid_file)?;
+    let _ = fs::remove_file(home_dir.join(RPC_FILE));
+    Ok(())
+}
+
+/// Endpoint recorded by the running testnet, if any. The file is removed when the
+/// testnet that recorded it is gone, e.g. after a crash or reboot.
+fn recorded_rpc_url(home_dir: &Path) -> Option<String> {
+    let rpc_file = home_dir.join(RPC_FILE);
+    if !recorded_pid(home_dir).is_some_and(is_process_running) {
+        let _ = fs::remove_file(&rpc_file);
+        return None;
+    }
+    fs::read_to_string(&rpc_file)
+        .ok()
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty())
+}
+
+/// Default `--rpc` of the commands talking to the testnet: the endpoint the running
+/// testnet recorded, or `ws://localhost:19800`
+pub fn default_rpc_url() -> String {
+    ToolchainConfig::home_dir()
+        .ok()
+        .and_then(|home| recorded_rpc_url(&home))
+        .unwrap_or_else(|| DEFAULT_RPC_URL.to_string())
+}
+
+/// Resolve a command's `--rpc` when it runs rather than when arguments are parsed:
+/// the given URL, else [`default_rpc_url`], normalized
+pub fn resolve_rpc_url(rpc: Option<&str>) -> Result<String> {
+    match rpc {
+        Some(url) => normalize_rpc_url(url),
+        None => normalize_rpc_url(&default_rpc_url()),
+    }
+}
+
+/// Validate up arguments and prerequisites, returning the testnet binary path
+pub fn validate(args: &UpArgs) -> Result<PathBuf> {
+    // Check toolchain is installed
+    let config = ToolchainConfig::load()?;
+    if !config.is_installed() {
+        return Err(CargoJamError::ToolchainMissing {
+            tool: "JAM toolchain".to_string(),
+            install_hint: "Run 'cargo polkajam setup' to install the JAM toolchain".to_string(),
+        });
+    }
+    // Started from a project, the testnet must match the toolchain it pins
+    config.check_required_version(&std::env::current_dir()?)?;
+
+    let testnet_bin = ToolchainConfig::binary_path("polkajam-testnet")?.ok_or_else(|| {
+        CargoJamError::ToolchainMissing {
+            tool: "polkajam-testnet".to_string(),
+            install_hint: "Run 'cargo polkajam setup --force' to reinstall the toolchain"
+                .to_string(),
+        }
+    })?;
+
+    normalize_rpc_url(&args.rpc)?;
+
+    if let Some(ref path) = args.testnet_config {
+        TestnetConfig::load(path)?;
+    }
+
+    Ok(testnet_bin)
 }
 
-#[cfg(windows)]
-fn is_process_running(pid: i32) -> bool {
-    use std::process::Command;
-    Command::new("tasklist")
-        .args(["/FI", &format!("PID eq {}", pid)])
-        .output()
-        .map(|o| String::from_utf8_lossy(&o.stdout).contains(&pid.to_string()))
-        .unwrap_or(false)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[test]
+    fn test_ensure_not_running() {
+        let home = tempfile::tempdir().unwrap();
+        assert!(ensure_not_running(home.path(), DEFAULT_RPC_URL).is_ok());
+
+        // This test process stands in for a running testnet
+        let pid = std::process::id() as i32;
+        fs::write(home.path().join(PID_FILE), pid.to_string()).unwrap();
+        fs::write(home.path().join(RPC_FILE), "ws://localhost:19801").unwrap();
+        match ensure_not_running(home.path(), DEFAULT_RPC_URL) {
+            Err(CargoJamError::TestnetAlreadyRunning { pid: running, rpc }) => {
+                assert_eq!(running, pid);
+                assert_eq!(rpc, "ws://localhost:19801");
+            }
+            other => panic!("expected TestnetAlreadyRunning, got {:?}", other),
+        }
+
+        fs::write(home.path().join(PID_FILE), i32::MAX.to_string()).unwrap();
+        assert!(ensure_not_running(home.path(), DEFAULT_RPC_URL).is_ok());
+        assert!(!home.path().join(PID_FILE).exists());
+        assert!(!home.path().join(RPC_FILE).exists());
+    }
+
+    #[test]
+    fn test_recorded_rpc_url_ignores_stale_file() {
+        let home = tempfile::tempdir().unwrap();
+        fs::write(home.path().join(RPC_FILE), "ws://localhost:19801").unwrap();
+
+        fs::write(home.path().join(PID_FILE), std::process::id().to_string()).unwrap();
+        assert_eq!(
+            recorded_rpc_url(home.path()).as_deref(),
+            Some("ws://localhost:19801")
+        );
+
+        // The testnet that recorded it is gone
+        fs::write(home.path().join(PID_FILE), i32::MAX.to_string()).unwrap();
+        assert_eq!(recorded_rpc_url(home.path()), None);
+        assert!(!home.path().join(RPC_FILE).exists());
+    }
+
+    #[test]
+    fn test_select_port() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let rpc = format!("ws://localhost:{}", port);
+
+        let args = UpArgs::parse_from(["up", "--rpc", &rpc]);
+        assert!(select_port(&args).is_err());
+
+        let args = UpArgs::parse_from(["up", "--rpc", &rpc, "--auto-port"]);
+        let selected = select_port(&args).unwrap();
+        assert_ne!(rpc_port(&selected), port);
+        assert!(selected.starts_with("ws://localhost:"));
+    }
 }
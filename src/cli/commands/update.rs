@@ -0,0 +1,243 @@
+use crate::cli::args::UpdateArgs;
+use crate::cli::commands::new::{warn_renamed_variables, TemplateSource};
+use crate::cli::output::{create_spinner, Glyph};
+use crate::error::{CargoJamError, Result};
+use crate::project::deps::DepsOptions;
+use crate::project::generator::ProjectGenerator;
+use crate::project::license::LICENSE_FILES;
+use crate::project::lockfile::{apply_lockfile_strategy, LockfileStrategy};
+use crate::project::manifest::{GenerationManifest, TemplateOrigin, MANIFEST_FILE};
+use crate::project::update::{apply_update, plan_update, UpdateAction, CONFLICT_SUFFIX};
+use crate::template::builtins::{apply_renamed_variables, builtin_variables};
+use crate::template::bundled::BundledTemplates;
+use crate::template::config::TemplateConfig;
+use crate::template::git::GitTemplateSource;
+use console::style;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+pub fn execute(args: UpdateArgs) -> Result<()> {
+    let (manifest, origin) = validate(&args)?;
+    let project_path = project_path(&args);
+
+    let spinner = create_spinner("Fetching template...");
+    let (_template_source, template_dir) = fetch_template(&origin, args.git_token.clone())?;
+    spinner.finish_and_clear();
+
+    let mut config = TemplateConfig::load_from_dir(&template_dir)?;
+    for conditional in &origin.conditionals {
+        if !config.apply_conditional(conditional) {
+            println!(
+                "{} Template no longer declares the '{}' conditional, generating all files",
+                style(Glyph::Warning).yellow(),
+                conditional
+            );
+        }
+    }
+
+    let variables = collect_variables(&args, &manifest, &config)?;
+
+    // Render the current template exactly as `new` did, post-processing included
+    let spinner = create_spinner("Rendering template...");
+    let temp_dir = tempfile::tempdir()?;
+    let rendered_dir = temp_dir.path().join("project");
+    let strict_undefined = config.strict_undefined(false);
+    let seed = manifest.seed.as_deref().and_then(|s| s.parse().ok());
+    ProjectGenerator::new(template_dir, rendered_dir.clone(), config)
+        .strict_undefined(strict_undefined)
+        .seed(seed)
+        .renames(manifest.renames.clone().into_iter().collect())
+        .toolchain(manifest.toolchain.clone())
+        .origin(origin)
+        .generate(&variables)?;
+    DepsOptions::from_variables(&variables)?.apply(&rendered_dir)?;
+    apply_lockfile_strategy(&rendered_dir, LockfileStrategy::recorded(&manifest))?;
+    let mut rendered = GenerationManifest::load(&rendered_dir)?;
+    // LICENSE files written by `new --license` aren't template output; keep them as generated
+    for name in LICENSE_FILES {
+        if let Some(hash) = manifest.files.get(*name) {
+            rendered
+                .files
+                .entry(name.to_string())
+                .or_insert_with(|| hash.clone());
+        }
+    }
+    spinner.finish_and_clear();
+
+    let plan = plan_update(&manifest, &rendered, &project_path)?;
+    let conflicts = print_plan(&plan, args.verbose);
+
+    if args.dry_run {
+        println!(
+            "\n{} Dry run, nothing was written",
+            style(Glyph::Arrow).cyan()
+        );
+        return Ok(());
+    }
+
+    apply_update(&plan, &rendered_dir, &project_path)?;
+    rendered.save(&project_path)?;
+
+    if conflicts > 0 {
+        println!(
+            "\n{} {} file(s) changed both locally and in the template. The template's version was written next to each as {}; merge it by hand and delete it",
+            style("!").yellow().bold(),
+            style(conflicts).yellow(),
+            style(format!("<file>{}", CONFLICT_SUFFIX)).cyan()
+        );
+    } else {
+        println!(
+            "\n{} Project is up to date with its template",
+            style(Glyph::Success).green().bold()
+        );
+    }
+
+    Ok(())
+}
+
+/// Validate that the project records the template it was generated from
+pub fn validate(args: &UpdateArgs) -> Result<(GenerationManifest, TemplateOrigin)> {
+    let manifest = GenerationManifest::load(&project_path(args))?;
+    let origin = manifest.template.clone().ok_or_else(|| {
+        CargoJamError::TemplateConfig(format!(
+            "{} does not record the project's template (it was generated by an older cargo-polkajam), so it can't be updated",
+            MANIFEST_FILE
+        ))
+    })?;
+
+    for define in &args.define {
+        if !define.contains('=') {
+            return Err(CargoJamError::InvalidArgument {
+                arg: "--define".to_string(),
+                reason: format!("'{}' is not in key=value form", define),
+            });
+        }
+    }
+
+    Ok((manifest, origin))
+}
+
+fn fetch_template(
+    origin: &TemplateOrigin,
+    token: Option<String>,
+) -> Result<(TemplateSource, PathBuf)> {
+    match (&origin.git, &origin.bundled) {
+        (Some(url), _) => {
+            let mut source = GitTemplateSource::new(url.clone())
+                .branch(origin.branch.clone())
+                .rev(origin.rev.clone())
+                .subpath(origin.path.as_ref().map(PathBuf::from))
+                .token(token);
+            let dir = source.fetch()?;
+            Ok((TemplateSource::Git(source), dir))
+        }
+        (None, Some(name)) => {
+            let mut templates = BundledTemplates::new();
+            let dir = templates.extract(name)?;
+            Ok((TemplateSource::Bundled(templates), dir))
+        }
+        (None, None) => Err(CargoJamError::TemplateConfig(format!(
+            "{} names neither a bundled nor a git template",
+            MANIFEST_FILE
+        ))),
+    }
+}
+
+/// The recorded variables, with `--define` overrides and defaults for placeholders
+/// the template gained since the project was generated
+fn collect_variables(
+    args: &UpdateArgs,
+    manifest: &GenerationManifest,
+    config: &TemplateConfig,
+) -> Result<HashMap<String, String>> {
+    let mut variables: HashMap<String, String> = manifest.variables.clone().into_iter().collect();
+
+    for define in &args.define {
+        if let Some((key, value)) = define.split_once('=') {
+            if let Some(placeholder) = config.placeholders.get(key) {
+                placeholder.validate_value(key, value)?;
+            }
+            variables.insert(key.to_string(), value.to_string());
+        }
+    }
+    // Values recorded or given under a variable's old name
+    warn_renamed_variables(apply_renamed_variables(&mut variables, config));
+
+    let builtins = builtin_variables();
+    let mut missing = Vec::new();
+    for (key, placeholder) in config.ordered_placeholders() {
+        if variables.contains_key(key) {
+            continue;
+        }
+        match builtins.get(key).cloned().or(placeholder.default_value()) {
+            Some(value) => {
+                println!(
+                    "{} New placeholder {} = {}",
+                    style(Glyph::Arrow).cyan(),
+                    style(key).cyan(),
+                    style(&value).green()
+                );
+                variables.insert(key.clone(), value);
+            }
+            None => missing.push(key.as_str()),
+        }
+    }
+
+    if !missing.is_empty() {
+        return Err(CargoJamError::InvalidArgument {
+            arg: "--define".to_string(),
+            reason: format!(
+                "the template has new placeholders without a default: {} (pass them with --define)",
+                missing.join(", ")
+            ),
+        });
+    }
+
+    Ok(variables)
+}
+
+/// Print each planned change, returning the number of conflicts
+fn print_plan(plan: &[(String, UpdateAction)], verbose: bool) -> usize {
+    let mut conflicts = 0;
+
+    for (path, action) in plan {
+        match action {
+            UpdateAction::Update => println!("  {} {}", style("U").green().bold(), path),
+            UpdateAction::Add => println!("  {} {}", style("A").green().bold(), path),
+            UpdateAction::Remove => println!("  {} {}", style("D").red().bold(), path),
+            UpdateAction::Conflict => {
+                conflicts += 1;
+                println!(
+                    "  {} {} {}",
+                    style("C").red().bold(),
+                    path,
+                    style(format!("(template version in {}{})", path, CONFLICT_SUFFIX)).dim()
+                );
+            }
+            UpdateAction::KeepLocal if verbose => println!(
+                "  {} {} {}",
+                style("M").yellow().bold(),
+                path,
+                style("(local changes kept, template unchanged)").dim()
+            ),
+            UpdateAction::KeepOrphan => println!(
+                "  {} {} {}",
+                style("M").yellow().bold(),
+                path,
+                style("(removed from the template, kept because it was modified)").dim()
+            ),
+            UpdateAction::UpToDate if verbose => {
+                println!("  {} {}", style("=").dim(), style(path).dim())
+            }
+            UpdateAction::KeepLocal | UpdateAction::UpToDate => {}
+        }
+    }
+
+    conflicts
+}
+
+fn project_path(args: &UpdateArgs) -> PathBuf {
+    args.path
+        .clone()
+        .unwrap_or_else(|| std::env::current_dir().expect("Failed to get current directory"))
+}
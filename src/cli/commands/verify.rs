@@ -0,0 +1,54 @@
+use crate::build::signing::{signature_path, verify_blob};
+use crate::cli::args::VerifyArgs;
+use crate::cli::output::Glyph;
+use crate::error::{CargoJamError, Result};
+use crate::toolchain::config::ToolchainConfig;
+use console::style;
+use std::path::Path;
+
+pub fn execute(args: VerifyArgs) -> Result<()> {
+    let public_keys = validate(&args)?;
+    let signature = args
+        .signature
+        .clone()
+        .unwrap_or_else(|| signature_path(&args.blob));
+
+    let key = verify_blob(&args.blob, &signature, &public_keys)?;
+
+    println!(
+        "{} Valid signature for {}",
+        style(Glyph::Success).green().bold(),
+        style(args.blob.display()).cyan()
+    );
+    println!(
+        "{} Signed by: {}",
+        style(Glyph::Arrow).cyan(),
+        style(key).dim()
+    );
+
+    Ok(())
+}
+
+/// Validate verify arguments, returning the public keys to check against
+pub fn validate(args: &VerifyArgs) -> Result<Vec<String>> {
+    if !args.blob.exists() {
+        return Err(CargoJamError::Build(format!(
+            "Service blob not found: {}",
+            args.blob.display()
+        )));
+    }
+
+    match args.public_key {
+        Some(ref key) => Ok(vec![read_public_key(key)?]),
+        None => Ok(ToolchainConfig::load()?.trusted_keys),
+    }
+}
+
+/// A public key given on the command line, either hex-encoded or a path to a `.pub` file
+fn read_public_key(key: &str) -> Result<String> {
+    let path = Path::new(key);
+    if path.is_file() {
+        return Ok(std::fs::read_to_string(path)?.trim().to_string());
+    }
+    Ok(key.trim().to_string())
+}
@@ -1,2 +1,4 @@
 pub mod args;
 pub mod commands;
+pub mod output;
+pub mod validate;
@@ -0,0 +1,261 @@
+use crate::error::Result;
+use console::style;
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
+use std::fmt;
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set from `--plain`, or when the terminal or locale can't show Unicode
+static PLAIN: AtomicBool = AtomicBool::new(false);
+
+/// Restrict decorative output to ASCII for the rest of the process
+pub fn set_plain(plain: bool) {
+    PLAIN.store(plain, Ordering::Relaxed);
+}
+
+/// Whether decorative output is restricted to ASCII
+pub fn is_plain() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
+
+/// Set from `--no-progress`
+static NO_PROGRESS: AtomicBool = AtomicBool::new(false);
+
+/// Replace spinners and progress bars with static status lines for the rest of the process
+pub fn set_no_progress(no_progress: bool) {
+    NO_PROGRESS.store(no_progress, Ordering::Relaxed);
+}
+
+/// Whether spinners and progress bars are drawn: not with `--no-progress`, nor
+/// when stderr (where they are drawn) isn't a terminal
+pub fn progress_enabled() -> bool {
+    !NO_PROGRESS.load(Ordering::Relaxed) && std::io::stderr().is_terminal()
+}
+
+/// Whether the environment suggests glyphs would be garbled: `TERM=dumb`, a locale
+/// that isn't UTF-8, or a Windows console other than Windows Terminal or VS Code
+pub fn detect_plain() -> bool {
+    plain_environment(|key| std::env::var(key).ok(), cfg!(windows))
+}
+
+fn plain_environment(env: impl Fn(&str) -> Option<String>, windows: bool) -> bool {
+    if env("TERM").as_deref() == Some("dumb") {
+        return true;
+    }
+    if windows {
+        return env("WT_SESSION").is_none() && env("TERM_PROGRAM").is_none();
+    }
+    // The first of these that is set decides the character encoding
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .into_iter()
+        .find_map(|key| env(key).filter(|value| !value.is_empty()));
+    match locale {
+        Some(locale) => {
+            let locale = locale.to_ascii_lowercase();
+            !locale.contains("utf-8") && !locale.contains("utf8")
+        }
+        None => false,
+    }
+}
+
+/// Decorative symbols, printed as ASCII in plain mode
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Glyph {
+    Success,
+    Failure,
+    Warning,
+    Arrow,
+    Bullet,
+    Play,
+    Test,
+    /// One segment of a horizontal rule
+    Rule,
+    /// `tree_lines` connectors, each four columns wide
+    TreeBranch,
+    TreeLast,
+    TreePipe,
+}
+
+impl Glyph {
+    pub fn as_str(self) -> &'static str {
+        self.text(is_plain())
+    }
+
+    fn text(self, plain: bool) -> &'static str {
+        let (unicode, ascii) = match self {
+            Self::Success => ("✓", "[ok]"),
+            Self::Failure => ("✗", "[fail]"),
+            Self::Warning => ("⚠", "!"),
+            Self::Arrow => ("→", "->"),
+            Self::Bullet => ("•", "*"),
+            Self::Play => ("▶", ">"),
+            Self::Test => ("🧪", "*"),
+            Self::Rule => ("─", "-"),
+            Self::TreeBranch => ("├── ", "|-- "),
+            Self::TreeLast => ("└── ", "`-- "),
+            Self::TreePipe => ("│   ", "|   "),
+        };
+        if plain {
+            ascii
+        } else {
+            unicode
+        }
+    }
+}
+
+impl fmt::Display for Glyph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// indicatif's default spinner, ticking with ASCII characters in plain mode
+pub fn spinner_style() -> ProgressStyle {
+    let style = ProgressStyle::default_spinner();
+    if is_plain() {
+        style.tick_chars("-\\|/ ")
+    } else {
+        style
+    }
+}
+
+/// A ticking spinner showing `message`.
+///
+/// Without progress output the message is printed once to stderr as a static
+/// status line instead, and the returned spinner is hidden.
+pub fn create_spinner(message: &str) -> ProgressBar {
+    if !progress_enabled() {
+        eprintln!("{} {}", style(Glyph::Arrow).cyan(), message);
+        return ProgressBar::hidden();
+    }
+    let spinner = ProgressBar::new_spinner();
+    spinner.set_style(spinner_style().template("{spinner:.green} {msg}").unwrap());
+    spinner.set_message(message.to_string());
+    spinner.enable_steady_tick(std::time::Duration::from_millis(100));
+    spinner
+}
+
+/// Change the message of a spinner or bar from `create_spinner`, `download_bar` or
+/// `multi_progress`. Without progress output the bar is hidden, so the new message is
+/// printed as a static status line instead, after the bar's prefix if it has one.
+pub fn set_message(bar: &ProgressBar, message: &str) {
+    if progress_enabled() {
+        bar.set_message(message.to_string());
+        return;
+    }
+    let prefix = bar.prefix();
+    if prefix.is_empty() {
+        eprintln!("{} {}", style(Glyph::Arrow).cyan(), message);
+    } else {
+        eprintln!("{} {}: {}", style(Glyph::Arrow).cyan(), prefix, message);
+    }
+}
+
+/// A progress bar for a download showing `message`, bytes transferred, speed and ETA.
+///
+/// The downloader sets its length. Without progress output the message is printed
+/// once as a static status line instead, as with `create_spinner`.
+pub fn download_bar(message: &str) -> ProgressBar {
+    if !progress_enabled() {
+        eprintln!("{} {}", style(Glyph::Arrow).cyan(), message);
+        return ProgressBar::hidden();
+    }
+    let bar = ProgressBar::new(0);
+    bar.set_style(download_style());
+    bar.set_message(message.to_string());
+    bar
+}
+
+/// Style of `download_bar`
+pub fn download_style() -> ProgressStyle {
+    ProgressStyle::default_bar()
+        .template("{msg} [{bar:30.cyan/blue}] {bytes}/{total_bytes} {bytes_per_sec} ETA {eta}")
+        .unwrap()
+        .progress_chars("=> ")
+}
+
+/// Style for a download of unknown size: a spinner with a running byte count
+pub fn download_spinner_style() -> ProgressStyle {
+    spinner_style()
+        .template("{spinner:.green} {msg} {bytes} ({bytes_per_sec})")
+        .unwrap()
+}
+
+/// A group of progress bars, drawing nothing without progress output
+pub fn multi_progress() -> MultiProgress {
+    if progress_enabled() {
+        MultiProgress::new()
+    } else {
+        MultiProgress::with_draw_target(ProgressDrawTarget::hidden())
+    }
+}
+
+/// Whether an `--output` path means stdout (`-`)
+pub fn is_stdout(path: &Path) -> bool {
+    path.as_os_str() == "-"
+}
+
+/// Write command output to a file, or to stdout when no path or `-` is given.
+///
+/// Returns whether a file was written.
+pub fn write_output(path: Option<&Path>, contents: &str) -> Result<bool> {
+    match path {
+        Some(path) if !is_stdout(path) => {
+            std::fs::write(path, contents)?;
+            Ok(true)
+        }
+        _ => {
+            let mut stdout = std::io::stdout().lock();
+            stdout.write_all(contents.as_bytes())?;
+            stdout.flush()?;
+            Ok(false)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_environment() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |key: &str| {
+                vars.iter()
+                    .find(|(k, _)| *k == key)
+                    .map(|(_, v)| v.to_string())
+            }
+        };
+        assert!(!plain_environment(env(&[("LANG", "en_US.UTF-8")]), false));
+        assert!(!plain_environment(env(&[]), false));
+        assert!(plain_environment(env(&[("LANG", "C")]), false));
+        // LC_ALL takes precedence over LANG
+        assert!(plain_environment(
+            env(&[("LC_ALL", "POSIX"), ("LANG", "C.utf8")]),
+            false
+        ));
+        assert!(plain_environment(
+            env(&[("TERM", "dumb"), ("LANG", "C.UTF-8")]),
+            false
+        ));
+        assert!(plain_environment(env(&[]), true));
+        assert!(!plain_environment(env(&[("WT_SESSION", "1")]), true));
+    }
+
+    #[test]
+    fn test_glyph_text() {
+        assert_eq!(Glyph::Success.text(false), "✓");
+        assert_eq!(Glyph::Success.text(true), "[ok]");
+        assert_eq!(Glyph::TreeLast.text(true).len(), 4);
+        assert!(Glyph::TreePipe.text(true).is_ascii());
+    }
+
+    #[test]
+    fn test_no_progress_spinner_is_hidden() {
+        set_no_progress(true);
+        assert!(!progress_enabled());
+        assert!(create_spinner("Working...").is_hidden());
+        assert!(multi_progress().add(ProgressBar::new(1)).is_hidden());
+    }
+}
@@ -0,0 +1,271 @@
+use crate::error::{CargoJamError, Result};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// URL schemes accepted for testnet RPC endpoints
+const RPC_SCHEMES: &[&str] = &["ws", "wss"];
+
+/// How long the RPC preflight waits for a TCP connection
+const RPC_CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Validate and normalize an RPC URL (`ws[s]://host[:port][/path]`).
+///
+/// The scheme and host are lowercased and any trailing slash is removed, so
+/// subprocesses always receive a canonical endpoint.
+pub fn normalize_rpc_url(url: &str) -> Result<String> {
+    let trimmed = url.trim();
+    let invalid = |reason: &str| CargoJamError::InvalidArgument {
+        arg: "--rpc".to_string(),
+        reason: format!("'{}' {}", trimmed, reason),
+    };
+
+    let (scheme, rest) = trimmed.split_once("://").ok_or_else(|| {
+        invalid("is not a valid URL (expected e.g. ws://localhost:19800, note the '//')")
+    })?;
+
+    let scheme = scheme.to_ascii_lowercase();
+    if !RPC_SCHEMES.contains(&scheme.as_str()) {
+        let hint = match scheme.as_str() {
+            "http" => " (did you mean ws://?)",
+            "https" => " (did you mean wss://?)",
+            _ => "",
+        };
+        return Err(invalid(&format!(
+            "has unsupported scheme '{}', expected one of: {}{}",
+            scheme,
+            RPC_SCHEMES.join(", "),
+            hint
+        )));
+    }
+
+    let (authority, path) = match rest.find('/') {
+        Some(i) => rest.split_at(i),
+        None => (rest, ""),
+    };
+    // Bracketed IPv6 hosts (e.g. [::1]) contain colons of their own
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) if !authority.ends_with(']') => (host, Some(port)),
+        _ => (authority, None),
+    };
+
+    if host.is_empty() {
+        return Err(invalid("is missing a host"));
+    }
+
+    let mut normalized = format!("{}://{}", scheme, host.to_ascii_lowercase());
+
+    if let Some(port) = port {
+        match port.parse::<u16>() {
+            Ok(port) if port != 0 => normalized.push_str(&format!(":{}", port)),
+            _ => return Err(invalid(&format!("has an invalid port '{}'", port))),
+        }
+    }
+
+    normalized.push_str(path.trim_end_matches('/'));
+
+    Ok(normalized)
+}
+
+/// Port of a normalized RPC URL, defaulting to 80 for `ws` and 443 for `wss`
+pub fn rpc_port(url: &str) -> u16 {
+    let (scheme, _, port, _) = split_rpc_url(url);
+    port.and_then(|p| p.parse().ok())
+        .unwrap_or(if scheme == "wss" { 443 } else { 80 })
+}
+
+/// A normalized RPC URL with its port replaced
+pub fn with_rpc_port(url: &str, port: u16) -> String {
+    let (scheme, host, _, path) = split_rpc_url(url);
+    format!("{}://{}:{}{}", scheme, host, port, path)
+}
+
+/// Split a normalized RPC URL into scheme, host, port and path
+fn split_rpc_url(url: &str) -> (&str, &str, Option<&str>, &str) {
+    let (scheme, rest) = url.split_once("://").unwrap_or(("ws", url));
+    let (authority, path) = match rest.find('/') {
+        Some(i) => rest.split_at(i),
+        None => (rest, ""),
+    };
+    match authority.rsplit_once(':') {
+        Some((host, port)) if !authority.ends_with(']') => (scheme, host, Some(port), path),
+        _ => (scheme, authority, None, path),
+    }
+}
+
+/// Check that something is listening at a normalized RPC URL before handing it to a tool
+pub fn check_rpc_reachable(url: &str) -> Result<()> {
+    let unreachable = |reason: String| CargoJamError::RpcUnreachable {
+        url: url.to_string(),
+        reason,
+    };
+
+    let (scheme, rest) = url.split_once("://").unwrap_or(("ws", url));
+    let authority = rest.split('/').next().unwrap_or(rest);
+    let address = match authority.rsplit_once(':') {
+        Some(_) if !authority.ends_with(']') => authority.to_string(),
+        _ => format!("{}:{}", authority, if scheme == "wss" { 443 } else { 80 }),
+    };
+
+    let addrs: Vec<_> = address
+        .to_socket_addrs()
+        .map_err(|e| unreachable(e.to_string()))?
+        .collect();
+
+    let mut last_error = format!("{} did not resolve", address);
+    for addr in addrs {
+        match TcpStream::connect_timeout(&addr, RPC_CONNECT_TIMEOUT) {
+            Ok(_) => return Ok(()),
+            Err(e) => last_error = e.to_string(),
+        }
+    }
+
+    Err(unreachable(last_error))
+}
+
+/// Clap value parser for path arguments, expanding a leading `~` or `~user`
+pub fn parse_path(value: &str) -> std::result::Result<PathBuf, String> {
+    Ok(expand_home(value))
+}
+
+/// Expand a leading `~` (current user) or `~user` to that user's home directory.
+///
+/// Paths that don't start with `~`, or name a user whose home can't be found,
+/// are returned unchanged.
+pub fn expand_home(path: &str) -> PathBuf {
+    expand_home_with(path, dirs::home_dir, user_home)
+}
+
+fn expand_home_with(
+    path: &str,
+    home: impl FnOnce() -> Option<PathBuf>,
+    user_home: impl FnOnce(&str) -> Option<PathBuf>,
+) -> PathBuf {
+    let Some(rest) = path.strip_prefix('~') else {
+        return PathBuf::from(path);
+    };
+
+    let (user, tail) = match rest.find(['/', '\\']) {
+        Some(index) => (&rest[..index], &rest[index + 1..]),
+        None => (rest, ""),
+    };
+
+    let dir = if user.is_empty() {
+        home()
+    } else {
+        user_home(user)
+    };
+
+    match dir {
+        Some(dir) if tail.is_empty() => dir,
+        Some(dir) => dir.join(tail),
+        None => PathBuf::from(path),
+    }
+}
+
+/// Home directory of another user, from the password database
+#[cfg(unix)]
+fn user_home(user: &str) -> Option<PathBuf> {
+    std::fs::read_to_string("/etc/passwd")
+        .ok()?
+        .lines()
+        .map(|line| line.split(':').collect::<Vec<_>>())
+        .find(|fields| fields.len() >= 6 && fields[0] == user)
+        .map(|fields| PathBuf::from(fields[5]))
+}
+
+#[cfg(not(unix))]
+fn user_home(_user: &str) -> Option<PathBuf> {
+    None
+}
+
+/// Parse a gas amount, reporting the offending flag on failure
+pub fn parse_gas(arg: &str, value: &str) -> Result<u64> {
+    value
+        .parse::<u64>()
+        .map_err(|_| CargoJamError::InvalidArgument {
+            arg: arg.to_string(),
+            reason: format!("'{}' is not a valid gas amount", value),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_home() {
+        let home = || Some(PathBuf::from("/home/me"));
+        let users = |user: &str| (user == "bob").then(|| PathBuf::from("/home/bob"));
+        let expand = |path: &str| expand_home_with(path, home, users);
+
+        assert_eq!(expand("~"), PathBuf::from("/home/me"));
+        assert_eq!(
+            expand("~/svc.jam"),
+            PathBuf::from("/home/me").join("svc.jam")
+        );
+        assert_eq!(
+            expand("~bob/blobs"),
+            PathBuf::from("/home/bob").join("blobs")
+        );
+        assert_eq!(expand("~nobody/x"), PathBuf::from("~nobody/x"));
+        assert_eq!(expand("./~/x"), PathBuf::from("./~/x"));
+        assert_eq!(expand("-"), PathBuf::from("-"));
+        assert_eq!(
+            expand_home_with("~/x", || None, users),
+            PathBuf::from("~/x")
+        );
+    }
+
+    #[test]
+    fn test_normalize_rpc_url() {
+        assert_eq!(
+            normalize_rpc_url("ws://localhost:19800").unwrap(),
+            "ws://localhost:19800"
+        );
+        assert_eq!(
+            normalize_rpc_url(" WSS://RPC.Example.com/jam/ ").unwrap(),
+            "wss://rpc.example.com/jam"
+        );
+        assert_eq!(normalize_rpc_url("ws://[::1]").unwrap(), "ws://[::1]");
+    }
+
+    #[test]
+    fn test_rpc_port() {
+        assert_eq!(rpc_port("ws://localhost:19800"), 19800);
+        assert_eq!(rpc_port("wss://rpc.example.com/jam"), 443);
+        assert_eq!(rpc_port("ws://[::1]"), 80);
+        assert_eq!(
+            with_rpc_port("ws://localhost:19800/jam", 19801),
+            "ws://localhost:19801/jam"
+        );
+        assert_eq!(with_rpc_port("ws://[::1]", 9944), "ws://[::1]:9944");
+    }
+
+    #[test]
+    fn test_invalid_rpc_urls() {
+        assert!(normalize_rpc_url("ws:/localhost:19800").is_err());
+        assert!(normalize_rpc_url("localhost:19800").is_err());
+        assert!(normalize_rpc_url("http://localhost:19800").is_err());
+        assert!(normalize_rpc_url("ws://").is_err());
+        assert!(normalize_rpc_url("ws://:19800").is_err());
+        assert!(normalize_rpc_url("ws://localhost:port").is_err());
+    }
+
+    #[test]
+    fn test_parse_gas() {
+        assert_eq!(parse_gas("--min-item-gas", "1000000").unwrap(), 1_000_000);
+        assert!(parse_gas("--min-item-gas", "lots").is_err());
+    }
+
+    #[test]
+    fn test_check_rpc_reachable() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        assert!(check_rpc_reachable(&format!("ws://127.0.0.1:{}", port)).is_ok());
+
+        drop(listener);
+        let err = check_rpc_reachable(&format!("ws://127.0.0.1:{}", port)).unwrap_err();
+        assert!(err.to_string().contains("cargo polkajam up"));
+    }
+}
@@ -23,12 +23,38 @@ pub enum CargoJamError {
     #[error("Toolchain not found: {tool}. Install with: {install_hint}")]
     ToolchainMissing { tool: String, install_hint: String },
 
+    #[error("Project requires toolchain {required} (pinned in jam-toolchain.toml) but {installed} is installed. Run 'cargo polkajam setup --version {required}'")]
+    ToolchainMismatch { required: String, installed: String },
+
     #[error("Project already exists at: {0}")]
     ProjectExists(String),
 
+    #[error("Invalid value for {arg}: {reason}")]
+    InvalidArgument { arg: String, reason: String },
+
+    #[error("{command} timed out after {secs}s (adjust with --timeout, CARGO_JAM_TIMEOUT or default_timeout in the config)")]
+    Timeout { command: String, secs: u64 },
+
+    #[error("Could not determine the home directory. Set CARGO_JAM_HOME to a directory for cargo-polkajam's toolchain and config (or set HOME)")]
+    HomeDirUnavailable,
+
+    #[error("Cannot reach the testnet RPC at {url}: {reason}. Start it with 'cargo polkajam up'")]
+    RpcUnreachable { url: String, reason: String },
+
+    #[error(
+        "A testnet is already running (PID {pid}) at {rpc}. Stop it with 'cargo polkajam down'"
+    )]
+    TestnetAlreadyRunning { pid: i32, rpc: String },
+
+    #[error("Signature error: {0}")]
+    Signature(String),
+
     #[error("Not a JAM service project: {0}")]
     NotJamProject(String),
 
+    #[error("Workspace manifest error: {0}")]
+    WorkspaceManifest(String),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
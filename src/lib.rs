@@ -17,6 +17,8 @@
 pub mod build;
 pub mod cli;
 pub mod error;
+pub mod hex;
+pub mod process;
 pub mod project;
 pub mod prompt;
 pub mod template;
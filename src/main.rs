@@ -1,36 +1,65 @@
 use anyhow::Result;
-use cargo_polkajam::cli::args::{Cargo, PolkajamCommand};
+use cargo_polkajam::cli::args::{normalize_args, Cargo, PolkajamCommand, SUBCOMMAND};
 use cargo_polkajam::cli::commands;
+use cargo_polkajam::cli::output::{detect_plain, set_no_progress, set_plain};
+use cargo_polkajam::toolchain::config::ToolchainConfig;
+use clap::error::ErrorKind;
 use clap::Parser;
 use console::style;
+use std::process::ExitCode;
+use tracing_subscriber::EnvFilter;
 
-fn main() {
-    if let Err(e) = run() {
-        eprintln!("{} {}", style("error:").red().bold(), e);
+fn main() -> ExitCode {
+    match run() {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("{} {}", style("error:").red().bold(), e);
 
-        // Print cause chain if available
-        let mut source = e.source();
-        while let Some(cause) = source {
-            eprintln!("  {} {}", style("caused by:").yellow(), cause);
-            source = cause.source();
-        }
+            // Print cause chain if available
+            let mut source = e.source();
+            while let Some(cause) = source {
+                eprintln!("  {} {}", style("caused by:").yellow(), cause);
+                source = cause.source();
+            }
 
-        std::process::exit(1);
+            ExitCode::FAILURE
+        }
     }
 }
 
-fn run() -> Result<()> {
-    let Cargo::Polkajam(args) = Cargo::parse();
+/// Environment variable holding the log filter, taking precedence over `RUST_LOG`
+const LOG_ENV: &str = "CARGO_JAM_LOG";
+
+fn run() -> Result<ExitCode> {
+    init_logging();
+    let Cargo::Polkajam(args) = parse_args();
+    set_plain(args.plain || detect_plain());
+    set_no_progress(args.no_progress);
+
+    if let Some(config) = args.config {
+        ToolchainConfig::set_config_path_override(config);
+    }
+
+    if args.check_only {
+        commands::check::execute(&args.command)?;
+        return Ok(ExitCode::SUCCESS);
+    }
 
     match args.command {
         PolkajamCommand::New(new_args) => {
-            commands::new::execute(new_args)?;
+            commands::new::execute(*new_args)?;
         }
         PolkajamCommand::Build(build_args) => {
             commands::build::execute(build_args)?;
         }
         PolkajamCommand::Setup(setup_args) => {
-            commands::setup::execute(setup_args)?;
+            return Ok(commands::setup::execute(setup_args)?.exit_code());
+        }
+        PolkajamCommand::CleanToolchain(clean_args) => {
+            commands::clean_toolchain::execute(clean_args)?;
+        }
+        PolkajamCommand::Uninstall(uninstall_args) => {
+            commands::uninstall::execute(uninstall_args)?;
         }
         PolkajamCommand::Up(up_args) => {
             commands::up::execute(up_args)?;
@@ -44,10 +73,80 @@ fn run() -> Result<()> {
         PolkajamCommand::Monitor(monitor_args) => {
             commands::monitor::execute(monitor_args)?;
         }
+        PolkajamCommand::Queue(queue_args) => {
+            commands::queue::execute(queue_args)?;
+        }
         PolkajamCommand::Test(test_args) => {
             commands::test::execute(test_args)?;
         }
+        PolkajamCommand::Bench(bench_args) => {
+            commands::bench::execute(bench_args)?;
+        }
+        PolkajamCommand::Diff(diff_args) => {
+            commands::diff::execute(diff_args)?;
+        }
+        PolkajamCommand::CheckCompat(compat_args) => {
+            commands::check_compat::execute(compat_args)?;
+        }
+        PolkajamCommand::Update(update_args) => {
+            commands::update::execute(update_args)?;
+        }
+        PolkajamCommand::Info(info_args) => {
+            commands::info::execute(info_args)?;
+        }
+        PolkajamCommand::Template(template_args) => {
+            commands::template::execute(template_args)?;
+        }
+        PolkajamCommand::Config(config_args) => {
+            commands::config::execute(config_args)?;
+        }
+        PolkajamCommand::Accounts(accounts_args) => {
+            commands::accounts::execute(accounts_args)?;
+        }
+        PolkajamCommand::Render(render_args) => {
+            commands::render::execute(render_args)?;
+        }
+        PolkajamCommand::Sign(sign_args) => {
+            commands::sign::execute(sign_args)?;
+        }
+        PolkajamCommand::Verify(verify_args) => {
+            commands::verify::execute(verify_args)?;
+        }
     }
 
-    Ok(())
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Send diagnostics to stderr, filtered by `CARGO_JAM_LOG` or `RUST_LOG` (default: warnings only)
+fn init_logging() {
+    let filter = EnvFilter::try_from_env(LOG_ENV)
+        .or_else(|_| EnvFilter::try_from_default_env())
+        .unwrap_or_else(|_| EnvFilter::new("warn"));
+
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .with_target(false)
+        .compact()
+        .init();
+}
+
+/// Parse the command line, pointing at the `cargo polkajam <command>` form when no command is given
+fn parse_args() -> Cargo {
+    Cargo::try_parse_from(normalize_args(std::env::args_os())).unwrap_or_else(|e| {
+        let missing_command = matches!(
+            e.kind(),
+            ErrorKind::MissingSubcommand | ErrorKind::DisplayHelpOnMissingArgumentOrSubcommand
+        );
+        let _ = e.print();
+        if missing_command || e.kind() == ErrorKind::InvalidSubcommand {
+            eprintln!(
+                "\n{} run as {}, e.g. {}",
+                style("hint:").cyan().bold(),
+                style(format!("cargo {} <command>", SUBCOMMAND)).green(),
+                style(format!("cargo {} new my-service", SUBCOMMAND)).green()
+            );
+        }
+        std::process::exit(e.exit_code());
+    })
 }
@@ -0,0 +1,160 @@
+use crate::error::{CargoJamError, Result};
+use crate::toolchain::config::ToolchainConfig;
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Command, Output, Stdio};
+use std::time::{Duration, Instant};
+use tracing::{debug, trace};
+
+/// Environment variable setting the default subprocess timeout in seconds
+pub const TIMEOUT_ENV: &str = "CARGO_JAM_TIMEOUT";
+
+/// How often a running subprocess is checked against its deadline
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Resolve the subprocess timeout for a command.
+///
+/// Precedence: `--timeout` flag, then `CARGO_JAM_TIMEOUT`, then
+/// `default_timeout` in the config file. Without any of them, or with a
+/// value of 0, subprocesses may run indefinitely.
+pub fn resolve_timeout(flag: Option<u64>) -> Result<Option<Duration>> {
+    let secs = match flag {
+        Some(secs) => Some(secs),
+        None => match std::env::var(TIMEOUT_ENV) {
+            Ok(value) if !value.trim().is_empty() => Some(value.trim().parse::<u64>().map_err(
+                |_| CargoJamError::InvalidArgument {
+                    arg: TIMEOUT_ENV.to_string(),
+                    reason: format!("'{}' is not a number of seconds", value),
+                },
+            )?),
+            _ => {
+                trace!("no --timeout or {}, using the config default", TIMEOUT_ENV);
+                ToolchainConfig::load()?.default_timeout
+            }
+        },
+    };
+    debug!(?secs, "resolved subprocess timeout");
+
+    Ok(secs.filter(|&s| s > 0).map(Duration::from_secs))
+}
+
+/// Run a command to completion like `Command::output`, killing it after `timeout`
+pub fn output_with_timeout(cmd: &mut Command, timeout: Option<Duration>) -> Result<Output> {
+    debug!(command = ?cmd, ?timeout, "running subprocess");
+    let Some(timeout) = timeout else {
+        return Ok(cmd.output()?);
+    };
+    run_piped(cmd, Some(timeout), false).map(|(output, _)| output)
+}
+
+/// Run a command like `output_with_timeout`, also returning how long after the start
+/// each line of stdout arrived, for commands that stream line-delimited messages
+pub fn output_with_line_times(
+    cmd: &mut Command,
+    timeout: Option<Duration>,
+) -> Result<(Output, Vec<Duration>)> {
+    debug!(command = ?cmd, ?timeout, "running subprocess, timing stdout lines");
+    run_piped(cmd, timeout, true)
+}
+
+fn run_piped(
+    cmd: &mut Command,
+    timeout: Option<Duration>,
+    line_times: bool,
+) -> Result<(Output, Vec<Duration>)> {
+    let started = Instant::now();
+    let mut child = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+
+    // Drain the pipes concurrently so a chatty child can't block on a full pipe
+    let stdout = drain(child.stdout.take(), line_times.then_some(started));
+    let stderr = drain(child.stderr.take(), None);
+
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if let Some(timeout) = timeout.filter(|&timeout| started.elapsed() >= timeout) {
+            debug!(pid = child.id(), "subprocess timed out, killing it");
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(CargoJamError::Timeout {
+                command: cmd.get_program().to_string_lossy().into_owned(),
+                secs: timeout.as_secs(),
+            });
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    };
+
+    trace!(%status, elapsed = ?started.elapsed(), "subprocess exited");
+    let (stdout, times) = stdout.join().unwrap_or_default();
+    Ok((
+        Output {
+            status,
+            stdout,
+            stderr: stderr.join().unwrap_or_default().0,
+        },
+        times,
+    ))
+}
+
+/// Read a pipe to the end, recording when each line arrived if `started` is given
+fn drain(
+    pipe: Option<impl Read + Send + 'static>,
+    started: Option<Instant>,
+) -> std::thread::JoinHandle<(Vec<u8>, Vec<Duration>)> {
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        let mut times = Vec::new();
+        let Some(pipe) = pipe else {
+            return (buf, times);
+        };
+        match started {
+            Some(started) => {
+                let mut reader = BufReader::new(pipe);
+                while let Ok(read) = reader.read_until(b'\n', &mut buf) {
+                    if read == 0 {
+                        break;
+                    }
+                    times.push(started.elapsed());
+                }
+            }
+            None => {
+                let mut pipe = pipe;
+                let _ = pipe.read_to_end(&mut buf);
+            }
+        }
+        (buf, times)
+    })
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_with_timeout() {
+        let output =
+            output_with_timeout(Command::new("echo").arg("hi"), Some(Duration::from_secs(5)))
+                .unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"hi\n");
+
+        let err = output_with_timeout(
+            Command::new("sleep").arg("5"),
+            Some(Duration::from_millis(100)),
+        )
+        .unwrap_err();
+        assert!(matches!(err, CargoJamError::Timeout { .. }));
+    }
+
+    #[test]
+    fn test_output_with_line_times() {
+        let (output, times) = output_with_line_times(
+            Command::new("sh").args(["-c", "echo a; sleep 0.2; echo b"]),
+            None,
+        )
+        .unwrap();
+        assert_eq!(output.stdout, b"a\nb\n");
+        assert_eq!(times.len(), 2);
+        assert!(times[1] - times[0] >= Duration::from_millis(150));
+    }
+}
@@ -0,0 +1,305 @@
+use crate::error::{CargoJamError, Result};
+use crate::project::manifest::GenerationManifest;
+use clap::ValueEnum;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, InlineTable, Item, Value};
+
+/// Where the JAM SDK dependencies of a generated project come from
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum DepsSource {
+    /// Published versions from crates.io, as written by the template
+    #[default]
+    Registry,
+    /// The upstream git repositories, optionally at a given rev
+    Git,
+    /// Local checkouts of the crates
+    Path,
+}
+
+impl DepsSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DepsSource::Registry => "registry",
+            DepsSource::Git => "git",
+            DepsSource::Path => "path",
+        }
+    }
+}
+
+/// A JAM SDK crate whose source can be switched with `--deps`
+pub struct SdkDependency {
+    pub name: &'static str,
+    /// Upstream repository used for `--deps git`
+    pub git: &'static str,
+    /// Whether `--deps-rev` pins it. The rev names a jam-sdk commit, which means nothing
+    /// in the polkavm repository, so polkavm stays on its default branch.
+    pub uses_rev: bool,
+}
+
+pub const SDK_DEPENDENCIES: &[SdkDependency] = &[
+    SdkDependency {
+        name: "jam-pvm-common",
+        git: "https://github.com/paritytech/jam-sdk",
+        uses_rev: true,
+    },
+    SdkDependency {
+        name: "polkavm-derive",
+        git: "https://github.com/paritytech/polkavm",
+        uses_rev: false,
+    },
+];
+
+/// Keys that select a dependency's source and are replaced when rewriting it
+const SOURCE_KEYS: &[&str] = &["version", "git", "rev", "branch", "tag", "path", "registry"];
+
+/// Dependency source settings from `new --deps`
+#[derive(Debug, Default)]
+pub struct DepsOptions {
+    pub source: DepsSource,
+    pub rev: Option<String>,
+    pub path: Option<PathBuf>,
+}
+
+impl DepsOptions {
+    /// Check that `--deps-rev` and `--deps-path` match the chosen source
+    pub fn validate(&self) -> Result<()> {
+        if self.rev.is_some() && self.source != DepsSource::Git {
+            return Err(CargoJamError::InvalidArgument {
+                arg: "--deps-rev".to_string(),
+                reason: "only applies to --deps git".to_string(),
+            });
+        }
+
+        match (self.source, &self.path) {
+            (DepsSource::Path, None) => Err(CargoJamError::InvalidArgument {
+                arg: "--deps".to_string(),
+                reason: "--deps path requires --deps-path <DIR>".to_string(),
+            }),
+            (DepsSource::Path, Some(dir)) => {
+                for dep in SDK_DEPENDENCIES {
+                    crate_dir(dir, dep.name)?;
+                }
+                Ok(())
+            }
+            (_, Some(_)) => Err(CargoJamError::InvalidArgument {
+                arg: "--deps-path".to_string(),
+                reason: "only applies to --deps path".to_string(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Template variables describing the dependency source
+    pub fn variables(&self) -> HashMap<String, String> {
+        let mut variables = HashMap::new();
+        variables.insert("deps_source".to_string(), self.source.as_str().to_string());
+        variables.insert("deps_rev".to_string(), self.rev.clone().unwrap_or_default());
+        variables.insert(
+            "deps_path".to_string(),
+            self.path
+                .as_ref()
+                .map(|p| p.display().to_string())
+                .unwrap_or_default(),
+        );
+        variables
+    }
+
+    /// Options recorded in a project's template variables (the inverse of `variables`)
+    pub fn from_variables(variables: &HashMap<String, String>) -> Result<Self> {
+        let value = |key: &str| {
+            variables
+                .get(key)
+                .filter(|v| !v.is_empty())
+                .map(String::as_str)
+        };
+
+        let source = match value("deps_source") {
+            Some(source) => {
+                DepsSource::from_str(source, true).map_err(|_| CargoJamError::InvalidArgument {
+                    arg: "deps_source".to_string(),
+                    reason: format!("unknown dependency source '{}'", source),
+                })?
+            }
+            None => DepsSource::Registry,
+        };
+
+        Ok(Self {
+            source,
+            rev: value("deps_rev").map(String::from),
+            path: value("deps_path").map(PathBuf::from),
+        })
+    }
+
+    /// Point the SDK dependencies in a generated `Cargo.toml` at git or local sources
+    ///
+    /// Returns the names of the rewritten dependencies. The generation manifest is updated
+    /// so that `diff` doesn't report the rewrite as a local modification.
+    pub fn apply(&self, project_dir: &Path) -> Result<Vec<&'static str>> {
+        if self.source == DepsSource::Registry {
+            return Ok(Vec::new());
+        }
+
+        let manifest_path = project_dir.join("Cargo.toml");
+        let content = std::fs::read_to_string(&manifest_path)?;
+        let mut doc: DocumentMut = content.parse().map_err(|e| {
+            CargoJamError::TemplateConfig(format!("Failed to parse generated Cargo.toml: {}", e))
+        })?;
+
+        let mut rewritten = Vec::new();
+        if let Some(deps) = doc
+            .get_mut("dependencies")
+            .and_then(Item::as_table_like_mut)
+        {
+            for dep in SDK_DEPENDENCIES {
+                let Some(item) = deps.get_mut(dep.name) else {
+                    continue;
+                };
+                *item = toml_edit::value(self.dependency(dep, item)?);
+                rewritten.push(dep.name);
+            }
+        }
+
+        let content = doc.to_string();
+        std::fs::write(&manifest_path, &content)?;
+
+        if let Ok(mut manifest) = GenerationManifest::load(project_dir) {
+            manifest.record("Cargo.toml", content.as_bytes());
+            manifest.save(project_dir)?;
+        }
+
+        Ok(rewritten)
+    }
+
+    /// The dependency entry with its source replaced, keeping features and other settings
+    fn dependency(&self, dep: &SdkDependency, item: &Item) -> Result<InlineTable> {
+        let mut table = InlineTable::new();
+        match self.source {
+            DepsSource::Registry => unreachable!("registry dependencies are left as generated"),
+            DepsSource::Git => {
+                table.insert("git", dep.git.into());
+                if let Some(rev) = self.rev.as_ref().filter(|_| dep.uses_rev) {
+                    table.insert("rev", rev.as_str().into());
+                }
+            }
+            DepsSource::Path => {
+                let dir = self.path.as_deref().unwrap_or(Path::new("."));
+                let path = crate_dir(dir, dep.name)?;
+                table.insert("path", path.display().to_string().into());
+            }
+        }
+
+        if let Some(existing) = item.as_table_like() {
+            for (key, value) in existing.iter() {
+                if SOURCE_KEYS.contains(&key) {
+                    continue;
+                }
+                if let Some(value) = value.as_value() {
+                    let mut value: Value = value.clone();
+                    value.decor_mut().clear();
+                    table.insert(key, value);
+                }
+            }
+        }
+
+        Ok(table)
+    }
+}
+
+/// Locate a crate under a `--deps-path` directory, as `<DIR>/<name>` or `<DIR>/crates/<name>`
+fn crate_dir(dir: &Path, name: &str) -> Result<PathBuf> {
+    [dir.join(name), dir.join("crates").join(name)]
+        .into_iter()
+        .find(|candidate| candidate.join("Cargo.toml").is_file())
+        .map(|found| found.canonicalize().unwrap_or(found))
+        .ok_or_else(|| CargoJamError::InvalidArgument {
+            arg: "--deps-path".to_string(),
+            reason: format!(
+                "{} not found in {} (expected {}/Cargo.toml or crates/{}/Cargo.toml)",
+                name,
+                dir.display(),
+                name,
+                name
+            ),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"[package]
+name = "svc"
+
+[dependencies]
+jam-pvm-common = { version = "0.1", default-features = false, features = ["service"] }
+polkavm-derive = "0.29"
+"#;
+
+    #[test]
+    fn test_options_round_trip_through_variables() {
+        let options = DepsOptions {
+            source: DepsSource::Git,
+            rev: Some("abc123".to_string()),
+            path: None,
+        };
+        let parsed = DepsOptions::from_variables(&options.variables()).unwrap();
+        assert_eq!(parsed.source, DepsSource::Git);
+        assert_eq!(parsed.rev.as_deref(), Some("abc123"));
+        assert_eq!(parsed.path, None);
+
+        let parsed = DepsOptions::from_variables(&HashMap::new()).unwrap();
+        assert_eq!(parsed.source, DepsSource::Registry);
+    }
+
+    #[test]
+    fn test_apply_git_deps() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), MANIFEST).unwrap();
+
+        let options = DepsOptions {
+            source: DepsSource::Git,
+            rev: Some("abc123".to_string()),
+            path: None,
+        };
+        options.validate().unwrap();
+        assert_eq!(
+            options.apply(dir.path()).unwrap(),
+            ["jam-pvm-common", "polkavm-derive"]
+        );
+
+        let content = std::fs::read_to_string(dir.path().join("Cargo.toml")).unwrap();
+        assert!(content.contains(
+            r#"jam-pvm-common = { git = "https://github.com/paritytech/jam-sdk", rev = "abc123", default-features = false, features = ["service"] }"#
+        ));
+        assert!(content
+            .contains(r#"polkavm-derive = { git = "https://github.com/paritytech/polkavm" }"#));
+    }
+
+    #[test]
+    fn test_validate_combinations() {
+        let rev_without_git = DepsOptions {
+            rev: Some("abc".to_string()),
+            ..DepsOptions::default()
+        };
+        assert!(rev_without_git.validate().is_err());
+
+        let path_without_dir = DepsOptions {
+            source: DepsSource::Path,
+            ..DepsOptions::default()
+        };
+        assert!(path_without_dir.validate().is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        for sub in ["jam-pvm-common", "crates/polkavm-derive"] {
+            std::fs::create_dir_all(dir.path().join(sub)).unwrap();
+            std::fs::write(dir.path().join(sub).join("Cargo.toml"), "").unwrap();
+        }
+        let path = DepsOptions {
+            source: DepsSource::Path,
+            path: Some(dir.path().to_path_buf()),
+            ..DepsOptions::default()
+        };
+        assert!(path.validate().is_ok());
+    }
+}
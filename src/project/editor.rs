@@ -0,0 +1,131 @@
+use crate::error::Result;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Environment variables naming the user's editor, in order of preference
+const EDITOR_ENV: &[&str] = &["VISUAL", "EDITOR"];
+
+/// Editor CLIs tried, in order, when no editor is configured
+const KNOWN_EDITORS: &[&str] = &["code", "zed", "cursor", "subl", "idea"];
+
+/// Terminal editor used for single files when neither `$VISUAL` nor `$EDITOR` is set
+const FALLBACK_FILE_EDITOR: &str = if cfg!(windows) { "notepad" } else { "vi" };
+
+/// The editor command to open a project with: `editor` if given, then
+/// `$VISUAL`, `$EDITOR`, and finally the first known IDE CLI found on `PATH`
+pub fn find_editor(editor: Option<&str>) -> Option<String> {
+    choose_editor(
+        editor,
+        |key| std::env::var(key).ok(),
+        |program| find_program(program).is_some(),
+    )
+}
+
+fn choose_editor(
+    editor: Option<&str>,
+    env: impl Fn(&str) -> Option<String>,
+    installed: impl Fn(&str) -> bool,
+) -> Option<String> {
+    editor
+        .map(str::to_string)
+        .into_iter()
+        .chain(EDITOR_ENV.iter().filter_map(|key| env(key)))
+        .map(|command| command.trim().to_string())
+        .find(|command| !command.is_empty())
+        .or_else(|| {
+            KNOWN_EDITORS
+                .iter()
+                .find(|program| installed(program))
+                .map(|program| program.to_string())
+        })
+}
+
+/// The editor command to edit a single file with, waiting for it to close:
+/// `editor` if given, then `$VISUAL`, `$EDITOR`, and finally `vi` (`notepad` on Windows).
+///
+/// IDE CLIs such as `code` return immediately unless given `--wait`, so they are
+/// only used when configured explicitly.
+pub fn find_file_editor(editor: Option<&str>) -> String {
+    choose_editor(editor, |key| std::env::var(key).ok(), |_| false)
+        .unwrap_or_else(|| FALLBACK_FILE_EDITOR.to_string())
+}
+
+/// Locate a program on `PATH`
+fn find_program(program: &str) -> Option<PathBuf> {
+    let extensions: &[&str] = if cfg!(windows) {
+        &["exe", "cmd", "bat"]
+    } else {
+        &[""]
+    };
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .flat_map(|dir| {
+            extensions
+                .iter()
+                .map(move |ext| dir.join(program).with_extension(ext))
+        })
+        .find(|candidate| candidate.is_file())
+}
+
+/// Run an editor command (a program and its arguments, such as `code --new-window`)
+/// on a project directory, waiting for it to return
+pub fn open_in_editor(editor: &str, project_dir: &Path) -> Result<()> {
+    run_editor(editor, Path::new("."), Some(project_dir))
+}
+
+/// Run an editor command on a single file, waiting for it to return
+pub fn edit_file(editor: &str, file: &Path) -> Result<()> {
+    run_editor(editor, file, None)
+}
+
+fn run_editor(editor: &str, target: &Path, dir: Option<&Path>) -> Result<()> {
+    let mut parts = editor.split_whitespace();
+    let Some(program) = parts.next() else {
+        return Err(std::io::Error::other("empty editor command").into());
+    };
+
+    let mut cmd = Command::new(program);
+    cmd.args(parts).arg(target);
+    if let Some(dir) = dir {
+        cmd.current_dir(dir);
+    }
+    let status = cmd.status().map_err(|e| {
+        std::io::Error::new(e.kind(), format!("failed to run '{}': {}", program, e))
+    })?;
+    if !status.success() {
+        return Err(std::io::Error::other(format!("'{}' exited with {}", editor, status)).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_choose_editor() {
+        let env = |key: &str| (key == "EDITOR").then(|| "vim".to_string());
+        let no_env = |_: &str| None;
+        let zed_installed = |program: &str| program == "zed";
+
+        assert_eq!(
+            choose_editor(Some("code --wait"), env, zed_installed).as_deref(),
+            Some("code --wait")
+        );
+        assert_eq!(
+            choose_editor(None, env, zed_installed).as_deref(),
+            Some("vim")
+        );
+        // $VISUAL wins over $EDITOR, and blank values are ignored
+        let both = |key: &str| Some(if key == "VISUAL" { " " } else { "nano" }.to_string());
+        assert_eq!(
+            choose_editor(None, both, zed_installed).as_deref(),
+            Some("nano")
+        );
+        assert_eq!(
+            choose_editor(None, no_env, zed_installed).as_deref(),
+            Some("zed")
+        );
+        assert_eq!(choose_editor(None, no_env, |_| false), None);
+    }
+}
@@ -1,15 +1,115 @@
+use crate::cli::output::Glyph;
 use crate::error::{CargoJamError, Result};
+use crate::project::manifest::{GenerationManifest, TemplateOrigin, MANIFEST_FILE};
+use crate::template::config::GenerateConfig;
 use crate::template::config::TemplateConfig;
 use crate::template::engine::TemplateEngine;
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
+use walkdir::{DirEntry, WalkDir};
+
+/// How a generated path was produced
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeneratedKind {
+    Directory,
+    /// Rendered through the template engine
+    Rendered,
+    /// Copied from the template unchanged
+    Copied,
+}
+
+/// A path written by `ProjectGenerator::generate`, relative to the output directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedPath {
+    pub path: String,
+    pub kind: GeneratedKind,
+}
+
+/// What generation would do with a template file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlannedAction {
+    /// Rendered through the template engine
+    Render,
+    /// Copied from the template unchanged
+    Copy,
+    /// Left out by the template's ignore rules
+    Skip,
+}
+
+/// A file `ProjectGenerator::plan` expects to write, relative to the output directory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlannedFile {
+    pub path: String,
+    pub action: PlannedAction,
+    /// Template file it is produced from, relative to the template directory
+    pub source: Option<String>,
+}
+
+/// One entry of a template walk: a directory to create or a file to produce
+enum Step {
+    Directory(String),
+    File(PlannedFile),
+}
+
+/// Lay out generated paths as a tree, one line per entry below the project root.
+///
+/// Files are annotated with how they were produced, e.g. `├── Cargo.toml (rendered)`.
+pub fn tree_lines(paths: &[GeneratedPath]) -> Vec<String> {
+    #[derive(Default)]
+    struct Node {
+        kind: Option<GeneratedKind>,
+        children: BTreeMap<String, Node>,
+    }
+
+    fn render(node: &Node, prefix: &str, lines: &mut Vec<String>) {
+        let count = node.children.len();
+        for (index, (name, child)) in node.children.iter().enumerate() {
+            let last = index + 1 == count;
+            let branch = if last {
+                Glyph::TreeLast
+            } else {
+                Glyph::TreeBranch
+            };
+            let label = match child.kind {
+                Some(GeneratedKind::Rendered) => format!("{} (rendered)", name),
+                Some(GeneratedKind::Copied) => format!("{} (copied)", name),
+                _ => format!("{}/", name),
+            };
+            lines.push(format!("{}{}{}", prefix, branch, label));
+            let indent = if last {
+                "    "
+            } else {
+                Glyph::TreePipe.as_str()
+            };
+            render(child, &format!("{}{}", prefix, indent), lines);
+        }
+    }
+
+    let mut root = Node::default();
+    for generated in paths {
+        let mut node = &mut root;
+        for part in generated.path.split(['/', '\\']).filter(|p| !p.is_empty()) {
+            node = node.children.entry(part.to_string()).or_default();
+        }
+        node.kind = Some(generated.kind);
+    }
+
+    let mut lines = Vec::new();
+    render(&root, "", &mut lines);
+    lines
+}
 
 pub struct ProjectGenerator {
     template_dir: PathBuf,
     output_dir: PathBuf,
     config: TemplateConfig,
     engine: TemplateEngine,
+    origin: Option<TemplateOrigin>,
+    toolchain: Option<String>,
+    /// Generated paths to move elsewhere, as (from, to) with `/` separators
+    renames: Vec<(String, String)>,
 }
 
 impl ProjectGenerator {
@@ -19,64 +119,390 @@ impl ProjectGenerator {
             output_dir,
             config,
             engine: TemplateEngine::new().expect("Failed to create template engine"),
+            origin: None,
+            toolchain: None,
+            renames: Vec::new(),
         }
     }
 
-    pub fn generate(&self, variables: &HashMap<String, String>) -> Result<()> {
+    /// Record where the template came from in the manifest, so `update` can re-render it
+    pub fn origin(mut self, origin: TemplateOrigin) -> Self {
+        self.origin = Some(origin);
+        self
+    }
+
+    /// Record the installed toolchain version in the manifest
+    pub fn toolchain(mut self, version: Option<String>) -> Self {
+        self.toolchain = version;
+        self
+    }
+
+    /// Error on undefined template variables instead of rendering them as empty
+    pub fn strict_undefined(mut self, strict: bool) -> Self {
+        self.engine = self.engine.strict_undefined(strict);
+        self
+    }
+
+    /// Seed the `random_hex` and `uuid` filters for reproducible output
+    pub fn seed(mut self, seed: Option<u64>) -> Self {
+        self.engine = self.engine.seed(seed);
+        self
+    }
+
+    /// Move generated paths elsewhere in the output, as (from, to) pairs.
+    ///
+    /// A directory takes its contents along. Both sides are output paths, after
+    /// `.liquid` stripping and filename templating.
+    pub fn renames(mut self, renames: Vec<(String, String)>) -> Self {
+        self.renames = renames
+            .into_iter()
+            .map(|(from, to)| (normalize_relative(&from), normalize_relative(&to)))
+            .collect();
+        self
+    }
+
+    /// Generate the project, returning every directory and file written
+    pub fn generate(&self, variables: &HashMap<String, String>) -> Result<Vec<GeneratedPath>> {
+        // Everything is decided before the output directory is touched
+        let steps = self.steps(variables)?;
+
         // Create output directory
         std::fs::create_dir_all(&self.output_dir)?;
 
-        // Walk through template directory
-        for entry in WalkDir::new(&self.template_dir) {
-            let entry = entry.map_err(|e| {
-                CargoJamError::Io(std::io::Error::other(format!(
-                    "Failed to walk directory: {}",
-                    e
-                )))
-            })?;
+        // Track hashes of generated files so local edits can be detected later
+        let mut manifest = GenerationManifest::new();
+        let mut written = Vec::new();
+        if self.origin.is_some() {
+            manifest.template = self.origin.clone();
+            manifest.seed = Some(self.engine.current_seed().to_string());
+            manifest.toolchain = self.toolchain.clone();
+            manifest.renames = self.renames.iter().cloned().collect();
+            manifest.variables = variables
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect();
+        }
+
+        for step in steps {
+            match step {
+                Step::Directory(path) => {
+                    std::fs::create_dir_all(self.output_dir.join(&path))?;
+                    written.push(GeneratedPath {
+                        path,
+                        kind: GeneratedKind::Directory,
+                    });
+                }
+                Step::File(file) => {
+                    let Some(ref source) = file.source else {
+                        continue;
+                    };
+                    if file.action == PlannedAction::Skip {
+                        continue;
+                    }
+                    let output_path = self.output_dir.join(&file.path);
+                    if let Some(parent) = output_path.parent() {
+                        std::fs::create_dir_all(parent)?;
+                    }
+                    let (contents, kind) =
+                        self.process_file(source, &output_path, file.action, variables)?;
+                    manifest.record(&file.path, &contents);
+                    written.push(GeneratedPath {
+                        path: file.path,
+                        kind,
+                    });
+                }
+            }
+        }
+
+        self.generate_loops(variables, &mut manifest, &mut written)?;
+
+        manifest.save(&self.output_dir)?;
 
+        Ok(written)
+    }
+
+    /// The files `generate` would write, without touching the output directory.
+    ///
+    /// Files left out by the template's ignore rules are listed as skipped, under their
+    /// template path with `.liquid` stripped. The generation manifest is listed too,
+    /// without a source.
+    pub fn plan(&self, variables: &HashMap<String, String>) -> Result<Vec<PlannedFile>> {
+        let mut planned: Vec<PlannedFile> = self
+            .steps(variables)?
+            .into_iter()
+            .filter_map(|step| match step {
+                Step::File(file) => Some(file),
+                Step::Directory(_) => None,
+            })
+            .collect();
+
+        for generate in &self.config.generate {
+            for (relative, _) in self.loop_outputs(generate, variables)? {
+                planned.push(PlannedFile {
+                    path: relative,
+                    action: PlannedAction::Render,
+                    source: Some(generate.source.trim_start_matches("./").to_string()),
+                });
+            }
+        }
+
+        planned.push(PlannedFile {
+            path: MANIFEST_FILE.to_string(),
+            action: PlannedAction::Render,
+            source: None,
+        });
+        Ok(planned)
+    }
+
+    /// Walk the template once, deciding where each directory and file goes and how it
+    /// is produced. `[[generate]]` sources are left to `loop_outputs`.
+    fn steps(&self, variables: &HashMap<String, String>) -> Result<Vec<Step>> {
+        self.check_renames(variables)?;
+
+        let mut steps = Vec::new();
+        for entry in self.template_entries()? {
             let path = entry.path();
             let relative_path = path.strip_prefix(&self.template_dir).unwrap_or(path);
-
             // Skip the template directory itself
             if relative_path.as_os_str().is_empty() {
                 continue;
             }
-
             let relative_str = relative_path.to_string_lossy().to_string();
+            // Listed once per rendered output
+            if self.is_loop_source(&relative_str) {
+                continue;
+            }
+
+            let ignored = self.config.should_ignore_file(&relative_str);
+            if entry.file_type().is_dir() {
+                if !ignored {
+                    let output = self.process_filename(&relative_str, variables)?;
+                    steps.push(Step::Directory(normalize_relative(&output)));
+                }
+                continue;
+            }
+            if !entry.file_type().is_file() {
+                continue;
+            }
 
-            // Check if this path should be ignored
-            if self.config.should_ignore_file(&relative_str) {
+            let source = Some(relative_str.replace('\\', "/"));
+            if ignored {
+                let path = relative_str
+                    .strip_suffix(".liquid")
+                    .unwrap_or(&relative_str);
+                steps.push(Step::File(PlannedFile {
+                    path: normalize_relative(path),
+                    action: PlannedAction::Skip,
+                    source,
+                }));
                 continue;
             }
 
-            // Process the filename (may contain template variables)
-            let processed_filename = self.process_filename(&relative_str, variables)?;
+            let action = if self.should_render(path, &relative_str) {
+                PlannedAction::Render
+            } else {
+                PlannedAction::Copy
+            };
+            steps.push(Step::File(PlannedFile {
+                path: normalize_relative(&self.process_filename(&relative_str, variables)?),
+                action,
+                source,
+            }));
+        }
 
-            // Determine the output path
-            let output_path = self.output_dir.join(&processed_filename);
+        Ok(steps)
+    }
 
-            if entry.file_type().is_dir() {
-                // Create directory
-                std::fs::create_dir_all(&output_path)?;
-            } else if entry.file_type().is_file() {
-                // Ensure parent directory exists
+    /// Whether a template file is only rendered through a `[[generate]]` entry
+    fn is_loop_source(&self, relative_path: &str) -> bool {
+        self.config.generate.iter().any(|generate| {
+            generate.source.trim_start_matches("./") == relative_path.replace('\\', "/")
+        })
+    }
+
+    /// Render each `[[generate]]` source once per item of its list variable
+    fn generate_loops(
+        &self,
+        variables: &HashMap<String, String>,
+        manifest: &mut GenerationManifest,
+        written: &mut Vec<GeneratedPath>,
+    ) -> Result<()> {
+        for generate in &self.config.generate {
+            let source = generate.source.trim_start_matches("./");
+            let outputs = self.loop_outputs(generate, variables)?;
+            let content = std::fs::read_to_string(self.template_dir.join(source))?;
+
+            for (relative, item_variables) in outputs {
+                let rendered =
+                    self.engine
+                        .render(&content, &item_variables)
+                        .map_err(|e| match e {
+                            CargoJamError::TemplateRender(msg) => CargoJamError::TemplateRender(
+                                format!("in {} (for {}): {}", source, relative, msg),
+                            ),
+                            other => other,
+                        })?;
+
+                let output_path = self.output_dir.join(&relative);
                 if let Some(parent) = output_path.parent() {
                     std::fs::create_dir_all(parent)?;
                 }
+                std::fs::write(&output_path, &rendered)?;
+                manifest.record(&relative, rendered.as_bytes());
+                written.push(GeneratedPath {
+                    path: relative,
+                    kind: GeneratedKind::Rendered,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The output path of each item of a `[[generate]]` entry, with the variables it is
+    /// rendered with
+    fn loop_outputs(
+        &self,
+        generate: &GenerateConfig,
+        variables: &HashMap<String, String>,
+    ) -> Result<Vec<(String, HashMap<String, String>)>> {
+        if !self
+            .template_dir
+            .join(generate.source.trim_start_matches("./"))
+            .is_file()
+        {
+            return Err(CargoJamError::TemplateConfig(format!(
+                "generate: source '{}' not found in template",
+                generate.source
+            )));
+        }
+
+        let mut outputs = Vec::new();
+        for (index, item) in generate.items(variables)?.into_iter().enumerate() {
+            let mut item_variables = variables.clone();
+            item_variables.insert(generate.item.clone(), item);
+            item_variables.insert(format!("{}_index", generate.item), index.to_string());
+
+            let relative = self.engine.render(&generate.output, &item_variables)?;
+            if Path::new(&relative)
+                .components()
+                .any(|c| !matches!(c, std::path::Component::Normal(_)))
+            {
+                return Err(CargoJamError::TemplateConfig(format!(
+                    "generate: output '{}' must be a relative path inside the project",
+                    relative
+                )));
+            }
+            outputs.push((relative, item_variables));
+        }
+        Ok(outputs)
+    }
+
+    /// Collect template entries in a stable order.
+    ///
+    /// Entries are sorted by file name within each directory and every directory
+    /// is yielded before its children, so output is reproducible across platforms.
+    fn template_entries(&self) -> Result<Vec<DirEntry>> {
+        WalkDir::new(&self.template_dir)
+            .sort_by_file_name()
+            .into_iter()
+            .map(|entry| {
+                entry.map_err(|e| {
+                    CargoJamError::Io(std::io::Error::other(format!(
+                        "Failed to walk directory: {}",
+                        e
+                    )))
+                })
+            })
+            .collect()
+    }
+
+    /// Check that every rename source is generated by the template and that no
+    /// destination collides with another generated path
+    fn check_renames(&self, variables: &HashMap<String, String>) -> Result<()> {
+        if self.renames.is_empty() {
+            return Ok(());
+        }
 
-                // Process file
-                self.process_file(path, &output_path, &relative_str, variables)?;
+        let mut generated = Vec::new();
+        for entry in self.template_entries()? {
+            let relative_path = entry
+                .path()
+                .strip_prefix(&self.template_dir)
+                .unwrap_or(entry.path());
+            let relative_str = relative_path.to_string_lossy().to_string();
+            if relative_path.as_os_str().is_empty()
+                || self.config.should_ignore_file(&relative_str)
+                || self.is_loop_source(&relative_str)
+            {
+                continue;
+            }
+            let original = normalize_relative(&self.template_filename(&relative_str, variables)?);
+            generated.push((original, entry.file_type().is_dir()));
+        }
+
+        let rename_error = |reason: String| CargoJamError::InvalidArgument {
+            arg: "--rename".to_string(),
+            reason,
+        };
+        for (from, _) in &self.renames {
+            if !generated.iter().any(|(path, _)| path == from) {
+                return Err(rename_error(format!(
+                    "'{}' is not generated by the template",
+                    from
+                )));
             }
         }
 
+        // Directories may merge; a file landing on any other path may not
+        let mut destinations: HashMap<String, (&str, bool)> = HashMap::new();
+        for (original, is_dir) in &generated {
+            let destination = self.rename(original);
+            if let Some((other, other_is_dir)) = destinations.get(&destination) {
+                if !(*is_dir && *other_is_dir) {
+                    return Err(rename_error(format!(
+                        "'{}' would be generated from both '{}' and '{}'",
+                        destination, other, original
+                    )));
+                }
+            }
+            destinations.insert(destination, (original, *is_dir));
+        }
+
         Ok(())
     }
 
+    /// Where a generated path ends up after `--rename`, by its most specific source
+    fn rename(&self, path: &str) -> String {
+        let normalized = normalize_relative(path);
+        self.renames
+            .iter()
+            .filter_map(|(from, to)| {
+                if normalized == *from {
+                    return Some((from.len(), to.clone()));
+                }
+                let rest = normalized.strip_prefix(&format!("{}/", from))?;
+                Some((from.len(), format!("{}/{}", to, rest)))
+            })
+            .max_by_key(|(len, _)| *len)
+            .map(|(_, renamed)| renamed)
+            .unwrap_or_else(|| path.to_string())
+    }
+
     fn process_filename(
         &self,
         filename: &str,
         variables: &HashMap<String, String>,
+    ) -> Result<String> {
+        let templated = self.template_filename(filename, variables)?;
+        Ok(self.rename(&templated))
+    }
+
+    /// The output name of a template path: `.liquid` stripped and placeholders rendered
+    fn template_filename(
+        &self,
+        filename: &str,
+        variables: &HashMap<String, String>,
     ) -> Result<String> {
         let mut result = filename.to_string();
 
@@ -93,34 +519,299 @@ impl ProjectGenerator {
         Ok(result)
     }
 
-    fn process_file(
-        &self,
-        source_path: &Path,
-        output_path: &Path,
-        relative_path: &str,
-        variables: &HashMap<String, String>,
-    ) -> Result<()> {
+    /// Whether a template file goes through the template engine rather than being copied
+    fn should_render(&self, source_path: &Path, relative_path: &str) -> bool {
         let is_liquid = source_path
             .extension()
             .map(|e| e == "liquid")
             .unwrap_or(false);
 
-        let should_process = is_liquid || self.config.should_process_file(relative_path);
+        is_liquid || self.config.should_process_file(relative_path)
+    }
 
-        if should_process {
+    /// Render or copy a template file (relative to the template directory) to `output_path`
+    fn process_file(
+        &self,
+        source: &str,
+        output_path: &Path,
+        action: PlannedAction,
+        variables: &HashMap<String, String>,
+    ) -> Result<(Vec<u8>, GeneratedKind)> {
+        let source_path = self.template_dir.join(source);
+        if action == PlannedAction::Render {
             // Read the file content
-            let content = std::fs::read_to_string(source_path)?;
+            let content = std::fs::read_to_string(&source_path)?;
 
-            // Render the template
-            let rendered = self.engine.render(&content, variables)?;
+            // Render the template, naming the file on failure
+            let rendered = self
+                .engine
+                .render(&content, variables)
+                .map_err(|e| match e {
+                    CargoJamError::TemplateRender(msg) => {
+                        CargoJamError::TemplateRender(format!("in {}: {}", source, msg))
+                    }
+                    other => other,
+                })?;
 
             // Write the output
-            std::fs::write(output_path, rendered)?;
+            std::fs::write(output_path, &rendered)?;
+
+            Ok((rendered.into_bytes(), GeneratedKind::Rendered))
         } else {
             // Copy the file as-is
-            std::fs::copy(source_path, output_path)?;
+            std::fs::copy(&source_path, output_path)?;
+
+            Ok((std::fs::read(output_path)?, GeneratedKind::Copied))
         }
+    }
+}
 
-        Ok(())
+/// A relative path with `/` separators and without `./` prefixes or trailing slashes
+fn normalize_relative(path: &str) -> String {
+    path.replace('\\', "/")
+        .split('/')
+        .filter(|part| !part.is_empty() && *part != ".")
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_entries_are_sorted() {
+        let template = tempfile::tempdir().unwrap();
+        for file in ["zeta.txt", "src/main.rs", "alpha.txt", "src/a/b.rs"] {
+            let path = template.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+
+        let config: TemplateConfig = toml::from_str("[template]\nname = \"test\"").unwrap();
+        let generator = ProjectGenerator::new(
+            template.path().to_path_buf(),
+            template.path().join("out"),
+            config,
+        );
+
+        let paths: Vec<String> = generator
+            .template_entries()
+            .unwrap()
+            .iter()
+            .map(|e| {
+                e.path()
+                    .strip_prefix(template.path())
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect();
+
+        assert_eq!(
+            paths,
+            [
+                "",
+                "alpha.txt",
+                "src",
+                "src/a",
+                "src/a/b.rs",
+                "src/main.rs",
+                "zeta.txt"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_renames() {
+        let template = tempfile::tempdir().unwrap();
+        let out = tempfile::tempdir().unwrap();
+        for file in ["README.md.liquid", "src/lib.rs", "src/util.rs"] {
+            let path = template.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+        let generator = |renames: &[(&str, &str)]| {
+            let config = toml::from_str("[template]\nname = \"test\"").unwrap();
+            ProjectGenerator::new(
+                template.path().to_path_buf(),
+                out.path().join("svc"),
+                config,
+            )
+            .renames(
+                renames
+                    .iter()
+                    .map(|(from, to)| (from.to_string(), to.to_string()))
+                    .collect(),
+            )
+        };
+
+        let output = out.path().join("svc");
+        generator(&[("README.md", "docs/README.md"), ("src", "./service/")])
+            .generate(&HashMap::new())
+            .unwrap();
+        assert!(output.join("docs/README.md").is_file());
+        assert!(output.join("service/lib.rs").is_file());
+        assert!(!output.join("src").exists());
+
+        for renames in [
+            [("src/main.rs", "src/bin.rs")],
+            [("src/lib.rs", "src/util.rs")],
+        ] {
+            let err = generator(&renames).generate(&HashMap::new()).unwrap_err();
+            assert!(
+                matches!(err, CargoJamError::InvalidArgument { .. }),
+                "{}",
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn test_generate_loop() {
+        let template = tempfile::tempdir().unwrap();
+        let out = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(template.path().join("src")).unwrap();
+        std::fs::write(
+            template.path().join("src/module.rs.liquid"),
+            "// {{ module_index }}: {{ module }} for {{ project_name }}\n",
+        )
+        .unwrap();
+
+        let config: TemplateConfig = toml::from_str(
+            r#"
+            [template]
+            name = "test"
+
+            [[generate]]
+            source = "src/module.rs.liquid"
+            output = "src/{{ module | snake_case }}.rs"
+            foreach = "modules"
+            as = "module"
+            "#,
+        )
+        .unwrap();
+
+        let mut variables = HashMap::new();
+        variables.insert("project_name".to_string(), "svc".to_string());
+        variables.insert(
+            "modules".to_string(),
+            "auth, work-queue,storage".to_string(),
+        );
+
+        let output = out.path().join("svc");
+        ProjectGenerator::new(template.path().to_path_buf(), output.clone(), config)
+            .generate(&variables)
+            .unwrap();
+
+        assert!(!output.join("src/module.rs").exists());
+        for (index, name) in ["auth", "work_queue", "storage"].iter().enumerate() {
+            let content = std::fs::read_to_string(output.join(format!("src/{}.rs", name))).unwrap();
+            let expected_item = name.replace('_', "-");
+            assert_eq!(
+                content,
+                format!("// {}: {} for svc\n", index, expected_item)
+            );
+        }
+
+        let manifest = GenerationManifest::load(&output).unwrap();
+        assert!(manifest.files.contains_key("src/work_queue.rs"));
+    }
+
+    #[test]
+    fn test_plan() {
+        let template = tempfile::tempdir().unwrap();
+        for file in [
+            "{{project_name}}.toml.liquid",
+            "data.bin",
+            "secrets.env",
+            "src/module.rs.liquid",
+        ] {
+            let path = template.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "{{ project_name }}").unwrap();
+        }
+
+        let config: TemplateConfig = toml::from_str(
+            r#"
+            [template]
+            name = "test"
+            exclude = ["*.bin"]
+            ignore = ["secrets.env"]
+
+            [[generate]]
+            source = "src/module.rs.liquid"
+            output = "src/{{ module }}.rs"
+            foreach = "modules"
+            as = "module"
+            "#,
+        )
+        .unwrap();
+
+        let mut variables = HashMap::new();
+        variables.insert("project_name".to_string(), "svc".to_string());
+        variables.insert("modules".to_string(), "auth,storage".to_string());
+
+        let output = template.path().join("out");
+        let planned = ProjectGenerator::new(template.path().to_path_buf(), output.clone(), config)
+            .plan(&variables)
+            .unwrap();
+
+        let file = |path: &str, action, source: &str| PlannedFile {
+            path: path.to_string(),
+            action,
+            source: Some(source.to_string()),
+        };
+        assert_eq!(
+            planned,
+            [
+                file("data.bin", PlannedAction::Copy, "data.bin"),
+                file("secrets.env", PlannedAction::Skip, "secrets.env"),
+                file(
+                    "svc.toml",
+                    PlannedAction::Render,
+                    "{{project_name}}.toml.liquid"
+                ),
+                file("src/auth.rs", PlannedAction::Render, "src/module.rs.liquid"),
+                file(
+                    "src/storage.rs",
+                    PlannedAction::Render,
+                    "src/module.rs.liquid"
+                ),
+                PlannedFile {
+                    path: MANIFEST_FILE.to_string(),
+                    action: PlannedAction::Render,
+                    source: None,
+                },
+            ]
+        );
+        assert!(!output.exists());
+    }
+
+    #[test]
+    fn test_tree_lines() {
+        let path = |path: &str, kind| GeneratedPath {
+            path: path.to_string(),
+            kind,
+        };
+        let paths = [
+            path("Cargo.toml", GeneratedKind::Rendered),
+            path("src", GeneratedKind::Directory),
+            path("src/lib.rs", GeneratedKind::Rendered),
+            path("README.md", GeneratedKind::Copied),
+            path("src/extra/mod.rs", GeneratedKind::Rendered),
+        ];
+
+        assert_eq!(
+            tree_lines(&paths),
+            [
+                "├── Cargo.toml (rendered)",
+                "├── README.md (copied)",
+                "└── src/",
+                "    ├── extra/",
+                "    │   └── mod.rs (rendered)",
+                "    └── lib.rs (rendered)",
+            ]
+        );
     }
 }
@@ -0,0 +1,135 @@
+use crate::error::Result;
+use crate::project::manifest::GenerationManifest;
+use std::path::Path;
+
+const MIT: &str = include_str!("licenses/MIT.txt");
+const APACHE_2_0: &str = include_str!("licenses/Apache-2.0.txt");
+const GPL_3_0: &str = include_str!("licenses/GPL-3.0.txt");
+
+/// License texts bundled for `cargo polkajam new --license`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum License {
+    Mit,
+    Apache2,
+    Gpl3,
+    /// `MIT OR Apache-2.0`, the Rust ecosystem's usual dual license
+    MitOrApache2,
+}
+
+impl License {
+    /// Recognize an SPDX expression we have texts for
+    pub fn from_spdx(spdx: &str) -> Option<Self> {
+        let spdx = spdx.trim().to_ascii_lowercase();
+        match spdx.as_str() {
+            "mit" => Some(Self::Mit),
+            "apache-2.0" => Some(Self::Apache2),
+            "gpl-3.0" | "gpl-3.0-only" | "gpl-3.0-or-later" => Some(Self::Gpl3),
+            "mit or apache-2.0" | "apache-2.0 or mit" | "mit/apache-2.0" => {
+                Some(Self::MitOrApache2)
+            }
+            _ => None,
+        }
+    }
+
+    /// The license files to write, as (file name, contents)
+    pub fn files(self, holder: &str, year: i32) -> Vec<(&'static str, String)> {
+        let mit = || {
+            MIT.replace("{year}", &year.to_string())
+                .replace("{holder}", holder)
+        };
+        let notice = |text: &str| format!("Copyright {} {}\n\n{}", year, holder, text);
+
+        match self {
+            Self::Mit => vec![("LICENSE", mit())],
+            Self::Apache2 => vec![("LICENSE", notice(APACHE_2_0))],
+            Self::Gpl3 => vec![("LICENSE", notice(GPL_3_0))],
+            Self::MitOrApache2 => vec![
+                ("LICENSE-MIT", mit()),
+                ("LICENSE-APACHE", notice(APACHE_2_0)),
+            ],
+        }
+    }
+}
+
+/// Names of every file `write_license` may write
+pub const LICENSE_FILES: &[&str] = &["LICENSE", "LICENSE-MIT", "LICENSE-APACHE"];
+
+/// Write a project's license files, returning their names.
+///
+/// The files are recorded in the project's generation manifest, if it has one.
+pub fn write_license(
+    project_dir: &Path,
+    license: License,
+    holder: &str,
+    year: i32,
+) -> Result<Vec<&'static str>> {
+    let files = license.files(holder, year);
+    for (name, contents) in &files {
+        std::fs::write(project_dir.join(name), contents)?;
+    }
+
+    if let Ok(mut manifest) = GenerationManifest::load(project_dir) {
+        for (name, contents) in &files {
+            manifest.record(name, contents.as_bytes());
+        }
+        manifest.save(project_dir)?;
+    }
+
+    Ok(files.into_iter().map(|(name, _)| name).collect())
+}
+
+/// The current calendar year (UTC)
+pub fn current_year() -> i32 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    year_from_days((secs / 86_400) as i64)
+}
+
+/// Year of the day `days` after 1970-01-01 in the proleptic Gregorian calendar
+fn year_from_days(days: i64) -> i32 {
+    // Howard Hinnant's civil_from_days, counting eras of 400 years from 0000-03-01
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    year as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_license_files() {
+        assert_eq!(License::from_spdx("gpl-3.0-or-later"), Some(License::Gpl3));
+        assert_eq!(
+            License::from_spdx("Apache-2.0 OR MIT"),
+            Some(License::MitOrApache2)
+        );
+        assert_eq!(License::from_spdx("Unlicense"), None);
+
+        let files = License::MitOrApache2.files("Jane Doe", 2026);
+        let names: Vec<_> = files.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, ["LICENSE-MIT", "LICENSE-APACHE"]);
+        assert!(files[0].1.contains("Copyright (c) 2026 Jane Doe\n"));
+        assert!(files[1].1.starts_with("Copyright 2026 Jane Doe\n"));
+        assert!(files[1].1.contains("Version 2.0, January 2004"));
+    }
+
+    #[test]
+    fn test_year_from_days() {
+        assert_eq!(year_from_days(0), 1970);
+        assert_eq!(year_from_days(364), 1970);
+        assert_eq!(year_from_days(365), 1971);
+        // 2024-12-31 and 2025-01-01
+        assert_eq!(year_from_days(20_088), 2024);
+        assert_eq!(year_from_days(20_089), 2025);
+    }
+}
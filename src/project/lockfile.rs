@@ -0,0 +1,111 @@
+use crate::error::{CargoJamError, Result};
+use crate::project::manifest::GenerationManifest;
+use std::path::Path;
+use std::process::Command;
+
+/// What to do with `Cargo.lock` in a generated project
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LockfileStrategy {
+    /// Leave whatever lockfile the template provides
+    Keep,
+    /// Delete any lockfile shipped by the template
+    #[default]
+    Remove,
+    /// Run `cargo generate-lockfile` in the generated project
+    Generate,
+}
+
+impl LockfileStrategy {
+    /// The strategy that reproduces a previous generation: keep the lockfile only if the
+    /// manifest still records the template's copy
+    pub fn recorded(manifest: &GenerationManifest) -> Self {
+        if manifest.files.contains_key("Cargo.lock") {
+            LockfileStrategy::Keep
+        } else {
+            LockfileStrategy::Remove
+        }
+    }
+}
+
+/// Apply a lockfile strategy to a freshly generated project
+pub fn apply_lockfile_strategy(project_dir: &Path, strategy: LockfileStrategy) -> Result<()> {
+    let lockfile = project_dir.join("Cargo.lock");
+
+    match strategy {
+        LockfileStrategy::Keep => Ok(()),
+        LockfileStrategy::Remove => {
+            if lockfile.exists() {
+                std::fs::remove_file(&lockfile)?;
+            }
+            forget_template_lockfile(project_dir)
+        }
+        LockfileStrategy::Generate => {
+            let output = Command::new("cargo")
+                .arg("generate-lockfile")
+                .current_dir(project_dir)
+                .output()
+                .map_err(|e| CargoJamError::Build(format!("Failed to execute cargo: {}", e)))?;
+
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(CargoJamError::Build(format!(
+                    "cargo generate-lockfile failed:\n{}",
+                    stderr
+                )));
+            }
+
+            forget_template_lockfile(project_dir)
+        }
+    }
+}
+
+/// Drop the template's lockfile from the generation manifest so `diff` doesn't report it
+fn forget_template_lockfile(project_dir: &Path) -> Result<()> {
+    if let Ok(mut manifest) = GenerationManifest::load(project_dir) {
+        if manifest.files.remove("Cargo.lock").is_some() {
+            manifest.save(project_dir)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generated_project() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.lock"), "# lock").unwrap();
+        let mut manifest = GenerationManifest::new();
+        manifest.record("Cargo.lock", b"# lock");
+        manifest.save(dir.path()).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_remove_deletes_lockfile_and_manifest_entry() {
+        let dir = generated_project();
+        apply_lockfile_strategy(dir.path(), LockfileStrategy::Remove).unwrap();
+
+        assert!(!dir.path().join("Cargo.lock").exists());
+        let manifest = GenerationManifest::load(dir.path()).unwrap();
+        assert!(!manifest.files.contains_key("Cargo.lock"));
+        assert_eq!(
+            LockfileStrategy::recorded(&manifest),
+            LockfileStrategy::Remove
+        );
+    }
+
+    #[test]
+    fn test_keep_leaves_lockfile_recorded() {
+        let dir = generated_project();
+        apply_lockfile_strategy(dir.path(), LockfileStrategy::Keep).unwrap();
+
+        assert!(dir.path().join("Cargo.lock").exists());
+        let manifest = GenerationManifest::load(dir.path()).unwrap();
+        assert_eq!(
+            LockfileStrategy::recorded(&manifest),
+            LockfileStrategy::Keep
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use crate::error::{CargoJamError, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// File name of the generation manifest written into generated projects
+pub const MANIFEST_FILE: &str = ".cargo-jam-manifest.toml";
+
+/// Record of the files written by `cargo polkajam new` and their hashes
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct GenerationManifest {
+    /// Template the project was generated from, used by `update` to re-render it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<TemplateOrigin>,
+    /// Seed of the `random_hex` and `uuid` filters (a string, as it may exceed TOML's integer range)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<String>,
+    /// JAM toolchain installed when the project was generated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub toolchain: Option<String>,
+    /// Generated paths moved with `new --rename`, from the template's path to the project's
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub renames: BTreeMap<String, String>,
+    /// Variables the template was rendered with
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub variables: BTreeMap<String, String>,
+    /// SHA-256 of each generated file, keyed by its path relative to the project root
+    #[serde(default)]
+    pub files: BTreeMap<String, String>,
+}
+
+/// Where a generated project's template came from
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateOrigin {
+    /// Name of a bundled template
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bundled: Option<String>,
+    /// Git repository URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// Commit, tag or branch checked out after cloning
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rev: Option<String>,
+    /// Template directory within the repository
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Named conditionals applied to the template config, e.g. `minimal`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conditionals: Vec<String>,
+}
+
+/// State of a generated file compared to its recorded hash
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileStatus {
+    Unchanged,
+    Modified,
+    Missing,
+}
+
+impl GenerationManifest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the hash of a generated file's contents
+    pub fn record(&mut self, relative_path: &str, contents: &[u8]) {
+        self.files
+            .insert(relative_path.replace('\\', "/"), hash_bytes(contents));
+    }
+
+    /// Load the manifest from a generated project directory
+    pub fn load(project_dir: &Path) -> Result<Self> {
+        let path = project_dir.join(MANIFEST_FILE);
+        if !path.exists() {
+            return Err(CargoJamError::NotJamProject(format!(
+                "{} not found (was this project generated by cargo polkajam new?)",
+                MANIFEST_FILE
+            )));
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        toml::from_str(&content).map_err(|e| {
+            CargoJamError::TemplateConfig(format!("Failed to parse {}: {}", MANIFEST_FILE, e))
+        })
+    }
+
+    /// Save the manifest into a generated project directory
+    pub fn save(&self, project_dir: &Path) -> Result<()> {
+        let content = toml::to_string_pretty(self).map_err(|e| {
+            CargoJamError::TemplateConfig(format!("Failed to serialize {}: {}", MANIFEST_FILE, e))
+        })?;
+        std::fs::write(project_dir.join(MANIFEST_FILE), content)?;
+        Ok(())
+    }
+
+    /// Compare the files on disk against the recorded hashes
+    pub fn diff(&self, project_dir: &Path) -> Result<Vec<(String, FileStatus)>> {
+        let mut statuses = Vec::with_capacity(self.files.len());
+
+        for (relative_path, recorded) in &self.files {
+            let path = project_dir.join(relative_path);
+            let status = if !path.exists() {
+                FileStatus::Missing
+            } else if &hash_bytes(&std::fs::read(&path)?) == recorded {
+                FileStatus::Unchanged
+            } else {
+                FileStatus::Modified
+            };
+            statuses.push((relative_path.clone(), status));
+        }
+
+        Ok(statuses)
+    }
+}
+
+/// Hex-encoded SHA-256 of a byte slice
+pub fn hash_bytes(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_detects_modified_and_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.txt"), "a").unwrap();
+        std::fs::write(dir.path().join("b.txt"), "b").unwrap();
+
+        let mut manifest = GenerationManifest::new();
+        manifest.record("a.txt", b"a");
+        manifest.record("b.txt", b"b");
+        manifest.record("c.txt", b"c");
+
+        std::fs::write(dir.path().join("b.txt"), "edited").unwrap();
+
+        let statuses = manifest.diff(dir.path()).unwrap();
+        assert_eq!(
+            statuses,
+            [
+                ("a.txt".to_string(), FileStatus::Unchanged),
+                ("b.txt".to_string(), FileStatus::Modified),
+                ("c.txt".to_string(), FileStatus::Missing),
+            ]
+        );
+    }
+}
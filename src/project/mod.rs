@@ -1,3 +1,10 @@
+pub mod deps;
+pub mod editor;
 pub mod generator;
 pub mod git_init;
+pub mod license;
+pub mod lockfile;
+pub mod manifest;
+pub mod update;
 pub mod validation;
+pub mod workspace;
@@ -0,0 +1,142 @@
+use crate::error::Result;
+use crate::project::manifest::{hash_bytes, GenerationManifest};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// Suffix of the file the new template output is written to when it conflicts with local edits
+pub const CONFLICT_SUFFIX: &str = ".new";
+
+/// What `update` does with one generated file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateAction {
+    /// Already matches the new template output
+    UpToDate,
+    /// Unmodified locally, replaced with the new template output
+    Update,
+    /// New in the template
+    Add,
+    /// Dropped from the template and unmodified locally, deleted
+    Remove,
+    /// Modified or deleted locally while the template output is unchanged, left alone
+    KeepLocal,
+    /// Dropped from the template but modified locally, left alone
+    KeepOrphan,
+    /// Changed both locally and in the template, the new output goes to `<file>.new`
+    /// for a manual merge
+    Conflict,
+}
+
+/// Decide what to do with a file from its three hashes: as generated (`recorded`),
+/// as the template renders it now (`rendered`) and as it is on disk (`current`).
+///
+/// Only hashes are compared; the generated contents aren't kept, so there is no base
+/// to merge lines against. A file changed on both sides becomes a [`UpdateAction::Conflict`]
+/// and the new output is written beside it instead (conflict-file mode).
+pub fn plan_file(
+    recorded: Option<&str>,
+    rendered: Option<&str>,
+    current: Option<&str>,
+) -> UpdateAction {
+    match (recorded, rendered, current) {
+        (_, Some(rendered), Some(current)) if rendered == current => UpdateAction::UpToDate,
+        (Some(recorded), Some(_), Some(current)) if recorded == current => UpdateAction::Update,
+        (Some(recorded), Some(rendered), _) if recorded == rendered => UpdateAction::KeepLocal,
+        (Some(_), Some(_), _) => UpdateAction::Conflict,
+        (None, Some(_), None) => UpdateAction::Add,
+        (None, Some(_), Some(_)) => UpdateAction::Conflict,
+        (Some(recorded), None, Some(current)) if recorded == current => UpdateAction::Remove,
+        (Some(_), None, Some(_)) => UpdateAction::KeepOrphan,
+        (_, None, _) => UpdateAction::UpToDate,
+    }
+}
+
+/// Plan the update of every file recorded in `previous` or rendered into `rendered`
+pub fn plan_update(
+    previous: &GenerationManifest,
+    rendered: &GenerationManifest,
+    project_dir: &Path,
+) -> Result<Vec<(String, UpdateAction)>> {
+    let paths: BTreeSet<&String> = previous.files.keys().chain(rendered.files.keys()).collect();
+
+    let mut plan = Vec::with_capacity(paths.len());
+    for path in paths {
+        let file = project_dir.join(path);
+        let current = if file.is_file() {
+            Some(hash_bytes(&std::fs::read(&file)?))
+        } else {
+            None
+        };
+        let action = plan_file(
+            previous.files.get(path).map(String::as_str),
+            rendered.files.get(path).map(String::as_str),
+            current.as_deref(),
+        );
+        plan.push((path.clone(), action));
+    }
+
+    Ok(plan)
+}
+
+/// Carry out a plan, copying files from the freshly rendered project in `rendered_dir`
+pub fn apply_update(
+    plan: &[(String, UpdateAction)],
+    rendered_dir: &Path,
+    project_dir: &Path,
+) -> Result<()> {
+    for (path, action) in plan {
+        let source = rendered_dir.join(path);
+        let target = match action {
+            UpdateAction::Update | UpdateAction::Add => project_dir.join(path),
+            UpdateAction::Conflict => project_dir.join(format!("{}{}", path, CONFLICT_SUFFIX)),
+            UpdateAction::Remove => {
+                std::fs::remove_file(project_dir.join(path))?;
+                continue;
+            }
+            UpdateAction::UpToDate | UpdateAction::KeepLocal | UpdateAction::KeepOrphan => continue,
+        };
+
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::copy(&source, &target)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_file() {
+        use UpdateAction::*;
+
+        // (recorded, rendered, current)
+        let cases = [
+            (Some("a"), Some("a"), Some("a"), UpToDate),
+            (Some("a"), Some("b"), Some("b"), UpToDate),
+            (Some("a"), Some("b"), Some("a"), Update),
+            (Some("a"), Some("a"), Some("x"), KeepLocal),
+            (Some("a"), Some("a"), None, KeepLocal),
+            (Some("a"), Some("b"), Some("x"), Conflict),
+            (Some("a"), Some("b"), None, Conflict),
+            (None, Some("b"), None, Add),
+            (None, Some("b"), Some("x"), Conflict),
+            (Some("a"), None, Some("a"), Remove),
+            (Some("a"), None, Some("x"), KeepOrphan),
+            (Some("a"), None, None, UpToDate),
+        ];
+
+        for (recorded, rendered, current, expected) in cases {
+            assert_eq!(
+                plan_file(recorded, rendered, current),
+                expected,
+                "recorded={:?} rendered={:?} current={:?}",
+                recorded,
+                rendered,
+                current
+            );
+        }
+    }
+}
@@ -42,6 +42,47 @@ pub fn validate_project_name(name: &str) -> Result<()> {
     Ok(())
 }
 
+/// Names Windows refuses to use for files or directories
+const WINDOWS_DEVICE_NAMES: &[&str] = &[
+    "con", "prn", "aux", "nul", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8",
+    "com9", "lpt1", "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+];
+
+/// Validate a project name with relaxed rules (`--force-name`).
+///
+/// Uppercase letters and reserved keywords are allowed. The name is still written
+/// into `Cargo.toml` as the package name and used as a directory, so it must be
+/// non-empty, at most 64 characters, start with a letter (Cargo rejects a leading
+/// digit), contain only letters, digits, `_` and `-` (Cargo rejects dots), and not
+/// be a Windows device name.
+pub fn validate_project_name_relaxed(name: &str) -> Result<()> {
+    let invalid = |reason: &str| CargoJamError::InvalidProjectName {
+        name: name.to_string(),
+        reason: reason.to_string(),
+    };
+
+    if name.is_empty() {
+        return Err(invalid("Project name cannot be empty"));
+    }
+
+    let re = Regex::new(r"^[A-Za-z][A-Za-z0-9_-]*$").unwrap();
+    if !re.is_match(name) {
+        return Err(invalid(
+            "Must start with a letter and contain only letters, numbers, '_' and '-' (Cargo package names can't start with a digit or contain '.')",
+        ));
+    }
+
+    if WINDOWS_DEVICE_NAMES.contains(&name.to_ascii_lowercase().as_str()) {
+        return Err(invalid("Name is reserved by the filesystem on Windows"));
+    }
+
+    if name.len() > 64 {
+        return Err(invalid("Project name must be 64 characters or less"));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -62,4 +103,19 @@ mod tests {
         assert!(validate_project_name("-service").is_err());
         assert!(validate_project_name("self").is_err());
     }
+
+    #[test]
+    fn test_relaxed_names() {
+        assert!(validate_project_name_relaxed("My-Service").is_ok());
+        assert!(validate_project_name_relaxed("self").is_ok());
+        assert!(validate_project_name_relaxed("My.Service").is_err());
+        assert!(validate_project_name_relaxed("123service").is_err());
+        assert!(validate_project_name_relaxed("").is_err());
+        assert!(validate_project_name_relaxed("..").is_err());
+        assert!(validate_project_name_relaxed("../escape").is_err());
+        assert!(validate_project_name_relaxed(".hidden").is_err());
+        assert!(validate_project_name_relaxed("my service").is_err());
+        assert!(validate_project_name_relaxed("CON").is_err());
+        assert!(validate_project_name_relaxed("nul").is_err());
+    }
 }
@@ -0,0 +1,297 @@
+use crate::error::{CargoJamError, Result};
+use std::path::{Path, PathBuf};
+use toml_edit::{Array, DocumentMut, Item, Table, Value};
+
+/// Outcome of registering a crate with a workspace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemberStatus {
+    /// The path was appended to `workspace.members`
+    Added(String),
+    /// An existing entry (or glob) already covers the path
+    AlreadyMember(String),
+}
+
+/// Find the nearest directory at or above `start` whose Cargo.toml has a `[workspace]` table
+pub fn find_workspace_root(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| is_workspace_root(dir))
+        .map(Path::to_path_buf)
+}
+
+/// Whether `dir` contains a Cargo.toml declaring a workspace
+pub fn is_workspace_root(dir: &Path) -> bool {
+    std::fs::read_to_string(dir.join("Cargo.toml"))
+        .ok()
+        .and_then(|content| content.parse::<DocumentMut>().ok())
+        .is_some_and(|doc| doc.get("workspace").is_some_and(Item::is_table_like))
+}
+
+/// Manifest written by `new --workspace-root` when the root doesn't exist yet
+const WORKSPACE_MANIFEST: &str = "[workspace]\nresolver = \"2\"\nmembers = []\n";
+
+/// Check that `root` can hold a workspace: its Cargo.toml, if any, must declare one
+pub fn check_workspace_root(root: &Path) -> Result<()> {
+    let manifest_path = root.join("Cargo.toml");
+    if manifest_path.exists() && !is_workspace_root(root) {
+        return Err(CargoJamError::InvalidArgument {
+            arg: "--workspace-root".to_string(),
+            reason: format!("{} has no [workspace] table", manifest_path.display()),
+        });
+    }
+    Ok(())
+}
+
+/// Create a workspace at `root` unless one exists, returning whether it was created
+pub fn init_workspace(root: &Path) -> Result<bool> {
+    check_workspace_root(root)?;
+    if is_workspace_root(root) {
+        return Ok(false);
+    }
+
+    std::fs::create_dir_all(root)?;
+    std::fs::write(root.join("Cargo.toml"), WORKSPACE_MANIFEST)?;
+    Ok(true)
+}
+
+/// Move the `[profile.*]` tables of a member's manifest to the workspace root,
+/// where Cargo reads them, returning the names of the moved profiles.
+///
+/// Profiles the root already defines are kept and the member's copy is dropped.
+pub fn hoist_profiles(root: &Path, member_dir: &Path) -> Result<Vec<String>> {
+    let read = |path: &Path| -> Result<DocumentMut> {
+        std::fs::read_to_string(path)?
+            .parse::<DocumentMut>()
+            .map_err(|e| {
+                CargoJamError::WorkspaceManifest(format!(
+                    "Failed to parse {}: {}",
+                    path.display(),
+                    e
+                ))
+            })
+    };
+    let member_path = member_dir.join("Cargo.toml");
+    let root_path = root.join("Cargo.toml");
+    let mut member = read(&member_path)?;
+    let Some(Item::Table(profiles)) = member.remove("profile") else {
+        return Ok(Vec::new());
+    };
+
+    let mut workspace = read(&root_path)?;
+    let root_profiles = workspace
+        .entry("profile")
+        .or_insert_with(|| {
+            let mut table = Table::new();
+            table.set_implicit(true);
+            Item::Table(table)
+        })
+        .as_table_mut()
+        .ok_or_else(|| {
+            CargoJamError::WorkspaceManifest(format!(
+                "'profile' in {} is not a table",
+                root_path.display()
+            ))
+        })?;
+
+    let mut moved = Vec::new();
+    for (name, profile) in profiles.iter() {
+        if !root_profiles.contains_key(name) {
+            root_profiles.insert(name, profile.clone());
+            moved.push(name.to_string());
+        }
+    }
+
+    std::fs::write(&root_path, workspace.to_string())?;
+    std::fs::write(&member_path, member.to_string())?;
+    Ok(moved)
+}
+
+/// Add `member_dir` to the `members` of the workspace rooted at `root`.
+///
+/// The manifest is edited in place, so comments and formatting are preserved.
+pub fn add_workspace_member(root: &Path, member_dir: &Path) -> Result<MemberStatus> {
+    let relative = relative_member_path(root, member_dir)?;
+
+    let manifest_path = root.join("Cargo.toml");
+    let content = std::fs::read_to_string(&manifest_path)?;
+    let mut doc = content.parse::<DocumentMut>().map_err(|e| {
+        CargoJamError::WorkspaceManifest(format!(
+            "Failed to parse {}: {}",
+            manifest_path.display(),
+            e
+        ))
+    })?;
+
+    let workspace = doc
+        .entry("workspace")
+        .or_insert_with(|| Item::Table(Table::new()))
+        .as_table_like_mut()
+        .ok_or_else(|| {
+            CargoJamError::WorkspaceManifest(format!(
+                "'workspace' in {} is not a table",
+                manifest_path.display()
+            ))
+        })?;
+
+    let members = workspace
+        .entry("members")
+        .or_insert(Item::Value(Value::Array(Array::new())))
+        .as_array_mut()
+        .ok_or_else(|| {
+            CargoJamError::WorkspaceManifest(format!(
+                "'workspace.members' in {} is not an array",
+                manifest_path.display()
+            ))
+        })?;
+
+    let covered = members.iter().filter_map(Value::as_str).any(|pattern| {
+        pattern.trim_end_matches('/') == relative
+            || glob::Pattern::new(pattern).is_ok_and(|p| p.matches(&relative))
+    });
+    if covered {
+        return Ok(MemberStatus::AlreadyMember(relative));
+    }
+
+    // Lay the new entry out like the existing ones one per line; an inline list keeps the default spacing
+    let prefix = members
+        .iter()
+        .last()
+        .and_then(|last| last.decor().prefix())
+        .and_then(|prefix| prefix.as_str())
+        .filter(|prefix| prefix.contains('\n'))
+        .map(str::to_string);
+    members.push(relative.as_str());
+    if let (Some(prefix), Some(added)) = (prefix, members.iter_mut().last()) {
+        added.decor_mut().set_prefix(prefix);
+    }
+    std::fs::write(&manifest_path, doc.to_string())?;
+
+    Ok(MemberStatus::Added(relative))
+}
+
+/// Path of `member_dir` relative to `root`, with forward slashes as Cargo expects
+fn relative_member_path(root: &Path, member_dir: &Path) -> Result<String> {
+    let root = root.canonicalize()?;
+    let member = member_dir.canonicalize()?;
+
+    let relative = member
+        .strip_prefix(&root)
+        .map_err(|_| CargoJamError::InvalidArgument {
+            arg: "--into-workspace".to_string(),
+            reason: format!(
+                "{} is not inside the workspace at {}",
+                member.display(),
+                root.display()
+            ),
+        })?;
+
+    Ok(relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_workspace_member_preserves_formatting() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "# Services\n[workspace]\nmembers = [\n    \"core\", # shared code\n]\nresolver = \"2\"\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("services/counter")).unwrap();
+
+        let nested = root.join("services/counter");
+        assert_eq!(find_workspace_root(&nested).as_deref(), Some(root));
+
+        assert_eq!(
+            add_workspace_member(root, &nested).unwrap(),
+            MemberStatus::Added("services/counter".to_string())
+        );
+        assert_eq!(
+            add_workspace_member(root, &nested).unwrap(),
+            MemberStatus::AlreadyMember("services/counter".to_string())
+        );
+
+        let manifest = std::fs::read_to_string(root.join("Cargo.toml")).unwrap();
+        assert!(manifest.starts_with("# Services\n[workspace]\n"));
+        assert!(manifest.contains("# shared code"));
+        assert!(manifest.contains("\n    \"core\",\n    \"services/counter\""));
+        assert!(manifest.contains("resolver = \"2\""));
+    }
+
+    #[test]
+    fn test_init_workspace_and_hoist_profiles() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("services");
+        let member = root.join("echo");
+
+        assert!(init_workspace(&root).unwrap());
+        assert!(!init_workspace(&root).unwrap());
+        assert!(is_workspace_root(&root));
+
+        std::fs::create_dir_all(&member).unwrap();
+        std::fs::write(
+            member.join("Cargo.toml"),
+            "[package]\nname = \"echo\"\n\n[profile.release]\nlto = true\n",
+        )
+        .unwrap();
+        assert_eq!(hoist_profiles(&root, &member).unwrap(), ["release"]);
+
+        let manifest = std::fs::read_to_string(root.join("Cargo.toml")).unwrap();
+        assert!(
+            manifest.contains("[profile.release]\nlto = true"),
+            "{}",
+            manifest
+        );
+        let member_manifest = std::fs::read_to_string(member.join("Cargo.toml")).unwrap();
+        assert!(!member_manifest.contains("profile"), "{}", member_manifest);
+
+        // A package manifest can't become a workspace root
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        assert!(init_workspace(dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_add_workspace_member_to_inline_list() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"core\"]\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("echo")).unwrap();
+
+        add_workspace_member(root, &root.join("echo")).unwrap();
+        let manifest = std::fs::read_to_string(root.join("Cargo.toml")).unwrap();
+        assert!(
+            manifest.contains("members = [\"core\", \"echo\"]"),
+            "{}",
+            manifest
+        );
+    }
+
+    #[test]
+    fn test_glob_members_cover_new_crate() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"services/*\"]\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("services/echo")).unwrap();
+
+        assert_eq!(
+            add_workspace_member(root, &root.join("services/echo")).unwrap(),
+            MemberStatus::AlreadyMember("services/echo".to_string())
+        );
+    }
+}
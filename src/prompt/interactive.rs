@@ -1,19 +1,47 @@
+use crate::cli::output::is_plain;
 use crate::error::{CargoJamError, Result};
-use crate::template::config::{Placeholder, TemplateConfig};
-use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
+use crate::template::config::{Choice, Placeholder, TemplateConfig};
+use console::style;
+use dialoguer::theme::{ColorfulTheme, SimpleTheme, Theme};
+use dialoguer::{Confirm, Input, Select};
 use std::collections::HashMap;
 
 pub struct PromptRunner {
-    theme: ColorfulTheme,
+    theme: Box<dyn Theme>,
+    defaults: HashMap<String, String>,
+    confirm_defaults: bool,
+}
+
+/// dialoguer's colorful theme, or its ASCII-only simple theme in plain mode
+fn theme() -> Box<dyn Theme> {
+    if is_plain() {
+        Box::new(SimpleTheme)
+    } else {
+        Box::new(ColorfulTheme::default())
+    }
 }
 
 impl PromptRunner {
     pub fn new() -> Self {
         Self {
-            theme: ColorfulTheme::default(),
+            theme: theme(),
+            defaults: HashMap::new(),
+            confirm_defaults: false,
         }
     }
 
+    /// Use these values as prompt defaults, taking precedence over template defaults
+    pub fn defaults(mut self, defaults: HashMap<String, String>) -> Self {
+        self.defaults = defaults;
+        self
+    }
+
+    /// Ask for confirmation whenever an answer equals the placeholder's default
+    pub fn confirm_defaults(mut self, confirm: bool) -> Self {
+        self.confirm_defaults = confirm;
+        self
+    }
+
     pub fn collect_variables(
         &self,
         config: &TemplateConfig,
@@ -21,7 +49,7 @@ impl PromptRunner {
     ) -> Result<HashMap<String, String>> {
         let mut variables = HashMap::new();
 
-        for (key, placeholder) in &config.placeholders {
+        for (key, placeholder) in config.ordered_placeholders() {
             // Skip if already defined
             if existing.contains_key(key) {
                 continue;
@@ -39,21 +67,66 @@ impl PromptRunner {
         Ok(variables)
     }
 
-    fn prompt_placeholder(&self, _key: &str, placeholder: &Placeholder) -> Result<String> {
+    fn prompt_placeholder(&self, key: &str, placeholder: &Placeholder) -> Result<String> {
+        loop {
+            let value = self.prompt_placeholder_once(key, placeholder)?;
+
+            // Leaving an optional value empty isn't relying on a default
+            if !self.confirm_defaults
+                || value.is_empty()
+                || self.default_for(key, placeholder) != Some(value.clone())
+            {
+                return Ok(value);
+            }
+
+            let keep = self.prompt_bool(
+                &format!("'{}' is the default for {}, use it anyway?", value, key),
+                false,
+            )?;
+            if keep {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// Value a placeholder's prompt falls back to when the user just presses enter
+    fn default_for(&self, key: &str, placeholder: &Placeholder) -> Option<String> {
+        match placeholder {
+            Placeholder::String { .. } => self
+                .defaults
+                .get(key)
+                .cloned()
+                .or(placeholder.default_value()),
+            Placeholder::Bool { default, .. } => Some(default.unwrap_or(false).to_string()),
+        }
+    }
+
+    fn prompt_placeholder_once(&self, key: &str, placeholder: &Placeholder) -> Result<String> {
+        // Prompts are drawn on stderr, so the help goes there too
+        if let Some(help) = placeholder.help() {
+            for line in help.lines() {
+                eprintln!("  {}", style(line).dim());
+            }
+        }
+
         match placeholder {
             Placeholder::String {
                 prompt,
                 default,
                 choices,
                 regex,
+                ..
             } => {
+                let default = self.defaults.get(key).or(default.as_ref());
                 if let Some(choices) = choices {
-                    self.prompt_select(prompt, choices, default.as_deref())
+                    self.prompt_select(prompt, choices, default.map(String::as_str))
                 } else {
-                    self.prompt_string(prompt, default.as_deref(), regex.as_deref())
+                    self.prompt_string(prompt, default.map(String::as_str), regex.as_deref())
                 }
             }
-            Placeholder::Bool { prompt, default } => {
+            Placeholder::Bool {
+                prompt, default, ..
+            } => {
                 let result = self.prompt_bool(prompt, default.unwrap_or(false))?;
                 Ok(result.to_string())
             }
@@ -66,7 +139,7 @@ impl PromptRunner {
         default: Option<&str>,
         regex: Option<&str>,
     ) -> Result<String> {
-        let mut input = Input::<String>::with_theme(&self.theme).with_prompt(prompt);
+        let mut input = Input::<String>::with_theme(self.theme.as_ref()).with_prompt(prompt);
 
         if let Some(default) = default {
             input = input.default(default.to_string());
@@ -94,25 +167,28 @@ impl PromptRunner {
     pub fn prompt_select(
         &self,
         prompt: &str,
-        choices: &[String],
+        choices: &[Choice],
         default: Option<&str>,
     ) -> Result<String> {
+        // Choices are displayed by label but stored by value
         let default_index = default
-            .and_then(|d| choices.iter().position(|c| c == d))
+            .and_then(|d| choices.iter().position(|c| c.value() == d))
             .unwrap_or(0);
 
-        let selection = Select::with_theme(&self.theme)
+        let labels: Vec<&str> = choices.iter().map(Choice::label).collect();
+
+        let selection = Select::with_theme(self.theme.as_ref())
             .with_prompt(prompt)
-            .items(choices)
+            .items(&labels)
             .default(default_index)
             .interact()
             .map_err(|e| CargoJamError::Io(std::io::Error::other(e)))?;
 
-        Ok(choices[selection].clone())
+        Ok(choices[selection].value().to_string())
     }
 
     pub fn prompt_bool(&self, prompt: &str, default: bool) -> Result<bool> {
-        Confirm::with_theme(&self.theme)
+        Confirm::with_theme(self.theme.as_ref())
             .with_prompt(prompt)
             .default(default)
             .interact()
@@ -0,0 +1,111 @@
+use crate::template::config::TemplateConfig;
+use std::collections::HashMap;
+
+/// Environment variables consulted for the author name when git config has none
+const AUTHOR_NAME_ENV: &[&str] = &["GIT_AUTHOR_NAME", "GIT_COMMITTER_NAME", "USER", "USERNAME"];
+
+/// Environment variables consulted for the author email when git config has none
+const AUTHOR_EMAIL_ENV: &[&str] = &["GIT_AUTHOR_EMAIL", "GIT_COMMITTER_EMAIL", "EMAIL"];
+
+/// Variables the bundled template has renamed, as `(old, new)`
+const RENAMED_VARIABLES: &[(&str, &str)] = &[("author", "author_name")];
+
+/// Collect the well-known variables that are available to every template.
+///
+/// `author_name` and `author_email` are read from `git config user.name` and
+/// `user.email`, falling back to the usual environment variables. Values that
+/// cannot be determined are left out.
+pub fn builtin_variables() -> HashMap<String, String> {
+    let git_config = git2::Config::open_default().ok();
+    let git_value = |key: &str| git_config.as_ref().and_then(|c| c.get_string(key).ok());
+
+    let mut variables = HashMap::new();
+
+    if let Some(name) = resolve(git_value("user.name"), AUTHOR_NAME_ENV) {
+        variables.insert("author_name".to_string(), name);
+    }
+
+    if let Some(email) = resolve(git_value("user.email"), AUTHOR_EMAIL_ENV) {
+        variables.insert("author_email".to_string(), email);
+    }
+
+    variables
+}
+
+/// Move values given under a renamed variable's old name, such as `-d author=...`,
+/// to the new name. Templates that still declare the old name keep it, and a value
+/// already set under the new name wins. Returns the `(old, new)` names moved.
+pub fn apply_renamed_variables(
+    variables: &mut HashMap<String, String>,
+    config: &TemplateConfig,
+) -> Vec<(&'static str, &'static str)> {
+    let mut moved = Vec::new();
+    for &(old, new) in RENAMED_VARIABLES {
+        if config.placeholders.contains_key(old) || !config.placeholders.contains_key(new) {
+            continue;
+        }
+        if let Some(value) = variables.remove(old) {
+            variables.entry(new.to_string()).or_insert(value);
+            moved.push((old, new));
+        }
+    }
+    moved
+}
+
+/// Pick the git config value if set, otherwise the first non-empty env var
+fn resolve(git_value: Option<String>, env_keys: &[&str]) -> Option<String> {
+    git_value
+        .into_iter()
+        .chain(env_keys.iter().filter_map(|key| std::env::var(key).ok()))
+        .map(|v| v.trim().to_string())
+        .find(|v| !v.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_prefers_git_value() {
+        let value = resolve(Some("Alice".to_string()), &["PATH"]);
+        assert_eq!(value.as_deref(), Some("Alice"));
+    }
+
+    #[test]
+    fn test_apply_renamed_variables() {
+        let config: TemplateConfig = toml::from_str(
+            "[template]\nname = \"t\"\n[placeholders.author_name]\ntype = \"string\"\nprompt = \"Author\"\n",
+        )
+        .unwrap();
+
+        let mut variables: HashMap<String, String> =
+            [("author".to_string(), "Alice".to_string())].into();
+        assert_eq!(
+            apply_renamed_variables(&mut variables, &config),
+            [("author", "author_name")]
+        );
+        assert_eq!(
+            variables.get("author_name").map(String::as_str),
+            Some("Alice")
+        );
+        assert!(!variables.contains_key("author"));
+
+        // An explicit value under the new name wins
+        let mut variables: HashMap<String, String> = [
+            ("author".to_string(), "Alice".to_string()),
+            ("author_name".to_string(), "Bob".to_string()),
+        ]
+        .into();
+        apply_renamed_variables(&mut variables, &config);
+        assert_eq!(
+            variables.get("author_name").map(String::as_str),
+            Some("Bob")
+        );
+    }
+
+    #[test]
+    fn test_resolve_skips_empty_values() {
+        let value = resolve(Some("  ".to_string()), &["CARGO_POLKAJAM_UNSET_VAR"]);
+        assert_eq!(value, None);
+    }
+}
@@ -1,11 +1,45 @@
 use crate::error::{CargoJamError, Result};
+use crate::template::config::TemplateConfig;
 use include_dir::{include_dir, Dir};
+use serde::Deserialize;
 use std::path::PathBuf;
 use tempfile::TempDir;
 
 // Embed the templates directory at compile time
 static TEMPLATES_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/templates");
 
+/// Index of the bundled templates, at the root of the templates directory
+const INDEX_FILE: &str = "index.toml";
+
+/// Category of a bundled template the index doesn't describe
+const DEFAULT_CATEGORY: &str = "other";
+
+/// Template `new` uses when none is given
+pub const DEFAULT_TEMPLATE: &str = "basic-service";
+
+/// A bundled template as described by the index
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct BundledTemplate {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Group the template is listed under, e.g. `starters` or `examples`
+    #[serde(default = "default_category")]
+    pub category: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+fn default_category() -> String {
+    DEFAULT_CATEGORY.to_string()
+}
+
+#[derive(Deserialize)]
+struct TemplateIndex {
+    #[serde(default)]
+    template: Vec<BundledTemplate>,
+}
+
 pub struct BundledTemplates {
     temp_dir: Option<TempDir>,
 }
@@ -15,11 +49,20 @@ impl BundledTemplates {
         Self { temp_dir: None }
     }
 
+    /// Names of the bundled templates, in index order
     pub fn list(&self) -> Vec<String> {
-        TEMPLATES_DIR
-            .dirs()
-            .map(|d| d.path().file_name().unwrap().to_string_lossy().to_string())
-            .collect()
+        self.templates()
+            .map(|templates| templates.into_iter().map(|t| t.name).collect())
+            .unwrap_or_else(|_| template_dirs())
+    }
+
+    /// Every bundled template: those in the index in its order, then any others by name
+    pub fn templates(&self) -> Result<Vec<BundledTemplate>> {
+        let index = match TEMPLATES_DIR.get_file(INDEX_FILE) {
+            Some(file) => parse_index(&String::from_utf8_lossy(file.contents()))?,
+            None => Vec::new(),
+        };
+        Ok(merge_index(index, template_dirs()))
     }
 
     pub fn extract(&mut self, template_name: &str) -> Result<PathBuf> {
@@ -66,8 +109,89 @@ impl BundledTemplates {
     }
 }
 
+/// Names of the embedded template directories, sorted
+fn template_dirs() -> Vec<String> {
+    let mut names: Vec<String> = TEMPLATES_DIR
+        .dirs()
+        .map(|d| d.path().file_name().unwrap().to_string_lossy().to_string())
+        .collect();
+    names.sort();
+    names
+}
+
+fn parse_index(content: &str) -> Result<Vec<BundledTemplate>> {
+    let index: TemplateIndex = toml::from_str(content).map_err(|e| {
+        CargoJamError::TemplateConfig(format!("Failed to parse {}: {}", INDEX_FILE, e))
+    })?;
+    Ok(index.template)
+}
+
+/// Keep the index entries that name a bundled directory and append the directories
+/// it leaves out, described by their own `cargo-polkajam.toml`
+fn merge_index(index: Vec<BundledTemplate>, dirs: Vec<String>) -> Vec<BundledTemplate> {
+    let mut templates: Vec<BundledTemplate> = index
+        .into_iter()
+        .filter(|template| dirs.contains(&template.name))
+        .collect();
+    for name in dirs {
+        if templates.iter().any(|template| template.name == name) {
+            continue;
+        }
+        let description = TEMPLATES_DIR
+            .get_file(format!("{}/cargo-polkajam.toml", name))
+            .and_then(|file| {
+                toml::from_str::<TemplateConfig>(&String::from_utf8_lossy(file.contents())).ok()
+            })
+            .and_then(|config| config.template.description);
+        templates.push(BundledTemplate {
+            name,
+            description,
+            category: default_category(),
+            tags: Vec::new(),
+        });
+    }
+    templates
+}
+
 impl Default for BundledTemplates {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_index_covers_templates() {
+        let templates = BundledTemplates::new().templates().unwrap();
+        let names: Vec<_> = templates.iter().map(|t| t.name.clone()).collect();
+        let mut sorted = names.clone();
+        sorted.sort();
+        assert_eq!(sorted, template_dirs());
+        assert!(names.contains(&DEFAULT_TEMPLATE.to_string()));
+        assert!(templates.iter().all(|t| t.description.is_some()));
+    }
+
+    #[test]
+    fn test_merge_index() {
+        let index = parse_index(
+            r#"
+[[template]]
+name = "zeta"
+category = "examples"
+tags = ["demo"]
+
+[[template]]
+name = "removed"
+"#,
+        )
+        .unwrap();
+        let merged = merge_index(index, vec!["alpha".to_string(), "zeta".to_string()]);
+        let names: Vec<_> = merged.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["zeta", "alpha"]);
+        assert_eq!(merged[0].category, "examples");
+        assert_eq!(merged[1].category, DEFAULT_CATEGORY);
+    }
+}
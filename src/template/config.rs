@@ -1,33 +1,53 @@
 use crate::error::{CargoJamError, Result};
+use indexmap::IndexMap;
+use schemars::JsonSchema;
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::path::Path;
 
-#[derive(Debug, Deserialize)]
+/// Contents of a template's `cargo-polkajam.toml`
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct TemplateConfig {
     pub template: TemplateMetadata,
+    /// Variables prompted for when generating a project, in declaration order
     #[serde(default)]
-    pub placeholders: HashMap<String, Placeholder>,
+    pub placeholders: IndexMap<String, Placeholder>,
+    /// Extra file rules applied when the named mode (e.g. `minimal`) is enabled
     #[serde(default)]
     pub conditional: HashMap<String, ConditionalConfig>,
+    /// Cases generated and checked by `cargo polkajam template test`
+    #[serde(default, rename = "test_case")]
+    pub test_cases: Vec<TestCase>,
+    /// Files rendered once per item of a list variable
+    #[serde(default)]
+    pub generate: Vec<GenerateConfig>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct TemplateMetadata {
     pub name: String,
     #[serde(default)]
     pub description: Option<String>,
     #[serde(default)]
     pub version: Option<String>,
+    /// Glob patterns of files rendered with Liquid (default: all files)
     #[serde(default)]
     pub include: Vec<String>,
+    /// Glob patterns of files copied verbatim without rendering
     #[serde(default)]
     pub exclude: Vec<String>,
+    /// Glob patterns of files left out of the generated project
     #[serde(default)]
     pub ignore: Vec<String>,
+    /// Error on undefined variables when rendering (default: false)
+    #[serde(default)]
+    pub strict_undefined: Option<bool>,
+    /// Placeholders to prompt for first, in this order (the rest follow in declaration order)
+    #[serde(default)]
+    pub order: Vec<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 #[serde(tag = "type", rename_all = "lowercase")]
 pub enum Placeholder {
     String {
@@ -37,15 +57,50 @@ pub enum Placeholder {
         #[serde(default)]
         regex: Option<String>,
         #[serde(default)]
-        choices: Option<Vec<String>>,
+        choices: Option<Vec<Choice>>,
+        /// Longer guidance shown under the prompt and by `template info`
+        #[serde(default)]
+        help: Option<String>,
     },
     Bool {
         prompt: String,
         #[serde(default)]
         default: Option<bool>,
+        /// Longer guidance shown under the prompt and by `template info`
+        #[serde(default)]
+        help: Option<String>,
     },
 }
 
+/// A selectable value for a string placeholder.
+///
+/// Either a bare string, used as both label and value, or a table such as
+/// `{ label = "Rust edition 2021", value = "2021" }`.
+#[derive(Debug, Clone, Deserialize, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum Choice {
+    Plain(String),
+    Labeled { label: String, value: String },
+}
+
+impl Choice {
+    /// Text shown in the prompt
+    pub fn label(&self) -> &str {
+        match self {
+            Choice::Plain(value) => value,
+            Choice::Labeled { label, .. } => label,
+        }
+    }
+
+    /// Value stored in the template variables
+    pub fn value(&self) -> &str {
+        match self {
+            Choice::Plain(value) => value,
+            Choice::Labeled { value, .. } => value,
+        }
+    }
+}
+
 impl Placeholder {
     pub fn prompt(&self) -> &str {
         match self {
@@ -54,6 +109,13 @@ impl Placeholder {
         }
     }
 
+    pub fn help(&self) -> Option<&str> {
+        match self {
+            Placeholder::String { help, .. } => help.as_deref(),
+            Placeholder::Bool { help, .. } => help.as_deref(),
+        }
+    }
+
     pub fn default_value(&self) -> Option<String> {
         match self {
             Placeholder::String { default, .. } => default.clone(),
@@ -61,7 +123,7 @@ impl Placeholder {
         }
     }
 
-    pub fn choices(&self) -> Option<&Vec<String>> {
+    pub fn choices(&self) -> Option<&Vec<Choice>> {
         match self {
             Placeholder::String { choices, .. } => choices.as_ref(),
             Placeholder::Bool { .. } => None,
@@ -78,9 +140,49 @@ impl Placeholder {
     pub fn is_bool(&self) -> bool {
         matches!(self, Placeholder::Bool { .. })
     }
+
+    /// Check a predefined value against the placeholder's pattern and allowed choices
+    pub fn validate_value(&self, key: &str, value: &str) -> Result<()> {
+        if let Placeholder::String {
+            regex: Some(pattern),
+            ..
+        } = self
+        {
+            let re = regex::Regex::new(pattern).map_err(|e| {
+                CargoJamError::TemplateConfig(format!("Invalid regex '{}': {}", pattern, e))
+            })?;
+            if !re.is_match(value) {
+                return Err(CargoJamError::InvalidArgument {
+                    arg: key.to_string(),
+                    reason: format!("'{}' does not match pattern: {}", value, pattern),
+                });
+            }
+        }
+
+        let Some(choices) = self.choices() else {
+            return Ok(());
+        };
+
+        if choices.iter().any(|c| c.value() == value) {
+            return Ok(());
+        }
+
+        Err(CargoJamError::InvalidArgument {
+            arg: key.to_string(),
+            reason: format!(
+                "'{}' is not one of: {}",
+                value,
+                choices
+                    .iter()
+                    .map(Choice::value)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        })
+    }
 }
 
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Deserialize, Default, JsonSchema)]
 pub struct ConditionalConfig {
     #[serde(default)]
     pub include: Vec<String>,
@@ -90,6 +192,57 @@ pub struct ConditionalConfig {
     pub ignore: Vec<String>,
 }
 
+/// A template file rendered once per item of a comma-separated list variable
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct GenerateConfig {
+    /// Template file to render, relative to the template root (not copied on its own)
+    pub source: String,
+    /// Output path, rendered with the loop variable (e.g. `src/{{ module }}.rs`)
+    pub output: String,
+    /// Variable holding the comma-separated list to iterate over
+    pub foreach: String,
+    /// Name the current item is bound to; `<as>_index` holds its 0-based position
+    #[serde(rename = "as", default = "default_loop_variable")]
+    pub item: String,
+}
+
+fn default_loop_variable() -> String {
+    "item".to_string()
+}
+
+impl GenerateConfig {
+    /// Items of the list variable, trimmed and without empty entries
+    pub fn items(&self, variables: &HashMap<String, String>) -> Result<Vec<String>> {
+        let list = variables.get(&self.foreach).ok_or_else(|| {
+            CargoJamError::TemplateConfig(format!(
+                "generate: '{}' iterates over undefined variable '{}'",
+                self.source, self.foreach
+            ))
+        })?;
+
+        Ok(list
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+}
+
+/// A set of placeholder values used to test-generate the template
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct TestCase {
+    /// Name shown in the test report (default: `case-<n>`)
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Placeholder values; unspecified placeholders use their defaults
+    #[serde(default)]
+    pub values: IndexMap<String, String>,
+    /// Conditionals to enable, as with `cargo polkajam new --minimal`
+    #[serde(default)]
+    pub conditionals: Vec<String>,
+}
+
 impl TemplateConfig {
     pub fn load_from_dir(dir: &Path) -> Result<Self> {
         let config_path = dir.join("cargo-polkajam.toml");
@@ -105,10 +258,67 @@ impl TemplateConfig {
             CargoJamError::TemplateConfig(format!("Failed to parse cargo-polkajam.toml: {}", e))
         })?;
 
+        if let Some(unknown) = config
+            .template
+            .order
+            .iter()
+            .find(|key| !config.placeholders.contains_key(*key))
+        {
+            return Err(CargoJamError::TemplateConfig(format!(
+                "template.order references unknown placeholder '{}'",
+                unknown
+            )));
+        }
+
         Ok(config)
     }
 
+    /// Whether rendering errors on undefined variables: when `strict` is set (by
+    /// `--strict-undefined` or `template validate`), else as the template configures
+    pub fn strict_undefined(&self, strict: bool) -> bool {
+        strict || self.template.strict_undefined.unwrap_or(false)
+    }
+
+    /// Placeholders in prompt order: `template.order` first, then declaration order
+    pub fn ordered_placeholders(&self) -> Vec<(&String, &Placeholder)> {
+        let mut ordered: Vec<_> = self
+            .template
+            .order
+            .iter()
+            .filter_map(|key| self.placeholders.get_key_value(key))
+            .collect();
+
+        for (key, placeholder) in &self.placeholders {
+            if !self.template.order.contains(key) {
+                ordered.push((key, placeholder));
+            }
+        }
+
+        ordered
+    }
+
+    /// Merge the named `[conditional.<name>]` section into the template rules.
+    ///
+    /// Returns `false` if the template does not declare that conditional.
+    pub fn apply_conditional(&mut self, name: &str) -> bool {
+        let Some(conditional) = self.conditional.remove(name) else {
+            return false;
+        };
+
+        self.template.include.extend(conditional.include);
+        self.template.exclude.extend(conditional.exclude);
+        self.template.ignore.extend(conditional.ignore);
+        true
+    }
+
     pub fn should_process_file(&self, path: &str) -> bool {
+        // Excluded files are copied verbatim
+        for pattern in &self.template.exclude {
+            if glob_match(pattern, path) {
+                return false;
+            }
+        }
+
         // Check if file should be processed with Liquid
         if self.template.include.is_empty() {
             // If no include patterns, process all non-ignored files
@@ -125,8 +335,11 @@ impl TemplateConfig {
     }
 
     pub fn should_ignore_file(&self, path: &str) -> bool {
+        // Patterns may name either the template file or its rendered output
+        let rendered = path.strip_suffix(".liquid").unwrap_or(path);
+
         for pattern in &self.template.ignore {
-            if glob_match(pattern, path) {
+            if glob_match(pattern, path) || glob_match(pattern, rendered) {
                 return true;
             }
         }
@@ -150,3 +363,69 @@ fn glob_match(pattern: &str, path: &str) -> bool {
     }
     path == pattern || path.starts_with(&format!("{}/", pattern))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_choices_accept_plain_and_labeled() {
+        let config: TemplateConfig = toml::from_str(
+            r#"
+            [template]
+            name = "test"
+
+            [placeholders.edition]
+            type = "string"
+            prompt = "Edition"
+            choices = ["2018", { label = "Rust edition 2021", value = "2021" }]
+            "#,
+        )
+        .unwrap();
+
+        let edition = &config.placeholders["edition"];
+        let choices = edition.choices().unwrap();
+        assert_eq!(choices[0].label(), "2018");
+        assert_eq!(choices[1].label(), "Rust edition 2021");
+        assert_eq!(choices[1].value(), "2021");
+
+        assert_eq!(edition.help(), None);
+        assert!(edition.validate_value("edition", "2021").is_ok());
+        assert!(edition
+            .validate_value("edition", "Rust edition 2021")
+            .is_err());
+    }
+
+    #[test]
+    fn test_placeholders_follow_declaration_and_explicit_order() {
+        let config: TemplateConfig = toml::from_str(
+            r#"
+            [template]
+            name = "test"
+            order = ["zeta"]
+
+            [placeholders.gamma]
+            type = "bool"
+            prompt = "Gamma"
+            help = "Enables gamma"
+
+            [placeholders.alpha]
+            type = "string"
+            prompt = "Alpha"
+
+            [placeholders.zeta]
+            type = "string"
+            prompt = "Zeta"
+            "#,
+        )
+        .unwrap();
+
+        let keys: Vec<_> = config
+            .ordered_placeholders()
+            .into_iter()
+            .map(|(key, _)| key.as_str())
+            .collect();
+        assert_eq!(keys, ["zeta", "gamma", "alpha"]);
+        assert_eq!(config.placeholders["gamma"].help(), Some("Enables gamma"));
+    }
+}
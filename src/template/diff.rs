@@ -0,0 +1,136 @@
+use crate::error::Result;
+use similar::TextDiff;
+use std::collections::BTreeSet;
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// Lines of context around each change in a unified diff
+const CONTEXT_LINES: usize = 3;
+
+/// How a file differs between two generated trees
+#[derive(Debug, PartialEq, Eq)]
+pub enum FileChange {
+    /// Only in the second tree
+    Added(String),
+    /// Only in the first tree
+    Removed(String),
+    /// In both with different contents; the unified diff is `None` for binary files
+    Modified { path: String, diff: Option<String> },
+}
+
+impl FileChange {
+    pub fn path(&self) -> &str {
+        match self {
+            FileChange::Added(path) | FileChange::Removed(path) => path,
+            FileChange::Modified { path, .. } => path,
+        }
+    }
+}
+
+/// Compare the files under two directories, in path order
+pub fn diff_trees(a: &Path, b: &Path) -> Result<Vec<FileChange>> {
+    let files_a = relative_files(a);
+    let files_b = relative_files(b);
+
+    let mut changes = Vec::new();
+    for path in files_a.union(&files_b) {
+        let change = match (files_a.contains(path), files_b.contains(path)) {
+            (true, false) => FileChange::Removed(path.clone()),
+            (false, true) => FileChange::Added(path.clone()),
+            _ => {
+                let old = std::fs::read(a.join(path))?;
+                let new = std::fs::read(b.join(path))?;
+                if old == new {
+                    continue;
+                }
+                FileChange::Modified {
+                    path: path.clone(),
+                    diff: unified_diff(path, &old, &new),
+                }
+            }
+        };
+        changes.push(change);
+    }
+
+    Ok(changes)
+}
+
+/// Unified diff of two versions of a text file, or `None` if either isn't UTF-8
+fn unified_diff(path: &str, old: &[u8], new: &[u8]) -> Option<String> {
+    let old = std::str::from_utf8(old).ok()?;
+    let new = std::str::from_utf8(new).ok()?;
+    let diff = TextDiff::from_lines(old, new)
+        .unified_diff()
+        .context_radius(CONTEXT_LINES)
+        .header(&format!("a/{}", path), &format!("b/{}", path))
+        .to_string();
+    Some(diff)
+}
+
+/// Paths of the files under `root`, relative and with `/` separators
+fn relative_files(root: &Path) -> BTreeSet<String> {
+    WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|e| e.file_name() != ".git")
+        .flatten()
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| {
+            e.path()
+                .strip_prefix(root)
+                .ok()
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_trees() {
+        let a = tempfile::tempdir().unwrap();
+        let b = tempfile::tempdir().unwrap();
+        let write = |root: &Path, path: &str, contents: &[u8]| {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        };
+
+        write(a.path(), "Cargo.toml", b"[package]\nname = \"svc\"\n");
+        write(
+            b.path(),
+            "Cargo.toml",
+            b"[package]\nname = \"svc\"\nedition = \"2021\"\n",
+        );
+        write(a.path(), "src/lib.rs", b"same\n");
+        write(b.path(), "src/lib.rs", b"same\n");
+        write(a.path(), "README.md", b"old\n");
+        write(b.path(), "build.rs", b"new\n");
+        write(a.path(), "logo.png", &[0xff, 0x00]);
+        write(b.path(), "logo.png", &[0xff, 0x01]);
+
+        let changes = diff_trees(a.path(), b.path()).unwrap();
+        let paths: Vec<&str> = changes.iter().map(FileChange::path).collect();
+        assert_eq!(paths, ["Cargo.toml", "README.md", "build.rs", "logo.png"]);
+
+        match &changes[0] {
+            FileChange::Modified {
+                diff: Some(diff), ..
+            } => {
+                assert!(diff.contains("--- a/Cargo.toml"));
+                assert!(diff.contains("+edition = \"2021\""));
+            }
+            other => panic!("unexpected change {:?}", other),
+        }
+        assert_eq!(changes[1], FileChange::Removed("README.md".to_string()));
+        assert_eq!(changes[2], FileChange::Added("build.rs".to_string()));
+        assert_eq!(
+            changes[3],
+            FileChange::Modified {
+                path: "logo.png".to_string(),
+                diff: None
+            }
+        );
+    }
+}
@@ -1,27 +1,66 @@
 use crate::error::{CargoJamError, Result};
+use crate::hex;
 use heck::{ToKebabCase, ToLowerCamelCase, ToPascalCase, ToSnakeCase, ToUpperCamelCase};
 use liquid::model::Value;
 use liquid::{Object, Parser, ParserBuilder};
-use liquid_core::{Filter, Runtime, ValueView};
-use liquid_derive::{Display_filter, FilterReflection, ParseFilter};
+use liquid_core::model::{DisplayCow, KStringCow, ObjectView, State};
+use liquid_core::parser::{FilterArguments, FilterParameters};
+use liquid_core::{Expression, Filter, Runtime, ValueView};
+use liquid_derive::{Display_filter, FilterParameters, FilterReflection, ParseFilter};
+use sha2::{Digest, Sha256};
+use std::collections::hash_map::RandomState;
 use std::collections::HashMap;
+use std::fmt;
+use std::hash::BuildHasher;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 
 pub struct TemplateEngine {
     parser: Parser,
+    seed: Arc<AtomicU64>,
+    strict_undefined: bool,
 }
 
 impl TemplateEngine {
     pub fn new() -> Result<Self> {
+        // Random unless a seed is given, so `random_hex`/`uuid` differ between generations
+        let seed = Arc::new(AtomicU64::new(RandomState::new().hash_one(0u8)));
+
         let parser = ParserBuilder::with_stdlib()
             .filter(PascalCaseFilter)
             .filter(SnakeCaseFilter)
             .filter(KebabCaseFilter)
             .filter(CamelCaseFilter)
             .filter(UpperCamelCaseFilter)
+            .filter(RandomHexFilter { seed: seed.clone() })
+            .filter(UuidFilter { seed: seed.clone() })
             .build()
             .map_err(|e| CargoJamError::TemplateRender(format!("Failed to build parser: {}", e)))?;
 
-        Ok(Self { parser })
+        Ok(Self {
+            parser,
+            seed,
+            strict_undefined: true,
+        })
+    }
+
+    /// Make the `random_hex` and `uuid` filters deterministic for a given seed
+    pub fn seed(self, seed: Option<u64>) -> Self {
+        if let Some(seed) = seed {
+            self.seed.store(seed, Ordering::Relaxed);
+        }
+        self
+    }
+
+    /// Seed currently used by the `random_hex` and `uuid` filters
+    pub fn current_seed(&self) -> u64 {
+        self.seed.load(Ordering::Relaxed)
+    }
+
+    /// Error on undefined variables (the default) instead of rendering them as empty
+    pub fn strict_undefined(mut self, strict: bool) -> Self {
+        self.strict_undefined = strict;
+        self
     }
 
     pub fn render(&self, template: &str, variables: &HashMap<String, String>) -> Result<String> {
@@ -34,8 +73,13 @@ impl TemplateEngine {
             globals.insert(key.clone().into(), Value::scalar(value.clone()));
         }
 
-        template
-            .render(&globals)
+        let rendered = if self.strict_undefined {
+            template.render(&globals)
+        } else {
+            template.render(&LenientGlobals(&globals))
+        };
+
+        rendered
             .map_err(|e| CargoJamError::TemplateRender(format!("Failed to render template: {}", e)))
     }
 
@@ -59,6 +103,76 @@ impl Default for TemplateEngine {
     }
 }
 
+/// Globals that resolve undefined variables to nil instead of erroring
+#[derive(Debug)]
+struct LenientGlobals<'a>(&'a Object);
+
+static NIL: Value = Value::Nil;
+
+impl ValueView for LenientGlobals<'_> {
+    fn as_debug(&self) -> &dyn fmt::Debug {
+        self
+    }
+
+    fn render(&self) -> DisplayCow<'_> {
+        self.0.render()
+    }
+
+    fn source(&self) -> DisplayCow<'_> {
+        self.0.source()
+    }
+
+    fn type_name(&self) -> &'static str {
+        self.0.type_name()
+    }
+
+    fn query_state(&self, state: State) -> bool {
+        self.0.query_state(state)
+    }
+
+    fn to_kstr(&self) -> KStringCow<'_> {
+        self.0.to_kstr()
+    }
+
+    fn to_value(&self) -> Value {
+        self.0.to_value()
+    }
+
+    fn as_object(&self) -> Option<&dyn ObjectView> {
+        Some(self)
+    }
+}
+
+impl ObjectView for LenientGlobals<'_> {
+    fn as_value(&self) -> &dyn ValueView {
+        self
+    }
+
+    fn size(&self) -> i64 {
+        ObjectView::size(self.0)
+    }
+
+    fn keys<'k>(&'k self) -> Box<dyn Iterator<Item = KStringCow<'k>> + 'k> {
+        ObjectView::keys(self.0)
+    }
+
+    fn values<'k>(&'k self) -> Box<dyn Iterator<Item = &'k dyn ValueView> + 'k> {
+        ObjectView::values(self.0)
+    }
+
+    fn iter<'k>(&'k self) -> Box<dyn Iterator<Item = (KStringCow<'k>, &'k dyn ValueView)> + 'k> {
+        ObjectView::iter(self.0)
+    }
+
+    fn contains_key(&self, _index: &str) -> bool {
+        true
+    }
+
+    fn get<'s>(&'s self, index: &str) -> Option<&'s dyn ValueView> {
+        Some(ObjectView::get(self.0, index).unwrap_or(&NIL))
+    }
+}
+
 // Custom Liquid filters for case conversion
 
 #[derive(Clone, ParseFilter, FilterReflection)]
@@ -176,6 +290,134 @@ impl Filter for UpperCamelCaseFilterImpl {
     }
 }
 
+// Seeded random value filters
+//
+// The input is a key: the same key yields the same value throughout a generation, so an ID
+// can be referenced from several files, and a fixed seed makes the output reproducible.
+
+/// Default number of random bytes rendered by `random_hex`
+const RANDOM_HEX_BYTES: usize = 16;
+
+/// Deterministic bytes derived from the seed and a key
+fn seeded_bytes(seed: u64, key: &str, len: usize) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(len);
+    let mut block = 0u64;
+    while bytes.len() < len {
+        let mut hasher = Sha256::new();
+        hasher.update(seed.to_le_bytes());
+        hasher.update(block.to_le_bytes());
+        hasher.update(key.as_bytes());
+        bytes.extend_from_slice(&hasher.finalize());
+        block += 1;
+    }
+    bytes.truncate(len);
+    bytes
+}
+
+#[derive(Debug, FilterParameters)]
+struct RandomHexArgs {
+    #[parameter(
+        description = "Number of random bytes (default: 16)",
+        arg_type = "integer"
+    )]
+    bytes: Option<Expression>,
+}
+
+#[derive(Clone, FilterReflection)]
+#[filter(
+    name = "random_hex",
+    description = "Random hex string keyed by the input, reproducible with --seed",
+    parameters(RandomHexArgs)
+)]
+pub struct RandomHexFilter {
+    seed: Arc<AtomicU64>,
+}
+
+impl liquid_core::ParseFilter for RandomHexFilter {
+    fn parse(&self, arguments: FilterArguments) -> liquid_core::Result<Box<dyn Filter>> {
+        let args = RandomHexArgs::from_args(arguments)?;
+        Ok(Box::new(RandomHexFilterImpl {
+            args,
+            seed: self.seed.clone(),
+        }))
+    }
+
+    fn reflection(&self) -> &dyn liquid_core::FilterReflection {
+        self
+    }
+}
+
+#[derive(Debug, Display_filter)]
+#[name = "random_hex"]
+struct RandomHexFilterImpl {
+    #[parameters]
+    args: RandomHexArgs,
+    seed: Arc<AtomicU64>,
+}
+
+impl Filter for RandomHexFilterImpl {
+    fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> liquid_core::Result<Value> {
+        let args = self.args.evaluate(runtime)?;
+        let len = match args.bytes {
+            Some(n) if n > 0 => n as usize,
+            Some(_) => return Err(liquid_core::Error::with_msg("bytes must be positive")),
+            None => RANDOM_HEX_BYTES,
+        };
+
+        let bytes = seeded_bytes(self.seed.load(Ordering::Relaxed), &input.to_kstr(), len);
+        Ok(Value::scalar(hex::encode(&bytes)))
+    }
+}
+
+#[derive(Clone, FilterReflection)]
+#[filter(
+    name = "uuid",
+    description = "Random version 4 UUID keyed by the input, reproducible with --seed"
+)]
+pub struct UuidFilter {
+    seed: Arc<AtomicU64>,
+}
+
+impl liquid_core::ParseFilter for UuidFilter {
+    fn parse(&self, _arguments: FilterArguments) -> liquid_core::Result<Box<dyn Filter>> {
+        Ok(Box::new(UuidFilterImpl {
+            seed: self.seed.clone(),
+        }))
+    }
+
+    fn reflection(&self) -> &dyn liquid_core::FilterReflection {
+        self
+    }
+}
+
+#[derive(Debug, Display_filter)]
+#[name = "uuid"]
+struct UuidFilterImpl {
+    seed: Arc<AtomicU64>,
+}
+
+impl Filter for UuidFilterImpl {
+    fn evaluate(
+        &self,
+        input: &dyn ValueView,
+        _runtime: &dyn Runtime,
+    ) -> liquid_core::Result<Value> {
+        let mut bytes = seeded_bytes(self.seed.load(Ordering::Relaxed), &input.to_kstr(), 16);
+        bytes[6] = (bytes[6] & 0x0f) | 0x40;
+        bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+        let hex = hex::encode(&bytes);
+        Ok(Value::scalar(format!(
+            "{}-{}-{}-{}-{}",
+            &hex[0..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..32]
+        )))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -209,4 +451,44 @@ mod tests {
         let result = engine.render("{{ name | snake_case }}", &vars).unwrap();
         assert_eq!(result, "my_service");
     }
+
+    #[test]
+    fn test_strict_undefined() {
+        let engine = TemplateEngine::new().unwrap();
+        let err = engine
+            .render("{{ crate_naem }}", &HashMap::new())
+            .unwrap_err();
+        assert!(err.to_string().contains("crate_naem"));
+
+        let lenient = TemplateEngine::new().unwrap().strict_undefined(false);
+        let result = lenient
+            .render(
+                "a{{ crate_naem }}b{% if author %}c{% endif %}",
+                &HashMap::new(),
+            )
+            .unwrap();
+        assert_eq!(result, "ab");
+    }
+
+    #[test]
+    fn test_seeded_random_filters() {
+        let template = "{{ 'id' | random_hex: 4 }} {{ 'id' | uuid }} {{ 'other' | random_hex: 4 }}";
+        let render = |seed| {
+            TemplateEngine::new()
+                .unwrap()
+                .seed(seed)
+                .render(template, &HashMap::new())
+                .unwrap()
+        };
+
+        let first = render(Some(42));
+        assert_eq!(first, render(Some(42)));
+        assert_ne!(first, render(Some(43)));
+
+        let parts: Vec<&str> = first.split(' ').collect();
+        assert_eq!(parts[0].len(), 8);
+        assert_ne!(parts[0], parts[2]);
+        assert_eq!(parts[1].len(), 36);
+        assert_eq!(&parts[1][14..15], "4");
+    }
 }
@@ -1,12 +1,35 @@
 use crate::error::{CargoJamError, Result};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tempfile::TempDir;
+use tracing::debug;
+use walkdir::WalkDir;
+
+/// Config file marking a directory as a template
+const TEMPLATE_CONFIG_FILE: &str = "cargo-polkajam.toml";
+
+/// First line of a Git LFS pointer file, which stands in for the real content
+const LFS_POINTER_PREFIX: &[u8] = b"version https://git-lfs.github.com/spec/v1";
+
+/// Pointer files are around 130 bytes; larger files are real content
+const LFS_POINTER_MAX_SIZE: u64 = 1024;
+
+/// Pointer files named in the error when LFS content can't be fetched
+const MAX_LISTED_LFS_FILES: usize = 5;
+
+/// Username sent with token credentials, accepted by GitHub and most other hosts
+const TOKEN_USERNAME: &str = "x-access-token";
+
+/// How many branches or tags a not-found error lists
+const MAX_LISTED_REFS: usize = 10;
 
 pub struct GitTemplateSource {
     url: String,
     branch: Option<String>,
+    rev: Option<String>,
     subpath: Option<PathBuf>,
+    token: Option<String>,
     temp_dir: Option<TempDir>,
+    commit: Option<String>,
 }
 
 impl GitTemplateSource {
@@ -14,8 +37,11 @@ impl GitTemplateSource {
         Self {
             url,
             branch: None,
+            rev: None,
             subpath: None,
+            token: None,
             temp_dir: None,
+            commit: None,
         }
     }
 
@@ -24,11 +50,23 @@ impl GitTemplateSource {
         self
     }
 
+    /// Commit, tag or branch to check out after cloning
+    pub fn rev(mut self, rev: Option<String>) -> Self {
+        self.rev = rev;
+        self
+    }
+
     pub fn subpath(mut self, subpath: Option<PathBuf>) -> Self {
         self.subpath = subpath;
         self
     }
 
+    /// Token for HTTPS authentication, taking precedence over `GITHUB_TOKEN`/`GIT_TOKEN`
+    pub fn token(mut self, token: Option<String>) -> Self {
+        self.token = token;
+        self
+    }
+
     pub fn fetch(&mut self) -> Result<PathBuf> {
         let temp_dir = TempDir::new().map_err(|e| {
             CargoJamError::Io(std::io::Error::other(format!(
@@ -43,15 +81,48 @@ impl GitTemplateSource {
         let url = self.expand_url(&self.url);
 
         // Clone the repository
+        let token = self.resolve_token(&url);
+        debug!(
+            %url,
+            branch = ?self.branch,
+            authenticated = token.is_some(),
+            dest = %clone_path.display(),
+            "cloning template repository"
+        );
         let mut builder = git2::build::RepoBuilder::new();
 
         if let Some(ref branch) = self.branch {
             builder.branch(branch);
         }
 
-        builder.clone(&url, clone_path).map_err(|e| {
-            CargoJamError::Git(format!("Failed to clone repository '{}': {}", url, e))
-        })?;
+        let mut fetch_options = git2::FetchOptions::new();
+        fetch_options.remote_callbacks(credential_callbacks(token.as_deref()));
+        builder.fetch_options(fetch_options);
+
+        let repo = builder
+            .clone(&url, clone_path)
+            .map_err(|e| match self.branch {
+                Some(ref branch) if is_missing_branch(&e, branch) => {
+                    let branches = remote_branches(&url, token.as_deref()).unwrap_or_default();
+                    not_found_error("Branch", branch, &url, &branches)
+                }
+                _ => clone_error(&url, token.is_some(), e),
+            })?;
+
+        if let Some(ref rev) = self.rev {
+            checkout_rev(&repo, rev, &url)?;
+        }
+
+        self.commit = repo
+            .head()
+            .and_then(|head| head.peel_to_commit())
+            .map(|commit| commit.id().to_string())
+            .ok();
+
+        // libgit2 checks LFS-tracked files out as pointer files
+        if uses_lfs(clone_path) {
+            pull_lfs_objects(clone_path, &url)?;
+        }
 
         // Determine the template path
         let template_path = if let Some(ref subpath) = self.subpath {
@@ -60,11 +131,17 @@ impl GitTemplateSource {
             clone_path.to_path_buf()
         };
 
-        if !template_path.exists() {
-            return Err(CargoJamError::Git(format!(
-                "Template path '{}' not found in repository",
-                template_path.display()
-            )));
+        // A --path must be a template; the repository root may hold several
+        let is_template = match self.subpath {
+            Some(_) => template_path.join(TEMPLATE_CONFIG_FILE).is_file(),
+            None => !discover_templates(&template_path).is_empty(),
+        };
+        if !is_template {
+            return Err(not_a_template_error(
+                &url,
+                self.subpath.as_deref(),
+                &discover_templates(clone_path),
+            ));
         }
 
         // Store temp dir to keep it alive
@@ -73,6 +150,25 @@ impl GitTemplateSource {
         Ok(template_path)
     }
 
+    /// Commit the template was checked out at, once fetched
+    pub fn commit(&self) -> Option<&str> {
+        self.commit.as_deref()
+    }
+
+    /// Token to authenticate with: `--git-token`, then `GITHUB_TOKEN` (GitHub URLs only), then `GIT_TOKEN`
+    fn resolve_token(&self, url: &str) -> Option<String> {
+        let env = |key: &str| std::env::var(key).ok().filter(|v| !v.is_empty());
+
+        self.token
+            .clone()
+            .or_else(|| {
+                url.starts_with("https://github.com/")
+                    .then(|| env("GITHUB_TOKEN"))
+                    .flatten()
+            })
+            .or_else(|| env("GIT_TOKEN"))
+    }
+
     fn expand_url(&self, url: &str) -> String {
         // Support shorthand URLs like gh:owner/repo, gl:owner/repo, etc.
         if let Some(rest) = url.strip_prefix("gh:") {
@@ -92,3 +188,489 @@ impl GitTemplateSource {
         }
     }
 }
+
+/// Offer the token (if any) for HTTPS authentication
+fn credential_callbacks(token: Option<&str>) -> git2::RemoteCallbacks<'_> {
+    let mut attempted = false;
+    let mut callbacks = git2::RemoteCallbacks::new();
+    callbacks.credentials(move |_url, _username, allowed| {
+        // git2 retries rejected credentials indefinitely, so only offer the token once
+        match token {
+            Some(token)
+                if !attempted && allowed.contains(git2::CredentialType::USER_PASS_PLAINTEXT) =>
+            {
+                attempted = true;
+                git2::Cred::userpass_plaintext(TOKEN_USERNAME, token)
+            }
+            _ => Err(git2::Error::from_str("no usable credentials")),
+        }
+    });
+    callbacks
+}
+
+/// Whether a clone failed because the requested branch doesn't exist
+fn is_missing_branch(e: &git2::Error, branch: &str) -> bool {
+    // libgit2 reports either "remote branch 'x' not found" or
+    // "reference 'refs/remotes/origin/x' not found", depending on the transport
+    let message = e.message();
+    message.contains("not found")
+        && (message.contains("remote branch")
+            || message.contains(&format!("refs/remotes/origin/{}'", branch)))
+}
+
+/// Branch names of a remote, listed without cloning it (like `git ls-remote --heads`)
+fn remote_branches(
+    url: &str,
+    token: Option<&str>,
+) -> std::result::Result<Vec<String>, git2::Error> {
+    let mut remote = git2::Remote::create_detached(url)?;
+    let connection = remote.connect_auth(
+        git2::Direction::Fetch,
+        Some(credential_callbacks(token)),
+        None,
+    )?;
+    let branches = connection
+        .list()?
+        .iter()
+        .filter_map(|head| head.name().strip_prefix("refs/heads/"))
+        .map(String::from)
+        .collect();
+    Ok(branches)
+}
+
+/// Check out `rev` (a commit, tag, or local or remote branch) in a fresh clone
+fn checkout_rev(repo: &git2::Repository, rev: &str, url: &str) -> Result<()> {
+    debug!(rev, "checking out revision");
+    let object = repo
+        .revparse_single(rev)
+        .or_else(|_| repo.revparse_single(&format!("origin/{}", rev)))
+        .map_err(|_| {
+            let mut refs: Vec<String> = repo
+                .branches(Some(git2::BranchType::Remote))
+                .into_iter()
+                .flatten()
+                .flatten()
+                .filter_map(|(branch, _)| {
+                    let name = branch.name().ok()??;
+                    let name = name.strip_prefix("origin/").unwrap_or(name);
+                    (name != "HEAD").then(|| name.to_string())
+                })
+                .collect();
+            if let Ok(tags) = repo.tag_names(None) {
+                refs.extend(tags.iter().flatten().map(String::from));
+            }
+            not_found_error("Revision", rev, url, &refs)
+        })?;
+
+    let checkout = || -> std::result::Result<(), git2::Error> {
+        repo.checkout_tree(&object, Some(git2::build::CheckoutBuilder::new().force()))?;
+        repo.set_head_detached(object.peel_to_commit()?.id())
+    };
+    checkout()
+        .map_err(|e| CargoJamError::Git(format!("Failed to check out '{}': {}", rev, e.message())))
+}
+
+/// "Branch 'mian' not found in <url>; did you mean 'main'?", listing what exists
+fn not_found_error(kind: &str, name: &str, url: &str, candidates: &[String]) -> CargoJamError {
+    let mut message = format!("{} '{}' not found in '{}'", kind, name, url);
+
+    if let Some(suggestion) = closest_match(name, candidates) {
+        message.push_str(&format!("; did you mean '{}'?", suggestion));
+    }
+
+    if !candidates.is_empty() {
+        let mut listed: Vec<&str> = candidates.iter().map(String::as_str).collect();
+        listed.sort_unstable();
+        listed.dedup();
+        let more = listed.len().saturating_sub(MAX_LISTED_REFS);
+        listed.truncate(MAX_LISTED_REFS);
+        message.push_str(&format!(" (available: {}", listed.join(", ")));
+        if more > 0 {
+            message.push_str(&format!(" and {} more", more));
+        }
+        message.push(')');
+    }
+
+    CargoJamError::Git(message)
+}
+
+/// A repository (or `--path` within it) without a template config, listing the
+/// templates found elsewhere in the repository
+fn not_a_template_error(url: &str, subpath: Option<&Path>, templates: &[PathBuf]) -> CargoJamError {
+    let Some(subpath) = subpath else {
+        return CargoJamError::TemplateNotFound(format!(
+            "no {} found in '{}'; the repository may not be a cargo-polkajam template",
+            TEMPLATE_CONFIG_FILE, url
+        ));
+    };
+
+    let mut message = format!(
+        "no {} at '{}' in '{}'",
+        TEMPLATE_CONFIG_FILE,
+        subpath.display(),
+        url
+    );
+    if templates.is_empty() {
+        message.push_str("; the repository may not be a cargo-polkajam template");
+    } else {
+        let names: Vec<String> = templates
+            .iter()
+            .map(|t| {
+                if t.as_os_str().is_empty() {
+                    ".".to_string()
+                } else {
+                    t.to_string_lossy().replace('\\', "/")
+                }
+            })
+            .collect();
+        message.push_str(&format!(
+            "; check --path (templates found: {})",
+            names.join(", ")
+        ));
+    }
+    CargoJamError::TemplateNotFound(message)
+}
+
+/// The candidate closest to `name` by edit distance, if it's plausibly a typo
+fn closest_match<'a>(name: &str, candidates: &'a [String]) -> Option<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(2);
+    candidates
+        .iter()
+        .map(|c| (edit_distance(name, c), c))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, c)| c.as_str())
+}
+
+/// Levenshtein distance between two strings, counted in characters
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+/// Whether any `.gitattributes` in the clone routes files through the LFS filter
+fn uses_lfs(root: &Path) -> bool {
+    WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|e| e.file_name() != ".git")
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.file_name() == ".gitattributes")
+        .filter_map(|e| std::fs::read_to_string(e.path()).ok())
+        .any(|attributes| attributes.contains("filter=lfs"))
+}
+
+/// Files in the clone that are still LFS pointers, relative to `root`
+fn lfs_pointers(root: &Path) -> Vec<PathBuf> {
+    WalkDir::new(root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| e.file_name() != ".git")
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| e.metadata().is_ok_and(|m| m.len() <= LFS_POINTER_MAX_SIZE))
+        .filter(|e| {
+            std::fs::read(e.path()).is_ok_and(|bytes| bytes.starts_with(LFS_POINTER_PREFIX))
+        })
+        .filter_map(|e| e.path().strip_prefix(root).ok().map(Path::to_path_buf))
+        .collect()
+}
+
+/// Replace LFS pointers with their content using `git lfs pull`, failing if
+/// any pointer is left
+fn pull_lfs_objects(root: &Path, url: &str) -> Result<()> {
+    if lfs_pointers(root).is_empty() {
+        return Ok(());
+    }
+
+    debug!(dest = %root.display(), "fetching Git LFS objects");
+    let failure = match std::process::Command::new("git")
+        .args(["lfs", "pull"])
+        .current_dir(root)
+        .output()
+    {
+        Ok(output) if output.status.success() => None,
+        Ok(output) => Some(String::from_utf8_lossy(&output.stderr).trim().to_string()),
+        Err(e) => Some(format!("could not run git: {}", e)),
+    };
+
+    let pointers = lfs_pointers(root);
+    if pointers.is_empty() {
+        return Ok(());
+    }
+    let examples: Vec<String> = pointers
+        .iter()
+        .take(MAX_LISTED_LFS_FILES)
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
+        .collect();
+    Err(CargoJamError::Git(format!(
+        "template '{}' stores {} file(s) in Git LFS ({}), which libgit2 cannot download, and `git lfs pull` failed{}; install Git LFS (https://git-lfs.com) and try again",
+        url,
+        pointers.len(),
+        examples.join(", "),
+        failure.map(|reason| format!(": {}", reason)).unwrap_or_default()
+    )))
+}
+
+/// Turn a clone failure into an error that tells auth problems apart from missing repos
+fn clone_error(url: &str, had_token: bool, e: git2::Error) -> CargoJamError {
+    let message = e.message().to_lowercase();
+    let auth_failed = e.code() == git2::ErrorCode::Auth
+        || e.class() == git2::ErrorClass::Http && message.contains("401")
+        || message.contains("authentication")
+        || message.contains("credentials");
+
+    if auth_failed {
+        let hint = if had_token {
+            "the token was rejected; check that it is valid and can read this repository"
+        } else {
+            "the repository may be private; pass a token with --git-token or set GITHUB_TOKEN/GIT_TOKEN"
+        };
+        return CargoJamError::Git(format!(
+            "Authentication failed for '{}': {} ({})",
+            url,
+            hint,
+            e.message()
+        ));
+    }
+
+    if e.code() == git2::ErrorCode::NotFound || message.contains("404") {
+        return CargoJamError::Git(format!("Repository '{}' not found: {}", url, e.message()));
+    }
+
+    CargoJamError::Git(format!("Failed to clone repository '{}': {}", url, e))
+}
+
+/// Find template directories (containing `cargo-polkajam.toml`) in a cloned repository.
+///
+/// Returns paths relative to `root`. A template at the root itself is returned
+/// alone, as an empty path, without scanning subdirectories.
+pub fn discover_templates(root: &Path) -> Vec<PathBuf> {
+    if root.join(TEMPLATE_CONFIG_FILE).exists() {
+        return vec![PathBuf::new()];
+    }
+
+    WalkDir::new(root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| {
+            let name = e.file_name().to_string_lossy();
+            name != ".git" && name != "target"
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.file_name() == TEMPLATE_CONFIG_FILE)
+        .filter_map(|e| {
+            e.path()
+                .parent()?
+                .strip_prefix(root)
+                .ok()
+                .map(Path::to_path_buf)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discover_templates() {
+        let dir = tempfile::tempdir().unwrap();
+        for sub in ["services/counter", "services/echo", ".git/hooks"] {
+            std::fs::create_dir_all(dir.path().join(sub)).unwrap();
+            std::fs::write(dir.path().join(sub).join(TEMPLATE_CONFIG_FILE), "").unwrap();
+        }
+
+        assert_eq!(
+            discover_templates(dir.path()),
+            [
+                PathBuf::from("services/counter"),
+                PathBuf::from("services/echo")
+            ]
+        );
+
+        std::fs::write(dir.path().join(TEMPLATE_CONFIG_FILE), "").unwrap();
+        assert_eq!(discover_templates(dir.path()), [PathBuf::new()]);
+    }
+
+    #[test]
+    fn test_clone_error_distinguishes_auth_from_not_found() {
+        let url = "https://github.com/org/private";
+
+        let auth = git2::Error::new(
+            git2::ErrorCode::Auth,
+            git2::ErrorClass::Http,
+            "too many redirects or authentication replays",
+        );
+        let msg = clone_error(url, false, auth).to_string();
+        assert!(msg.contains("Authentication failed"));
+        assert!(msg.contains("--git-token"));
+
+        let missing = git2::Error::new(
+            git2::ErrorCode::NotFound,
+            git2::ErrorClass::Http,
+            "unexpected http status code: 404",
+        );
+        let msg = clone_error(url, true, missing).to_string();
+        assert!(msg.contains("not found"));
+        assert!(!msg.contains("Authentication"));
+    }
+
+    /// A local repository with `main` and `develop` branches, a `v1.0.0` tag
+    /// and a template in `service/`
+    fn fixture_repo(dir: &Path) -> String {
+        let repo = git2::Repository::init_opts(
+            dir,
+            git2::RepositoryInitOptions::new().initial_head("main"),
+        )
+        .unwrap();
+        let signature = git2::Signature::now("test", "test@example.com").unwrap();
+        std::fs::create_dir(dir.join("service")).unwrap();
+        std::fs::write(dir.join("service").join(TEMPLATE_CONFIG_FILE), "").unwrap();
+        let commit = |message: &str, parents: &[&git2::Commit]| {
+            std::fs::write(dir.join("README.md"), message).unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(Path::new("README.md")).unwrap();
+            index
+                .add_path(&Path::new("service").join(TEMPLATE_CONFIG_FILE))
+                .unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let id = repo
+                .commit(
+                    Some("HEAD"),
+                    &signature,
+                    &signature,
+                    message,
+                    &tree,
+                    parents,
+                )
+                .unwrap();
+            repo.find_commit(id).unwrap()
+        };
+
+        let first = commit("first", &[]);
+        repo.tag_lightweight("v1.0.0", first.as_object(), false)
+            .unwrap();
+        repo.branch("develop", &first, false).unwrap();
+        commit("second", &[&first]);
+
+        format!("file://{}", dir.display())
+    }
+
+    #[test]
+    fn test_missing_branch_and_rev_suggest_closest() {
+        let remote = tempfile::tempdir().unwrap();
+        let url = fixture_repo(remote.path());
+
+        let err = GitTemplateSource::new(url.clone())
+            .branch(Some("devlop".into()))
+            .fetch()
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Branch 'devlop' not found"), "{}", err);
+        assert!(err.contains("did you mean 'develop'?"), "{}", err);
+
+        let err = GitTemplateSource::new(url.clone())
+            .rev(Some("v1.0".into()))
+            .fetch()
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Revision 'v1.0' not found"), "{}", err);
+        assert!(err.contains("did you mean 'v1.0.0'?"), "{}", err);
+
+        let mut source = GitTemplateSource::new(url).rev(Some("v1.0.0".into()));
+        let dir = source.fetch().unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.join("README.md")).unwrap(),
+            "first"
+        );
+    }
+
+    #[test]
+    fn test_fetch_requires_template_config() {
+        let remote = tempfile::tempdir().unwrap();
+        let url = fixture_repo(remote.path());
+
+        let mut source = GitTemplateSource::new(url.clone()).subpath(Some("service".into()));
+        assert!(source.fetch().unwrap().join(TEMPLATE_CONFIG_FILE).is_file());
+
+        for subpath in ["services", "."] {
+            let err = GitTemplateSource::new(url.clone())
+                .subpath(Some(subpath.into()))
+                .fetch()
+                .unwrap_err()
+                .to_string();
+            assert!(
+                err.contains("check --path (templates found: service)"),
+                "{}",
+                err
+            );
+        }
+
+        let err = not_a_template_error(&url, None, &[]).to_string();
+        assert!(
+            err.contains("may not be a cargo-polkajam template"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_lfs_pointers_are_detected() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir(root.join("assets")).unwrap();
+        std::fs::write(root.join("README.md"), "not a pointer").unwrap();
+        assert!(!uses_lfs(root));
+
+        std::fs::write(
+            root.join(".gitattributes"),
+            "*.png filter=lfs diff=lfs merge=lfs -text\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("assets").join("logo.png"),
+            "version https://git-lfs.github.com/spec/v1\noid sha256:abc\nsize 12345\n",
+        )
+        .unwrap();
+        assert!(uses_lfs(root));
+        assert_eq!(lfs_pointers(root), [Path::new("assets").join("logo.png")]);
+
+        // Not a git repository, so `git lfs pull` fails and the pointer remains
+        let err = pull_lfs_objects(root, "https://example.com/t.git")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("stores 1 file(s) in Git LFS (assets/logo.png)"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_closest_match() {
+        let candidates = vec!["main".to_string(), "develop".to_string()];
+        assert_eq!(closest_match("mian", &candidates), Some("main"));
+        assert_eq!(closest_match("release", &candidates), None);
+    }
+
+    #[test]
+    fn test_explicit_token_takes_precedence() {
+        let source = GitTemplateSource::new("gh:org/private".into()).token(Some("abc".into()));
+        assert_eq!(
+            source.resolve_token("https://github.com/org/private"),
+            Some("abc".to_string())
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use crate::error::{CargoJamError, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// Header written above the recorded values
+const LOCK_HEADER: &str = "# Template pinned by `cargo polkajam new --template-lock`.\n# Regenerate with --update-lock after reviewing a template change.\n\n";
+
+/// A template pinned to the exact content it had when the lock was written
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateLock {
+    /// Name of a bundled template
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bundled: Option<String>,
+    /// Git repository URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git: Option<String>,
+    /// Commit the repository was checked out at
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rev: Option<String>,
+    /// Template directory within the repository
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// `tree_hash` of the template directory
+    pub hash: String,
+}
+
+/// What `TemplateLock::enforce` did
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockOutcome {
+    /// No lock existed; it was written
+    Recorded,
+    /// The template matches the lock
+    Verified,
+    /// The template differed and `--update-lock` rewrote the lock
+    Updated,
+}
+
+impl TemplateLock {
+    /// Read a lock file, or `None` if it doesn't exist yet
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => toml::from_str(&content).map(Some).map_err(|e| {
+                CargoJamError::TemplateConfig(format!("Failed to parse {}: {}", path.display(), e))
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = toml::to_string(self).map_err(|e| {
+            CargoJamError::TemplateConfig(format!("Failed to serialize template lock: {}", e))
+        })?;
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, format!("{}{}", LOCK_HEADER, content))?;
+        Ok(())
+    }
+
+    /// Compare against the lock at `path`, writing it when missing (or differing with `update`)
+    pub fn enforce(&self, path: &Path, update: bool) -> Result<LockOutcome> {
+        let locked = match Self::load(path)? {
+            None => {
+                self.save(path)?;
+                return Ok(LockOutcome::Recorded);
+            }
+            Some(locked) if locked == *self => return Ok(LockOutcome::Verified),
+            Some(locked) => locked,
+        };
+
+        if update {
+            self.save(path)?;
+            return Ok(LockOutcome::Updated);
+        }
+
+        let fields = [
+            ("bundled", &locked.bundled, &self.bundled),
+            ("git", &locked.git, &self.git),
+            ("rev", &locked.rev, &self.rev),
+            ("path", &locked.path, &self.path),
+        ];
+        let mut changes: Vec<String> = fields
+            .iter()
+            .filter(|(_, locked, current)| locked != current)
+            .map(|(name, locked, current)| {
+                format!(
+                    "{} {} -> {}",
+                    name,
+                    locked.as_deref().unwrap_or("(none)"),
+                    current.as_deref().unwrap_or("(none)")
+                )
+            })
+            .collect();
+        if changes.is_empty() {
+            changes.push(format!("content {} -> {}", locked.hash, self.hash));
+        }
+
+        Err(CargoJamError::InvalidArgument {
+            arg: "--template-lock".to_string(),
+            reason: format!(
+                "the template does not match {} ({}); review the change and pass --update-lock to accept it",
+                path.display(),
+                changes.join(", ")
+            ),
+        })
+    }
+}
+
+/// SHA-256 over every file below `dir` (paths and contents, in sorted order), as
+/// `sha256:<hex>`. A `.git` directory is skipped.
+pub fn tree_hash(dir: &Path) -> Result<String> {
+    let mut hasher = Sha256::new();
+    let entries = WalkDir::new(dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| entry.file_name() != ".git");
+    for entry in entries {
+        let entry = entry.map_err(|e| {
+            CargoJamError::Io(std::io::Error::other(format!(
+                "Failed to walk directory: {}",
+                e
+            )))
+        })?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(dir).unwrap_or(entry.path());
+        let contents = std::fs::read(entry.path())?;
+        // Length prefixes keep a path from running into the contents
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        hasher.update((relative.len() as u64).to_le_bytes());
+        hasher.update(relative.as_bytes());
+        hasher.update((contents.len() as u64).to_le_bytes());
+        hasher.update(&contents);
+    }
+    Ok(format!("sha256:{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enforce_template_lock() {
+        let template = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(template.path().join(".git")).unwrap();
+        std::fs::write(template.path().join(".git/HEAD"), "ref").unwrap();
+        std::fs::write(template.path().join("Cargo.toml.liquid"), "v1").unwrap();
+        let locks = tempfile::tempdir().unwrap();
+        let lock_path = locks.path().join("locks/template.lock");
+
+        let lock = |hash: String| TemplateLock {
+            bundled: Some("basic-service".to_string()),
+            git: None,
+            rev: None,
+            path: None,
+            hash,
+        };
+        let first = lock(tree_hash(template.path()).unwrap());
+        assert_eq!(
+            first.enforce(&lock_path, false).unwrap(),
+            LockOutcome::Recorded
+        );
+        assert_eq!(
+            first.enforce(&lock_path, false).unwrap(),
+            LockOutcome::Verified
+        );
+
+        // Git metadata is not part of the template
+        std::fs::write(template.path().join(".git/HEAD"), "other").unwrap();
+        assert_eq!(tree_hash(template.path()).unwrap(), first.hash);
+
+        std::fs::write(template.path().join("Cargo.toml.liquid"), "v2").unwrap();
+        let changed = lock(tree_hash(template.path()).unwrap());
+        let err = changed.enforce(&lock_path, false).unwrap_err();
+        assert!(err.to_string().contains("--update-lock"), "{}", err);
+        assert_eq!(
+            changed.enforce(&lock_path, true).unwrap(),
+            LockOutcome::Updated
+        );
+        assert_eq!(TemplateLock::load(&lock_path).unwrap(), Some(changed));
+    }
+}
@@ -1,4 +1,9 @@
+pub mod builtins;
 pub mod bundled;
 pub mod config;
+pub mod diff;
 pub mod engine;
 pub mod git;
+pub mod lock;
+pub mod schema;
+pub mod values;
@@ -0,0 +1,37 @@
+use crate::error::{CargoJamError, Result};
+use crate::template::config::TemplateConfig;
+
+/// JSON Schema for `cargo-polkajam.toml`, derived from the config structs
+pub fn config_schema() -> serde_json::Value {
+    let mut schema = schemars::schema_for!(TemplateConfig);
+    schema.insert(
+        "title".to_string(),
+        serde_json::Value::String("cargo-polkajam.toml".to_string()),
+    );
+    schema.to_value()
+}
+
+/// The schema as pretty-printed JSON
+pub fn config_schema_json() -> Result<String> {
+    serde_json::to_string_pretty(&config_schema())
+        .map_err(|e| CargoJamError::TemplateConfig(format!("Failed to serialize schema: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_covers_config_sections() {
+        let schema = config_schema();
+        let properties = schema["properties"].as_object().unwrap();
+        assert!(properties.contains_key("template"));
+        assert!(properties.contains_key("placeholders"));
+        assert!(properties.contains_key("conditional"));
+        assert_eq!(schema["required"], serde_json::json!(["template"]));
+
+        let placeholder = serde_json::to_string(&schema["$defs"]["Placeholder"]).unwrap();
+        assert!(placeholder.contains("\"string\""));
+        assert!(placeholder.contains("\"bool\""));
+    }
+}
@@ -0,0 +1,65 @@
+use crate::error::{CargoJamError, Result};
+use clap::ValueEnum;
+use std::collections::HashMap;
+
+/// Format of a template values document
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ValuesFormat {
+    Toml,
+    Json,
+}
+
+impl ValuesFormat {
+    /// Guess the format of a document: JSON if it is an object, TOML otherwise
+    pub fn detect(content: &str) -> Self {
+        if content.trim_start().starts_with('{') {
+            ValuesFormat::Json
+        } else {
+            ValuesFormat::Toml
+        }
+    }
+}
+
+/// Parse a values document of string keys and values, detecting its format unless given
+pub fn parse_values(
+    content: &str,
+    format: Option<ValuesFormat>,
+    arg: &str,
+) -> Result<HashMap<String, String>> {
+    let format = format.unwrap_or_else(|| ValuesFormat::detect(content));
+    let invalid = |reason: String| CargoJamError::InvalidArgument {
+        arg: arg.to_string(),
+        reason,
+    };
+
+    match format {
+        ValuesFormat::Toml => {
+            toml::from_str(content).map_err(|e| invalid(format!("invalid TOML values: {}", e)))
+        }
+        ValuesFormat::Json => serde_json::from_str(content)
+            .map_err(|e| invalid(format!("invalid JSON values: {}", e))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_values() {
+        let json =
+            parse_values(r#" {"description": "From JSON"}"#, None, "--values-stdin").unwrap();
+        assert_eq!(json["description"], "From JSON");
+
+        let toml = parse_values("description = \"From TOML\"\n", None, "--values-stdin").unwrap();
+        assert_eq!(toml["description"], "From TOML");
+
+        let err = parse_values(
+            "description = \"x\"",
+            Some(ValuesFormat::Json),
+            "--values-stdin",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("invalid JSON values"));
+    }
+}
@@ -0,0 +1,178 @@
+use crate::build::signing::{public_key_path, SigningKey};
+use crate::error::{CargoJamError, Result};
+use crate::toolchain::config::ToolchainConfig;
+use std::path::{Path, PathBuf};
+
+/// Directory in the cargo-polkajam home holding dev account keys
+const ACCOUNTS_DIR: &str = "accounts";
+
+/// Extension of an account's key file; its public key is in `<name>.key.pub`
+const KEY_EXTENSION: &str = "key";
+
+/// A dev account: an ed25519 keypair stored under a name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Account {
+    pub name: String,
+    /// Hex-encoded public key
+    pub public_key: String,
+}
+
+/// Dev accounts stored in a directory, one `<name>.key` file (readable only by the
+/// owner on Unix) per account
+pub struct AccountStore {
+    dir: PathBuf,
+}
+
+impl AccountStore {
+    /// The accounts in the cargo-polkajam home
+    pub fn open() -> Result<Self> {
+        Ok(Self::at(ToolchainConfig::home_dir()?.join(ACCOUNTS_DIR)))
+    }
+
+    pub(crate) fn at(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// File holding the private key of account `name`
+    pub fn key_path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{}.{}", name, KEY_EXTENSION))
+    }
+
+    /// Generate a keypair and store it as `name`, refusing to replace an existing account
+    pub fn create(&self, name: &str) -> Result<Account> {
+        check_account_name(name)?;
+        let path = self.key_path(name);
+        if path.exists() {
+            return Err(CargoJamError::InvalidArgument {
+                arg: "<NAME>".to_string(),
+                reason: format!("account '{}' already exists", name),
+            });
+        }
+
+        create_private_dir(&self.dir)?;
+        let key = SigningKey::generate()?;
+        key.save(&path)?;
+        Ok(Account {
+            name: name.to_string(),
+            public_key: key.public_key(),
+        })
+    }
+
+    /// Whether an account called `name` exists
+    pub fn contains(&self, name: &str) -> bool {
+        self.key_path(name).is_file()
+    }
+
+    /// The stored key of an account
+    pub fn key(&self, name: &str) -> Result<SigningKey> {
+        if !self.contains(name) {
+            return Err(unknown_account(name));
+        }
+        SigningKey::load(&self.key_path(name))
+    }
+
+    /// Every stored account, sorted by name
+    pub fn list(&self) -> Result<Vec<Account>> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut accounts = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_none_or(|ext| ext != KEY_EXTENSION) {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            // Listing reads the public key file, falling back to deriving it from the seed
+            let public_key = match std::fs::read_to_string(public_key_path(&path)) {
+                Ok(public_key) => public_key.trim().to_string(),
+                Err(_) => SigningKey::load(&path)?.public_key(),
+            };
+            accounts.push(Account {
+                name: name.to_string(),
+                public_key,
+            });
+        }
+        accounts.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(accounts)
+    }
+}
+
+/// Error for an account that isn't stored
+pub fn unknown_account(name: &str) -> CargoJamError {
+    CargoJamError::InvalidArgument {
+        arg: "--account".to_string(),
+        reason: format!(
+            "no account named '{}' (create it with 'cargo polkajam accounts new {}')",
+            name, name
+        ),
+    }
+}
+
+/// Account names become file names: letters, digits, `-` and `_` only
+fn check_account_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(CargoJamError::InvalidArgument {
+            arg: "<NAME>".to_string(),
+            reason: format!(
+                "'{}' is not a valid account name (use letters, digits, '-' and '_')",
+                name
+            ),
+        })
+    }
+}
+
+/// Create a directory only its owner can enter on Unix
+fn create_private_dir(dir: &Path) -> Result<()> {
+    std::fs::create_dir_all(dir)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_account_store() {
+        let home = tempfile::tempdir().unwrap();
+        let store = AccountStore::at(home.path().join(ACCOUNTS_DIR));
+        assert!(store.list().unwrap().is_empty());
+
+        let bob = store.create("bob").unwrap();
+        let alice = store.create("alice").unwrap();
+        assert!(store.create("alice").is_err());
+        assert!(store.create("../evil").is_err());
+
+        assert_eq!(store.list().unwrap(), vec![alice.clone(), bob]);
+        assert_eq!(store.key("alice").unwrap().public_key(), alice.public_key);
+        assert!(store.key("carol").is_err());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = |path: &Path| std::fs::metadata(path).unwrap().permissions().mode() & 0o777;
+            assert_eq!(mode(store.dir()), 0o700);
+            assert_eq!(mode(&store.key_path("alice")), 0o600);
+        }
+    }
+}
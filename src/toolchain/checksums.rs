@@ -0,0 +1,116 @@
+use crate::error::{CargoJamError, Result};
+use crate::project::manifest::{hash_bytes, FileStatus};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// File in the toolchain directory recording the hashes of the installed files
+pub const CHECKSUM_FILE: &str = "checksums.toml";
+
+/// Directory the toolchain archive is normalized into
+pub const INSTALL_DIR: &str = "polkajam-nightly";
+
+/// SHA-256 of every file of an installed toolchain, recorded at install time
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ToolchainChecksums {
+    /// Hash of each file, keyed by its path relative to `polkajam-nightly/`
+    #[serde(default)]
+    pub files: BTreeMap<String, String>,
+}
+
+impl ToolchainChecksums {
+    /// Hash every file under `<toolchain_dir>/polkajam-nightly`
+    pub fn collect(toolchain_dir: &Path) -> Result<Self> {
+        let install_dir = toolchain_dir.join(INSTALL_DIR);
+        let mut files = BTreeMap::new();
+
+        for entry in WalkDir::new(&install_dir).sort_by_file_name() {
+            let entry = entry.map_err(|e| std::io::Error::other(e.to_string()))?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let relative = entry
+                .path()
+                .strip_prefix(&install_dir)
+                .unwrap_or(entry.path())
+                .to_string_lossy()
+                .replace('\\', "/");
+            files.insert(relative, hash_bytes(&std::fs::read(entry.path())?));
+        }
+
+        Ok(Self { files })
+    }
+
+    /// Load the recorded checksums, if any were stored
+    pub fn load(toolchain_dir: &Path) -> Result<Option<Self>> {
+        let path = toolchain_dir.join(CHECKSUM_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        toml::from_str(&content).map(Some).map_err(|e| {
+            CargoJamError::TemplateConfig(format!("Failed to parse {}: {}", CHECKSUM_FILE, e))
+        })
+    }
+
+    pub fn save(&self, toolchain_dir: &Path) -> Result<()> {
+        let content = toml::to_string_pretty(self).map_err(|e| {
+            CargoJamError::TemplateConfig(format!("Failed to serialize {}: {}", CHECKSUM_FILE, e))
+        })?;
+        std::fs::write(toolchain_dir.join(CHECKSUM_FILE), content)?;
+        Ok(())
+    }
+
+    /// Files that are missing or no longer match their recorded hash
+    pub fn damaged(&self, toolchain_dir: &Path) -> Result<Vec<(String, FileStatus)>> {
+        let install_dir = toolchain_dir.join(INSTALL_DIR);
+        let mut damaged = Vec::new();
+
+        for (relative, recorded) in &self.files {
+            let path = install_dir.join(relative);
+            if !path.exists() {
+                damaged.push((relative.clone(), FileStatus::Missing));
+            } else if &hash_bytes(&std::fs::read(&path)?) != recorded {
+                damaged.push((relative.clone(), FileStatus::Modified));
+            }
+        }
+
+        Ok(damaged)
+    }
+}
+
+/// Record the checksums of a freshly installed toolchain
+pub fn record_checksums(toolchain_dir: &Path) -> Result<()> {
+    ToolchainChecksums::collect(toolchain_dir)?.save(toolchain_dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_damaged_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let install = dir.path().join(INSTALL_DIR);
+        std::fs::create_dir_all(&install).unwrap();
+        for name in ["jamt", "polkajam", "polkajam-testnet"] {
+            std::fs::write(install.join(name), name).unwrap();
+        }
+
+        record_checksums(dir.path()).unwrap();
+        let checksums = ToolchainChecksums::load(dir.path()).unwrap().unwrap();
+        assert!(checksums.damaged(dir.path()).unwrap().is_empty());
+
+        std::fs::remove_file(install.join("jamt")).unwrap();
+        std::fs::write(install.join("polkajam"), "corrupt").unwrap();
+        assert_eq!(
+            checksums.damaged(dir.path()).unwrap(),
+            [
+                ("jamt".to_string(), FileStatus::Missing),
+                ("polkajam".to_string(), FileStatus::Modified),
+            ]
+        );
+    }
+}
@@ -0,0 +1,231 @@
+use crate::error::{CargoJamError, Result};
+use crate::project::workspace::find_workspace_root;
+use semver::{Op, Version, VersionReq};
+use std::path::Path;
+
+/// SDK crates tied to the toolchain's PVM ABI
+pub const SDK_CRATES: &[&str] = &["jam-pvm-common", "polkavm-derive"];
+
+/// The bundled template's manifest, whose SDK versions are the ones the supported
+/// toolchain releases build against
+const TEMPLATE_MANIFEST: &str = include_str!("../../templates/basic-service/Cargo.toml.liquid");
+
+/// Each SDK crate with the version requirement the bundled template declares for it
+pub fn sdk_versions() -> Vec<(&'static str, String)> {
+    // Everything before [dependencies] may hold Liquid tags that aren't valid TOML
+    let dependencies = TEMPLATE_MANIFEST
+        .find("[dependencies]")
+        .map_or("", |start| &TEMPLATE_MANIFEST[start..]);
+    let manifest: Option<toml::Table> = toml::from_str(dependencies).ok();
+    SDK_CRATES
+        .iter()
+        .filter_map(|&name| {
+            let declared = manifest.as_ref()?.get("dependencies")?.get(name)?;
+            Some((name, declared_version(declared)?.to_string()))
+        })
+        .collect()
+}
+
+/// Dependency tables an SDK crate may be declared in
+const DEPENDENCY_TABLES: &[&str] = &["dependencies", "build-dependencies", "dev-dependencies"];
+
+/// How a project's SDK dependency relates to the supported version
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SdkStatus {
+    Compatible,
+    /// The version requirement excludes the supported version
+    Incompatible,
+    /// A git or path dependency, or a requirement too complex to compare
+    Unchecked,
+}
+
+/// An SDK crate as declared by a project
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SdkDependency {
+    pub name: &'static str,
+    /// The declared requirement, or the kind of source for git and path dependencies
+    pub requirement: String,
+    /// The version the supported toolchain builds against
+    pub expected: String,
+    pub status: SdkStatus,
+}
+
+/// Compare the SDK dependencies in a project's `Cargo.toml` against `sdk_versions()`.
+///
+/// Dependencies inherited with `workspace = true` are read from the workspace root.
+pub fn check_sdk_dependencies(project_dir: &Path) -> Result<Vec<SdkDependency>> {
+    let manifest = read_manifest(&project_dir.join("Cargo.toml"))?;
+    let workspace = find_workspace_root(project_dir)
+        .map(|root| read_manifest(&root.join("Cargo.toml")))
+        .transpose()?;
+
+    let mut found = Vec::new();
+    for (name, expected) in sdk_versions() {
+        let declared = DEPENDENCY_TABLES
+            .iter()
+            .find_map(|table| manifest.get(table).and_then(|deps| deps.get(name)));
+        let Some(mut declared) = declared else {
+            continue;
+        };
+
+        let inherited = declared
+            .get("workspace")
+            .and_then(toml::Value::as_bool)
+            .unwrap_or(false);
+        if inherited {
+            let workspace_dep = workspace
+                .as_ref()
+                .and_then(|ws| ws.get("workspace"))
+                .and_then(|ws| ws.get("dependencies"))
+                .and_then(|deps| deps.get(name));
+            match workspace_dep {
+                Some(dep) => declared = dep,
+                None => continue,
+            }
+        }
+
+        let (requirement, status) = match declared_version(declared) {
+            Some(requirement) => {
+                let status = match is_compatible(requirement, &expected) {
+                    Some(true) => SdkStatus::Compatible,
+                    Some(false) => SdkStatus::Incompatible,
+                    None => SdkStatus::Unchecked,
+                };
+                (requirement.to_string(), status)
+            }
+            None => (dependency_source(declared), SdkStatus::Unchecked),
+        };
+        found.push(SdkDependency {
+            name,
+            requirement,
+            expected,
+            status,
+        });
+    }
+
+    Ok(found)
+}
+
+fn read_manifest(path: &Path) -> Result<toml::Value> {
+    let content = std::fs::read_to_string(path)?;
+    toml::from_str(&content).map_err(|e| {
+        CargoJamError::TemplateConfig(format!("Failed to parse {}: {}", path.display(), e))
+    })
+}
+
+/// Version requirement of a dependency: `"0.1"` or `{ version = "0.1", ... }`
+fn declared_version(dependency: &toml::Value) -> Option<&str> {
+    dependency
+        .as_str()
+        .or_else(|| dependency.get("version").and_then(toml::Value::as_str))
+}
+
+/// Where a dependency without a version comes from
+fn dependency_source(dependency: &toml::Value) -> String {
+    ["git", "path"]
+        .into_iter()
+        .find(|key| dependency.get(key).is_some())
+        .map_or("unversioned", |key| key)
+        .to_string()
+}
+
+/// Whether a Cargo version requirement admits a version in the `expected` series
+/// (itself a requirement such as `0.29`), or `None` when either doesn't parse.
+///
+/// `0.29.4` is compatible with `0.29`, as is `>=0.27, <0.30`; `0.27` and `=0.30.0` are not.
+pub fn is_compatible(requirement: &str, expected: &str) -> Option<bool> {
+    let requirement = VersionReq::parse(requirement.trim()).ok()?;
+    let expected = VersionReq::parse(expected.trim()).ok()?;
+
+    // Where two requirements overlap, the lowest version they share is one of their
+    // lower bounds, so checking those is enough
+    let candidates: Vec<Version> = requirement
+        .comparators
+        .iter()
+        .chain(&expected.comparators)
+        .flat_map(lower_bounds)
+        .collect();
+    Some(
+        candidates
+            .iter()
+            .any(|version| requirement.matches(version) && expected.matches(version)),
+    )
+}
+
+/// The smallest versions a comparator can admit (a strict `>` admits the next one)
+fn lower_bounds(comparator: &semver::Comparator) -> Vec<Version> {
+    let mut version = Version::new(
+        comparator.major,
+        comparator.minor.unwrap_or(0),
+        comparator.patch.unwrap_or(0),
+    );
+    version.pre = comparator.pre.clone();
+    if comparator.op != Op::Greater {
+        return vec![version];
+    }
+    match (comparator.minor, comparator.patch) {
+        (Some(_), Some(_)) => version.patch += 1,
+        (Some(_), None) => (version.minor, version.patch) = (version.minor + 1, 0),
+        _ => (version.major, version.minor, version.patch) = (version.major + 1, 0, 0),
+    }
+    version.pre = semver::Prerelease::EMPTY;
+    vec![version]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_compatible() {
+        assert_eq!(is_compatible("0.29", "0.29"), Some(true));
+        assert_eq!(is_compatible("0.29.4", "0.29"), Some(true));
+        assert_eq!(is_compatible("^0.29.1", "0.29"), Some(true));
+        assert_eq!(is_compatible("~0.29.2", "0.29"), Some(true));
+        assert_eq!(is_compatible("0.27", "0.29"), Some(false));
+        assert_eq!(is_compatible("0.30", "0.29"), Some(false));
+        assert_eq!(is_compatible("1.2", "1.5"), Some(true));
+        assert_eq!(is_compatible("=0.1.1", "0.1"), Some(true));
+        assert_eq!(is_compatible("=0.2.0", "0.1"), Some(false));
+        assert_eq!(is_compatible(">=0.27, <0.30", "0.29"), Some(true));
+        assert_eq!(is_compatible(">0.29", "0.29"), Some(false));
+        assert_eq!(is_compatible(">0.28", "0.29"), Some(true));
+        assert_eq!(is_compatible("<0.29", "0.29"), Some(false));
+        assert_eq!(is_compatible("0.*", "0.29"), Some(true));
+        assert_eq!(is_compatible("not a version", "0.29"), None);
+    }
+
+    #[test]
+    fn test_check_sdk_dependencies() {
+        let dir = tempfile::tempdir().unwrap();
+        let member = dir.path().join("service");
+        std::fs::create_dir(&member).unwrap();
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"service\"]\n\n[workspace.dependencies]\npolkavm-derive = \"0.27\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            member.join("Cargo.toml"),
+            "[package]\nname = \"service\"\n\n[dependencies]\njam-pvm-common = { git = \"https://example.com/sdk\" }\npolkavm-derive = { workspace = true }\n",
+        )
+        .unwrap();
+
+        let found = check_sdk_dependencies(&member).unwrap();
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].requirement, "git");
+        assert_eq!(found[0].status, SdkStatus::Unchecked);
+        assert_eq!(found[1].requirement, "0.27");
+        assert_eq!(found[1].status, SdkStatus::Incompatible);
+    }
+
+    #[test]
+    fn test_sdk_versions_from_bundled_template() {
+        let versions = sdk_versions();
+        let names: Vec<_> = versions.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, SDK_CRATES);
+        assert!(versions
+            .iter()
+            .all(|(_, version)| VersionReq::parse(version).is_ok()));
+    }
+}
@@ -1,6 +1,40 @@
 use crate::error::{CargoJamError, Result};
+use crate::toolchain::checksums::INSTALL_DIR;
+use crate::toolchain::lock::ToolchainLock;
+use crate::toolchain::platform::Platform;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tracing::{debug, trace};
+
+/// Per-project file pinning the required toolchain version
+pub const TOOLCHAIN_PIN_FILE: &str = "jam-toolchain.toml";
+
+/// Written by `config edit` when no config file exists yet
+pub const CONFIG_TEMPLATE: &str = r#"# cargo-polkajam configuration
+#
+# The toolchain fields are managed by `cargo polkajam setup`:
+# installed_version = "nightly-2025-12-29"
+# toolchain_path = "/home/you/.cargo-polkajam/toolchain"
+# installed_at = "1767015039"
+
+# Default subprocess timeout in seconds for build, deploy, queue and test (0 disables)
+# default_timeout = 1800
+
+# Hex-encoded ed25519 public keys accepted by `verify` and `deploy --require-signed`
+# trusted_keys = []
+
+# Dev account `deploy` uses when --account isn't given (managed by `cargo polkajam accounts use`)
+# default_account = "alice"
+
+# Platform whose toolchain `setup` installs instead of the detected one, e.g. macos-x86_64
+# to run the Intel build under Rosetta
+# platform = "macos-x86_64"
+"#;
+
+/// Config file location set by `--config`/`CARGO_JAM_CONFIG`, if any
+static CONFIG_PATH_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
 
 /// Configuration for the installed toolchain
 #[derive(Debug, Serialize, Deserialize, Default)]
@@ -11,80 +45,187 @@ pub struct ToolchainConfig {
     pub toolchain_path: Option<PathBuf>,
     /// Installation timestamp
     pub installed_at: Option<String>,
+    /// Default subprocess timeout in seconds for build, deploy and test
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_timeout: Option<u64>,
+    /// Hex-encoded ed25519 public keys accepted by `verify` and `deploy --require-signed`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trusted_keys: Vec<String>,
+    /// Dev account `deploy` uses when `--account` isn't given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_account: Option<String>,
+    /// Platform `setup` installs the toolchain for when `--platform` isn't given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub platform: Option<Platform>,
+    /// Directory of the binaries relative to `polkajam-nightly/`, when not at its top level
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub binary_dir: Option<PathBuf>,
+    /// Whether the archive was installed with `--no-normalize`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub preserve_layout: bool,
+}
+
+/// Contents of a project's `jam-toolchain.toml`
+#[derive(Debug, Deserialize)]
+struct ToolchainPin {
+    /// Required toolchain version (e.g., "nightly-2025-12-29")
+    version: String,
 }
 
 impl ToolchainConfig {
-    /// Get the cargo-polkajam home directory (~/.cargo-polkajam)
+    /// Get the cargo-polkajam home directory.
+    ///
+    /// `CARGO_JAM_HOME` is used as-is when set; otherwise this is
+    /// `.cargo-polkajam` under `HOME`, `USERPROFILE` or the platform's home directory.
     pub fn home_dir() -> Result<PathBuf> {
-        let home = dirs::home_dir().ok_or_else(|| {
-            CargoJamError::Io(std::io::Error::new(
-                std::io::ErrorKind::NotFound,
-                "Could not determine home directory",
-            ))
-        })?;
-        Ok(home.join(".cargo-polkajam"))
+        resolve_home_dir(|key| std::env::var_os(key), dirs::home_dir)
+            .ok_or(CargoJamError::HomeDirUnavailable)
     }
 
-    /// Get the config file path (~/.cargo-polkajam/config.toml)
+    /// Get the config file path (~/.cargo-polkajam/config.toml unless overridden)
     pub fn config_path() -> Result<PathBuf> {
+        if let Some(path) = Self::config_path_override() {
+            return Ok(path.clone());
+        }
         Ok(Self::home_dir()?.join("config.toml"))
     }
 
+    /// Use an alternate config file for the rest of the process.
+    ///
+    /// Only the first call takes effect.
+    pub fn set_config_path_override(path: PathBuf) {
+        let _ = CONFIG_PATH_OVERRIDE.set(path);
+    }
+
+    /// The alternate config file in use, if one was set
+    pub fn config_path_override() -> Option<&'static PathBuf> {
+        CONFIG_PATH_OVERRIDE.get()
+    }
+
     /// Get the toolchain installation directory (~/.cargo-polkajam/toolchain)
     pub fn toolchain_dir() -> Result<PathBuf> {
         Ok(Self::home_dir()?.join("toolchain"))
     }
 
+    /// Get the download cache directory (~/.cargo-polkajam/cache)
+    pub fn cache_dir() -> Result<PathBuf> {
+        Ok(Self::home_dir()?.join("cache"))
+    }
+
     /// Get the path to a specific toolchain binary
     pub fn binary_path(binary_name: &str) -> Result<Option<PathBuf>> {
         let config = Self::load()?;
-        if let Some(toolchain_path) = config.toolchain_path {
-            let binary_path = toolchain_path.join("polkajam-nightly").join(binary_name);
+        if let Some(bin_dir) = config.bin_dir() {
+            let binary_path = bin_dir.join(binary_name);
             if binary_path.exists() {
+                debug!(binary = binary_name, path = %binary_path.display(), "found toolchain binary");
                 return Ok(Some(binary_path));
             }
         }
+        debug!(binary = binary_name, "toolchain binary not found");
         Ok(None)
     }
 
-    /// Get the path to the polkajam toolchain directory
+    /// Get the path to the directory holding the toolchain binaries
     pub fn polkajam_dir() -> Result<Option<PathBuf>> {
-        let config = Self::load()?;
-        if let Some(toolchain_path) = config.toolchain_path {
-            let nightly_dir = toolchain_path.join("polkajam-nightly");
-            if nightly_dir.exists() {
-                return Ok(Some(nightly_dir));
-            }
-        }
-        Ok(None)
+        Ok(Self::load()?.bin_dir().filter(|dir| dir.exists()))
+    }
+
+    /// Directory the installed archive put its binaries in
+    fn bin_dir(&self) -> Option<PathBuf> {
+        let install_dir = self.toolchain_path.as_ref()?.join(INSTALL_DIR);
+        Some(match self.binary_dir {
+            Some(ref dir) => install_dir.join(dir),
+            None => install_dir,
+        })
     }
 
     /// Load the config from disk
     pub fn load() -> Result<Self> {
-        let config_path = Self::config_path()?;
+        Self::load_from(&Self::config_path()?)
+    }
+
+    pub(crate) fn load_from(config_path: &Path) -> Result<Self> {
         if !config_path.exists() {
+            trace!(path = %config_path.display(), "no config file, using defaults");
             return Ok(Self::default());
         }
 
-        let content = std::fs::read_to_string(&config_path)?;
-        let config: ToolchainConfig = toml::from_str(&content)
-            .map_err(|e| CargoJamError::TemplateConfig(format!("Failed to parse config: {}", e)))?;
-        Ok(config)
+        trace!(path = %config_path.display(), "loading config");
+        let content = std::fs::read_to_string(config_path)?;
+        Self::parse(&content)
     }
 
-    /// Save the config to disk
+    /// Change the config on disk without losing concurrent updates: the config is
+    /// re-read and written back under the toolchain lock
+    pub fn update<T>(change: impl FnOnce(&mut ToolchainConfig) -> T) -> Result<T> {
+        ToolchainLock::acquire()?.update_config(change)
+    }
+
+    /// Parse the contents of a config file
+    pub fn parse(content: &str) -> Result<Self> {
+        toml::from_str(content)
+            .map_err(|e| CargoJamError::TemplateConfig(format!("Failed to parse config: {}", e)))
+    }
+
+    /// Save the config to disk, replacing it atomically.
+    ///
+    /// Use `update` to change the config, so that concurrent updates aren't lost.
     pub fn save(&self) -> Result<()> {
-        let home_dir = Self::home_dir()?;
-        std::fs::create_dir_all(&home_dir)?;
+        self.save_to(&Self::config_path()?)
+    }
+
+    pub(crate) fn save_to(&self, config_path: &Path) -> Result<()> {
+        let parent = config_path
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        std::fs::create_dir_all(parent)?;
 
-        let config_path = Self::config_path()?;
         let content = toml::to_string_pretty(self).map_err(|e| {
             CargoJamError::TemplateConfig(format!("Failed to serialize config: {}", e))
         })?;
-        std::fs::write(&config_path, content)?;
+        debug!(path = %config_path.display(), "saving config");
+        // Readers never see a partially written file
+        let mut temp = tempfile::NamedTempFile::new_in(parent)?;
+        std::io::Write::write_all(&mut temp, content.as_bytes())?;
+        temp.persist(config_path).map_err(|e| e.error)?;
         Ok(())
     }
 
+    /// Toolchain version pinned by the project's `jam-toolchain.toml`, if any
+    pub fn required_version(project_dir: &Path) -> Result<Option<String>> {
+        let pin_path = project_dir.join(TOOLCHAIN_PIN_FILE);
+        if !pin_path.exists() {
+            trace!(path = %pin_path.display(), "no toolchain pin");
+            return Ok(None);
+        }
+
+        let content = std::fs::read_to_string(&pin_path)?;
+        let pin: ToolchainPin = toml::from_str(&content).map_err(|e| {
+            CargoJamError::TemplateConfig(format!("Failed to parse {}: {}", TOOLCHAIN_PIN_FILE, e))
+        })?;
+        Ok(Some(pin.version.trim().to_string()))
+    }
+
+    /// Refuse to run against a toolchain other than the one pinned by the project's
+    /// `jam-toolchain.toml`, returning the pinned version if there is one
+    pub fn check_required_version(&self, project_dir: &Path) -> Result<Option<String>> {
+        let Some(required) = Self::required_version(project_dir)? else {
+            return Ok(None);
+        };
+        if self.installed_version.as_deref() != Some(required.as_str()) {
+            return Err(CargoJamError::ToolchainMismatch {
+                installed: self
+                    .installed_version
+                    .clone()
+                    .unwrap_or_else(|| "no toolchain".to_string()),
+                required,
+            });
+        }
+        Ok(Some(required))
+    }
+
     /// Check if a toolchain is installed
     pub fn is_installed(&self) -> bool {
         if let Some(ref path) = self.toolchain_path {
@@ -94,12 +235,49 @@ impl ToolchainConfig {
         }
     }
 
+    /// Check if `version` is the installed toolchain
+    pub fn is_installed_version(&self, version: &str) -> bool {
+        self.is_installed() && self.installed_version.as_deref() == Some(version)
+    }
+
     /// Update config after installation
     pub fn set_installed(&mut self, version: &str, path: PathBuf) {
         self.installed_version = Some(version.to_string());
         self.toolchain_path = Some(path);
         self.installed_at = Some(chrono_lite_now());
     }
+
+    /// Forget the installed toolchain after it has been removed
+    pub fn clear_installed(&mut self) {
+        self.installed_version = None;
+        self.toolchain_path = None;
+        self.installed_at = None;
+        self.binary_dir = None;
+        self.preserve_layout = false;
+    }
+
+    /// Record where the installed archive keeps its binaries
+    pub fn set_layout(&mut self, binary_dir: PathBuf, preserve_layout: bool) {
+        self.binary_dir = Some(binary_dir).filter(|dir| !dir.as_os_str().is_empty());
+        self.preserve_layout = preserve_layout;
+    }
+}
+
+/// Resolve the cargo-polkajam home from environment variables, falling back to `platform_home`
+fn resolve_home_dir(
+    var: impl Fn(&str) -> Option<OsString>,
+    platform_home: impl FnOnce() -> Option<PathBuf>,
+) -> Option<PathBuf> {
+    let non_empty = |key: &str| var(key).filter(|v| !v.is_empty()).map(PathBuf::from);
+
+    if let Some(dir) = non_empty("CARGO_JAM_HOME") {
+        return Some(dir);
+    }
+
+    non_empty("HOME")
+        .or_else(|| non_empty("USERPROFILE"))
+        .or_else(platform_home)
+        .map(|home| home.join(".cargo-polkajam"))
 }
 
 /// Simple timestamp without pulling in chrono
@@ -110,3 +288,114 @@ fn chrono_lite_now() -> String {
         .unwrap_or_default();
     format!("{}", duration.as_secs())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_template_parses() {
+        let config = ToolchainConfig::parse(CONFIG_TEMPLATE).unwrap();
+        assert!(!config.is_installed());
+        assert!(config.trusted_keys.is_empty());
+
+        let uncommented: String = CONFIG_TEMPLATE
+            .lines()
+            .filter(|line| line.contains(" = "))
+            .map(|line| format!("{}\n", line.trim_start_matches("# ")))
+            .collect();
+        let config = ToolchainConfig::parse(&uncommented).unwrap();
+        assert_eq!(config.default_timeout, Some(1800));
+        assert!(ToolchainConfig::parse("default_timeout = \"soon\"").is_err());
+    }
+
+    #[test]
+    fn test_resolve_home_dir() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |key: &str| {
+                vars.iter()
+                    .find(|(k, _)| *k == key)
+                    .map(|(_, v)| OsString::from(v))
+            }
+        };
+
+        assert_eq!(
+            resolve_home_dir(
+                env(&[("CARGO_JAM_HOME", "/jam"), ("HOME", "/home/me")]),
+                || None
+            ),
+            Some(PathBuf::from("/jam"))
+        );
+        assert_eq!(
+            resolve_home_dir(env(&[("CARGO_JAM_HOME", ""), ("HOME", "/home/me")]), || {
+                None
+            }),
+            Some(PathBuf::from("/home/me/.cargo-polkajam"))
+        );
+        assert_eq!(
+            resolve_home_dir(env(&[("USERPROFILE", "C:/Users/me")]), || None),
+            Some(PathBuf::from("C:/Users/me/.cargo-polkajam"))
+        );
+        assert_eq!(resolve_home_dir(env(&[]), || None), None);
+    }
+
+    #[test]
+    fn test_required_version() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(ToolchainConfig::required_version(dir.path()).unwrap(), None);
+
+        std::fs::write(
+            dir.path().join(TOOLCHAIN_PIN_FILE),
+            "version = \"nightly-2025-12-29\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            ToolchainConfig::required_version(dir.path())
+                .unwrap()
+                .as_deref(),
+            Some("nightly-2025-12-29")
+        );
+
+        std::fs::write(dir.path().join(TOOLCHAIN_PIN_FILE), "channel = 1").unwrap();
+        assert!(ToolchainConfig::required_version(dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_check_required_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = |version: &str| ToolchainConfig {
+            installed_version: Some(version.to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            config("nightly-2025-12-29")
+                .check_required_version(dir.path())
+                .unwrap(),
+            None
+        );
+
+        std::fs::write(
+            dir.path().join(TOOLCHAIN_PIN_FILE),
+            "version = \"nightly-2025-12-29\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config("nightly-2025-12-29")
+                .check_required_version(dir.path())
+                .unwrap()
+                .as_deref(),
+            Some("nightly-2025-12-29")
+        );
+
+        let err = config("nightly-2026-01-05")
+            .check_required_version(dir.path())
+            .unwrap_err();
+        assert!(matches!(err, CargoJamError::ToolchainMismatch { .. }));
+        assert!(err
+            .to_string()
+            .contains("setup --version nightly-2025-12-29"));
+        assert!(ToolchainConfig::default()
+            .check_required_version(dir.path())
+            .is_err());
+    }
+}
@@ -0,0 +1,130 @@
+use crate::error::{CargoJamError, Result};
+use crate::toolchain::config::ToolchainConfig;
+use blake2::digest::consts::U32;
+use blake2::{Blake2b, Digest};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Code hashes of the services deployed from this machine, in the cargo-polkajam home
+const DEPLOYMENTS_FILE: &str = "deployments.toml";
+
+/// The on-chain code hash of a blob: its 32-byte BLAKE2b hash, `0x`-prefixed hex
+pub fn code_hash(blob: &[u8]) -> String {
+    let digest = Blake2b::<U32>::digest(blob);
+    format!("0x{:x}", digest)
+}
+
+/// Code hash last deployed for each service, keyed by RPC URL and service ID.
+///
+/// Chains are not queried for a service's code, so the hash is only known for
+/// services created or upgraded with `cargo polkajam deploy`.
+#[derive(Debug, Default)]
+pub struct Deployments {
+    path: PathBuf,
+    services: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl Deployments {
+    /// Load the records from the cargo-polkajam home (empty when there are none yet)
+    pub fn load() -> Result<Self> {
+        Self::load_from(ToolchainConfig::home_dir()?.join(DEPLOYMENTS_FILE))
+    }
+
+    fn load_from(path: PathBuf) -> Result<Self> {
+        let services = match std::fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content).map_err(|e| {
+                CargoJamError::Build(format!("Failed to parse {}: {}", path.display(), e))
+            })?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self { path, services })
+    }
+
+    /// The code hash recorded for a service
+    pub fn code_hash(&self, rpc: &str, service_id: &str) -> Option<&str> {
+        self.services
+            .get(rpc)
+            .and_then(|services| services.get(&normalize_id(service_id)))
+            .map(String::as_str)
+    }
+
+    /// Record the code a service now runs and write the records back
+    pub fn record(&mut self, rpc: &str, service_id: &str, code_hash: &str) -> Result<()> {
+        self.services
+            .entry(rpc.to_string())
+            .or_default()
+            .insert(normalize_id(service_id), code_hash.to_string());
+        self.save()
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = toml::to_string(&self.services)
+            .map_err(|e| CargoJamError::Build(format!("Failed to serialize deployments: {}", e)))?;
+        std::fs::write(&self.path, content)?;
+        Ok(())
+    }
+}
+
+/// Service IDs are hex; `0x1A` and `1a` name the same service
+fn normalize_id(service_id: &str) -> String {
+    let id = service_id.trim();
+    let id = id
+        .strip_prefix("0x")
+        .or_else(|| id.strip_prefix("0X"))
+        .unwrap_or(id);
+    id.to_ascii_lowercase()
+}
+
+/// Parse a `--upgrade` service ID: up to 8 hex digits (a 32-bit ID), `0x` optional
+pub fn parse_service_id(value: &str) -> std::result::Result<String, String> {
+    let id = normalize_id(value);
+    if id.is_empty() || id.len() > 8 || !id.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!(
+            "'{}' is not a service ID (expected up to 8 hex digits)",
+            value
+        ));
+    }
+    Ok(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_hash() {
+        // BLAKE2b-256 of the empty input
+        assert_eq!(
+            code_hash(b""),
+            "0x0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8"
+        );
+    }
+
+    #[test]
+    fn test_parse_service_id() {
+        assert_eq!(parse_service_id("0x1A2b").unwrap(), "1a2b");
+        assert_eq!(parse_service_id("ffffffff").unwrap(), "ffffffff");
+        assert!(parse_service_id("100000000").is_err());
+        assert!(parse_service_id("my-service").is_err());
+        assert!(parse_service_id("").is_err());
+    }
+
+    #[test]
+    fn test_deployments_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(DEPLOYMENTS_FILE);
+        let rpc = "ws://localhost:19800";
+
+        let mut deployments = Deployments::load_from(path.clone()).unwrap();
+        assert_eq!(deployments.code_hash(rpc, "1a"), None);
+        deployments.record(rpc, "0x1A", "0xabc").unwrap();
+
+        let deployments = Deployments::load_from(path).unwrap();
+        assert_eq!(deployments.code_hash(rpc, "1a"), Some("0xabc"));
+        assert_eq!(deployments.code_hash("ws://other:9944", "1a"), None);
+    }
+}
@@ -1,16 +1,154 @@
 use crate::error::{CargoJamError, Result};
+use crate::project::manifest::hash_bytes;
+use crate::toolchain::checksums::{record_checksums, INSTALL_DIR};
 use crate::toolchain::config::ToolchainConfig;
+use crate::toolchain::lock::ToolchainLock;
 use crate::toolchain::platform::Platform;
 use flate2::read::GzDecoder;
-use serde::Deserialize;
+use indicatif::{ProgressBar, ProgressStyle};
+use reqwest::header::{HeaderMap, CONTENT_RANGE, ETAG, IF_RANGE, LAST_MODIFIED, RANGE};
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::fs::File;
-use std::io;
+use std::io::{self, Write};
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
 use tar::Archive;
+use tracing::debug;
 
 const GITHUB_API_URL: &str = "https://api.github.com/repos/paritytech/polkajam-releases/releases";
 
-#[derive(Debug, Deserialize)]
+/// Default seconds allowed to establish a connection
+pub const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
+
+/// Default seconds a request may wait for a response, or for more data while downloading
+pub const DEFAULT_READ_TIMEOUT_SECS: u64 = 30;
+
+/// Timeouts set with `setup --timeout`/`--connect-timeout`, if any
+static RELEASE_CLIENT: OnceLock<ReleaseClient> = OnceLock::new();
+
+/// Settings of the HTTP client that fetches release metadata and downloads archives
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReleaseClient {
+    /// Limit on waiting for the response and on each read of the body, so slow
+    /// but steady downloads aren't cut off
+    pub timeout: Option<Duration>,
+    /// Limit on establishing the connection
+    pub connect_timeout: Option<Duration>,
+}
+
+impl Default for ReleaseClient {
+    fn default() -> Self {
+        Self::from_secs(DEFAULT_READ_TIMEOUT_SECS, DEFAULT_CONNECT_TIMEOUT_SECS)
+    }
+}
+
+impl ReleaseClient {
+    /// Timeouts in seconds, 0 disabling one
+    pub fn from_secs(timeout: u64, connect_timeout: u64) -> Self {
+        let secs = |s: u64| (s > 0).then(|| Duration::from_secs(s));
+        Self {
+            timeout: secs(timeout),
+            connect_timeout: secs(connect_timeout),
+        }
+    }
+
+    /// Use these settings for every request made by the rest of the process.
+    ///
+    /// Only the first call takes effect.
+    pub fn install(self) {
+        let _ = RELEASE_CLIENT.set(self);
+    }
+
+    fn current() -> Self {
+        RELEASE_CLIENT.get().copied().unwrap_or_default()
+    }
+
+    fn build(&self) -> Result<reqwest::blocking::Client> {
+        debug!(timeout = ?self.timeout, connect_timeout = ?self.connect_timeout, "building HTTP client");
+        let mut builder = reqwest::blocking::Client::builder()
+            .user_agent("cargo-polkajam")
+            .timeout(self.timeout);
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        builder
+            .build()
+            .map_err(|e| CargoJamError::Git(format!("Failed to create HTTP client: {}", e)))
+    }
+}
+
+/// HTTP client with the configured timeouts
+fn http_client() -> Result<reqwest::blocking::Client> {
+    ReleaseClient::current().build()
+}
+
+/// Describe a failed request, reporting timeouts as such
+fn request_error(context: &str, url: &str, e: reqwest::Error) -> CargoJamError {
+    if e.is_timeout() {
+        timed_out(url)
+    } else {
+        CargoJamError::Git(format!("{}: {}", context, e))
+    }
+}
+
+fn timed_out(url: &str) -> CargoJamError {
+    CargoJamError::Git(format!(
+        "request timed out: {} (adjust with setup --timeout or --connect-timeout)",
+        url
+    ))
+}
+
+/// Stream a response body to a file, reporting a stalled transfer as a timeout.
+///
+/// Returns the SHA-256 of the file as hex: `hasher` (holding whatever the file
+/// already contained) updated with the body while it is written.
+fn copy_body(
+    url: &str,
+    body: &mut impl io::Read,
+    file: &mut File,
+    hasher: Sha256,
+) -> Result<String> {
+    let mut writer = HashingWriter {
+        inner: file,
+        hasher,
+    };
+    io::copy(body, &mut writer).map_err(|e| {
+        let timeout = e
+            .get_ref()
+            .and_then(|inner| inner.downcast_ref::<reqwest::Error>())
+            .is_some_and(reqwest::Error::is_timeout);
+        if timeout {
+            timed_out(url)
+        } else {
+            e.into()
+        }
+    })?;
+    Ok(format!("{:x}", writer.hasher.finalize()))
+}
+
+/// Writer hashing everything written through it
+struct HashingWriter<'a> {
+    inner: &'a mut File,
+    hasher: Sha256,
+}
+
+impl Write for HashingWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
 pub struct GitHubRelease {
     pub tag_name: String,
     pub name: Option<String>,
@@ -18,87 +156,321 @@ pub struct GitHubRelease {
     pub assets: Vec<GitHubAsset>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct GitHubAsset {
     pub name: String,
     pub browser_download_url: String,
     pub size: u64,
+    /// Digest GitHub computed for the asset, e.g. `sha256:<hex>` (missing on older releases)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest: Option<String>,
 }
 
-/// Fetch available releases from GitHub
-pub fn fetch_releases(limit: usize) -> Result<Vec<GitHubRelease>> {
-    let client = reqwest::blocking::Client::builder()
-        .user_agent("cargo-polkajam")
-        .build()
-        .map_err(|e| CargoJamError::Git(format!("Failed to create HTTP client: {}", e)))?;
+/// Suffix of the asset holding another asset's SHA-256, as written by `sha256sum`
+const CHECKSUM_ASSET_SUFFIX: &str = ".sha256";
 
-    let url = format!("{}?per_page={}", GITHUB_API_URL, limit);
-    let mut request = client.get(&url);
-
-    // Use GITHUB_TOKEN if available (for CI environments with rate limits)
-    if let Ok(token) = std::env::var("GITHUB_TOKEN") {
-        request = request.header("Authorization", format!("Bearer {}", token));
+/// The SHA-256 (hex) an asset should have: GitHub's `digest` for it, else the
+/// contents of a `<name>.sha256` asset. `None` when the release publishes neither.
+pub fn expected_sha256(release: &GitHubRelease, asset: &GitHubAsset) -> Result<Option<String>> {
+    if let Some(hex) = asset
+        .digest
+        .as_deref()
+        .and_then(|digest| digest.strip_prefix("sha256:"))
+    {
+        return Ok(Some(hex.to_ascii_lowercase()));
     }
 
-    let response = request
+    let checksum_name = format!("{}{}", asset.name, CHECKSUM_ASSET_SUFFIX);
+    let Some(checksum_asset) = release.assets.iter().find(|a| a.name == checksum_name) else {
+        return Ok(None);
+    };
+    let url = &checksum_asset.browser_download_url;
+    debug!(%url, "fetching checksum");
+    let response = http_client()?
+        .get(url)
         .send()
-        .map_err(|e| CargoJamError::Git(format!("Failed to fetch releases: {}", e)))?;
-
+        .map_err(|e| request_error("Failed to download checksum", url, e))?;
     if !response.status().is_success() {
         return Err(CargoJamError::Git(format!(
-            "GitHub API returned status: {}",
+            "Checksum download failed with status: {}",
             response.status()
         )));
     }
+    let body = response
+        .text()
+        .map_err(|e| request_error("Failed to download checksum", url, e))?;
+    parse_checksum(&body).map(Some).ok_or_else(|| {
+        CargoJamError::Git(format!(
+            "{} does not contain a SHA-256 digest",
+            checksum_name
+        ))
+    })
+}
 
-    let releases: Vec<GitHubRelease> = response
-        .json()
-        .map_err(|e| CargoJamError::Git(format!("Failed to parse releases: {}", e)))?;
+/// The digest in a `sha256sum`-style line: 64 hex digits, optionally followed by a file name
+fn parse_checksum(content: &str) -> Option<String> {
+    let hex = content.split_whitespace().next()?;
+    (hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+        .then(|| hex.to_ascii_lowercase())
+}
+
+/// Compare a downloaded file's SHA-256 with the expected one, deleting the file on mismatch
+fn check_download(path: &Path, expected: Option<&str>, actual: &str) -> Result<()> {
+    let Some(expected) = expected else {
+        debug!(file = %path.display(), "release publishes no checksum, download not verified");
+        return Ok(());
+    };
+    if expected == actual {
+        debug!(file = %path.display(), sha256 = %actual, "checksum verified");
+        return Ok(());
+    }
 
-    Ok(releases)
+    let _ = std::fs::remove_file(path);
+    Err(CargoJamError::Git(format!(
+        "Checksum mismatch for {}: expected sha256 {}, got {}. The download is truncated or corrupted; run setup again (or pass --no-verify for a mirror without matching checksums)",
+        path.file_name().unwrap_or_default().to_string_lossy(),
+        expected,
+        actual
+    )))
 }
 
-/// Get the latest nightly release
-pub fn get_latest_release() -> Result<GitHubRelease> {
-    let releases = fetch_releases(10)?;
-    releases
-        .into_iter()
-        .find(|r| r.tag_name.starts_with("nightly"))
-        .ok_or_else(|| CargoJamError::Git("No nightly releases found".to_string()))
+/// Release channel used when none is selected: tags starting with `nightly`
+pub const DEFAULT_CHANNEL: &str = "nightly";
+
+/// Attempts made for a release metadata request that fails transiently
+const FETCH_ATTEMPTS: u32 = 3;
+
+/// Delay before the first retry, growing with each attempt
+const RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// File in the cache directory holding the last fetched releases list
+const RELEASES_CACHE_FILE: &str = "releases.json";
+
+/// Seconds a cached releases list is used before it is fetched again
+const RELEASES_CACHE_TTL_SECS: u64 = 600;
+
+/// Releases list as cached on disk
+#[derive(Debug, Deserialize, Serialize)]
+struct ReleasesCache {
+    /// Unix time of the fetch
+    fetched_at: u64,
+    /// Number of releases requested, so a shorter list isn't used for a longer search
+    limit: usize,
+    releases: Vec<GitHubRelease>,
 }
 
-/// Get a specific release by version
-pub fn get_release(version: &str) -> Result<GitHubRelease> {
-    let client = reqwest::blocking::Client::builder()
-        .user_agent("cargo-polkajam")
-        .build()
-        .map_err(|e| CargoJamError::Git(format!("Failed to create HTTP client: {}", e)))?;
+/// A request that failed before a status was received
+struct RequestFailure {
+    error: CargoJamError,
+    /// Worth retrying: a timeout or a failure to connect
+    transient: bool,
+}
 
-    let url = format!("{}/tags/{}", GITHUB_API_URL, version);
-    let mut request = client.get(&url);
+/// Server errors and rate limiting usually pass
+fn is_transient_status(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
 
+/// GET a GitHub API URL and parse its JSON body, retrying transient failures.
+///
+/// An unsuccessful status is returned as `Ok(Err(status))` for the caller to describe.
+fn get_json<T: DeserializeOwned>(
+    url: &str,
+    context: &str,
+) -> Result<std::result::Result<T, StatusCode>> {
+    let client = http_client()?;
+    let mut attempt = 1;
+    loop {
+        let outcome = try_get_json(&client, url, context);
+        let transient = match &outcome {
+            Ok(Ok(_)) => false,
+            Ok(Err(status)) => is_transient_status(*status),
+            Err(failure) => failure.transient,
+        };
+        if !transient || attempt >= FETCH_ATTEMPTS {
+            return outcome.map_err(|failure| failure.error);
+        }
+
+        let delay = RETRY_DELAY * attempt;
+        debug!(%url, attempt, ?delay, "transient failure, retrying");
+        std::thread::sleep(delay);
+        attempt += 1;
+    }
+}
+
+fn try_get_json<T: DeserializeOwned>(
+    client: &reqwest::blocking::Client,
+    url: &str,
+    context: &str,
+) -> std::result::Result<std::result::Result<T, StatusCode>, RequestFailure> {
+    let failure = |e: reqwest::Error| RequestFailure {
+        transient: e.is_timeout() || e.is_connect(),
+        error: request_error(context, url, e),
+    };
+
+    let mut request = client.get(url);
     // Use GITHUB_TOKEN if available (for CI environments with rate limits)
     if let Ok(token) = std::env::var("GITHUB_TOKEN") {
+        debug!("authenticating with GITHUB_TOKEN");
         request = request.header("Authorization", format!("Bearer {}", token));
     }
 
-    let response = request
-        .send()
-        .map_err(|e| CargoJamError::Git(format!("Failed to fetch release {}: {}", version, e)))?;
-
+    let response = request.send().map_err(failure)?;
     if !response.status().is_success() {
-        return Err(CargoJamError::Git(format!(
-            "Release '{}' not found (status: {})",
-            version,
-            response.status()
-        )));
+        return Ok(Err(response.status()));
+    }
+    response.json().map(Ok).map_err(failure)
+}
+
+/// Fetch available releases from GitHub, newest first.
+///
+/// A list fetched within the last ten minutes is reused from the cache directory.
+pub fn fetch_releases(limit: usize) -> Result<Vec<GitHubRelease>> {
+    let cache_path = ToolchainConfig::cache_dir()?.join(RELEASES_CACHE_FILE);
+    if let Some(releases) = read_releases_cache(&cache_path, limit, unix_now()) {
+        debug!(path = %cache_path.display(), "using cached releases");
+        return Ok(releases);
     }
 
-    let release: GitHubRelease = response
-        .json()
-        .map_err(|e| CargoJamError::Git(format!("Failed to parse release: {}", e)))?;
+    let url = format!("{}?per_page={}", GITHUB_API_URL, limit);
+    debug!(%url, "fetching releases");
+    let releases: Vec<GitHubRelease> = get_json(&url, "Failed to fetch releases")?
+        .map_err(|status| CargoJamError::Git(format!("GitHub API returned status: {}", status)))?;
 
-    Ok(release)
+    let cache = ReleasesCache {
+        fetched_at: unix_now(),
+        limit,
+        releases,
+    };
+    if let Err(e) = write_releases_cache(&cache_path, &cache) {
+        debug!(path = %cache_path.display(), error = %e, "failed to cache releases");
+    }
+    Ok(cache.releases)
+}
+
+/// The cached releases list, if it is fresh and long enough for `limit`
+fn read_releases_cache(path: &Path, limit: usize, now: u64) -> Option<Vec<GitHubRelease>> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let cache: ReleasesCache = serde_json::from_str(&content).ok()?;
+    let age = now.checked_sub(cache.fetched_at)?;
+    if age >= RELEASES_CACHE_TTL_SECS || cache.limit < limit {
+        return None;
+    }
+
+    let mut releases = cache.releases;
+    releases.truncate(limit);
+    Some(releases)
+}
+
+fn write_releases_cache(path: &Path, cache: &ReleasesCache) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string(cache).map_err(io::Error::other)?;
+    std::fs::write(path, json)?;
+    Ok(())
+}
+
+fn unix_now() -> u64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Number of releases searched by `get_latest_release`
+const LATEST_RELEASE_SEARCH_LIMIT: usize = 10;
+
+/// Newest release of a channel, and whether it is the installed toolchain
+#[derive(Debug)]
+pub struct LatestRelease {
+    pub release: GitHubRelease,
+    pub installed: bool,
+}
+
+/// Whether a release tag belongs to a channel (tags are prefixed with the channel name)
+pub fn in_channel(tag: &str, channel: &str) -> bool {
+    tag.starts_with(channel)
+}
+
+/// Get the latest release of a channel
+pub fn get_latest_release(channel: &str) -> Result<LatestRelease> {
+    let release = fetch_releases(LATEST_RELEASE_SEARCH_LIMIT)?
+        .into_iter()
+        .find(|r| in_channel(&r.tag_name, channel))
+        .ok_or_else(|| {
+            CargoJamError::Git(format!(
+                "No {} releases found (searched the latest {})",
+                channel, LATEST_RELEASE_SEARCH_LIMIT
+            ))
+        })?;
+    let installed = ToolchainConfig::load()?.is_installed_version(&release.tag_name);
+    Ok(LatestRelease { release, installed })
+}
+
+/// Number of releases searched by `get_latest_release_before` (GitHub's page size limit)
+const DATED_RELEASE_SEARCH_LIMIT: usize = 100;
+
+/// Get the newest release of a channel published on or before `date` (YYYY-MM-DD)
+pub fn get_latest_release_before(channel: &str, date: &str) -> Result<GitHubRelease> {
+    validate_release_date(date)?;
+    let releases = fetch_releases(DATED_RELEASE_SEARCH_LIMIT)?;
+    latest_before(releases, channel, date).ok_or_else(|| {
+        CargoJamError::Git(format!(
+            "No {} releases published on or before {} (searched the latest {})",
+            channel, date, DATED_RELEASE_SEARCH_LIMIT
+        ))
+    })
+}
+
+/// Check that a date is a valid YYYY-MM-DD calendar date
+pub fn validate_release_date(date: &str) -> Result<()> {
+    let invalid = || CargoJamError::InvalidArgument {
+        arg: "--date".to_string(),
+        reason: format!("'{}' is not a date in YYYY-MM-DD format", date),
+    };
+
+    let parts: Vec<&str> = date.split('-').collect();
+    let [year, month, day] = parts.as_slice() else {
+        return Err(invalid());
+    };
+    if year.len() != 4 || month.len() != 2 || day.len() != 2 {
+        return Err(invalid());
+    }
+
+    let number = |s: &str| s.parse::<u32>().map_err(|_| invalid());
+    let (_, month, day) = (number(year)?, number(month)?, number(day)?);
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return Err(invalid());
+    }
+
+    Ok(())
+}
+
+/// Newest release of `channel` whose `published_at` date is on or before `date`
+fn latest_before(releases: Vec<GitHubRelease>, channel: &str, date: &str) -> Option<GitHubRelease> {
+    releases
+        .into_iter()
+        .filter(|r| in_channel(&r.tag_name, channel))
+        .filter_map(|r| {
+            let published = r.published_at.as_deref()?.get(..10)?.to_string();
+            (published.as_str() <= date).then_some((published, r))
+        })
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, r)| r)
+}
+
+/// Get a specific release by version
+pub fn get_release(version: &str) -> Result<GitHubRelease> {
+    let url = format!("{}/tags/{}", GITHUB_API_URL, version);
+    debug!(%url, "fetching release");
+    get_json(&url, &format!("Failed to fetch release {}", version))?.map_err(|status| {
+        CargoJamError::Git(format!(
+            "Release '{}' not found (status: {})",
+            version, status
+        ))
+    })
 }
 
 /// Download and install a release
@@ -106,8 +478,14 @@ pub fn download_and_install(
     release: &GitHubRelease,
     platform: &Platform,
     force: bool,
+    normalize: bool,
+    verify: bool,
+    progress: &ProgressBar,
+    unknown_size_style: &ProgressStyle,
 ) -> Result<PathBuf> {
-    let mut config = ToolchainConfig::load()?;
+    // Installs replace the toolchain directory and the config; one at a time
+    let lock = ToolchainLock::acquire()?;
+    let config = ToolchainConfig::load()?;
 
     // Check if already installed
     if !force && config.is_installed() {
@@ -122,10 +500,95 @@ pub fn download_and_install(
     }
 
     // Find the asset for this platform
-    let asset = release
+    let asset = find_asset(release, platform)?;
+
+    // Create toolchain directory
+    let toolchain_dir = ToolchainConfig::toolchain_dir()?;
+    std::fs::create_dir_all(&toolchain_dir)?;
+
+    // Download the archive, resuming only a partial file of this same release
+    let download_url = &asset.browser_download_url;
+    let download_dir = downloads_dir(&toolchain_dir, &release.tag_name)?;
+    let archive_path = download_dir.join(&asset.name);
+    let partial_path = partial_path(&archive_path);
+    debug!(url = %download_url, archive = %archive_path.display(), "downloading toolchain");
+
+    let expected = if verify {
+        expected_sha256(release, asset)?
+    } else {
+        None
+    };
+    progress.set_length(asset.size);
+    let actual =
+        download_file_with_progress(download_url, &partial_path, progress, unknown_size_style);
+    // The bar has nothing more to show while the archive is unpacked
+    progress.finish_and_clear();
+    let actual = actual?;
+    check_download(&partial_path, expected.as_deref(), &actual)?;
+    std::fs::rename(&partial_path, &archive_path)?;
+
+    let unpacked = unpack_toolchain(&archive_path, &toolchain_dir, platform, normalize);
+
+    // Clean up the archive, whether or not it could be installed
+    std::fs::remove_dir_all(&download_dir)?;
+    let binary_dir = unpacked?;
+
+    // Update config
+    lock.update_config(|config| {
+        config.set_installed(&release.tag_name, toolchain_dir.clone());
+        config.set_layout(binary_dir, !normalize);
+    })?;
+
+    Ok(toolchain_dir)
+}
+
+/// Directory an install downloads `tag`'s archive into, under `toolchain_dir/downloads/`.
+///
+/// Partial downloads of other releases are removed: they can never be resumed into this one.
+fn downloads_dir(toolchain_dir: &Path, tag: &str) -> Result<PathBuf> {
+    let root = toolchain_dir.join(DOWNLOADS_DIR);
+    if let Ok(entries) = std::fs::read_dir(&root) {
+        for entry in entries.flatten() {
+            if entry.file_name() != tag {
+                debug!(dir = %entry.path().display(), "removing partial download of another release");
+                let _ = std::fs::remove_dir_all(entry.path());
+            }
+        }
+    }
+    let dir = root.join(tag);
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Subdirectory of the toolchain directory holding in-progress downloads, one per tag
+const DOWNLOADS_DIR: &str = "downloads";
+
+/// Where an archive is downloaded to before it's complete and verified
+fn partial_path(archive_path: &Path) -> PathBuf {
+    let mut path = archive_path.as_os_str().to_owned();
+    path.push(".part");
+    PathBuf::from(path)
+}
+
+/// File next to a partial download recording the `ETag` or `Last-Modified` value it
+/// was started from, sent back as `If-Range` when resuming
+fn validator_path(partial: &Path) -> PathBuf {
+    let mut path = partial.as_os_str().to_owned();
+    path.push(".validator");
+    PathBuf::from(path)
+}
+
+fn is_partial(name: &str) -> bool {
+    name.ends_with(".part") || name.ends_with(".part.validator")
+}
+
+/// Find the release asset built for a platform
+pub fn find_asset<'a>(release: &'a GitHubRelease, platform: &Platform) -> Result<&'a GitHubAsset> {
+    release
         .assets
         .iter()
         .find(|a| a.name.contains(platform.asset_suffix()))
+        .inspect(|a| debug!(asset = %a.name, %platform, "selected release asset"))
         .ok_or_else(|| {
             CargoJamError::Git(format!(
                 "No asset found for platform '{}' in release '{}'. Available assets: {}",
@@ -138,102 +601,451 @@ pub fn download_and_install(
                     .collect::<Vec<_>>()
                     .join(", ")
             ))
-        })?;
+        })
+}
 
-    // Create toolchain directory
+/// Download a platform's archive into `cache_dir/<tag>/` without installing it.
+///
+/// Archives already in the cache are kept. With `verify`, a download that doesn't
+/// match the release's checksum is deleted and reported. Returns the archive path.
+pub fn prefetch_asset(
+    release: &GitHubRelease,
+    platform: &Platform,
+    cache_dir: &Path,
+    progress: &ProgressBar,
+    unknown_size_style: &ProgressStyle,
+    verify: bool,
+) -> Result<PathBuf> {
+    let asset = find_asset(release, platform)?;
+
+    let release_dir = cache_dir.join(&release.tag_name);
+    std::fs::create_dir_all(&release_dir)?;
+
+    let archive_path = release_dir.join(&asset.name);
+    if archive_path.exists() {
+        debug!(archive = %archive_path.display(), "archive already cached");
+        return Ok(archive_path);
+    }
+
+    // Download next to the final path so a failed transfer never looks cached
+    let partial_path = partial_path(&archive_path);
+    let expected = if verify {
+        expected_sha256(release, asset)?
+    } else {
+        None
+    };
+    progress.set_length(asset.size);
+    let actual = download_file_with_progress(
+        &asset.browser_download_url,
+        &partial_path,
+        progress,
+        unknown_size_style,
+    )?;
+    check_download(&partial_path, expected.as_deref(), &actual)?;
+    std::fs::rename(&partial_path, &archive_path)?;
+
+    Ok(archive_path)
+}
+
+/// Install a toolchain from an archive already on disk, without network access
+pub fn install_from_archive(
+    archive_path: &Path,
+    platform: &Platform,
+    label: &str,
+    normalize: bool,
+) -> Result<PathBuf> {
+    validate_archive(archive_path, platform)?;
+
+    let lock = ToolchainLock::acquire()?;
     let toolchain_dir = ToolchainConfig::toolchain_dir()?;
     std::fs::create_dir_all(&toolchain_dir)?;
 
-    // Download the archive
-    let download_url = &asset.browser_download_url;
-    let archive_path = toolchain_dir.join(&asset.name);
+    let binary_dir = unpack_toolchain(archive_path, &toolchain_dir, platform, normalize)?;
 
-    download_file(download_url, &archive_path)?;
+    lock.update_config(|config| {
+        config.set_installed(label, toolchain_dir.clone());
+        config.set_layout(binary_dir, !normalize);
+    })?;
 
-    // Remove old installation if it exists
-    let normalized_dir = toolchain_dir.join("polkajam-nightly");
-    if normalized_dir.exists() {
-        std::fs::remove_dir_all(&normalized_dir)?;
+    Ok(toolchain_dir)
+}
+
+/// Check that a local archive exists and has the extension this platform ships
+pub fn validate_archive(archive_path: &Path, platform: &Platform) -> Result<()> {
+    if !archive_path.is_file() {
+        return Err(CargoJamError::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("Toolchain archive not found: {}", archive_path.display()),
+        )));
     }
 
-    // Extract the archive
-    let extract_dir = toolchain_dir.clone();
-    extract_archive(&archive_path, &extract_dir, platform)?;
+    let expected = platform.archive_extension();
+    let file_name = archive_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if !file_name.ends_with(&format!(".{}", expected)) {
+        return Err(CargoJamError::InvalidArgument {
+            arg: "--from-archive".to_string(),
+            reason: format!(
+                "'{}' is not a .{} archive, which is expected on {}",
+                file_name, expected, platform
+            ),
+        });
+    }
 
-    // Clean up the archive
-    std::fs::remove_file(&archive_path)?;
+    Ok(())
+}
+
+/// Replace any existing installation with the contents of an archive.
+///
+/// The archive is extracted into a staging directory next to the installation
+/// and only moved into place once every entry was written, so a failed
+/// extraction (disk full, permissions, corrupt archive) leaves the previous
+/// installation untouched and no partial toolchain behind.
+///
+/// Returns the directory holding the binaries, relative to `polkajam-nightly/`.
+fn unpack_toolchain(
+    archive_path: &Path,
+    toolchain_dir: &Path,
+    platform: &Platform,
+    normalize: bool,
+) -> Result<PathBuf> {
+    // Dropping the staging directory removes whatever was extracted on failure
+    std::fs::create_dir_all(toolchain_dir)?;
+    let staging = tempfile::tempdir_in(toolchain_dir)?;
+    let staging_dir = staging.path().to_path_buf();
 
-    // Normalize the extracted directory name to polkajam-nightly
-    normalize_extracted_dir(&toolchain_dir)?;
+    let extracted = extract_install_root(archive_path, &staging_dir, platform, normalize)?;
+    let binary_dir = find_binary_dir(&extracted).ok_or_else(|| {
+        CargoJamError::Git(format!(
+            "Archive {} does not contain the jamt binary",
+            archive_path.display()
+        ))
+    })?;
+    debug!(dir = %binary_dir.display(), "located toolchain binaries");
 
-    // Update config
-    config.set_installed(&release.tag_name, toolchain_dir.clone());
-    config.save()?;
+    // Move the old installation aside so it can be restored if the swap fails
+    let installed = toolchain_dir.join(INSTALL_DIR);
+    let previous = staging_dir.join("previous");
+    let had_previous = installed.exists();
+    if had_previous {
+        std::fs::rename(&installed, &previous)?;
+    }
 
-    Ok(toolchain_dir)
+    if let Err(e) = std::fs::rename(&extracted, &installed) {
+        if had_previous {
+            let _ = std::fs::rename(&previous, &installed);
+        }
+        return Err(e.into());
+    }
+    debug!(dir = %installed.display(), "installed extracted toolchain");
+
+    // Remember what was installed so `setup --repair` can spot damaged files
+    record_checksums(toolchain_dir)?;
+    Ok(binary_dir)
 }
 
-/// Normalize the extracted directory name to polkajam-nightly
-fn normalize_extracted_dir(toolchain_dir: &PathBuf) -> Result<()> {
-    let normalized_name = "polkajam-nightly";
-    let normalized_path = toolchain_dir.join(normalized_name);
+/// The archive `prefetch_asset` writes for a release, if it is already in the cache
+/// with the size the release lists (a truncated copy is ignored)
+pub fn cached_release_archive(
+    release: &GitHubRelease,
+    platform: &Platform,
+    cache_dir: &Path,
+) -> Option<PathBuf> {
+    let asset = find_asset(release, platform).ok()?;
+    let archive_path = cache_dir.join(&release.tag_name).join(&asset.name);
+    let size = std::fs::metadata(&archive_path).ok()?.len();
+    if asset.size != 0 && size != asset.size {
+        debug!(archive = %archive_path.display(), size, expected = asset.size, "ignoring cached archive of the wrong size");
+        return None;
+    }
+    Some(archive_path)
+}
 
-    // Find any directory starting with "polkajam-" that isn't already normalized
-    if let Ok(entries) = std::fs::read_dir(toolchain_dir) {
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if path.is_dir() {
-                let name = path.file_name().unwrap().to_string_lossy();
-                if name.starts_with("polkajam-") && name != normalized_name {
-                    // Rename to normalized name
-                    std::fs::rename(&path, &normalized_path)?;
-                    return Ok(());
-                }
-            }
+/// Find a cached archive for a version and platform (as written by `prefetch_asset`)
+pub fn cached_archive(cache_dir: &Path, version: &str, platform: &Platform) -> Option<PathBuf> {
+    let entries = std::fs::read_dir(cache_dir.join(version)).ok()?;
+    let mut archives: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|path| {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            name.contains(platform.asset_suffix()) && !is_partial(&name)
+        })
+        .collect();
+    archives.sort();
+    archives.into_iter().next()
+}
+
+/// Restore missing or damaged toolchain files from an archive, leaving intact files alone.
+///
+/// Every file in the archive is compared with its installed copy; those that are missing or
+/// differ are copied over. Returns the repaired paths, relative to `polkajam-nightly/`.
+pub fn repair_from_archive(
+    archive_path: &Path,
+    toolchain_dir: &Path,
+    platform: &Platform,
+    normalize: bool,
+) -> Result<Vec<String>> {
+    let staging = tempfile::tempdir_in(toolchain_dir)?;
+    let extracted = extract_install_root(archive_path, staging.path(), platform, normalize)?;
+
+    let installed = toolchain_dir.join(INSTALL_DIR);
+    let mut repaired = Vec::new();
+
+    for entry in walkdir::WalkDir::new(&extracted).sort_by_file_name() {
+        let entry = entry.map_err(|e| io::Error::other(e.to_string()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(&extracted)
+            .unwrap_or(entry.path());
+        let target = installed.join(relative);
+
+        let intact = target.is_file()
+            && hash_bytes(&std::fs::read(&target)?) == hash_bytes(&std::fs::read(entry.path())?);
+        if intact {
+            continue;
+        }
+
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
         }
+        debug!(file = %target.display(), "restoring from archive");
+        std::fs::copy(entry.path(), &target)?;
+        repaired.push(relative.to_string_lossy().replace('\\', "/"));
     }
 
-    Ok(())
+    record_checksums(toolchain_dir)?;
+    Ok(repaired)
 }
 
-/// Download a file with progress indication
-fn download_file(url: &str, dest: &PathBuf) -> Result<()> {
-    let client = reqwest::blocking::Client::builder()
-        .user_agent("cargo-polkajam")
-        .build()
-        .map_err(|e| CargoJamError::Git(format!("Failed to create HTTP client: {}", e)))?;
+/// Extract an archive under `staging_dir` and return the directory to install as `polkajam-nightly`.
+///
+/// When normalizing, an archive holding a single top-level directory (whatever its
+/// name, e.g. `polkajam-nightly-2025-12-29-linux-x86_64/`) is installed without it;
+/// otherwise the archive's layout is kept as-is.
+fn extract_install_root(
+    archive_path: &Path,
+    staging_dir: &Path,
+    platform: &Platform,
+    normalize: bool,
+) -> Result<PathBuf> {
+    let extracted = staging_dir.join("extracted");
+    std::fs::create_dir_all(&extracted)?;
+    extract_archive(archive_path, &extracted, platform)?;
 
-    let mut response = client
-        .get(url)
+    if !normalize {
+        return Ok(extracted);
+    }
+
+    let entries: Vec<PathBuf> = std::fs::read_dir(&extracted)?
+        .map(|e| e.map(|e| e.path()))
+        .collect::<io::Result<_>>()?;
+    match entries.as_slice() {
+        [single] if single.is_dir() => {
+            debug!(dir = %single.display(), "normalizing extracted directory");
+            Ok(single.clone())
+        }
+        _ => Ok(extracted),
+    }
+}
+
+/// Directory containing `jamt`, relative to `root`, preferring the shallowest match
+fn find_binary_dir(root: &Path) -> Option<PathBuf> {
+    walkdir::WalkDir::new(root)
+        .sort_by_file_name()
+        .into_iter()
+        .flatten()
+        .filter(|e| {
+            e.file_type().is_file() && (e.file_name() == "jamt" || e.file_name() == "jamt.exe")
+        })
+        .min_by_key(|e| e.depth())
+        .and_then(|e| {
+            e.path()
+                .parent()
+                .and_then(|dir| dir.strip_prefix(root).ok())
+                .map(Path::to_path_buf)
+        })
+}
+
+/// Download a file, reporting transferred bytes on a progress bar and returning
+/// its SHA-256 as hex. Without a `Content-Length` the bar switches to `unknown_size_style`,
+/// e.g. a spinner counting bytes.
+///
+/// A partial file left at `dest` by an interrupted download is resumed with a
+/// `Range` request, conditional through `If-Range` on the `ETag` or `Last-Modified`
+/// value the download started with. A partial file without one is never resumed.
+/// When the server doesn't answer with the missing bytes (`206 Partial Content`
+/// from the end of the file), it is downloaded from the start.
+fn download_file_with_progress(
+    url: &str,
+    dest: &Path,
+    progress: &ProgressBar,
+    unknown_size_style: &ProgressStyle,
+) -> Result<String> {
+    let validator_path = validator_path(dest);
+    let validator = std::fs::read_to_string(&validator_path).ok();
+    let partial = match validator {
+        Some(_) => std::fs::metadata(dest).map(|m| m.len()).unwrap_or(0),
+        None => 0,
+    };
+    let mut request = http_client()?.get(url);
+    if let (true, Some(validator)) = (partial > 0, &validator) {
+        request = request
+            .header(RANGE, format!("bytes={}-", partial))
+            .header(IF_RANGE, validator.trim());
+    }
+    let response = request
         .send()
-        .map_err(|e| CargoJamError::Git(format!("Failed to download: {}", e)))?;
+        .map_err(|e| request_error("Failed to download", url, e))?;
 
-    if !response.status().is_success() {
+    let status = response.status();
+    if partial > 0 && status == StatusCode::RANGE_NOT_SATISFIABLE {
+        // Nothing follows the partial file, so it isn't a prefix of this download
+        debug!(file = %dest.display(), "partial download not resumable, restarting");
+        std::fs::remove_file(dest)?;
+        return download_file_with_progress(url, dest, progress, unknown_size_style);
+    }
+    if !status.is_success() {
         return Err(CargoJamError::Git(format!(
             "Download failed with status: {}",
-            response.status()
+            status
         )));
     }
 
-    let mut file = File::create(dest)?;
-    io::copy(&mut response, &mut file)?;
+    let resumed = status == StatusCode::PARTIAL_CONTENT;
+    if resumed && content_range_start(&response) != Some(partial) {
+        debug!(file = %dest.display(), "server sent another range, restarting");
+        std::fs::remove_file(dest)?;
+        return download_file_with_progress(url, dest, progress, unknown_size_style);
+    }
 
-    Ok(())
+    let (mut file, hasher, offset) = if resumed {
+        debug!(file = %dest.display(), offset = partial, "resuming download");
+        let mut hasher = Sha256::new();
+        io::copy(&mut File::open(dest)?, &mut hasher)?;
+        let file = File::options().append(true).open(dest)?;
+        (file, hasher, partial)
+    } else {
+        // A `200 OK` carries the whole file, replacing any partial one
+        match resume_validator(response.headers()) {
+            Some(validator) => std::fs::write(&validator_path, validator)?,
+            None => remove_if_exists(&validator_path)?,
+        }
+        (File::create(dest)?, Sha256::new(), 0)
+    };
+
+    match response.content_length() {
+        Some(len) => progress.set_length(offset + len),
+        None => {
+            progress.unset_length();
+            progress.set_style(unknown_size_style.clone());
+            progress.enable_steady_tick(Duration::from_millis(100));
+        }
+    }
+
+    progress.set_position(offset);
+
+    let digest = copy_body(url, &mut progress.wrap_read(response), &mut file, hasher)?;
+    // Complete, so there's nothing left to resume
+    remove_if_exists(&validator_path)?;
+    Ok(digest)
+}
+
+/// The value to send as `If-Range` when resuming this response's body: its strong
+/// `ETag`, else its `Last-Modified` date (weak tags can't be used with ranges)
+fn resume_validator(headers: &HeaderMap) -> Option<String> {
+    let value = |name| headers.get(name)?.to_str().ok().map(str::to_string);
+    value(ETAG)
+        .filter(|etag| !etag.starts_with("W/"))
+        .or_else(|| value(LAST_MODIFIED))
+}
+
+fn remove_if_exists(path: &Path) -> Result<()> {
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+/// First byte of a `206 Partial Content` response, from `Content-Range: bytes <start>-<end>/<size>`
+fn content_range_start(response: &reqwest::blocking::Response) -> Option<u64> {
+    let range = response.headers().get(CONTENT_RANGE)?.to_str().ok()?;
+    let (start, _) = range.strip_prefix("bytes ")?.split_once('-')?;
+    start.trim().parse().ok()
 }
 
 /// Extract an archive (tar.gz or zip)
-fn extract_archive(archive_path: &PathBuf, dest: &PathBuf, platform: &Platform) -> Result<()> {
-    match platform.archive_extension() {
-        "tar.gz" => extract_tar_gz(archive_path, dest),
-        "zip" => extract_zip(archive_path, dest),
-        ext => Err(CargoJamError::Git(format!(
-            "Unknown archive extension: {}",
-            ext
-        ))),
+fn extract_archive(archive_path: &Path, dest: &Path, platform: &Platform) -> Result<()> {
+    let root = extraction_root(dest)?;
+    let extracted = match platform.archive_extension() {
+        "tar.gz" => extract_tar_gz(archive_path, &root),
+        "zip" => extract_zip(archive_path, &root),
+        ext => {
+            return Err(CargoJamError::Git(format!(
+                "Unknown archive extension: {}",
+                ext
+            )))
+        }
+    };
+    extracted.map_err(|e| match e {
+        CargoJamError::Io(e) if is_path_too_long(&e) => path_too_long(dest, e),
+        e => e,
+    })
+}
+
+/// Where to extract into: on Windows the `\\?\` form of `dest`, so entries nested
+/// deeper than MAX_PATH (260 characters) can still be written
+fn extraction_root(dest: &Path) -> Result<PathBuf> {
+    #[cfg(windows)]
+    {
+        let absolute = std::path::absolute(dest)?;
+        let extended = extended_length_path(&absolute.to_string_lossy());
+        Ok(extended.map(PathBuf::from).unwrap_or(absolute))
+    }
+    #[cfg(not(windows))]
+    Ok(dest.to_path_buf())
+}
+
+/// The extended-length (`\\?\`) form of an absolute, normalized Windows path, or
+/// `None` for a relative one
+#[cfg(any(windows, test))]
+fn extended_length_path(path: &str) -> Option<String> {
+    if path.starts_with(r"\\?\") {
+        return Some(path.to_string());
+    }
+    if let Some(share) = path.strip_prefix(r"\\") {
+        return Some(format!(r"\\?\UNC\{}", share));
     }
+    let bytes = path.as_bytes();
+    let drive = bytes.len() >= 3
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && matches!(bytes[2], b'\\' | b'/');
+    drive.then(|| format!(r"\\?\{}", path.replace('/', r"\")))
+}
+
+/// Windows' ERROR_FILENAME_EXCED_RANGE, which the tar crate only keeps in the message
+fn is_path_too_long(e: &io::Error) -> bool {
+    e.raw_os_error() == Some(206) || e.to_string().contains("(os error 206)")
 }
 
-fn extract_tar_gz(archive_path: &PathBuf, dest: &PathBuf) -> Result<()> {
+fn path_too_long(dest: &Path, e: io::Error) -> CargoJamError {
+    CargoJamError::Git(format!(
+        "A path in the toolchain archive is too long for Windows under {} ({}). Enable long path support (set HKLM\\SYSTEM\\CurrentControlSet\\Control\\FileSystem\\LongPathsEnabled to 1) or install into a shorter directory with CARGO_JAM_HOME, e.g. C:\\jam",
+        dest.display(),
+        e
+    ))
+}
+
+fn extract_tar_gz(archive_path: &Path, dest: &Path) -> Result<()> {
     let file = File::open(archive_path)?;
     let decoder = GzDecoder::new(file);
     let mut archive = Archive::new(decoder);
@@ -251,8 +1063,11 @@ fn extract_zip(archive_path: &Path, dest: &Path) -> Result<()> {
             .by_index(i)
             .map_err(|e| CargoJamError::Git(format!("Failed to read zip entry: {}", e)))?;
 
+        // Joined component by component: a `\\?\` root doesn't accept `/` separators
         let outpath = match file.enclosed_name() {
-            Some(path) => dest.join(path),
+            Some(path) => path
+                .components()
+                .fold(dest.to_path_buf(), |out, c| out.join(c)),
             None => continue,
         };
 
@@ -280,3 +1095,455 @@ fn extract_zip(archive_path: &Path, dest: &Path) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+
+    /// A local HTTP server answering one connection per reply, in order, that reports
+    /// each request's headers (names lower-cased). With `hold` the connections stay open
+    /// after the reply, so a short or missing body stalls the client.
+    fn serve(
+        replies: Vec<&'static str>,
+        hold: bool,
+    ) -> (String, mpsc::Receiver<HashMap<String, String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let (sender, received) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut open = Vec::new();
+            for reply in replies {
+                let (mut stream, _) = listener.accept().unwrap();
+                // Replying before the request is read is an unsolicited response to hyper
+                let mut reader = BufReader::new(&stream);
+                let mut headers = HashMap::new();
+                let mut line = String::new();
+                while reader.read_line(&mut line).is_ok_and(|n| n > 2) {
+                    if let Some((name, value)) = line.split_once(':') {
+                        headers.insert(name.to_ascii_lowercase(), value.trim().to_string());
+                    }
+                    line.clear();
+                }
+                let _ = sender.send(headers);
+                let _ = stream.write_all(reply.as_bytes());
+                if hold {
+                    open.push(stream);
+                }
+            }
+            if hold {
+                // Keep the connections open for as long as the test process runs
+                for _ in listener.incoming() {}
+            }
+        });
+        (url, received)
+    }
+
+    fn download(url: &str, dest: &Path, progress: &ProgressBar) -> Result<String> {
+        download_file_with_progress(url, dest, progress, &ProgressStyle::default_spinner())
+    }
+
+    fn release(tag: &str, published_at: &str) -> GitHubRelease {
+        GitHubRelease {
+            tag_name: tag.to_string(),
+            name: None,
+            published_at: Some(published_at.to_string()),
+            assets: Vec::new(),
+        }
+    }
+
+    /// Write a .tar.gz holding `files` (path, contents), optionally cut off halfway
+    fn tar_gz(path: &Path, files: &[(&str, &str)], truncate: bool) {
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::default(),
+        ));
+        for (name, contents) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o755);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, name, contents.as_bytes())
+                .unwrap();
+        }
+        let mut bytes = builder.into_inner().unwrap().finish().unwrap();
+        if truncate {
+            bytes.truncate(bytes.len() / 2);
+        }
+        std::fs::write(path, bytes).unwrap();
+    }
+
+    #[test]
+    fn test_failed_unpack_keeps_previous_installation() {
+        let dir = tempfile::tempdir().unwrap();
+        let toolchain_dir = dir.path().join("toolchain");
+        let archive = dir.path().join("polkajam.tar.gz");
+        let platform = Platform::LinuxX86_64;
+
+        tar_gz(&archive, &[("polkajam-nightly-1/jamt", "v1")], false);
+        unpack_toolchain(&archive, &toolchain_dir, &platform, true).unwrap();
+        let jamt = toolchain_dir.join(INSTALL_DIR).join("jamt");
+        assert_eq!(std::fs::read_to_string(&jamt).unwrap(), "v1");
+
+        // A truncated archive fails midway through extraction
+        let files = [
+            ("polkajam-nightly-2/jamt", "v2"),
+            ("polkajam-nightly-2/polkajam", &"x".repeat(64 * 1024)),
+        ];
+        tar_gz(&archive, &files, true);
+        assert!(unpack_toolchain(&archive, &toolchain_dir, &platform, true).is_err());
+
+        // An archive without jamt is rejected
+        tar_gz(&archive, &[("polkajam-nightly-3/README.md", "v3")], false);
+        assert!(unpack_toolchain(&archive, &toolchain_dir, &platform, true).is_err());
+
+        assert_eq!(std::fs::read_to_string(&jamt).unwrap(), "v1");
+        let entries: Vec<_> = std::fs::read_dir(&toolchain_dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(entries.len(), 2, "leftover staging files: {:?}", entries);
+    }
+
+    #[test]
+    fn test_unpack_locates_binaries() {
+        let dir = tempfile::tempdir().unwrap();
+        let toolchain_dir = dir.path().join("toolchain");
+        let archive = dir.path().join("polkajam.tar.gz");
+        let platform = Platform::LinuxX86_64;
+        let install_dir = toolchain_dir.join(INSTALL_DIR);
+
+        let files = [
+            ("jam-2026.1/bin/jamt", "jamt"),
+            ("jam-2026.1/bin/tools/jamt", "nested"),
+            ("jam-2026.1/README.md", "readme"),
+        ];
+        tar_gz(&archive, &files, false);
+
+        // The single top-level directory is flattened, whatever its name
+        let binary_dir = unpack_toolchain(&archive, &toolchain_dir, &platform, true).unwrap();
+        assert_eq!(binary_dir, Path::new("bin"));
+        assert!(install_dir.join("bin").join("jamt").is_file());
+
+        // --no-normalize keeps the archive's layout
+        let binary_dir = unpack_toolchain(&archive, &toolchain_dir, &platform, false).unwrap();
+        assert_eq!(binary_dir, Path::new("jam-2026.1").join("bin"));
+        assert!(install_dir.join("jam-2026.1").join("README.md").is_file());
+
+        // Binaries at the top level of a flat archive
+        tar_gz(
+            &archive,
+            &[("jamt", "jamt"), ("polkajam", "polkajam")],
+            false,
+        );
+        let binary_dir = unpack_toolchain(&archive, &toolchain_dir, &platform, true).unwrap();
+        assert_eq!(binary_dir, Path::new(""));
+        assert!(install_dir.join("polkajam").is_file());
+    }
+
+    #[test]
+    fn test_stalled_requests_time_out() {
+        let client = ReleaseClient::from_secs(1, 1).build().unwrap();
+        let is_timeout = |e: CargoJamError| e.to_string().contains("request timed out");
+
+        // No response at all
+        let (url, _) = serve(vec![""], true);
+        let err = client.get(&url).send().unwrap_err();
+        assert!(is_timeout(request_error("Failed to download", &url, err)));
+
+        // Headers arrive, but the body stalls
+        let (url, _) = serve(
+            vec!["HTTP/1.1 200 OK\r\nContent-Length: 100\r\n\r\npartial"],
+            true,
+        );
+        let mut response = client.get(&url).send().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let mut file = File::create(dir.path().join("download")).unwrap();
+        assert!(is_timeout(
+            copy_body(&url, &mut response, &mut file, Sha256::new()).unwrap_err()
+        ));
+    }
+
+    #[test]
+    fn test_latest_nightly_before() {
+        let releases = || {
+            vec![
+                release("nightly-2025-12-29", "2025-12-29T03:00:00Z"),
+                release("v0.1.0", "2025-12-20T03:00:00Z"),
+                release("nightly-2025-12-15", "2025-12-15T03:00:00Z"),
+                release("nightly-2025-12-01", "2025-12-01T03:00:00Z"),
+            ]
+        };
+
+        let found = latest_before(releases(), DEFAULT_CHANNEL, "2025-12-20").unwrap();
+        assert_eq!(found.tag_name, "nightly-2025-12-15");
+
+        let found = latest_before(releases(), DEFAULT_CHANNEL, "2025-12-29").unwrap();
+        assert_eq!(found.tag_name, "nightly-2025-12-29");
+
+        assert!(latest_before(releases(), DEFAULT_CHANNEL, "2025-11-30").is_none());
+
+        // Other channels are matched by their tag prefix
+        let found = latest_before(releases(), "v", "2025-12-29").unwrap();
+        assert_eq!(found.tag_name, "v0.1.0");
+    }
+
+    #[test]
+    fn test_cached_release_archive() {
+        let dir = tempfile::tempdir().unwrap();
+        let platform = Platform::LinuxX86_64;
+        let mut release = release("nightly-2025-12-29", "2025-12-29T00:00:00Z");
+        release.assets.push(GitHubAsset {
+            name: "polkajam-linux-x86_64.tar.gz".to_string(),
+            browser_download_url: String::new(),
+            size: 4,
+            digest: None,
+        });
+        assert_eq!(
+            cached_release_archive(&release, &platform, dir.path()),
+            None
+        );
+
+        let release_dir = dir.path().join("nightly-2025-12-29");
+        std::fs::create_dir(&release_dir).unwrap();
+        let archive = release_dir.join("polkajam-linux-x86_64.tar.gz");
+        std::fs::write(&archive, "ab").unwrap();
+        // A truncated download isn't reused
+        assert_eq!(
+            cached_release_archive(&release, &platform, dir.path()),
+            None
+        );
+
+        std::fs::write(&archive, "abcd").unwrap();
+        assert_eq!(
+            cached_release_archive(&release, &platform, dir.path()),
+            Some(archive)
+        );
+        assert_eq!(
+            cached_release_archive(&release, &Platform::MacosAarch64, dir.path()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_releases_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(RELEASES_CACHE_FILE);
+        assert!(read_releases_cache(&path, 10, 1_000).is_none());
+
+        let cache = ReleasesCache {
+            fetched_at: 1_000,
+            limit: 10,
+            releases: vec![
+                release("nightly-2025-12-29", "2025-12-29T03:00:00Z"),
+                release("nightly-2025-12-15", "2025-12-15T03:00:00Z"),
+            ],
+        };
+        write_releases_cache(&path, &cache).unwrap();
+
+        let releases = read_releases_cache(&path, 10, 1_000 + RELEASES_CACHE_TTL_SECS - 1).unwrap();
+        assert_eq!(releases.len(), 2);
+        assert_eq!(read_releases_cache(&path, 1, 1_000).unwrap().len(), 1);
+
+        // Stale, too short for the search, or written in the future
+        assert!(read_releases_cache(&path, 10, 1_000 + RELEASES_CACHE_TTL_SECS).is_none());
+        assert!(read_releases_cache(&path, 100, 1_000).is_none());
+        assert!(read_releases_cache(&path, 10, 999).is_none());
+    }
+
+    #[test]
+    fn test_extended_length_path() {
+        assert_eq!(
+            extended_length_path(r"C:\Users\me\.cargo-polkajam").as_deref(),
+            Some(r"\\?\C:\Users\me\.cargo-polkajam")
+        );
+        assert_eq!(
+            extended_length_path("D:/jam/toolchain").as_deref(),
+            Some(r"\\?\D:\jam\toolchain")
+        );
+        assert_eq!(
+            extended_length_path(r"\\server\share\jam").as_deref(),
+            Some(r"\\?\UNC\server\share\jam")
+        );
+        assert_eq!(
+            extended_length_path(r"\\?\C:\jam").as_deref(),
+            Some(r"\\?\C:\jam")
+        );
+        assert_eq!(extended_length_path(r"jam\toolchain"), None);
+
+        let too_long = io::Error::other("failed to unpack `x` (os error 206)");
+        assert!(is_path_too_long(&too_long));
+        assert!(!is_path_too_long(&io::Error::other("disk full")));
+        let message = path_too_long(Path::new("C:/jam"), too_long).to_string();
+        assert!(message.contains("LongPathsEnabled"), "{}", message);
+    }
+
+    #[test]
+    fn test_download_progress() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("download");
+        let hello_sha256 = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+        let (url, _) = serve(
+            vec!["HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello"],
+            false,
+        );
+        let progress = ProgressBar::hidden();
+        let digest = download(&url, &dest, &progress).unwrap();
+        assert_eq!(digest, hello_sha256);
+        assert_eq!(progress.length(), Some(5));
+        assert_eq!(progress.position(), 5);
+
+        // Without a Content-Length only the bytes received are counted
+        let (url, _) = serve(
+            vec!["HTTP/1.1 200 OK\r\nConnection: close\r\n\r\nhello"],
+            false,
+        );
+        let progress = ProgressBar::new(100);
+        download(&url, &dest, &progress).unwrap();
+        assert_eq!(progress.length(), None);
+        assert_eq!(progress.position(), 5);
+        assert_eq!(std::fs::read(&dest).unwrap(), b"hello");
+    }
+
+    #[test]
+    fn test_resume_download() {
+        let server = |replies| serve(replies, false);
+        let range = |headers: HashMap<String, String>| {
+            (
+                headers.get("range").cloned(),
+                headers.get("if-range").cloned(),
+            )
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("archive.tar.gz.part");
+        let hello_sha256 = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+        let full = "HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello";
+
+        // An interrupted download keeps its bytes and the ETag it started from...
+        let (url, _headers) = server(vec![
+            "HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nContent-Length: 5\r\n\r\nhel",
+        ]);
+        assert!(download(&url, &dest, &ProgressBar::hidden()).is_err());
+        assert_eq!(std::fs::read(&dest).unwrap(), b"hel");
+
+        // ...and resumes conditionally on it, appending the missing bytes
+        let (url, headers) = server(vec![
+            "HTTP/1.1 206 Partial Content\r\nContent-Range: bytes 3-4/5\r\nContent-Length: 2\r\n\r\nlo",
+        ]);
+        let progress = ProgressBar::hidden();
+        let digest = download(&url, &dest, &progress).unwrap();
+        assert_eq!(
+            range(headers.recv().unwrap()),
+            (Some("bytes=3-".to_string()), Some("\"v1\"".to_string()))
+        );
+        assert_eq!(digest, hello_sha256);
+        assert_eq!(std::fs::read(&dest).unwrap(), b"hello");
+        assert_eq!(progress.position(), 5);
+        assert_eq!(progress.length(), Some(5));
+        assert!(!validator_path(&dest).exists());
+
+        // A partial file with no recorded validator is downloaded again
+        std::fs::write(&dest, "hel").unwrap();
+        let (url, headers) = server(vec![full]);
+        download(&url, &dest, &ProgressBar::hidden()).unwrap();
+        assert_eq!(range(headers.recv().unwrap()), (None, None));
+        assert_eq!(std::fs::read(&dest).unwrap(), b"hello");
+
+        // A server without range support, or whose file changed, sends everything again
+        std::fs::write(&dest, "xyz").unwrap();
+        std::fs::write(validator_path(&dest), "\"v1\"").unwrap();
+        let (url, _headers) = server(vec![full]);
+        let digest = download(&url, &dest, &ProgressBar::hidden()).unwrap();
+        assert_eq!(digest, hello_sha256);
+        assert_eq!(std::fs::read(&dest).unwrap(), b"hello");
+
+        // A partial file longer than the download is discarded
+        std::fs::write(&dest, "hello world").unwrap();
+        std::fs::write(validator_path(&dest), "\"v1\"").unwrap();
+        let (url, headers) = server(vec![
+            "HTTP/1.1 416 Range Not Satisfiable\r\nContent-Length: 0\r\n\r\n",
+            full,
+        ]);
+        download(&url, &dest, &ProgressBar::hidden()).unwrap();
+        assert_eq!(
+            range(headers.recv().unwrap()).0.as_deref(),
+            Some("bytes=11-")
+        );
+        assert_eq!(range(headers.recv().unwrap()).0, None);
+        assert_eq!(std::fs::read(&dest).unwrap(), b"hello");
+    }
+
+    #[test]
+    fn test_resume_validator() {
+        let headers = |pairs: &[(&'static str, &'static str)]| {
+            let mut map = HeaderMap::new();
+            for (name, value) in pairs {
+                map.insert(*name, value.parse().unwrap());
+            }
+            map
+        };
+        let date = "Wed, 21 Oct 2015 07:28:00 GMT";
+        assert_eq!(
+            resume_validator(&headers(&[("etag", "\"abc\""), ("last-modified", date)])).as_deref(),
+            Some("\"abc\"")
+        );
+        assert_eq!(
+            resume_validator(&headers(&[("etag", "W/\"abc\""), ("last-modified", date)]))
+                .as_deref(),
+            Some(date)
+        );
+        assert_eq!(resume_validator(&headers(&[])), None);
+    }
+
+    #[test]
+    fn test_download_checksums() {
+        let asset = GitHubAsset {
+            name: "polkajam-linux-x86_64.tar.gz".to_string(),
+            browser_download_url: "http://127.0.0.1:1/archive".to_string(),
+            size: 0,
+            digest: Some("sha256:ABC123".to_string()),
+        };
+        let release = release("nightly-2025-12-29", "2025-12-29T00:00:00Z");
+        assert_eq!(
+            expected_sha256(&release, &asset).unwrap().as_deref(),
+            Some("abc123")
+        );
+
+        let hex = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+        assert_eq!(
+            parse_checksum(&format!("{}  polkajam.tar.gz\n", hex.to_uppercase())).as_deref(),
+            Some(hex)
+        );
+        assert_eq!(parse_checksum("not a digest"), None);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("archive.tar.gz");
+        std::fs::write(&path, "truncated").unwrap();
+        check_download(&path, None, "anything").unwrap();
+        check_download(&path, Some(hex), hex).unwrap();
+        let err = check_download(&path, Some(hex), "0000").unwrap_err();
+        assert!(err.to_string().contains("Checksum mismatch"), "{}", err);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_transient_status() {
+        assert!(is_transient_status(StatusCode::BAD_GATEWAY));
+        assert!(is_transient_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!is_transient_status(StatusCode::NOT_FOUND));
+        assert!(!is_transient_status(StatusCode::FORBIDDEN));
+    }
+
+    #[test]
+    fn test_validate_release_date() {
+        assert!(validate_release_date("2025-12-29").is_ok());
+        for date in ["2025-13-01", "2025-1-01", "29-12-2025", "yesterday"] {
+            assert!(validate_release_date(date).is_err(), "{}", date);
+        }
+    }
+}
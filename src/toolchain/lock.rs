@@ -0,0 +1,106 @@
+use crate::cli::output::Glyph;
+use crate::error::Result;
+use crate::toolchain::config::ToolchainConfig;
+use console::style;
+use std::fs::{File, TryLockError};
+use std::path::Path;
+use tracing::debug;
+
+/// Lock file in the cargo-polkajam home, held while installing a toolchain or updating the config
+const LOCK_FILE: &str = "toolchain.lock";
+
+/// Exclusive lock serializing toolchain installs and config updates across processes.
+///
+/// The lock is released when the value is dropped, including when the process dies.
+pub struct ToolchainLock {
+    _file: File,
+}
+
+impl ToolchainLock {
+    /// Take the lock in the cargo-polkajam home, waiting for other processes to release it
+    pub fn acquire() -> Result<Self> {
+        Self::acquire_at(&ToolchainConfig::home_dir()?.join(LOCK_FILE))
+    }
+
+    pub(crate) fn acquire_at(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = File::options()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path)?;
+
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                eprintln!(
+                    "{} Waiting for another cargo-polkajam process to finish updating the toolchain...",
+                    style(Glyph::Arrow).cyan()
+                );
+                file.lock()?;
+            }
+            Err(TryLockError::Error(e)) => return Err(e.into()),
+        }
+        debug!(path = %path.display(), "acquired toolchain lock");
+        Ok(Self { _file: file })
+    }
+
+    /// Re-read the config, apply `change` and write it back while holding this lock
+    pub fn update_config<T>(&self, change: impl FnOnce(&mut ToolchainConfig) -> T) -> Result<T> {
+        let path = ToolchainConfig::config_path()?;
+        update_config_at(&path, change)
+    }
+}
+
+/// Read-modify-write of a config file; the caller holds the lock
+pub(crate) fn update_config_at<T>(
+    path: &Path,
+    change: impl FnOnce(&mut ToolchainConfig) -> T,
+) -> Result<T> {
+    let mut config = ToolchainConfig::load_from(path)?;
+    let result = change(&mut config);
+    config.save_to(path)?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_interleaved_config_updates() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        let lock_path = dir.path().join(LOCK_FILE);
+
+        // Without the lock every writer would save the config it read before the
+        // others wrote theirs, keeping only the last key
+        let writers: Vec<_> = (0..8)
+            .map(|i| {
+                let config_path = config_path.clone();
+                let lock_path = lock_path.clone();
+                std::thread::spawn(move || {
+                    let _lock = ToolchainLock::acquire_at(&lock_path).unwrap();
+                    update_config_at(&config_path, |config| {
+                        std::thread::sleep(Duration::from_millis(20));
+                        config.trusted_keys.push(format!("key-{}", i));
+                    })
+                    .unwrap();
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let mut keys = ToolchainConfig::load_from(&config_path)
+            .unwrap()
+            .trusted_keys;
+        keys.sort();
+        let expected: Vec<_> = (0..8).map(|i| format!("key-{}", i)).collect();
+        assert_eq!(keys, expected);
+    }
+}
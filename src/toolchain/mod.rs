@@ -1,3 +1,10 @@
+pub mod accounts;
+pub mod checksums;
+pub mod compat;
 pub mod config;
+pub mod deployments;
 pub mod download;
+pub mod lock;
 pub mod platform;
+pub mod prune;
+pub mod testnet;
@@ -1,7 +1,9 @@
 use crate::error::{CargoJamError, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::str::FromStr;
 
 /// Supported platform targets for the polkajam toolchain
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Platform {
     MacosAarch64,
     MacosX86_64,
@@ -11,6 +13,15 @@ pub enum Platform {
 }
 
 impl Platform {
+    /// Every platform the toolchain is published for
+    pub const ALL: [Platform; 5] = [
+        Platform::MacosAarch64,
+        Platform::MacosX86_64,
+        Platform::LinuxX86_64,
+        Platform::LinuxAarch64,
+        Platform::WindowsX86_64,
+    ];
+
     /// Detect the current platform
     pub fn detect() -> Result<Self> {
         let os = std::env::consts::OS;
@@ -25,8 +36,10 @@ impl Platform {
             _ => Err(CargoJamError::ToolchainMissing {
                 tool: "polkajam".to_string(),
                 install_hint: format!(
-                    "Unsupported platform: {}-{}. Supported: macos-aarch64, macos-x86_64, linux-x86_64, linux-aarch64, windows-x86_64",
-                    os, arch
+                    "Unsupported platform: {}-{}. Supported: {}",
+                    os,
+                    arch,
+                    supported_platforms()
                 ),
             }),
         }
@@ -67,6 +80,47 @@ impl std::fmt::Display for Platform {
     }
 }
 
+/// Parses the asset suffix `Display` prints, e.g. `linux-x86_64`
+impl FromStr for Platform {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        Platform::ALL
+            .into_iter()
+            .find(|platform| platform.asset_suffix() == value)
+            .ok_or_else(|| {
+                format!(
+                    "unknown platform '{}' (expected one of: {})",
+                    value,
+                    supported_platforms()
+                )
+            })
+    }
+}
+
+impl Serialize for Platform {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.asset_suffix())
+    }
+}
+
+impl<'de> Deserialize<'de> for Platform {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// The asset suffixes of every platform, comma-separated
+fn supported_platforms() -> String {
+    Platform::ALL
+        .iter()
+        .map(Platform::asset_suffix)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -83,4 +137,16 @@ mod tests {
         assert_eq!(Platform::MacosAarch64.asset_suffix(), "macos-aarch64");
         assert_eq!(Platform::LinuxX86_64.asset_suffix(), "linux-x86_64");
     }
+
+    #[test]
+    fn test_parse_platform() {
+        for platform in Platform::ALL {
+            assert_eq!(Platform::from_str(&platform.to_string()), Ok(platform));
+        }
+
+        let err = Platform::from_str("linux-amd64").unwrap_err();
+        assert!(err.contains("'linux-amd64'"), "{}", err);
+        assert!(err.contains("linux-x86_64, linux-aarch64"), "{}", err);
+        assert!(Platform::from_str("").is_err());
+    }
 }
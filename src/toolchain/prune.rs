@@ -0,0 +1,143 @@
+use crate::error::Result;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use walkdir::WalkDir;
+
+/// A toolchain version kept in the download cache (`cache/<version>/`)
+#[derive(Debug)]
+pub struct CachedVersion {
+    pub version: String,
+    pub path: PathBuf,
+    /// Total size of the version's files in bytes
+    pub size: u64,
+    /// When the version was last downloaded
+    pub modified: SystemTime,
+}
+
+/// List the cached toolchain versions, most recent first
+pub fn cached_versions(cache_dir: &Path) -> Result<Vec<CachedVersion>> {
+    if !cache_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut versions = Vec::new();
+    for entry in std::fs::read_dir(cache_dir)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let path = entry.path();
+        versions.push(CachedVersion {
+            version: entry.file_name().to_string_lossy().to_string(),
+            size: dir_size(&path),
+            modified: last_modified(&path),
+            path,
+        });
+    }
+
+    // Nightly tags sort by date, which breaks ties between equal timestamps
+    versions.sort_by(|a, b| {
+        b.modified
+            .cmp(&a.modified)
+            .then_with(|| b.version.cmp(&a.version))
+    });
+    Ok(versions)
+}
+
+/// The versions to remove to keep only the `keep` most recent, never including `active`
+pub fn prunable<'a>(
+    versions: &'a [CachedVersion],
+    keep: usize,
+    active: Option<&str>,
+) -> Vec<&'a CachedVersion> {
+    versions
+        .iter()
+        .skip(keep)
+        .filter(|v| Some(v.version.as_str()) != active)
+        .collect()
+}
+
+/// Format a byte count for display, e.g. "118.4 MiB"
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// Total size of the files in a directory
+pub fn dir_size(dir: &Path) -> u64 {
+    WalkDir::new(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.metadata().ok())
+        .filter(|m| m.is_file())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// Newest modification time of the files in a directory, or of the directory if it's empty
+fn last_modified(dir: &Path) -> SystemTime {
+    WalkDir::new(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.metadata().ok())
+        .filter(|m| m.is_file())
+        .filter_map(|m| m.modified().ok())
+        .max()
+        .or_else(|| std::fs::metadata(dir).ok()?.modified().ok())
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_prunable_keeps_recent_and_active() {
+        let version = |name: &str, age: u64| CachedVersion {
+            version: name.to_string(),
+            path: PathBuf::from(name),
+            size: 0,
+            modified: SystemTime::UNIX_EPOCH + Duration::from_secs(1_000 - age),
+        };
+        let versions = vec![
+            version("nightly-2025-12-29", 0),
+            version("nightly-2025-12-15", 10),
+            version("nightly-2025-12-01", 20),
+            version("nightly-2025-11-20", 30),
+        ];
+
+        let names = |pruned: Vec<&CachedVersion>| -> Vec<String> {
+            pruned.iter().map(|v| v.version.clone()).collect()
+        };
+        assert_eq!(
+            names(prunable(&versions, 2, Some("nightly-2025-12-01"))),
+            ["nightly-2025-11-20"]
+        );
+        assert_eq!(
+            names(prunable(&versions, 1, None)),
+            [
+                "nightly-2025-12-15",
+                "nightly-2025-12-01",
+                "nightly-2025-11-20"
+            ]
+        );
+        assert!(prunable(&versions, 4, None).is_empty());
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(118 * 1024 * 1024), "118.0 MiB");
+    }
+}
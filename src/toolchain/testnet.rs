@@ -0,0 +1,185 @@
+use crate::error::{CargoJamError, Result};
+use std::path::Path;
+
+/// PID of the running testnet (or its supervisor), in the cargo-polkajam home
+pub const PID_FILE: &str = "testnet.pid";
+
+/// Marker left by `down` so a supervisor (`up --supervise`) doesn't restart the testnet it stopped
+pub const STOP_FILE: &str = "testnet.stop";
+
+/// The PID recorded in `home_dir`, if the PID file exists and holds one
+pub fn recorded_pid(home_dir: &Path) -> Option<i32> {
+    std::fs::read_to_string(home_dir.join(PID_FILE))
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+#[cfg(unix)]
+pub fn is_process_running(pid: i32) -> bool {
+    std::process::Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+#[cfg(windows)]
+pub fn is_process_running(pid: i32) -> bool {
+    std::process::Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {}", pid)])
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).contains(&pid.to_string()))
+        .unwrap_or(false)
+}
+
+/// Testnet settings from `up --testnet-config`, as `polkajam-testnet` arguments
+#[derive(Debug, Default, PartialEq)]
+pub struct TestnetConfig {
+    /// Arguments passed to `polkajam-testnet`
+    pub args: Vec<String>,
+    /// Keys that were not recognized and are ignored
+    pub unknown_keys: Vec<String>,
+}
+
+/// Keys a testnet config may set
+pub const KNOWN_KEYS: &[&str] = &["validators", "block_time", "cores", "genesis", "args"];
+
+/// Integer settings and the `polkajam-testnet` flag each becomes
+const INTEGER_KEYS: &[(&str, &str)] = &[
+    ("validators", "--validators"),
+    ("block_time", "--block-time"),
+    ("cores", "--cores"),
+];
+
+impl TestnetConfig {
+    /// Read and validate a testnet config file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| invalid(&format!("cannot read {}: {}", path.display(), e)))?;
+        let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+        Self::parse(&content, base_dir)
+    }
+
+    /// Parse a testnet config, resolving relative paths against `base_dir`
+    pub fn parse(content: &str, base_dir: &Path) -> Result<Self> {
+        let table: toml::Table = toml::from_str(content)?;
+        let mut config = Self::default();
+
+        // Arguments follow a fixed order, whatever the order of the file
+        for (key, flag) in INTEGER_KEYS {
+            if let Some(value) = table.get(*key) {
+                let number = value
+                    .as_integer()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| invalid(&format!("'{}' must be a positive integer", key)))?;
+                config.args.push(flag.to_string());
+                config.args.push(number.to_string());
+            }
+        }
+
+        if let Some(value) = table.get("genesis") {
+            let file = value
+                .as_str()
+                .ok_or_else(|| invalid("'genesis' must be a file path"))?;
+            let file = base_dir.join(file);
+            if !file.is_file() {
+                return Err(invalid(&format!(
+                    "genesis file {} does not exist",
+                    file.display()
+                )));
+            }
+            config.args.push("--genesis".to_string());
+            config.args.push(file.to_string_lossy().to_string());
+        }
+
+        if let Some(value) = table.get("args") {
+            let extra = value
+                .as_array()
+                .and_then(|items| {
+                    items
+                        .iter()
+                        .map(|item| item.as_str().map(String::from))
+                        .collect::<Option<Vec<_>>>()
+                })
+                .ok_or_else(|| invalid("'args' must be an array of strings"))?;
+            config.args.extend(extra);
+        }
+
+        config.unknown_keys = table
+            .keys()
+            .filter(|key| !KNOWN_KEYS.contains(&key.as_str()))
+            .cloned()
+            .collect();
+
+        Ok(config)
+    }
+}
+
+fn invalid(reason: &str) -> CargoJamError {
+    CargoJamError::InvalidArgument {
+        arg: "--testnet-config".to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recorded_pid() {
+        let home = tempfile::tempdir().unwrap();
+        assert_eq!(recorded_pid(home.path()), None);
+
+        std::fs::write(home.path().join(PID_FILE), "4242\n").unwrap();
+        assert_eq!(recorded_pid(home.path()), Some(4242));
+
+        std::fs::write(home.path().join(PID_FILE), "garbage").unwrap();
+        assert_eq!(recorded_pid(home.path()), None);
+
+        // This test process is certainly running
+        assert!(is_process_running(std::process::id() as i32));
+    }
+
+    #[test]
+    fn test_parse_testnet_config() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("genesis.json"), "{}").unwrap();
+
+        let config = TestnetConfig::parse(
+            "validators = 6\nblock_time = 2\ngenesis = \"genesis.json\"\nargs = [\"--log\", \"debug\"]\nvalidator = 3\n",
+            dir.path(),
+        )
+        .unwrap();
+        let genesis = dir
+            .path()
+            .join("genesis.json")
+            .to_string_lossy()
+            .to_string();
+        assert_eq!(
+            config.args,
+            [
+                "--validators",
+                "6",
+                "--block-time",
+                "2",
+                "--genesis",
+                &genesis,
+                "--log",
+                "debug"
+            ]
+        );
+        assert_eq!(config.unknown_keys, ["validator"]);
+
+        for bad in [
+            "validators = 0",
+            "block_time = \"fast\"",
+            "genesis = \"missing.json\"",
+            "args = [1]",
+        ] {
+            assert!(TestnetConfig::parse(bad, dir.path()).is_err(), "{}", bad);
+        }
+    }
+}
@@ -7,7 +7,7 @@
 //! - Internet connection (for `cargo jam setup`)
 
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 /// Get the path to the cargo-polkajam binary
@@ -62,6 +62,27 @@ fn test_help() {
     assert!(stdout.contains("JAM service") || stdout.contains("Polkadot"));
 }
 
+#[test]
+fn test_direct_and_legacy_invocation() {
+    for args in [&["--help"][..], &["jam", "--help"]] {
+        let output = Command::new(cargo_jam_bin())
+            .args(args)
+            .output()
+            .expect("Failed to run cargo-polkajam");
+
+        assert!(output.status.success(), "{:?} failed", args);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("JAM service") || stdout.contains("Polkadot"));
+    }
+
+    let output = Command::new(cargo_jam_bin())
+        .args(["polkajam"])
+        .output()
+        .expect("Failed to run cargo-polkajam");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cargo polkajam <command>"));
+}
+
 #[test]
 fn test_setup_info_no_toolchain() {
     // This test checks --info when no toolchain might be installed
@@ -79,14 +100,21 @@ fn test_setup_info_no_toolchain() {
 #[test]
 fn test_setup_list() {
     let output = Command::new(cargo_jam_bin())
-        .args(["polkajam", "setup", "--list"])
+        .args(["polkajam", "setup", "--list", "--json"])
         .output()
-        .expect("Failed to run cargo-polkajam jam setup --list");
+        .expect("Failed to run cargo-polkajam jam setup --list --json");
 
     assert!(output.status.success());
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    assert!(stdout.contains("Available releases"));
-    assert!(stdout.contains("nightly"));
+    let releases: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let releases = releases.as_array().unwrap();
+    assert!(!releases.is_empty());
+    assert!(releases
+        .iter()
+        .any(|r| r["tag_name"].as_str().unwrap().starts_with("nightly")));
+    for release in releases {
+        assert!(release["installed"].is_boolean());
+        assert!(release["platforms"]["linux-x86_64"].is_boolean());
+    }
 }
 
 #[test]
@@ -270,3 +298,1599 @@ fn test_build_fails_without_jam_project() {
 
     cleanup(&temp);
 }
+
+#[test]
+fn test_check_only_does_not_create_project() {
+    let temp = temp_dir();
+    let project_name = "checked-service";
+
+    let output = Command::new(cargo_jam_bin())
+        .args([
+            "polkajam",
+            "--check-only",
+            "new",
+            project_name,
+            "--defaults",
+        ])
+        .current_dir(&temp)
+        .output()
+        .expect("Failed to run cargo-polkajam new --check-only");
+
+    assert!(
+        output.status.success(),
+        "check-only failed: {:?}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        !temp.join(project_name).exists(),
+        "--check-only should not generate a project"
+    );
+
+    let invalid = Command::new(cargo_jam_bin())
+        .args(["polkajam", "new", "Bad-Name", "--check-only"])
+        .current_dir(&temp)
+        .output()
+        .expect("Failed to run cargo-polkajam new --check-only");
+
+    assert!(!invalid.status.success());
+    let stderr = String::from_utf8_lossy(&invalid.stderr);
+    assert!(stderr.contains("Invalid project name"));
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_new_minimal_strips_optional_files() {
+    let temp = temp_dir();
+    let project_name = "minimal-service";
+    let project_path = temp.join(project_name);
+
+    let output = Command::new(cargo_jam_bin())
+        .args(["polkajam", "new", project_name, "--defaults", "--minimal"])
+        .current_dir(&temp)
+        .output()
+        .expect("Failed to run cargo-polkajam new --minimal");
+
+    assert!(
+        output.status.success(),
+        "cargo-polkajam new --minimal failed: {:?}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    assert!(project_path.join("Cargo.toml").exists());
+    assert!(project_path.join("src").join("lib.rs").exists());
+    assert!(!project_path.join("README.md").exists());
+    assert!(!project_path.join(".cargo").exists());
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_new_no_prompt_defaults_requires_explicit_values() {
+    let temp = temp_dir();
+    let project_name = "explicit-service";
+
+    let output = Command::new(cargo_jam_bin())
+        .args([
+            "polkajam",
+            "new",
+            project_name,
+            "--defaults",
+            "--no-prompt-defaults",
+            "-d",
+            "license=MIT",
+        ])
+        .current_dir(&temp)
+        .output()
+        .expect("Failed to run cargo-polkajam new --no-prompt-defaults");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("description"), "stderr: {}", stderr);
+    assert!(!stderr.contains("license"), "stderr: {}", stderr);
+    assert!(!temp.join(project_name).exists());
+
+    let output = Command::new(cargo_jam_bin())
+        .args([
+            "polkajam",
+            "new",
+            project_name,
+            "--defaults",
+            "--no-prompt-defaults",
+            "-d",
+            "license=MIT",
+            "-d",
+            "description=Explicit",
+            "-d",
+            "author_name=Jam Dev",
+            "-d",
+            "author_email=dev@example.com",
+        ])
+        .current_dir(&temp)
+        .output()
+        .expect("Failed to run cargo-polkajam new --no-prompt-defaults");
+
+    assert!(
+        output.status.success(),
+        "cargo-polkajam new --no-prompt-defaults failed: {:?}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_new_no_interactive_requires_values() {
+    let temp = temp_dir();
+    let new = |args: &[&str]| {
+        Command::new(cargo_jam_bin())
+            .args(["polkajam", "new"])
+            .args(args)
+            .arg("--no-git")
+            .current_dir(&temp)
+            .output()
+            .expect("Failed to run cargo-polkajam new --no-interactive")
+    };
+
+    let output = new(&["strict-service", "--no-interactive"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("no value given for: license, description"),
+        "stderr: {}",
+        stderr
+    );
+    // Optional placeholders don't need a value
+    assert!(!stderr.contains("homepage"), "stderr: {}", stderr);
+    assert!(!temp.join("strict-service").exists());
+
+    let output = new(&[
+        "strict-service",
+        "--no-interactive",
+        "-d",
+        "license=MIT",
+        "-d",
+        "description=Strict",
+    ]);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // --defaults fills the gaps again
+    let output = new(&["lenient-service", "--no-interactive", "--defaults"]);
+    assert!(output.status.success());
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_template_test_generates_cases() {
+    let template = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("templates")
+        .join("basic-service");
+
+    let output = Command::new(cargo_jam_bin())
+        .args(["polkajam", "template", "test", "--no-check"])
+        .arg(&template)
+        .output()
+        .expect("Failed to run cargo-polkajam template test");
+
+    assert!(
+        output.status.success(),
+        "cargo-polkajam template test failed: {:?}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("defaults"));
+    assert!(stdout.contains("minimal"));
+}
+
+#[test]
+fn test_no_progress_status_lines() {
+    let temp = temp_dir();
+
+    let output = Command::new(cargo_jam_bin())
+        .args([
+            "polkajam",
+            "--no-progress",
+            "new",
+            "status-service",
+            "--defaults",
+            "--no-git",
+        ])
+        .current_dir(&temp)
+        .output()
+        .expect("Failed to run cargo-polkajam new");
+    assert!(output.status.success());
+
+    // Each spinner message becomes its own line instead of being lost
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Preparing template..."), "{}", stderr);
+    assert!(stderr.contains("Loading bundled template..."), "{}", stderr);
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_new_author_alias() {
+    let temp = temp_dir();
+
+    let output = Command::new(cargo_jam_bin())
+        .args([
+            "polkajam",
+            "new",
+            "author-service",
+            "--defaults",
+            "--no-git",
+            "-d",
+            "author=Alice Example",
+        ])
+        .current_dir(&temp)
+        .output()
+        .expect("Failed to run cargo-polkajam new");
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("renamed to 'author_name'"));
+
+    let cargo_toml = fs::read_to_string(temp.join("author-service").join("Cargo.toml")).unwrap();
+    assert!(cargo_toml.contains("Alice Example"), "{}", cargo_toml);
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_template_validate() {
+    let validate = |template: &Path| {
+        Command::new(cargo_jam_bin())
+            .args(["polkajam", "template", "validate"])
+            .arg(template)
+            .output()
+            .expect("Failed to run cargo-polkajam template validate")
+    };
+
+    let bundled = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("templates")
+        .join("basic-service");
+    let output = validate(&bundled);
+    assert!(
+        output.status.success(),
+        "stdout: {}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+
+    // Lenient for generation by default, but validate still catches the typo
+    let temp = temp_dir();
+    let template = temp.join("typo-template");
+    fs::create_dir_all(&template).unwrap();
+    fs::write(
+        template.join("cargo-polkajam.toml"),
+        "[template]\nname = \"typo\"\n",
+    )
+    .unwrap();
+    fs::write(template.join("README.md"), "# {{ crate_naem }}\n").unwrap();
+
+    let output = validate(&template);
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("crate_naem"), "stdout: {}", stdout);
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_diff_reports_modified_files() {
+    let temp = temp_dir();
+    let project_name = "diff-service";
+    let project_path = temp.join(project_name);
+
+    let output = Command::new(cargo_jam_bin())
+        .args(["polkajam", "new", project_name, "--defaults"])
+        .current_dir(&temp)
+        .output()
+        .expect("Failed to run cargo-polkajam new");
+    assert!(output.status.success());
+    assert!(project_path.join(".cargo-jam-manifest.toml").exists());
+
+    let clean = Command::new(cargo_jam_bin())
+        .args(["polkajam", "diff"])
+        .current_dir(&project_path)
+        .output()
+        .expect("Failed to run cargo-polkajam diff");
+    assert!(clean.status.success());
+    assert!(String::from_utf8_lossy(&clean.stdout).contains("match the template output"));
+
+    fs::write(project_path.join("src").join("lib.rs"), "// edited").unwrap();
+
+    let dirty = Command::new(cargo_jam_bin())
+        .args(["polkajam", "diff"])
+        .current_dir(&project_path)
+        .output()
+        .expect("Failed to run cargo-polkajam diff");
+    assert!(dirty.status.success());
+    let stdout = String::from_utf8_lossy(&dirty.stdout);
+    assert!(stdout.contains("src/lib.rs"));
+    assert!(stdout.contains("1 modified"));
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_new_with_license() {
+    use cargo_polkajam::project::manifest::GenerationManifest;
+
+    let temp = temp_dir();
+    let project_name = "licensed-service";
+    let project_path = temp.join(project_name);
+
+    let output = Command::new(cargo_jam_bin())
+        .args([
+            "polkajam",
+            "new",
+            project_name,
+            "--defaults",
+            "--no-git",
+            "--license",
+            "MIT OR Apache-2.0",
+            "-d",
+            "author_name=Jane Doe",
+        ])
+        .current_dir(&temp)
+        .output()
+        .expect("Failed to run cargo-polkajam new");
+    assert!(output.status.success());
+
+    let cargo_toml = fs::read_to_string(project_path.join("Cargo.toml")).unwrap();
+    assert!(cargo_toml.contains(r#"license = "MIT OR Apache-2.0""#));
+    let mit = fs::read_to_string(project_path.join("LICENSE-MIT")).unwrap();
+    assert!(mit.contains("Jane Doe"));
+    assert!(project_path.join("LICENSE-APACHE").exists());
+    assert!(!project_path.join("LICENSE").exists());
+
+    // The license files are recorded, so diff checks them and update keeps them
+    let manifest = GenerationManifest::load(&project_path).unwrap();
+    assert!(manifest.files.contains_key("LICENSE-MIT"));
+    fs::write(project_path.join("LICENSE-MIT"), "edited").unwrap();
+    let output = Command::new(cargo_jam_bin())
+        .args(["polkajam", "diff"])
+        .current_dir(&project_path)
+        .output()
+        .expect("Failed to run cargo-polkajam diff");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("LICENSE-MIT"));
+
+    let output = Command::new(cargo_jam_bin())
+        .args(["polkajam", "update"])
+        .current_dir(&project_path)
+        .output()
+        .expect("Failed to run cargo-polkajam update");
+    assert!(output.status.success());
+    assert!(project_path.join("LICENSE-APACHE").exists());
+    assert_eq!(
+        fs::read_to_string(project_path.join("LICENSE-MIT")).unwrap(),
+        "edited"
+    );
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_new_rename() {
+    use cargo_polkajam::project::manifest::GenerationManifest;
+
+    let temp = temp_dir();
+    let project_path = temp.join("renamed-service");
+
+    let output = Command::new(cargo_jam_bin())
+        .args([
+            "polkajam",
+            "new",
+            "renamed-service",
+            "--defaults",
+            "--no-git",
+            "--rename",
+            "README.md=docs/README.md",
+        ])
+        .current_dir(&temp)
+        .output()
+        .expect("Failed to run cargo-polkajam new");
+    assert!(output.status.success());
+    assert!(project_path.join("docs/README.md").is_file());
+    assert!(!project_path.join("README.md").exists());
+
+    // The rename is recorded, so the project diffs clean and update keeps the new path
+    let manifest = GenerationManifest::load(&project_path).unwrap();
+    assert_eq!(
+        manifest.renames.get("README.md").map(String::as_str),
+        Some("docs/README.md")
+    );
+    for command in ["diff", "update"] {
+        let output = Command::new(cargo_jam_bin())
+            .args(["polkajam", command])
+            .current_dir(&project_path)
+            .output()
+            .expect("Failed to run cargo-polkajam");
+        assert!(output.status.success(), "{}", command);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(!stdout.contains("README.md"), "{}: {}", command, stdout);
+    }
+    assert!(!project_path.join("README.md").exists());
+
+    // Sources must exist and destinations must not clash
+    for rename in ["src/main.rs=src/bin.rs", "README.md=Cargo.toml"] {
+        let output = Command::new(cargo_jam_bin())
+            .args([
+                "polkajam",
+                "new",
+                "clashing-service",
+                "--defaults",
+                "--no-git",
+                "--rename",
+                rename,
+            ])
+            .current_dir(&temp)
+            .output()
+            .expect("Failed to run cargo-polkajam new");
+        assert!(!output.status.success(), "{}", rename);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("--rename"), "{}", stderr);
+    }
+    assert!(!temp.join("clashing-service").exists());
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_new_template_lock() {
+    let temp = temp_dir();
+    let lock = temp.join("template.lock");
+    let new = |name: &str, extra: &[&str]| {
+        Command::new(cargo_jam_bin())
+            .args(["polkajam", "new", name, "--defaults", "--no-git"])
+            .arg("--template-lock")
+            .arg(&lock)
+            .args(extra)
+            .current_dir(&temp)
+            .output()
+            .expect("Failed to run cargo-polkajam new")
+    };
+
+    let output = new("first-service", &[]);
+    assert!(output.status.success());
+    let recorded = fs::read_to_string(&lock).unwrap();
+    assert!(recorded.contains(r#"bundled = "basic-service""#));
+    assert!(recorded.contains(r#"hash = "sha256:"#));
+
+    let output = new("second-service", &[]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Template matches"));
+
+    // A lock recorded for different content refuses the template
+    let tampered = recorded
+        .lines()
+        .map(|line| match line.starts_with("hash = ") {
+            true => r#"hash = "sha256:0000""#,
+            false => line,
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    fs::write(&lock, tampered).unwrap();
+    let output = new("third-service", &[]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--update-lock"));
+    assert!(!temp.join("third-service").exists());
+
+    let output = new("third-service", &["--update-lock"]);
+    assert!(output.status.success());
+    assert_eq!(fs::read_to_string(&lock).unwrap(), recorded);
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_plain_output() {
+    let temp = temp_dir();
+
+    let output = Command::new(cargo_jam_bin())
+        .args([
+            "polkajam",
+            "--plain",
+            "new",
+            "plain-service",
+            "--defaults",
+            "--no-git",
+            "--print-tree",
+        ])
+        .current_dir(&temp)
+        .output()
+        .expect("Failed to run cargo-polkajam new");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("[ok] Created JAM service"), "{}", stdout);
+    assert!(stdout.contains("`-- src/"), "{}", stdout);
+    assert!(stdout.is_ascii(), "{}", stdout);
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_new_dry_run_json() {
+    let temp = temp_dir();
+
+    let output = Command::new(cargo_jam_bin())
+        .args([
+            "polkajam",
+            "new",
+            "dry-service",
+            "--defaults",
+            "--dry-run",
+            "--json",
+            "--license",
+            "MIT",
+        ])
+        .current_dir(&temp)
+        .output()
+        .expect("Failed to run cargo-polkajam new");
+    assert!(output.status.success());
+    assert!(!temp.join("dry-service").exists());
+
+    let plan: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(plan["target_dir"], "dry-service");
+    assert_eq!(plan["variables"]["crate_name"], "dry_service");
+    let files = plan["files"].as_array().unwrap();
+    let action = |path: &str| {
+        files
+            .iter()
+            .find(|file| file["path"] == path)
+            .map(|file| file["action"].as_str().unwrap().to_string())
+    };
+    assert_eq!(action("Cargo.toml").as_deref(), Some("render"));
+    assert_eq!(action("LICENSE").as_deref(), Some("render"));
+    assert_eq!(action("cargo-polkajam.toml").as_deref(), Some("skip"));
+    let lib = files
+        .iter()
+        .find(|file| file["path"] == "src/lib.rs")
+        .unwrap();
+    assert_eq!(lib["source"], "src/lib.rs.liquid");
+    assert_eq!(
+        action(".cargo-jam-manifest.toml").as_deref(),
+        Some("render")
+    );
+    assert_eq!(plan["edits"].as_array().unwrap().len(), 0);
+
+    // The preview lists exactly the files a real run leaves behind
+    let output = Command::new(cargo_jam_bin())
+        .args([
+            "polkajam",
+            "new",
+            "dry-service",
+            "--defaults",
+            "--no-git",
+            "--license",
+            "MIT",
+        ])
+        .current_dir(&temp)
+        .output()
+        .expect("Failed to run cargo-polkajam new");
+    assert!(output.status.success());
+    let mut planned: Vec<&str> = files
+        .iter()
+        .filter(|file| file["action"] != "skip")
+        .map(|file| file["path"].as_str().unwrap())
+        .collect();
+    planned.sort();
+    let project = temp.join("dry-service");
+    let mut written: Vec<String> = walkdir::WalkDir::new(&project)
+        .into_iter()
+        .map(|entry| entry.unwrap())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| {
+            entry
+                .path()
+                .strip_prefix(&project)
+                .unwrap()
+                .to_string_lossy()
+                .replace('\\', "/")
+        })
+        .collect();
+    written.sort();
+    assert_eq!(planned, written);
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_new_package_metadata() {
+    let temp = temp_dir();
+
+    let output = Command::new(cargo_jam_bin())
+        .args([
+            "polkajam",
+            "new",
+            "meta-service",
+            "--defaults",
+            "--no-git",
+            "--description",
+            "Counts things",
+            "--homepage",
+            "https://example.com",
+            "--repository",
+            "https://github.com/example/meta-service",
+        ])
+        .current_dir(&temp)
+        .output()
+        .expect("Failed to run cargo-polkajam new");
+    assert!(output.status.success());
+
+    let cargo_toml = fs::read_to_string(temp.join("meta-service/Cargo.toml")).unwrap();
+    assert!(cargo_toml.contains(r#"description = "Counts things""#));
+    assert!(cargo_toml.contains(r#"homepage = "https://example.com""#));
+    assert!(cargo_toml.contains(r#"repository = "https://github.com/example/meta-service""#));
+
+    // Empty metadata is left out rather than written as empty strings
+    let output = Command::new(cargo_jam_bin())
+        .args(["polkajam", "new", "plain-service", "--defaults", "--no-git"])
+        .current_dir(&temp)
+        .output()
+        .expect("Failed to run cargo-polkajam new");
+    assert!(output.status.success());
+    let cargo_toml = fs::read_to_string(temp.join("plain-service/Cargo.toml")).unwrap();
+    assert!(!cargo_toml.contains("homepage"));
+    assert!(!cargo_toml.contains("repository"));
+
+    let output = Command::new(cargo_jam_bin())
+        .args([
+            "polkajam",
+            "new",
+            "bad-service",
+            "--defaults",
+            "--no-git",
+            "--homepage",
+            "example.com",
+        ])
+        .current_dir(&temp)
+        .output()
+        .expect("Failed to run cargo-polkajam new");
+    assert!(!output.status.success());
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_new_open_skipped_non_interactive() {
+    let temp = temp_dir();
+
+    // `false` would fail if it were run; --open must never fail the command
+    let output = Command::new(cargo_jam_bin())
+        .args([
+            "polkajam",
+            "new",
+            "open-service",
+            "--defaults",
+            "--no-git",
+            "--open",
+            "--editor",
+            "false",
+        ])
+        .current_dir(&temp)
+        .output()
+        .expect("Failed to run cargo-polkajam new");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Not opening an editor"), "{}", stdout);
+    assert!(temp.join("open-service/Cargo.toml").exists());
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_new_workspace_root() {
+    let temp = temp_dir();
+    let root = temp.join("services");
+
+    for name in ["first-service", "second-service"] {
+        let output = Command::new(cargo_jam_bin())
+            .args(["polkajam", "new", name, "--defaults", "--workspace-root"])
+            .arg(&root)
+            .current_dir(&temp)
+            .output()
+            .expect("Failed to run cargo-polkajam new");
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        assert!(root.join(name).join("src").exists());
+    }
+
+    let manifest = fs::read_to_string(root.join("Cargo.toml")).unwrap();
+    assert!(manifest.contains("[workspace]"));
+    assert!(manifest.contains(r#"members = ["first-service", "second-service"]"#));
+    assert!(manifest.contains("[profile.release]"));
+    let member = fs::read_to_string(root.join("first-service/Cargo.toml")).unwrap();
+    assert!(!member.contains("[profile.release]"));
+
+    // One repository for the whole workspace
+    assert!(root.join(".git").exists());
+    assert!(!root.join("first-service/.git").exists());
+
+    // A package manifest isn't taken over as a workspace
+    fs::write(temp.join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+    let output = Command::new(cargo_jam_bin())
+        .args([
+            "polkajam",
+            "new",
+            "third-service",
+            "--defaults",
+            "--workspace-root",
+            ".",
+        ])
+        .current_dir(&temp)
+        .output()
+        .expect("Failed to run cargo-polkajam new");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("has no [workspace] table"));
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_new_values_stdin() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let temp = temp_dir();
+    let project_name = "piped-service";
+
+    let mut child = Command::new(cargo_jam_bin())
+        .args([
+            "polkajam",
+            "new",
+            project_name,
+            "--no-git",
+            "--values-stdin",
+            "-d",
+            "license=MIT",
+        ])
+        .current_dir(&temp)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to run cargo-polkajam new --values-stdin");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(br#"{"description": "Piped in", "license": "Apache-2.0"}"#)
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(
+        output.status.success(),
+        "cargo-polkajam new --values-stdin failed: {:?}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let cargo_toml = fs::read_to_string(temp.join(project_name).join("Cargo.toml")).unwrap();
+    assert!(cargo_toml.contains("Piped in"));
+    assert!(cargo_toml.contains(r#"license = "MIT""#));
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_update_applies_template_changes() {
+    use cargo_polkajam::project::manifest::{hash_bytes, GenerationManifest};
+
+    let temp = temp_dir();
+    let project_name = "update-service";
+    let project_path = temp.join(project_name);
+
+    let output = Command::new(cargo_jam_bin())
+        .args(["polkajam", "new", project_name, "--defaults", "--no-git"])
+        .current_dir(&temp)
+        .output()
+        .expect("Failed to run cargo-polkajam new");
+    assert!(output.status.success());
+
+    // Pretend the project came from an older template: README.md was rendered
+    // differently and is unmodified, src/lib.rs was rendered differently and edited
+    let readme = project_path.join("README.md");
+    let lib = project_path.join("src").join("lib.rs");
+    let expected_readme = fs::read_to_string(&readme).unwrap();
+    fs::write(&readme, "old readme").unwrap();
+    fs::write(&lib, "// edited").unwrap();
+
+    let mut manifest = GenerationManifest::load(&project_path).unwrap();
+    manifest
+        .files
+        .insert("README.md".to_string(), hash_bytes(b"old readme"));
+    manifest
+        .files
+        .insert("src/lib.rs".to_string(), hash_bytes(b"// old lib"));
+    manifest.save(&project_path).unwrap();
+
+    let dry_run = Command::new(cargo_jam_bin())
+        .args(["polkajam", "update", "--dry-run"])
+        .current_dir(&project_path)
+        .output()
+        .expect("Failed to run cargo-polkajam update");
+    assert!(dry_run.status.success());
+    assert_eq!(fs::read_to_string(&readme).unwrap(), "old readme");
+
+    let output = Command::new(cargo_jam_bin())
+        .args(["polkajam", "update"])
+        .current_dir(&project_path)
+        .output()
+        .expect("Failed to run cargo-polkajam update");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("U README.md"));
+    assert!(stdout.contains("C src/lib.rs"));
+
+    assert_eq!(fs::read_to_string(&readme).unwrap(), expected_readme);
+    assert_eq!(fs::read_to_string(&lib).unwrap(), "// edited");
+    assert!(project_path.join("src").join("lib.rs.new").exists());
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_config_override() {
+    let temp = temp_dir();
+    let toolchain = temp.join("toolchain");
+    fs::create_dir_all(&toolchain).unwrap();
+
+    let config_path = temp.join("config.toml");
+    fs::write(
+        &config_path,
+        format!(
+            "installed_version = \"nightly-test\"\ntoolchain_path = {:?}\n",
+            toolchain
+        ),
+    )
+    .unwrap();
+
+    let output = Command::new(cargo_jam_bin())
+        .args(["polkajam", "setup", "--info", "--config"])
+        .arg(&config_path)
+        .output()
+        .expect("Failed to run cargo-polkajam setup --info --config");
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("nightly-test"));
+
+    let output = Command::new(cargo_jam_bin())
+        .args(["polkajam", "setup", "--info"])
+        .env("CARGO_JAM_CONFIG", &config_path)
+        .output()
+        .expect("Failed to run cargo-polkajam setup --info");
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("nightly-test"));
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_test_json_stdout() {
+    let temp = temp_dir();
+    let toolchain = temp.join("toolchain");
+    fs::create_dir_all(&toolchain).unwrap();
+    let config_path = temp.join("config.toml");
+    fs::write(
+        &config_path,
+        format!(
+            "installed_version = \"nightly-test\"\ntoolchain_path = {:?}\n",
+            toolchain
+        ),
+    )
+    .unwrap();
+
+    let output = Command::new(cargo_jam_bin())
+        .args([
+            "polkajam",
+            "test",
+            "--filter",
+            "create",
+            "--json",
+            "--summary-file",
+        ])
+        .arg(temp.join("summary.json"))
+        .arg("--dir")
+        .arg(temp.join("e2e"))
+        .env("CARGO_JAM_CONFIG", &config_path)
+        .output()
+        .expect("Failed to run cargo-polkajam test");
+    assert!(output.status.success());
+
+    // Progress goes to stderr, leaving stdout to the summary alone
+    let summary: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .unwrap_or_else(|e| panic!("{}: {}", e, String::from_utf8_lossy(&output.stdout)));
+    assert_eq!(summary["toolchain"], "nightly-test");
+    assert_eq!(summary["steps"][0]["phase"], "create");
+    assert_eq!(summary["steps"][0]["status"], "passed");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Test Results"));
+    let file: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(temp.join("summary.json")).unwrap()).unwrap();
+    assert_eq!(file, summary);
+
+    cleanup(&temp);
+}
+
+#[test]
+#[cfg(unix)]
+fn test_deploy_post_deploy_hook() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp = temp_dir();
+    let toolchain = temp.join("toolchain");
+    let bin_dir = toolchain.join("polkajam-nightly");
+    fs::create_dir_all(&bin_dir).unwrap();
+    fs::write(
+        temp.join("config.toml"),
+        format!(
+            "installed_version = \"nightly-test\"\ntoolchain_path = {:?}\n",
+            toolchain
+        ),
+    )
+    .unwrap();
+    // A jamt (called as `jamt --rpc <url> create-service ...`) that creates service 42,
+    // or fails when FAKE_JAMT_FAIL is set
+    let jamt = bin_dir.join("jamt");
+    fs::write(
+        &jamt,
+        "#!/bin/sh\n[ \"$3\" = create-service ] || exit 0\n[ -z \"$FAKE_JAMT_FAIL\" ] || exit 1\necho 'Service 42 created'\n",
+    )
+    .unwrap();
+    fs::set_permissions(&jamt, fs::Permissions::from_mode(0o755)).unwrap();
+    let blob = temp.join("service.jam");
+    fs::write(&blob, b"PVM blob").unwrap();
+    let hook_out = temp.join("hook.out");
+
+    // Accepts the reachability check's connection
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let rpc = format!("ws://{}", listener.local_addr().unwrap());
+
+    let deploy = |fail: bool| {
+        let mut cmd = Command::new(cargo_jam_bin());
+        cmd.args(["polkajam", "deploy", "--rpc", &rpc])
+            .arg(&blob)
+            .arg("--post-deploy")
+            .arg(format!(
+                "echo \"$CARGO_JAM_SERVICE_ID $CARGO_JAM_RPC\" > {}",
+                hook_out.display()
+            ))
+            .env("CARGO_JAM_HOME", &temp);
+        if fail {
+            cmd.env("FAKE_JAMT_FAIL", "1");
+        }
+        cmd.output().expect("Failed to run cargo-polkajam deploy")
+    };
+
+    // Skipped when the deploy fails
+    let output = deploy(true);
+    assert!(!output.status.success());
+    assert!(!hook_out.exists());
+
+    let output = deploy(false);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Post-deploy script passed"));
+    assert_eq!(
+        fs::read_to_string(&hook_out).unwrap().trim(),
+        format!("42 {}", rpc)
+    );
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_up_toolchain_pin() {
+    let temp = temp_dir();
+    let toolchain = temp.join("toolchain");
+    fs::create_dir_all(&toolchain).unwrap();
+    fs::write(
+        temp.join("config.toml"),
+        format!(
+            "installed_version = \"nightly-2026-01-05\"\ntoolchain_path = {:?}\n",
+            toolchain
+        ),
+    )
+    .unwrap();
+    let project = temp.join("project");
+    fs::create_dir_all(&project).unwrap();
+    fs::write(
+        project.join("jam-toolchain.toml"),
+        "version = \"nightly-2025-12-29\"\n",
+    )
+    .unwrap();
+
+    let output = Command::new(cargo_jam_bin())
+        .args(["polkajam", "--check-only", "up"])
+        .env("CARGO_JAM_HOME", &temp)
+        .current_dir(&project)
+        .output()
+        .expect("Failed to run cargo-polkajam up");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("requires toolchain nightly-2025-12-29"),
+        "{}",
+        stderr
+    );
+    assert!(
+        stderr.contains("nightly-2026-01-05 is installed"),
+        "{}",
+        stderr
+    );
+    assert!(
+        stderr.contains("setup --version nightly-2025-12-29"),
+        "{}",
+        stderr
+    );
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_sign_and_verify_blob() {
+    let temp = temp_dir();
+    let config_path = temp.join("config.toml");
+    let key_path = temp.join("signing.key");
+    let blob = temp.join("service.jam");
+    fs::write(&blob, b"PVM blob").unwrap();
+
+    let run = |args: &[&str]| {
+        Command::new(cargo_jam_bin())
+            .arg("polkajam")
+            .args(args)
+            .env("CARGO_JAM_CONFIG", &config_path)
+            .output()
+            .expect("Failed to run cargo-polkajam")
+    };
+
+    let output = run(&[
+        "sign",
+        blob.to_str().unwrap(),
+        "--key",
+        key_path.to_str().unwrap(),
+        "--generate-key",
+    ]);
+    assert!(
+        output.status.success(),
+        "sign failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(temp.join("service.jam.sig").exists());
+    assert!(temp.join("signing.key.pub").exists());
+
+    let output = run(&["verify", blob.to_str().unwrap()]);
+    assert!(output.status.success());
+
+    fs::write(&blob, b"tampered").unwrap();
+    let output = run(&["verify", blob.to_str().unwrap()]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("trusted key"));
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_info_reports_project() {
+    let temp = temp_dir();
+    let project_path = temp.join("info-service");
+
+    let output = Command::new(cargo_jam_bin())
+        .args([
+            "polkajam",
+            "new",
+            "info-service",
+            "--defaults",
+            "--no-git",
+            "-d",
+            "author_name=Test",
+            "-d",
+            "author_email=test@example.com",
+            "--output",
+            project_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to run cargo-polkajam new");
+    assert!(output.status.success());
+
+    let output = Command::new(cargo_jam_bin())
+        .args(["polkajam", "info", "--json", "--path"])
+        .arg(&project_path)
+        .output()
+        .expect("Failed to run cargo-polkajam info");
+    assert!(output.status.success());
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["project"]["name"], "info-service");
+    assert_eq!(report["project"]["version"], "0.1.0");
+    assert!(report["blob"].is_null());
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_setup_platform() {
+    let temp = temp_dir();
+    let archive = temp.join("polkajam-linux-x86_64.tar.gz");
+    fs::write(&archive, "").unwrap();
+
+    let setup = |extra: &[&str]| {
+        Command::new(cargo_jam_bin())
+            .args(["polkajam", "--check-only", "setup"])
+            .args(extra)
+            .env("CARGO_JAM_HOME", &temp)
+            .output()
+            .expect("Failed to run cargo-polkajam setup")
+    };
+    let from_archive = ["--from-archive", archive.to_str().unwrap(), "--label", "x"];
+
+    let output = setup(&["--platform", "linux-amd64"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("expected one of: macos-aarch64"),
+        "{}",
+        stderr
+    );
+
+    let output = setup(&[&from_archive[..], &["--platform", "windows-x86_64"]].concat());
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("not a .zip archive"));
+    let output = setup(&[&from_archive[..], &["--platform", "linux-x86_64"]].concat());
+    assert!(output.status.success());
+
+    // The config's platform is checked the same way
+    fs::write(temp.join("config.toml"), "platform = \"windows-x86_64\"\n").unwrap();
+    let output = setup(&from_archive);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("not a .zip archive"));
+    fs::write(temp.join("config.toml"), "platform = \"win64\"\n").unwrap();
+    let output = setup(&from_archive);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown platform 'win64'"));
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_setup_from_archive() {
+    let temp = temp_dir();
+    let archive = temp.join("polkajam-linux-x86_64.tar.gz");
+    let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+        fs::File::create(&archive).unwrap(),
+        flate2::Compression::default(),
+    ));
+    for name in [
+        "polkajam-nightly-local/jamt",
+        "polkajam-nightly-local/polkajam",
+    ] {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(2);
+        header.set_mode(0o755);
+        header.set_cksum();
+        builder.append_data(&mut header, name, &b"#!"[..]).unwrap();
+    }
+    builder.into_inner().unwrap().finish().unwrap();
+
+    let output = Command::new(cargo_jam_bin())
+        .args(["polkajam", "setup", "--platform", "linux-x86_64"])
+        .args(["--from-archive", archive.to_str().unwrap()])
+        .args(["--label", "nightly-local"])
+        .env("CARGO_JAM_HOME", &temp)
+        .output()
+        .expect("Failed to run cargo-polkajam setup");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        String::from_utf8_lossy(&output.stdout).contains("Installed JAM toolchain nightly-local")
+    );
+
+    // Installed without touching the network, under the --label version
+    assert!(temp.join("toolchain/polkajam-nightly/jamt").is_file());
+    let config = fs::read_to_string(temp.join("config.toml")).unwrap();
+    assert!(
+        config.contains("installed_version = \"nightly-local\""),
+        "{}",
+        config
+    );
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_clean_toolchain_prune() {
+    let temp = temp_dir();
+    let cache = temp.join("cache");
+
+    // Oldest first; the oldest is the active toolchain
+    let versions = [
+        "nightly-2025-11-01",
+        "nightly-2025-12-01",
+        "nightly-2026-01-01",
+    ];
+    for (age, version) in versions.iter().rev().enumerate() {
+        let dir = cache.join(version);
+        fs::create_dir_all(&dir).unwrap();
+        let archive = fs::File::create(dir.join("polkajam-linux-x86_64.tgz")).unwrap();
+        archive.set_len(2048).unwrap();
+        let modified =
+            std::time::SystemTime::now() - std::time::Duration::from_secs(3600 * (age as u64 + 1));
+        archive.set_modified(modified).unwrap();
+    }
+    fs::write(
+        temp.join("config.toml"),
+        "installed_version = \"nightly-2025-11-01\"\n",
+    )
+    .unwrap();
+
+    let run = |extra: &[&str]| {
+        let output = Command::new(cargo_jam_bin())
+            .args(["polkajam", "clean-toolchain", "--prune", "--keep", "1"])
+            .args(extra)
+            .env("CARGO_JAM_HOME", &temp)
+            .output()
+            .expect("Failed to run cargo-polkajam clean-toolchain");
+        assert!(output.status.success());
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let stdout = run(&["--dry-run"]);
+    assert!(stdout.contains("Would remove 1 version(s), freeing 2.0 KiB"));
+    assert!(cache.join("nightly-2025-12-01").exists());
+
+    let stdout = run(&[]);
+    assert!(stdout.contains("Removed 1 version(s)"));
+    assert!(!cache.join("nightly-2025-12-01").exists());
+    assert!(cache.join("nightly-2025-11-01").exists());
+    assert!(cache.join("nightly-2026-01-01").exists());
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_uninstall() {
+    let temp = temp_dir();
+    let toolchain = temp.join("toolchain");
+    fs::create_dir_all(toolchain.join("polkajam-nightly")).unwrap();
+    fs::write(toolchain.join("polkajam-nightly/jamt"), "jamt").unwrap();
+    fs::write(toolchain.join("checksums.toml"), "[files]\n").unwrap();
+    fs::write(temp.join("testnet.pid"), "1").unwrap();
+    fs::write(
+        temp.join("config.toml"),
+        format!(
+            "installed_version = \"nightly-2025-12-29\"\ntoolchain_path = {:?}\ninstalled_at = \"0\"\ndefault_timeout = 30\n",
+            toolchain.to_string_lossy()
+        ),
+    )
+    .unwrap();
+
+    let run = |extra: &[&str]| {
+        let output = Command::new(cargo_jam_bin())
+            .args(["polkajam", "uninstall"])
+            .args(extra)
+            .env("CARGO_JAM_HOME", &temp)
+            .output()
+            .expect("Failed to run cargo-polkajam uninstall");
+        assert!(output.status.success());
+        output
+    };
+
+    let output = run(&[]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("polkajam-nightly"), "{}", stdout);
+    assert!(stdout.contains("nightly-2025-12-29"), "{}", stdout);
+    assert!(!toolchain.join("polkajam-nightly").exists());
+    assert!(!toolchain.join("checksums.toml").exists());
+    let config = fs::read_to_string(temp.join("config.toml")).unwrap();
+    assert!(!config.contains("installed_version"), "{}", config);
+    assert!(!config.contains("toolchain_path"), "{}", config);
+    assert!(config.contains("default_timeout = 30"), "{}", config);
+    assert!(temp.join("testnet.pid").exists());
+
+    // Nothing left to remove is a warning, not an error
+    let output = run(&[]);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No toolchain is installed"));
+
+    run(&["--purge"]);
+    assert!(!temp.exists());
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_template_diff() {
+    let temp = temp_dir();
+    let template = temp.join("template");
+    let bundled = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("templates/basic-service");
+    for entry in walkdir::WalkDir::new(&bundled).into_iter().flatten() {
+        let target = template.join(entry.path().strip_prefix(&bundled).unwrap());
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&target).unwrap();
+        } else {
+            fs::copy(entry.path(), &target).unwrap();
+        }
+    }
+    fs::write(template.join("src/extra.rs"), "// extra\n").unwrap();
+
+    let output = Command::new(cargo_jam_bin())
+        .args(["polkajam", "template", "diff", "basic-service"])
+        .arg(&template)
+        .output()
+        .expect("Failed to run cargo-polkajam template diff");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("1 file(s) differ"), "stdout: {}", stdout);
+    assert!(stdout.contains("A src/extra.rs"), "stdout: {}", stdout);
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_template_info() {
+    let output = Command::new(cargo_jam_bin())
+        .args(["polkajam", "template", "info", "basic-service"])
+        .output()
+        .expect("Failed to run cargo-polkajam template info");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("basic-service 0.1.0"), "stdout: {}", stdout);
+    assert!(
+        stdout.contains("license (string) License"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(
+        stdout.contains("default: MIT OR Apache-2.0"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(
+        stdout.contains("SPDX license expression"),
+        "stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_check_compat() {
+    let temp = temp_dir();
+    let output = Command::new(cargo_jam_bin())
+        .args([
+            "polkajam",
+            "new",
+            "compat-service",
+            "--defaults",
+            "--no-git",
+        ])
+        .current_dir(&temp)
+        .output()
+        .expect("Failed to run cargo-polkajam new");
+    assert!(output.status.success());
+    let project = temp.join("compat-service");
+
+    let check = |strict: bool| {
+        let mut cmd = Command::new(cargo_jam_bin());
+        cmd.args(["polkajam", "check-compat", "--path"])
+            .arg(&project)
+            .env("CARGO_JAM_HOME", temp.join("home"));
+        if strict {
+            cmd.arg("--strict");
+        }
+        cmd.output()
+            .expect("Failed to run cargo-polkajam check-compat")
+    };
+
+    let output = check(true);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("No known incompatibilities"), "{}", stdout);
+
+    // An SDK from before the supported toolchain
+    let cargo_toml = project.join("Cargo.toml");
+    let manifest = fs::read_to_string(&cargo_toml).unwrap();
+    fs::write(
+        &cargo_toml,
+        manifest.replace(r#"polkavm-derive = "0.29""#, r#"polkavm-derive = "0.27""#),
+    )
+    .unwrap();
+
+    let output = check(false);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains(r#"set polkavm-derive = "0.29" in Cargo.toml"#),
+        "{}",
+        stdout
+    );
+    assert!(!check(true).status.success());
+
+    cleanup(&temp);
+}
+
+#[test]
+#[cfg(unix)]
+fn test_config_edit() {
+    let temp = temp_dir();
+    let home = temp.join("home");
+    let config = |args: &[&str]| {
+        Command::new(cargo_jam_bin())
+            .args(["polkajam", "config"])
+            .args(args)
+            .env("CARGO_JAM_HOME", &home)
+            .output()
+            .expect("Failed to run cargo-polkajam config")
+    };
+
+    let output = config(&["path"]);
+    assert!(output.status.success());
+    let config_path = home.join("config.toml");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        config_path.display().to_string()
+    );
+
+    // `true` leaves the file untouched, standing in for an editor session
+    let output = config(&["edit", "--editor", "true"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let created = fs::read_to_string(&config_path).unwrap();
+    assert!(created.contains("# default_timeout = 1800"));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("is valid"));
+
+    fs::write(&config_path, "default_timeout = \"soon\"\n").unwrap();
+    let output = config(&["edit", "--editor", "true"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Failed to parse config"));
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_accounts() {
+    let temp = temp_dir();
+    let home = temp.join("home");
+    let accounts = |args: &[&str]| {
+        Command::new(cargo_jam_bin())
+            .args(["polkajam", "accounts"])
+            .args(args)
+            .env("CARGO_JAM_HOME", &home)
+            .output()
+            .expect("Failed to run cargo-polkajam accounts")
+    };
+
+    let output = accounts(&["list"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("No accounts yet"));
+
+    assert!(accounts(&["new", "alice"]).status.success());
+    assert!(accounts(&["new", "bob"]).status.success());
+    assert!(!accounts(&["new", "alice"]).status.success());
+    assert!(home.join("accounts/alice.key").is_file());
+
+    // The first account becomes the default
+    let list = |marked: &str| {
+        let output = accounts(&["list"]);
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let default: Vec<_> = stdout
+            .lines()
+            .filter(|l| l.ends_with("(default)"))
+            .collect();
+        assert_eq!(default.len(), 1, "{}", stdout);
+        assert!(default[0].contains(marked), "{}", stdout);
+    };
+    list("alice");
+
+    assert!(accounts(&["use", "bob"]).status.success());
+    list("bob");
+    let config = fs::read_to_string(home.join("config.toml")).unwrap();
+    assert!(config.contains("default_account = \"bob\""), "{}", config);
+
+    let output = accounts(&["use", "carol"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no account named 'carol'"));
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_template_render_all() {
+    let temp = temp_dir();
+    let values = temp.join("values.toml");
+    fs::write(
+        &values,
+        "project_name = \"golden-service\"\nauthor_name = \"Golden\"\n",
+    )
+    .unwrap();
+    let render_all = |out: &str, extra: &[&str]| {
+        Command::new(cargo_jam_bin())
+            .args([
+                "polkajam",
+                "template",
+                "render-all",
+                "basic-service",
+                "--values",
+            ])
+            .arg(&values)
+            .arg("--out")
+            .arg(temp.join(out))
+            .args(extra)
+            .output()
+            .expect("Failed to run cargo-polkajam template render-all")
+    };
+
+    let output = render_all("a", &[]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(render_all("b", &[]).status.success());
+    assert!(!temp.join("a/.git").exists());
+    let cargo_toml = fs::read_to_string(temp.join("a/Cargo.toml")).unwrap();
+    assert!(cargo_toml.contains("name = \"golden-service\""));
+    assert!(cargo_toml.contains("Golden"));
+
+    // Identical inputs render identical trees
+    for entry in walkdir::WalkDir::new(temp.join("a")) {
+        let entry = entry.unwrap();
+        if entry.file_type().is_file() {
+            let relative = entry.path().strip_prefix(temp.join("a")).unwrap();
+            assert_eq!(
+                fs::read(entry.path()).unwrap(),
+                fs::read(temp.join("b").join(relative)).unwrap(),
+                "{} differs",
+                relative.display()
+            );
+        }
+    }
+
+    assert!(!render_all("a", &[]).status.success());
+    assert!(render_all("a", &["--force"]).status.success());
+
+    fs::write(&values, "author_name = \"Golden\"\n").unwrap();
+    let output = render_all("c", &[]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("project_name"));
+
+    cleanup(&temp);
+}
+
+#[test]
+fn test_template_list() {
+    let output = Command::new(cargo_jam_bin())
+        .args(["polkajam", "template", "list"])
+        .output()
+        .expect("Failed to run cargo-polkajam template list");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.lines().next() == Some("starters"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(stdout.contains("basic-service"), "stdout: {}", stdout);
+    assert!(
+        stdout.contains("[refine, accumulate]"),
+        "stdout: {}",
+        stdout
+    );
+
+    let output = Command::new(cargo_jam_bin())
+        .args(["polkajam", "template", "list", "--category", "examples"])
+        .output()
+        .expect("Failed to run cargo-polkajam template list");
+    assert!(output.status.success());
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("basic-service"));
+}